@@ -0,0 +1,153 @@
+use casino_client::accounts::{BettingState, Fight, SolVault, SupportedMint, Treasury, UserBettingAccount};
+use casino_client::{HousePnl, TokenType};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::units::{format_amount, format_bps, format_odds, format_price, format_usd};
+
+fn row(label: &str, value: impl std::fmt::Display) {
+    println!("  {:<28} {}", label, value);
+}
+
+pub fn print_betting_state(address: &Pubkey, state: &BettingState) {
+    println!("BettingState {}", address);
+    row("authority", state.authority);
+    row("paused", state.is_paused);
+    row("house fee", format_bps(state.house_fee as u64));
+    row("RAPR odds multiplier", format_bps(state.rapr_multiplier));
+    row("DUMBS per SOL", state.sol_dumbs_rate);
+    row("RAPR per SOL", state.sol_rapr_rate);
+    row("max bet", format_amount(state.max_bet));
+    row("bets placed", state.total_bets_placed);
+    row("bets settled", state.total_bets_settled);
+    row("DUMBS wagered", format_amount(state.total_dumbs_wagered));
+    row("RAPR wagered", format_amount(state.total_rapr_wagered));
+    row("DUMBS won", format_amount(state.total_dumbs_won));
+    row("RAPR won", format_amount(state.total_rapr_won));
+    row("fees collected", format_amount(state.total_fees_collected));
+    row("outstanding payout", format_amount(state.total_potential_payout));
+    row("DUMBS in circulation", format_amount(state.total_dumbs_in_circulation));
+    row("DUMBS max supply", format_cap(state.dumbs_max_supply));
+    row("DUMBS epoch budget", format_cap(state.dumbs_epoch_budget));
+    row(&format!("DUMBS emitted (epoch {})", state.emission_epoch), format_amount(state.dumbs_emitted));
+    row("house DUMBS", format_amount(state.house_dumbs));
+    row("DUMBS winnings queued", format_amount(state.total_dumbs_queued));
+    row("RAPR in circulation", format_amount(state.total_rapr_in_circulation));
+    row("rounding dust (units)", casino_client::Dust(state.rounding_dust).whole_units());
+    row("DUMBS mint", state.dumbs_mint);
+    row("RAPR mint", state.rapr_mint);
+}
+
+fn format_cap(cap: u64) -> String {
+    if cap == u64::MAX {
+        "none".to_string()
+    } else {
+        format_amount(cap)
+    }
+}
+
+pub fn print_treasury(address: &Pubkey, treasury: &Treasury, lamports: u64) {
+    println!("Treasury {}", address);
+    row("balance (SOL)", format_amount(lamports));
+    row("SOL fees", format_amount(treasury.sol_fees_collected));
+    row("DUMBS fees", format_amount(treasury.dumbs_fees_collected));
+    row("RAPR fees", format_amount(treasury.rapr_fees_collected));
+    row("RAPR fees harvested", format_amount(treasury.rapr_fees_harvested));
+    row("RAPR lock penalties", format_amount(treasury.rapr_lock_penalties));
+    row("total fees", format_amount(treasury.total_fees_collected));
+    row("house edge", format_amount(treasury.total_house_edge));
+    row("withdrawn (SOL)", format_amount(treasury.total_withdrawals));
+    row("last withdrawal", treasury.last_withdrawal_timestamp);
+}
+
+pub fn print_sol_vault(address: &Pubkey, vault: &SolVault, lamports: u64) {
+    println!("SolVault {}", address);
+    row("balance (SOL)", format_amount(lamports));
+    row("tracked balance (SOL)", format_amount(vault.balance));
+    row("deposits", vault.total_deposits);
+    row("SOL received", format_amount(vault.total_sol_received));
+    row("DUMBS minted", format_amount(vault.total_dumbs_minted));
+    row("min deposit (SOL)", format_amount(vault.min_deposit_amount));
+    row("max deposit (SOL)", format_amount(vault.max_deposit_amount));
+}
+
+pub fn print_house_pnl(pnl: &HousePnl) {
+    println!("House P&L at {} SOL/USD (published {})", format_price(pnl.sol_usd.price, pnl.sol_usd.expo), pnl.sol_usd.publish_time);
+    println!("  {:<28} {:>18} {:>18}", "", "SOL", "USD");
+    for (label, sol, usd) in [
+        ("fees", pnl.fees as i64, pnl.fees_usd as i64),
+        ("house edge", pnl.house_edge as i64, pnl.house_edge_usd as i64),
+        ("backing", pnl.backing as i64, pnl.backing_usd as i64),
+        ("liabilities", pnl.liabilities as i64, pnl.liabilities_usd as i64),
+        ("net", pnl.net, pnl.net_usd),
+    ] {
+        let sol = if sol < 0 { format!("-{}", format_amount(sol.unsigned_abs())) } else { format_amount(sol as u64) };
+        println!("  {:<28} {:>18} {:>18}", label, sol, format_usd(usd));
+    }
+}
+
+/// Registered mints keep their own decimals, so amounts stay in base units.
+pub fn print_supported_mint(address: &Pubkey, asset: &SupportedMint) {
+    println!("SupportedMint {} ({})", asset.mint, address);
+    row("enabled", asset.enabled);
+    row("token program", asset.token_program);
+    row("vault", asset.vault);
+    row("decimals", asset.decimals);
+    row("odds multiplier", format_bps(asset.multiplier as u64));
+    row("min bet (units)", asset.min_bet);
+    row("max bet (units)", asset.max_bet);
+    row("SOL per token", format_amount(asset.lamports_per_token));
+    if asset.usd_feed != Pubkey::default() {
+        row("USD feed", asset.usd_feed);
+    }
+    row("open liability (units)", asset.open_liability);
+    row("staked (units)", asset.total_staked);
+    row("paid (units)", asset.total_paid);
+    row("fees (units)", asset.fees_collected);
+    row("SOL liquidity", format_amount(asset.sol_liquidity));
+}
+
+pub fn print_fight(address: &Pubkey, fight: &Fight) {
+    println!("Fight {} ({})", fight.fight_id, address);
+    row("fighter one", fight.fighter_one);
+    row("fighter two", fight.fighter_two);
+    row("status", format!("{:?}", fight.status));
+    if fight.resolved_at != 0 {
+        row("winner", fight.winner);
+    }
+    if fight.reversed_at != 0 {
+        row("overturned winner", fight.overturned_winner);
+    }
+}
+
+pub fn print_open_bets(fight_id: u64, bets: &[(Pubkey, UserBettingAccount)]) {
+    println!("{} open bet(s) on fight {}", bets.len(), fight_id);
+    for (bettor, account) in bets {
+        let Some(bet) = &account.active_bet else {
+            continue;
+        };
+        let token = match bet.token_type {
+            TokenType::DUMBS => "DUMBS",
+            TokenType::RAPR => "RAPR",
+        };
+        println!(
+            "  {}  {:>14} {:<5}  on {}  odds {}  payout {}",
+            bettor,
+            format_amount(bet.amount),
+            token,
+            bet.fighter,
+            format_odds(bet.odds),
+            format_amount(bet.potential_payout),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uncapped_supply_and_budget_show_as_none() {
+        assert_eq!(format_cap(u64::MAX), "none");
+        assert_eq!(format_cap(5_000_000_000), "5");
+    }
+}
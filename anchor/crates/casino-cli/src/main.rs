@@ -0,0 +1,657 @@
+//! Operator CLI for a betting program house: bootstrap, inspect state,
+//! change config, pause, withdraw and harvest fees and run fights.
+
+mod display;
+mod runner;
+mod units;
+
+use std::path::PathBuf;
+
+use anyhow::{anyhow, bail, Context, Result};
+use casino_client::{
+    pda, CasinoClient, ConfigChange, House, MetadataField, OracleParams, SupportedMintParams, TokenType,
+    ORACLE_CHANGE_KIND,
+};
+use clap::{Parser, Subcommand, ValueEnum};
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Keypair},
+    signer::Signer,
+};
+
+use crate::runner::Runner;
+use crate::units::parse_amount;
+
+#[derive(Parser)]
+#[command(name = "casino-cli", about = "Administer a betting program house")]
+struct Cli {
+    /// RPC endpoint, defaults to a local solana-test-validator
+    #[arg(long, env = "RPC_URL", default_value = "http://127.0.0.1:8899")]
+    rpc_url: String,
+
+    /// Keypair that signs and pays for transactions
+    #[arg(long, env = "KEYPAIR", default_value = "~/.config/solana/id.json")]
+    keypair: String,
+
+    /// House authority, defaults to the keypair's public key
+    #[arg(long, env = "HOUSE_AUTHORITY")]
+    house: Option<Pubkey>,
+
+    /// Simulate transactions instead of sending them
+    #[arg(long, global = true)]
+    dry_run: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create every house account for the signing authority and grant it all roles
+    Bootstrap {
+        /// Existing Token-2022 RAPR mint
+        #[arg(long)]
+        rapr_mint: Pubkey,
+        /// Metadata URI for DUMBS
+        #[arg(long, default_value = "")]
+        dumbs_uri: String,
+        /// Metadata URI for RAPR. RAPR metadata is only written when given, and
+        /// the mint's metadata pointer must name the mint itself
+        #[arg(long)]
+        rapr_uri: Option<String>,
+        /// Create DUMBS as non-transferable play money
+        #[arg(long)]
+        non_transferable_dumbs: bool,
+        /// Make the governance PDA DUMBS's permanent delegate, so winnings from
+        /// reversed results can be clawed back
+        #[arg(long)]
+        clawback_dumbs: bool,
+        /// Register the rapr_transfer_hook accounts. The RAPR mint's
+        /// TransferHook extension must name the hook program
+        #[arg(long)]
+        rapr_transfer_hook: bool,
+    },
+    /// Show BettingState, Treasury and SolVault in human units
+    Show,
+    /// Queue, execute or cancel timelocked config changes
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+    /// Stop deposits and bets
+    Pause,
+    /// Resume deposits and bets
+    Unpause,
+    /// Withdraw collected SOL fees from the treasury
+    WithdrawFees {
+        /// Amount in SOL, e.g. 1.5
+        amount: String,
+        /// Recipient, defaults to the signer
+        #[arg(long)]
+        recipient: Option<Pubkey>,
+    },
+    /// Harvest RAPR transfer fees into the RAPR vault and the treasury ledger
+    HarvestFees {
+        /// RAPR token accounts to harvest besides the RAPR vault
+        #[arg(long = "source")]
+        sources: Vec<Pubkey>,
+    },
+    /// Register a fight between two fighters
+    CreateFight {
+        fight_id: u64,
+        fighter_one: Pubkey,
+        fighter_two: Pubkey,
+    },
+    /// Post a fight result, then settle every open bet on it
+    PostResult {
+        fight_id: u64,
+        winner: Pubkey,
+        /// Only record the result, leave bets for a later settlement run
+        #[arg(long)]
+        no_settle: bool,
+    },
+    /// Overturn a posted fight result after a dispute
+    ReverseResult { fight_id: u64, winner: Pubkey },
+    /// Claw back winnings paid on a fight whose result was reversed
+    Clawback {
+        fight_id: u64,
+        bettor: Pubkey,
+        token: TokenArg,
+        /// Amount in token units, e.g. 1.5
+        amount: String,
+    },
+    /// Show a fight and its open bets
+    OpenBets { fight_id: u64 },
+    /// Register a mint to take bets in, or update a registered one. It takes
+    /// no bets or cash-outs until its first price change executes
+    RegisterMint {
+        mint: Pubkey,
+        /// Odds multiplier in basis points, 10000 = 1x
+        #[arg(long, default_value_t = 10_000)]
+        multiplier: u32,
+        /// Minimum bet in the mint's base units
+        #[arg(long)]
+        min_bet: u64,
+        /// Maximum bet in the mint's base units
+        #[arg(long)]
+        max_bet: u64,
+        /// Register or leave the mint without taking bets
+        #[arg(long)]
+        disabled: bool,
+    },
+    /// Fund a registered mint's cash-outs with SOL from the signer
+    FundMint {
+        mint: Pubkey,
+        /// Amount in SOL, e.g. 1.5
+        amount: String,
+    },
+    /// Queue, execute or cancel a registered mint's price change, behind
+    /// the same timelock as config changes
+    MintPrice {
+        #[command(subcommand)]
+        action: MintPriceAction,
+    },
+    /// List registered mints
+    Mints,
+    /// Queue, execute or cancel a change to the price feeds the house is
+    /// valued in and, optionally, trades at
+    Oracle {
+        #[command(subcommand)]
+        action: OracleAction,
+    },
+    /// Show house fees and net position in SOL and USD
+    Pnl,
+    /// Set a metadata field of the DUMBS or RAPR mint
+    Metadata {
+        token: TokenArg,
+        /// name, symbol, uri, or a custom key such as rate
+        field: String,
+        value: String,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum TokenArg {
+    Dumbs,
+    Rapr,
+}
+
+impl From<TokenArg> for TokenType {
+    fn from(token: TokenArg) -> Self {
+        match token {
+            TokenArg::Dumbs => TokenType::DUMBS,
+            TokenArg::Rapr => TokenType::RAPR,
+        }
+    }
+}
+
+fn metadata_field(field: &str) -> MetadataField {
+    match field {
+        "name" => MetadataField::Name,
+        "symbol" => MetadataField::Symbol,
+        "uri" => MetadataField::Uri,
+        key => MetadataField::Key(key.to_string()),
+    }
+}
+
+#[derive(Subcommand)]
+enum ConfigAction {
+    /// Queue a change; it can be executed once the timelock expires
+    Queue {
+        param: ConfigParam,
+        /// Basis points for house-fee, token units for max-bet and the DUMBS
+        /// supply and budget, raw values otherwise
+        value: String,
+    },
+    /// Apply a queued change whose timelock has expired
+    Execute { param: ConfigParam },
+    /// Drop a queued change
+    Cancel { param: ConfigParam },
+    /// List queued changes
+    Pending,
+}
+
+#[derive(Subcommand)]
+enum OracleAction {
+    /// Queue new settings, creating the oracle config first if needed
+    Queue {
+        /// Pyth-style SOL/USD price account
+        sol_usd_feed: Pubkey,
+        /// Program owning the price account, e.g. Pyth's
+        #[arg(long)]
+        oracle_program: Pubkey,
+        /// Seconds a price stays usable after publishing
+        #[arg(long, default_value_t = 60)]
+        max_age: i64,
+        /// Widest confidence interval, in basis points of the price
+        #[arg(long, default_value_t = 200)]
+        max_confidence: u16,
+        /// DUMBS/USD price account, required with --oracle-rates
+        #[arg(long)]
+        dumbs_usd_feed: Option<Pubkey>,
+        /// RAPR/USD price account, required with --oracle-rates
+        #[arg(long)]
+        rapr_usd_feed: Option<Pubkey>,
+        /// Price deposits, swaps and cash-outs through the feeds instead of the fixed SOL rates
+        #[arg(long, requires_all = ["dumbs_usd_feed", "rapr_usd_feed"])]
+        oracle_rates: bool,
+    },
+    /// Apply the queued settings once the timelock expires
+    Execute,
+    /// Drop the queued settings
+    Cancel,
+}
+
+#[derive(Subcommand)]
+enum MintPriceAction {
+    /// Queue a new price; it can be executed once the timelock expires
+    Queue {
+        mint: Pubkey,
+        /// SOL value of one whole token, e.g. 0.05; limits count bets at it
+        #[arg(long)]
+        sol_per_token: String,
+        /// Token/USD price account to price cash-outs through instead
+        #[arg(long)]
+        usd_feed: Option<Pubkey>,
+    },
+    /// Apply a queued price whose timelock has expired
+    Execute { mint: Pubkey },
+    /// Drop a queued price
+    Cancel { mint: Pubkey },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ConfigParam {
+    HouseFee,
+    RaprMultiplier,
+    SolDumbsRate,
+    SolRaprRate,
+    MaxBet,
+    DumbsMaxSupply,
+    DumbsEpochBudget,
+}
+
+const CONFIG_PARAMS: [ConfigParam; 7] = [
+    ConfigParam::HouseFee,
+    ConfigParam::RaprMultiplier,
+    ConfigParam::SolDumbsRate,
+    ConfigParam::SolRaprRate,
+    ConfigParam::MaxBet,
+    ConfigParam::DumbsMaxSupply,
+    ConfigParam::DumbsEpochBudget,
+];
+
+impl ConfigParam {
+    fn change(self, value: &str) -> Result<ConfigChange> {
+        Ok(match self {
+            ConfigParam::HouseFee => ConfigChange::HouseFee(value.parse().context("house fee must be basis points")?),
+            ConfigParam::RaprMultiplier => ConfigChange::RaprMultiplier(value.parse()?),
+            ConfigParam::SolDumbsRate => ConfigChange::SolDumbsRate(value.parse()?),
+            ConfigParam::SolRaprRate => ConfigChange::SolRaprRate(value.parse()?),
+            ConfigParam::MaxBet => ConfigChange::MaxBet(parse_amount(value)?),
+            ConfigParam::DumbsMaxSupply => ConfigChange::DumbsMaxSupply(parse_amount(value)?),
+            ConfigParam::DumbsEpochBudget => ConfigChange::DumbsEpochBudget(parse_amount(value)?),
+        })
+    }
+
+    fn kind(self) -> u8 {
+        match self {
+            ConfigParam::HouseFee => ConfigChange::HouseFee(0),
+            ConfigParam::RaprMultiplier => ConfigChange::RaprMultiplier(0),
+            ConfigParam::SolDumbsRate => ConfigChange::SolDumbsRate(0),
+            ConfigParam::SolRaprRate => ConfigChange::SolRaprRate(0),
+            ConfigParam::MaxBet => ConfigChange::MaxBet(0),
+            ConfigParam::DumbsMaxSupply => ConfigChange::DumbsMaxSupply(0),
+            ConfigParam::DumbsEpochBudget => ConfigChange::DumbsEpochBudget(0),
+        }
+        .kind()
+    }
+}
+
+fn load_keypair(path: &str) -> Result<Keypair> {
+    let path = match path.strip_prefix("~/") {
+        Some(rest) => PathBuf::from(std::env::var("HOME")?).join(rest),
+        None => PathBuf::from(path),
+    };
+    read_keypair_file(&path).map_err(|e| anyhow!("failed to read keypair {}: {}", path.display(), e))
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let payer = load_keypair(&cli.keypair)?;
+    let signer = payer.pubkey();
+    let authority = cli.house.unwrap_or(signer);
+
+    let client = match &cli.command {
+        Command::Bootstrap { rapr_mint, rapr_transfer_hook, .. } => {
+            if authority != signer {
+                bail!("bootstrap must be signed by the house authority");
+            }
+            let mut house = House::new(authority, *rapr_mint);
+            if *rapr_transfer_hook {
+                house = house.with_rapr_transfer_hook();
+            }
+            CasinoClient::with_house(&cli.rpc_url, payer, house)
+        }
+        _ => CasinoClient::connect(&cli.rpc_url, payer, authority)
+            .with_context(|| format!("no house found for authority {}", authority))?,
+    };
+    let runner = Runner { client, dry_run: cli.dry_run };
+    let client = &runner.client;
+    let house = *client.house();
+
+    match cli.command {
+        Command::Bootstrap { dumbs_uri, rapr_uri, non_transferable_dumbs, clawback_dumbs, rapr_transfer_hook, .. } => {
+            let steps = [
+                ("initialize_betting_state_base", house.initialize_betting_state_base()),
+                ("initialize_dumbs_mint", house.initialize_dumbs_mint(non_transferable_dumbs, clawback_dumbs)),
+                ("initialize_state_accounts", house.initialize_state_accounts()),
+                ("initialize_bet_vault", house.initialize_bet_vault()),
+                ("initialize_rapr_vault", house.initialize_rapr_vault()),
+                ("initialize_sol_vault", house.initialize_sol_vault()),
+                ("initialize_treasury", house.initialize_treasury()),
+                ("initialize_betting_state", house.initialize_betting_state()),
+                ("initialize_roles", house.initialize_roles()),
+            ];
+            for (label, ix) in steps {
+                runner.run(label, &[ix])?;
+            }
+            runner.run(
+                "initialize_token_metadata",
+                &[house.initialize_token_metadata(TokenType::DUMBS, "freeDUMBS", "DUMBS", &dumbs_uri)],
+            )?;
+            if let Some(rapr_uri) = rapr_uri {
+                runner.run(
+                    "initialize_token_metadata",
+                    &[house.initialize_token_metadata(TokenType::RAPR, "RAPR", "RAPR", &rapr_uri)],
+                )?;
+            }
+            if rapr_transfer_hook {
+                runner.run("initialize_rapr_transfer_hook", &[house.initialize_rapr_transfer_hook(&signer)])?;
+            }
+            // initialize_roles only makes the authority an admin
+            let grants: Vec<_> = [
+                casino_client::Role::Settler,
+                casino_client::Role::Treasurer,
+                casino_client::Role::Pauser,
+                casino_client::Role::OddsMaker,
+            ]
+            .into_iter()
+            .map(|role| house.grant_role(&signer, signer, role))
+            .collect();
+            runner.run("grant_role", &grants)?;
+            println!("House {} bootstrapped", house.betting_state);
+        }
+        Command::Show => {
+            display::print_betting_state(&house.betting_state, &client.betting_state()?);
+            println!();
+            let treasury = pda::treasury().0;
+            display::print_treasury(&treasury, &client.treasury()?, client.rpc().get_balance(&treasury)?);
+            println!();
+            let sol_vault = pda::sol_vault().0;
+            display::print_sol_vault(&sol_vault, &client.sol_vault()?, client.rpc().get_balance(&sol_vault)?);
+        }
+        Command::Config { action } => match action {
+            ConfigAction::Queue { param, value } => {
+                runner.run("queue_change", &[house.queue_change(&signer, param.change(&value)?)])?;
+            }
+            ConfigAction::Execute { param } => {
+                let pending = client
+                    .pending_change(param.kind())?
+                    .ok_or_else(|| anyhow!("no change queued for this parameter"))?;
+                runner.run("execute_change", &[house.execute_change(&signer, &pending.proposer, param.kind())])?;
+            }
+            ConfigAction::Cancel { param } => {
+                let pending = client
+                    .pending_change(param.kind())?
+                    .ok_or_else(|| anyhow!("no change queued for this parameter"))?;
+                runner.run("cancel_change", &[house.cancel_change(&signer, &pending.proposer, param.kind())])?;
+            }
+            ConfigAction::Pending => {
+                for param in CONFIG_PARAMS {
+                    if let Some(pending) = client.pending_change(param.kind())? {
+                        println!("{:?} queued by {}, executable at {}", pending.change, pending.proposer, pending.eta);
+                    }
+                }
+            }
+        },
+        Command::Pause => runner.run("set_paused", &[house.set_paused(&signer, true)])?,
+        Command::Unpause => runner.run("set_paused", &[house.set_paused(&signer, false)])?,
+        Command::WithdrawFees { amount, recipient } => {
+            let recipient = recipient.unwrap_or(signer);
+            runner.run(
+                "withdraw_treasury",
+                &[house.withdraw_treasury(&signer, &recipient, parse_amount(&amount)?)],
+            )?;
+        }
+        Command::HarvestFees { sources } => {
+            runner.run("harvest_and_withdraw_fees", &[house.harvest_and_withdraw_fees(&signer, &sources)])?;
+        }
+        Command::CreateFight { fight_id, fighter_one, fighter_two } => {
+            runner.run("create_fight", &[house.create_fight(&signer, fight_id, fighter_one, fighter_two)])?;
+        }
+        Command::PostResult { fight_id, winner, no_settle } => {
+            runner.run("post_fight_result", &[house.post_fight_result(&signer, fight_id, winner)])?;
+            if !no_settle {
+                // settle_bet reads the posted result and pays bets on that fighter
+                for (bettor, _) in client.open_bets(fight_id)? {
+                    let label = format!("settle_bet {}", bettor);
+                    runner.run(&label, &client.settle_bet_instructions(&bettor, fight_id))?;
+                }
+                for (bettor, bet) in client.open_asset_bets(fight_id)? {
+                    let label = format!("settle_asset_bet {}", bettor);
+                    runner.run(&label, &[client.settle_asset_bet_instruction(&bet)?])?;
+                }
+            }
+        }
+        Command::ReverseResult { fight_id, winner } => {
+            runner.run("reverse_fight_result", &[house.reverse_fight_result(&signer, fight_id, winner)])?;
+        }
+        Command::Clawback { fight_id, bettor, token, amount } => {
+            runner.run(
+                "clawback",
+                &[house.clawback(&signer, &bettor, fight_id, token.into(), parse_amount(&amount)?)],
+            )?;
+        }
+        Command::OpenBets { fight_id } => {
+            let fight = pda::fight(&house.betting_state, fight_id).0;
+            display::print_fight(&fight, &client.fight(fight_id)?);
+            display::print_open_bets(fight_id, &client.open_bets(fight_id)?);
+        }
+        Command::RegisterMint { mint, multiplier, min_bet, max_bet, disabled } => {
+            let params = SupportedMintParams { multiplier, min_bet, max_bet, enabled: !disabled };
+            if client.supported_mint(&mint)?.is_some() {
+                runner.run("update_supported_mint", &[house.update_supported_mint(&signer, &mint, params)])?;
+            } else {
+                // The mint's owner tells Token-2022 and legacy SPL Token mints apart
+                let token_program = client.rpc().get_account(&mint)?.owner;
+                runner.run("register_mint", &[house.register_mint(&signer, &mint, &token_program, params)])?;
+            }
+        }
+        Command::FundMint { mint, amount } => {
+            runner.run("fund_mint_liquidity", &[house.fund_mint_liquidity(&signer, &mint, parse_amount(&amount)?)])?;
+        }
+        Command::MintPrice { action } => match action {
+            MintPriceAction::Queue { mint, sol_per_token, usd_feed } => {
+                let change = ConfigChange::MintPrice {
+                    mint,
+                    lamports_per_token: parse_amount(&sol_per_token)?,
+                    usd_feed: usd_feed.unwrap_or_default(),
+                };
+                runner.run("queue_change", &[house.queue_change(&signer, change)])?;
+            }
+            MintPriceAction::Execute { mint } => {
+                let pending = client
+                    .pending_mint_price(&mint)?
+                    .ok_or_else(|| anyhow!("no price change queued for this mint"))?;
+                runner.run("execute_change", &[house.execute_mint_price_change(&signer, &pending.proposer, &mint)])?;
+            }
+            MintPriceAction::Cancel { mint } => {
+                let pending = client
+                    .pending_mint_price(&mint)?
+                    .ok_or_else(|| anyhow!("no price change queued for this mint"))?;
+                runner.run("cancel_change", &[house.cancel_mint_price_change(&signer, &pending.proposer, &mint)])?;
+            }
+        },
+        Command::Mints => {
+            for (mint, asset) in client.supported_mints()? {
+                display::print_supported_mint(&pda::supported_mint(&house.betting_state, &mint).0, &asset);
+                println!();
+            }
+        }
+        Command::Oracle { action } => match action {
+            OracleAction::Queue {
+                sol_usd_feed,
+                oracle_program,
+                max_age,
+                max_confidence,
+                dumbs_usd_feed,
+                rapr_usd_feed,
+                oracle_rates,
+            } => {
+                let params = OracleParams {
+                    oracle_program,
+                    sol_usd_feed,
+                    dumbs_usd_feed: dumbs_usd_feed.unwrap_or_default(),
+                    rapr_usd_feed: rapr_usd_feed.unwrap_or_default(),
+                    max_age,
+                    max_confidence,
+                    oracle_rates,
+                };
+                if client.oracle_config()?.is_none() {
+                    runner.run("create_oracle_config", &[house.create_oracle_config(&signer)])?;
+                }
+                runner.run("queue_change", &[house.queue_change(&signer, ConfigChange::Oracle(params))])?;
+            }
+            OracleAction::Execute => {
+                let pending = client
+                    .pending_change(ORACLE_CHANGE_KIND)?
+                    .ok_or_else(|| anyhow!("no oracle change queued"))?;
+                runner.run("execute_change", &[house.execute_oracle_change(&signer, &pending.proposer)])?;
+            }
+            OracleAction::Cancel => {
+                let pending = client
+                    .pending_change(ORACLE_CHANGE_KIND)?
+                    .ok_or_else(|| anyhow!("no oracle change queued"))?;
+                runner.run("cancel_change", &[house.cancel_change(&signer, &pending.proposer, ORACLE_CHANGE_KIND)])?;
+            }
+        },
+        Command::Pnl => display::print_house_pnl(&client.house_pnl()?),
+        Command::Metadata { token, field, value } => {
+            runner.run(
+                "update_token_metadata",
+                &[house.update_token_metadata(&signer, token.into(), metadata_field(&field), &value)],
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Cli, clap::Error> {
+        Cli::try_parse_from(std::iter::once("casino-cli").chain(args.iter().copied()))
+    }
+
+    #[test]
+    fn cli_definition_is_valid() {
+        use clap::CommandFactory;
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn global_options_have_defaults() {
+        let cli = parse(&["show"]).unwrap();
+        assert_eq!(cli.rpc_url, "http://127.0.0.1:8899");
+        assert!(cli.house.is_none());
+        assert!(!cli.dry_run);
+
+        // --dry-run is accepted after the subcommand too
+        assert!(parse(&["pause", "--dry-run"]).unwrap().dry_run);
+    }
+
+    #[test]
+    fn subcommands_parse_their_arguments() {
+        let (one, two) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let cli = parse(&["create-fight", "7", &one.to_string(), &two.to_string()]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Command::CreateFight { fight_id: 7, fighter_one, fighter_two } if fighter_one == one && fighter_two == two
+        ));
+
+        let cli = parse(&["post-result", "7", &one.to_string(), "--no-settle"]).unwrap();
+        assert!(matches!(cli.command, Command::PostResult { fight_id: 7, no_settle: true, .. }));
+
+        let cli = parse(&["clawback", "7", &one.to_string(), "rapr", "1.5"]).unwrap();
+        assert!(matches!(cli.command, Command::Clawback { token: TokenArg::Rapr, ref amount, .. } if amount == "1.5"));
+
+        let cli = parse(&["harvest-fees", "--source", &one.to_string(), "--source", &two.to_string()]).unwrap();
+        assert!(matches!(cli.command, Command::HarvestFees { ref sources } if *sources == vec![one, two]));
+
+        let cli = parse(&["mint-price", "queue", &one.to_string(), "--sol-per-token", "0.05"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Command::MintPrice { action: MintPriceAction::Queue { ref sol_per_token, usd_feed: None, .. } }
+                if sol_per_token == "0.05"
+        ));
+        assert!(parse(&["mint-price", "queue", &one.to_string()]).is_err());
+
+        assert!(parse(&["create-fight", "7", "not-a-key", &two.to_string()]).is_err());
+        assert!(parse(&["clawback", "7", &one.to_string(), "sol", "1"]).is_err());
+    }
+
+    #[test]
+    fn oracle_rates_need_both_token_feeds() {
+        let (feed, program) = (Pubkey::new_unique().to_string(), Pubkey::new_unique().to_string());
+        let base = ["oracle", "queue", feed.as_str(), "--oracle-program", program.as_str()];
+        let cli = parse(&base).unwrap();
+        assert!(matches!(
+            cli.command,
+            Command::Oracle {
+                action: OracleAction::Queue { max_age: 60, max_confidence: 200, oracle_rates: false, dumbs_usd_feed: None, .. }
+            }
+        ));
+
+        let mut args = base.to_vec();
+        args.extend(["--oracle-rates", "--dumbs-usd-feed", feed.as_str()]);
+        assert!(parse(&args).is_err());
+        args.extend(["--rapr-usd-feed", feed.as_str()]);
+        assert!(matches!(
+            parse(&args).unwrap().command,
+            Command::Oracle { action: OracleAction::Queue { oracle_rates: true, .. } }
+        ));
+        assert!(matches!(parse(&["oracle", "execute"]).unwrap().command, Command::Oracle { action: OracleAction::Execute }));
+    }
+
+    #[test]
+    fn config_values_are_read_in_their_units() {
+        assert_eq!(ConfigParam::HouseFee.change("250").unwrap(), ConfigChange::HouseFee(250));
+        assert_eq!(ConfigParam::MaxBet.change("1.5").unwrap(), ConfigChange::MaxBet(1_500_000_000));
+        assert_eq!(ConfigParam::SolRaprRate.change("42").unwrap(), ConfigChange::SolRaprRate(42));
+        assert!(ConfigParam::HouseFee.change("2.5").is_err());
+        assert!(ConfigParam::DumbsMaxSupply.change("lots").is_err());
+
+        let cli = parse(&["config", "queue", "dumbs-epoch-budget", "100"]).unwrap();
+        let Command::Config { action: ConfigAction::Queue { param, value } } = cli.command else {
+            panic!("config queue did not parse");
+        };
+        assert_eq!(param.change(&value).unwrap(), ConfigChange::DumbsEpochBudget(100_000_000_000));
+    }
+
+    #[test]
+    fn every_config_param_has_its_own_kind() {
+        let mut kinds: Vec<u8> = CONFIG_PARAMS.iter().map(|param| param.kind()).collect();
+        kinds.sort_unstable();
+        kinds.dedup();
+        assert_eq!(kinds.len(), CONFIG_PARAMS.len());
+    }
+
+    #[test]
+    fn metadata_fields_map_to_named_or_custom_keys() {
+        assert!(matches!(metadata_field("uri"), MetadataField::Uri));
+        assert!(matches!(metadata_field("rate"), MetadataField::Key(key) if key == "rate"));
+    }
+}
@@ -0,0 +1,76 @@
+use anyhow::{anyhow, Result};
+use casino_client::{program_error, CasinoClient, ClientError};
+use solana_client::{
+    client_error::ClientErrorKind,
+    rpc_request::{RpcError, RpcResponseErrorData},
+};
+use solana_sdk::{instruction::Instruction, transaction::TransactionError};
+
+/// Sends transactions, or only simulates them when `dry_run` is set.
+pub struct Runner {
+    pub client: CasinoClient,
+    pub dry_run: bool,
+}
+
+impl Runner {
+    pub fn run(&self, label: &str, instructions: &[Instruction]) -> Result<()> {
+        if self.dry_run {
+            let result = self.client.simulate(instructions, &[])?;
+            if let Some(err) = result.err {
+                print_logs(result.logs.as_deref());
+                return Err(anyhow!("{} would fail: {}", label, describe(&err)));
+            }
+            println!(
+                "{}: simulation succeeded ({} compute units)",
+                label,
+                result.units_consumed.unwrap_or_default()
+            );
+            return Ok(());
+        }
+
+        match self.client.send(instructions, &[]) {
+            Ok(signature) => {
+                println!("{}: {}", label, signature);
+                Ok(())
+            }
+            Err(err) => {
+                print_logs(preflight_logs(&err));
+                let reason = match &err {
+                    ClientError::Rpc(rpc) => rpc
+                        .get_transaction_error()
+                        .map(|e| describe(&e))
+                        .unwrap_or_else(|| err.to_string()),
+                    _ => err.to_string(),
+                };
+                Err(anyhow!("{} failed: {}", label, reason))
+            }
+        }
+    }
+}
+
+/// Names betting program errors as declared in `error_code.rs`.
+fn describe(err: &TransactionError) -> String {
+    match program_error(err) {
+        Some(code) => format!("{} ({}): {}", code.name(), u32::from(code), code),
+        None => err.to_string(),
+    }
+}
+
+fn preflight_logs(err: &ClientError) -> Option<&[String]> {
+    let ClientError::Rpc(rpc) = err else {
+        return None;
+    };
+    match rpc.kind() {
+        ClientErrorKind::RpcError(RpcError::RpcResponseError {
+            data: RpcResponseErrorData::SendTransactionPreflightFailure(result),
+            ..
+        }) => result.logs.as_deref(),
+        _ => None,
+    }
+}
+
+fn print_logs(logs: Option<&[String]>) {
+    for line in logs.unwrap_or_default() {
+        eprintln!("  {}", line);
+    }
+}
@@ -0,0 +1,95 @@
+use anyhow::{anyhow, Result};
+use casino_client::{BasisPoints, Lamports, Odds};
+
+/// Formats a base-unit amount as a decimal string, e.g. `1500000000` -> `1.5`.
+/// SOL, DUMBS and RAPR all use 9 decimals.
+pub fn format_amount(amount: u64) -> String {
+    Lamports::new(amount).to_string()
+}
+
+/// Parses a decimal string into base units without going through floats.
+pub fn parse_amount(input: &str) -> Result<u64> {
+    input
+        .parse::<Lamports>()
+        .map(Lamports::base_units)
+        .map_err(|err| anyhow!("invalid amount {:?}: {:?}", input, err))
+}
+
+/// Formats millionths of a dollar as dollars and cents, e.g. `-1234567` -> `-$1.23`.
+pub fn format_usd(micro_usd: i64) -> String {
+    let sign = if micro_usd < 0 { "-" } else { "" };
+    let cents = micro_usd.unsigned_abs() / 10_000;
+    format!("{}${}.{:02}", sign, cents / 100, cents % 100)
+}
+
+/// Formats an oracle price `price · 10^expo` as a decimal string.
+pub fn format_price(price: i64, expo: i32) -> String {
+    if expo >= 0 {
+        return (price as i128 * 10i128.pow(expo as u32)).to_string();
+    }
+    let scale = 10u128.pow(expo.unsigned_abs());
+    let sign = if price < 0 { "-" } else { "" };
+    let price = price.unsigned_abs() as u128;
+    format!("{}{}.{:0width$}", sign, price / scale, price % scale, width = expo.unsigned_abs() as usize)
+}
+
+/// Formats basis points as a percentage, e.g. `250` -> `2.50%`.
+pub fn format_bps(bps: u64) -> String {
+    match BasisPoints::from_u64(bps) {
+        Ok(bps) => bps.to_string(),
+        Err(_) => format!("{} bps", bps),
+    }
+}
+
+/// Formats decimal odds in basis points, e.g. `15000` -> `1.50x`.
+pub fn format_odds(odds: u32) -> String {
+    match Odds::from_bps(odds) {
+        Ok(odds) => odds.to_string(),
+        Err(_) => format!("{} bps", odds),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn amounts_round_trip_through_decimal_strings() {
+        for (units, text) in [(1_500_000_000, "1.5"), (1, "0.000000001"), (2_000_000_000, "2"), (0, "0")] {
+            assert_eq!(format_amount(units), text);
+            assert_eq!(parse_amount(text).unwrap(), units);
+        }
+        assert_eq!(parse_amount(".25").unwrap(), 250_000_000);
+    }
+
+    #[test]
+    fn malformed_amounts_are_rejected() {
+        for input in ["", ".", "1.0000000001", "-1", "1e9", "abc", "18446744073.709551616"] {
+            assert!(parse_amount(input).is_err(), "{:?} parsed", input);
+        }
+    }
+
+    #[test]
+    fn usd_is_shown_in_dollars_and_cents() {
+        assert_eq!(format_usd(1_234_567), "$1.23");
+        assert_eq!(format_usd(-1_234_567), "-$1.23");
+        assert_eq!(format_usd(9_999), "$0.00");
+        assert_eq!(format_usd(0), "$0.00");
+    }
+
+    #[test]
+    fn prices_apply_their_exponent() {
+        assert_eq!(format_price(15_000_000_000, -8), "150.00000000");
+        assert_eq!(format_price(-5, -2), "-0.05");
+        assert_eq!(format_price(12, 3), "12000");
+    }
+
+    #[test]
+    fn bps_and_odds_fall_back_to_raw_values() {
+        assert_eq!(format_bps(250), "2.50%");
+        assert_eq!(format_bps(u64::MAX), format!("{} bps", u64::MAX));
+        assert_eq!(format_odds(15_000), "1.50x");
+        assert_eq!(format_odds(12_345), "1.2345x");
+        assert_eq!(format_odds(9_999), "9999 bps");
+    }
+}
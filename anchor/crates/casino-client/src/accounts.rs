@@ -0,0 +1,50 @@
+//! Account deserializers for every program-owned account type.
+
+use anchor_lang::AccountDeserialize;
+use anchor_spl::token_2022::spl_token_2022::{
+    extension::{transfer_hook::TransferHook, BaseStateWithExtensions, StateWithExtensions},
+    state::Mint,
+};
+use solana_sdk::pubkey::Pubkey;
+
+pub use betting_program::state::{
+    AssetBet, BetHistory, BettingState, Fight, HistoryEntry, LockPeriod, OracleConfig, PendingConfigChange, RaprLock, RoleRegistry, SolVault,
+    SupportedMint, Treasury, UserBettingAccount, UserLimits, WinRecord,
+};
+pub use anchor_spl::token_interface::spl_token_metadata_interface::state::TokenMetadata;
+pub use rapr_transfer_hook::state::{HolderVolume, LoyaltyTier};
+pub use nft_game_agent_program::state::{
+    AIAgent, Collection, Treasury as AgentTreasury,
+};
+
+use crate::error::{ClientError, Result};
+
+/// Decodes raw account data, checking the Anchor discriminator.
+pub fn decode<T: AccountDeserialize>(address: &Pubkey, data: &[u8]) -> Result<T> {
+    let mut slice = data;
+    T::try_deserialize(&mut slice).map_err(|source| ClientError::Deserialize {
+        address: *address,
+        source: Box::new(source),
+    })
+}
+
+/// Decodes the Token-2022 metadata stored in a mint's own account, if any.
+pub fn decode_token_metadata(address: &Pubkey, data: &[u8]) -> Result<Option<TokenMetadata>> {
+    let mint = StateWithExtensions::<Mint>::unpack(data).map_err(|err| ClientError::Deserialize {
+        address: *address,
+        source: Box::new(err.into()),
+    })?;
+    Ok(mint.get_variable_len_extension::<TokenMetadata>().ok())
+}
+
+/// The transfer hook program a mint's TransferHook extension names, if any.
+pub fn decode_transfer_hook_program(address: &Pubkey, data: &[u8]) -> Result<Option<Pubkey>> {
+    let mint = StateWithExtensions::<Mint>::unpack(data).map_err(|err| ClientError::Deserialize {
+        address: *address,
+        source: Box::new(err.into()),
+    })?;
+    Ok(mint
+        .get_extension::<TransferHook>()
+        .ok()
+        .and_then(|hook| Option::<Pubkey>::from(hook.program_id)))
+}
@@ -0,0 +1,525 @@
+use anchor_lang::{AccountDeserialize, AnchorDeserialize, Discriminator};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use betting_program::state::betting_state::TokenType;
+use betting_program::state::{BetQuote, CashOutQuote, DepositQuote, HousePnl, LimitSettings, SwapQuote};
+use casino_math::Odds;
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::{
+    rpc_client::RpcClient,
+    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+    rpc_filter::{Memcmp, RpcFilterType},
+    rpc_response::RpcSimulateTransactionResult,
+};
+use solana_transaction_status::UiReturnDataEncoding;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signature},
+    signer::Signer,
+    transaction::Transaction,
+};
+
+use crate::accounts::{
+    self, AssetBet, BetHistory, BettingState, HolderVolume, TokenMetadata, Fight, LockPeriod, OracleConfig, PendingConfigChange, RaprLock, RoleRegistry, SolVault, SupportedMint, Treasury, UserBettingAccount, UserLimits, WinRecord,
+};
+use crate::error::{ClientError, Result};
+use crate::instructions::{self, create_token_2022_ata, House, OracleFeeds, DEFAULT_MAX_SLIPPAGE};
+use crate::pda;
+
+// Offsets into UserBettingAccount data used to filter open bets server-side:
+// discriminator (8) + owner (32), then the Option<Bet> tag, then Bet fields.
+const ACTIVE_BET_TAG_OFFSET: usize = 8 + 32;
+const ACTIVE_BET_FIGHT_ID_OFFSET: usize = ACTIVE_BET_TAG_OFFSET + 1 + 32 + 1 + 8;
+
+// AssetBet data: discriminator (8) + bettor (32) + supported_mint (32), then fight_id.
+const ASSET_BET_FIGHT_ID_OFFSET: usize = 8 + 32 + 32;
+
+/// High-level client that pays for and signs transactions with `payer`.
+pub struct CasinoClient {
+    rpc: RpcClient,
+    payer: Keypair,
+    house: House,
+}
+
+impl CasinoClient {
+    /// Connects to an existing house, reading its mints from `BettingState`,
+    /// whether RAPR runs the transfer hook from the RAPR mint and whether it
+    /// prices through oracle rates from its oracle config.
+    pub fn connect(rpc_url: impl ToString, payer: Keypair, house_authority: Pubkey) -> Result<Self> {
+        let rpc = RpcClient::new_with_commitment(rpc_url.to_string(), CommitmentConfig::confirmed());
+        let address = pda::betting_state(&house_authority).0;
+        let state: BettingState = fetch(&rpc, &address)?;
+        let mut house = House::from_state(&state);
+
+        let rapr_mint = rpc
+            .get_account_with_commitment(&house.rapr_mint, rpc.commitment())?
+            .value
+            .ok_or(ClientError::AccountNotFound(house.rapr_mint))?;
+        if accounts::decode_transfer_hook_program(&house.rapr_mint, &rapr_mint.data)? == Some(rapr_transfer_hook::ID) {
+            house = house.with_rapr_transfer_hook();
+        }
+        match fetch::<OracleConfig>(&rpc, &house.oracle_config()) {
+            // Asset cash-outs read the feeds even while rates are fixed
+            Ok(config) if config.sol_usd_feed != Pubkey::default() => {
+                house = house.with_oracle_feeds(OracleFeeds::from_params(&config.params()));
+            }
+            Ok(_) | Err(ClientError::AccountNotFound(_)) => {}
+            Err(err) => return Err(err),
+        }
+
+        Ok(Self { rpc, payer, house })
+    }
+
+    /// Uses a known house without reading chain state, e.g. before it is bootstrapped.
+    pub fn with_house(rpc_url: impl ToString, payer: Keypair, house: House) -> Self {
+        Self {
+            rpc: RpcClient::new_with_commitment(rpc_url.to_string(), CommitmentConfig::confirmed()),
+            payer,
+            house,
+        }
+    }
+
+    pub fn rpc(&self) -> &RpcClient {
+        &self.rpc
+    }
+
+    pub fn payer(&self) -> &Keypair {
+        &self.payer
+    }
+
+    pub fn house(&self) -> &House {
+        &self.house
+    }
+
+    // Account reads
+
+    pub fn account<T: AccountDeserialize>(&self, address: &Pubkey) -> Result<T> {
+        fetch(&self.rpc, address)
+    }
+
+    pub fn betting_state(&self) -> Result<BettingState> {
+        self.account(&self.house.betting_state)
+    }
+
+    pub fn treasury(&self) -> Result<Treasury> {
+        self.account(&pda::treasury().0)
+    }
+
+    pub fn sol_vault(&self) -> Result<SolVault> {
+        self.account(&pda::sol_vault().0)
+    }
+
+    pub fn roles(&self) -> Result<RoleRegistry> {
+        self.account(&self.house.roles())
+    }
+
+    pub fn user_betting_account(&self, owner: &Pubkey) -> Result<UserBettingAccount> {
+        self.account(&pda::user_betting_account(owner).0)
+    }
+
+    /// Limits `owner` has set on themselves, if any.
+    pub fn user_limits(&self, owner: &Pubkey) -> Result<Option<UserLimits>> {
+        match self.account(&pda::user_limits(owner).0) {
+            Ok(limits) => Ok(Some(limits)),
+            Err(ClientError::AccountNotFound(_)) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// `owner`'s on-chain bet history, if they have created one.
+    pub fn bet_history(&self, owner: &Pubkey) -> Result<Option<BetHistory>> {
+        match self.account(&pda::bet_history(owner).0) {
+            Ok(history) => Ok(Some(history)),
+            Err(ClientError::AccountNotFound(_)) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// RAPR `owner` has locked for boosted odds, if any.
+    pub fn rapr_lock(&self, owner: &Pubkey) -> Result<Option<RaprLock>> {
+        match self.account(&pda::rapr_lock(owner).0) {
+            Ok(lock) => Ok(Some(lock)),
+            Err(ClientError::AccountNotFound(_)) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// RAPR volume the transfer hook has recorded for `owner`, if they opted in.
+    pub fn holder_volume(&self, owner: &Pubkey) -> Result<Option<HolderVolume>> {
+        match self.account(&pda::holder_volume(owner).0) {
+            Ok(volume) => Ok(Some(volume)),
+            Err(ClientError::AccountNotFound(_)) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Metadata of the `token_type` mint, if it has been written.
+    pub fn token_metadata(&self, token_type: TokenType) -> Result<Option<TokenMetadata>> {
+        let mint = self.house.mint(token_type);
+        let account = self
+            .rpc
+            .get_account_with_commitment(&mint, self.rpc.commitment())?
+            .value
+            .ok_or(ClientError::AccountNotFound(mint))?;
+        accounts::decode_token_metadata(&mint, &account.data)
+    }
+
+    pub fn fight(&self, fight_id: u64) -> Result<Fight> {
+        self.account(&pda::fight(&self.house.betting_state, fight_id).0)
+    }
+
+    /// What `bettor` was paid for winning `fight_id`, if the house keeps win
+    /// records for clawbacks.
+    pub fn win_record(&self, fight_id: u64, bettor: &Pubkey) -> Result<Option<WinRecord>> {
+        match self.account(&pda::win_record(&self.house.betting_state, fight_id, bettor).0) {
+            Ok(record) => Ok(Some(record)),
+            Err(ClientError::AccountNotFound(_)) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// The house's price feed settings, if an admin has configured them.
+    pub fn oracle_config(&self) -> Result<Option<OracleConfig>> {
+        match self.account(&pda::oracle_config(&self.house.betting_state).0) {
+            Ok(config) => Ok(Some(config)),
+            Err(ClientError::AccountNotFound(_)) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Registry entry for `mint`, if the house takes bets in it.
+    pub fn supported_mint(&self, mint: &Pubkey) -> Result<Option<SupportedMint>> {
+        match self.account(&pda::supported_mint(&self.house.betting_state, mint).0) {
+            Ok(asset) => Ok(Some(asset)),
+            Err(ClientError::AccountNotFound(_)) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Every mint registered with this house, keyed by mint.
+    pub fn supported_mints(&self) -> Result<Vec<(Pubkey, SupportedMint)>> {
+        let filters = vec![
+            RpcFilterType::DataSize(SupportedMint::LEN as u64),
+            RpcFilterType::Memcmp(Memcmp::new_raw_bytes(0, SupportedMint::DISCRIMINATOR.to_vec())),
+            RpcFilterType::Memcmp(Memcmp::new_raw_bytes(8, self.house.betting_state.to_bytes().to_vec())),
+        ];
+        let config = RpcProgramAccountsConfig {
+            filters: Some(filters),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                commitment: Some(self.rpc.commitment()),
+                ..RpcAccountInfoConfig::default()
+            },
+            ..RpcProgramAccountsConfig::default()
+        };
+
+        let mut mints = Vec::new();
+        for (address, account) in self.rpc.get_program_accounts_with_config(&betting_program::ID, config)? {
+            let asset: SupportedMint = accounts::decode(&address, &account.data)?;
+            mints.push((asset.mint, asset));
+        }
+        Ok(mints)
+    }
+
+    /// Unsettled bets on `fight_id`, keyed by bettor.
+    pub fn open_bets(&self, fight_id: u64) -> Result<Vec<(Pubkey, UserBettingAccount)>> {
+        let filters = vec![
+            RpcFilterType::DataSize(UserBettingAccount::LEN as u64),
+            RpcFilterType::Memcmp(Memcmp::new_raw_bytes(0, UserBettingAccount::DISCRIMINATOR.to_vec())),
+            RpcFilterType::Memcmp(Memcmp::new_raw_bytes(ACTIVE_BET_TAG_OFFSET, vec![1])),
+            RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+                ACTIVE_BET_FIGHT_ID_OFFSET,
+                (fight_id as u32).to_le_bytes().to_vec(),
+            )),
+        ];
+        let config = RpcProgramAccountsConfig {
+            filters: Some(filters),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                commitment: Some(self.rpc.commitment()),
+                ..RpcAccountInfoConfig::default()
+            },
+            ..RpcProgramAccountsConfig::default()
+        };
+
+        let mut bets = Vec::new();
+        for (address, account) in self.rpc.get_program_accounts_with_config(&betting_program::ID, config)? {
+            let user: UserBettingAccount = accounts::decode(&address, &account.data)?;
+            if user.active_bet.as_ref().is_some_and(|bet| !bet.settled) {
+                bets.push((user.owner, user));
+            }
+        }
+        Ok(bets)
+    }
+
+    /// Open bets on `fight_id` in registered mints, keyed by bettor. Asset
+    /// bets are closed on settlement, so every one found is unsettled.
+    pub fn open_asset_bets(&self, fight_id: u64) -> Result<Vec<(Pubkey, AssetBet)>> {
+        let filters = vec![
+            RpcFilterType::DataSize(AssetBet::LEN as u64),
+            RpcFilterType::Memcmp(Memcmp::new_raw_bytes(0, AssetBet::DISCRIMINATOR.to_vec())),
+            RpcFilterType::Memcmp(Memcmp::new_raw_bytes(ASSET_BET_FIGHT_ID_OFFSET, fight_id.to_le_bytes().to_vec())),
+        ];
+        let config = RpcProgramAccountsConfig {
+            filters: Some(filters),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                commitment: Some(self.rpc.commitment()),
+                ..RpcAccountInfoConfig::default()
+            },
+            ..RpcProgramAccountsConfig::default()
+        };
+
+        let mut bets = Vec::new();
+        for (address, account) in self.rpc.get_program_accounts_with_config(&betting_program::ID, config)? {
+            let bet: AssetBet = accounts::decode(&address, &account.data)?;
+            bets.push((bet.bettor, bet));
+        }
+        Ok(bets)
+    }
+
+    /// Pending change for a parameter kind, if one is queued.
+    pub fn pending_change(&self, kind: u8) -> Result<Option<PendingConfigChange>> {
+        match self.account(&pda::pending_change(&self.house.betting_state, kind).0) {
+            Ok(change) => Ok(Some(change)),
+            Err(ClientError::AccountNotFound(_)) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Pending price change for a registered mint, if one is queued.
+    pub fn pending_mint_price(&self, mint: &Pubkey) -> Result<Option<PendingConfigChange>> {
+        match self.account(&pda::pending_mint_price(&self.house.betting_state, mint).0) {
+            Ok(change) => Ok(Some(change)),
+            Err(ClientError::AccountNotFound(_)) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    // Transactions
+
+    pub fn transaction(&self, instructions: &[Instruction], signers: &[&Keypair]) -> Result<Transaction> {
+        let blockhash = self.rpc.get_latest_blockhash()?;
+        let mut all_signers: Vec<&dyn Signer> = vec![&self.payer];
+        all_signers.extend(signers.iter().map(|s| *s as &dyn Signer));
+        Ok(Transaction::new_signed_with_payer(
+            instructions,
+            Some(&self.payer.pubkey()),
+            &all_signers,
+            blockhash,
+        ))
+    }
+
+    pub fn send(&self, instructions: &[Instruction], signers: &[&Keypair]) -> Result<Signature> {
+        let transaction = self.transaction(instructions, signers)?;
+        Ok(self.rpc.send_and_confirm_transaction(&transaction)?)
+    }
+
+    pub fn simulate(&self, instructions: &[Instruction], signers: &[&Keypair]) -> Result<RpcSimulateTransactionResult> {
+        let transaction = self.transaction(instructions, signers)?;
+        Ok(self.rpc.simulate_transaction(&transaction)?.value)
+    }
+
+    /// Simulates a quote instruction and decodes the value it returned.
+    pub fn quote<T: AnchorDeserialize>(&self, instruction: Instruction) -> Result<T> {
+        let result = self.simulate(&[instruction], &[])?;
+        if let Some(err) = result.err {
+            return Err(ClientError::Simulation(err));
+        }
+        let (data, encoding) = result.return_data.ok_or(ClientError::MissingReturnData)?.data;
+        if encoding != UiReturnDataEncoding::Base64 {
+            return Err(ClientError::MissingReturnData);
+        }
+        let bytes = BASE64.decode(data).map_err(|_| ClientError::MissingReturnData)?;
+        T::try_from_slice(&bytes).map_err(|_| ClientError::MissingReturnData)
+    }
+
+    pub fn quote_bet(&self, amount: u64, odds: Odds, token_type: TokenType) -> Result<BetQuote> {
+        self.quote(self.house.quote_bet(&self.payer.pubkey(), amount, odds, token_type))
+    }
+
+    pub fn quote_deposit(&self, amount: u64) -> Result<DepositQuote> {
+        self.quote(self.house.quote_deposit(amount))
+    }
+
+    pub fn quote_cash_out(&self, amount: u64, token_type: TokenType) -> Result<CashOutQuote> {
+        self.quote(self.house.quote_cash_out(amount, token_type))
+    }
+
+    pub fn quote_swap(&self, sol_amount: u64) -> Result<SwapQuote> {
+        self.quote(self.house.quote_swap(sol_amount))
+    }
+
+    /// House P&L at the configured SOL/USD feed's current price.
+    pub fn house_pnl(&self) -> Result<HousePnl> {
+        let config = self
+            .oracle_config()?
+            .ok_or(ClientError::AccountNotFound(pda::oracle_config(&self.house.betting_state).0))?;
+        self.quote(self.house.quote_house_pnl(&config.sol_usd_feed))
+    }
+
+    // Composed user flows, each preceded by idempotent ATA creation. Trades
+    // are quoted first and fail if they would fill more than
+    // `DEFAULT_MAX_SLIPPAGE` below the quote.
+
+    pub fn deposit_sol_instructions(&self, amount: u64) -> Result<Vec<Instruction>> {
+        let user = self.payer.pubkey();
+        let quoted = self.quote_deposit(amount)?.dumbs_minted;
+        Ok(vec![
+            create_token_2022_ata(&user, &user, &self.house.dumbs_mint),
+            self.house.deposit_sol_with_slippage(&user, amount, quoted, DEFAULT_MAX_SLIPPAGE),
+        ])
+    }
+
+    pub fn deposit_sol(&self, amount: u64) -> Result<Signature> {
+        self.send(&self.deposit_sol_instructions(amount)?, &[])
+    }
+
+    pub fn swap_sol_for_rapr_instructions(&self, sol_amount: u64) -> Result<Vec<Instruction>> {
+        let user = self.payer.pubkey();
+        let quoted = self.quote_swap(sol_amount)?.rapr_minted;
+        Ok(vec![
+            create_token_2022_ata(&user, &user, &self.house.rapr_mint),
+            self.house.swap_sol_for_rapr_with_slippage(&user, sol_amount, quoted, DEFAULT_MAX_SLIPPAGE),
+        ])
+    }
+
+    pub fn swap_sol_for_rapr(&self, sol_amount: u64) -> Result<Signature> {
+        self.send(&self.swap_sol_for_rapr_instructions(sol_amount)?, &[])
+    }
+
+    /// Backs `fighter` in the open fight `fight_id`.
+    pub fn place_bet_instructions(&self, amount: u64, fight_id: u64, fighter: Pubkey, odds: Odds, token_type: TokenType) -> Vec<Instruction> {
+        let user = self.payer.pubkey();
+        vec![
+            create_token_2022_ata(&user, &user, &self.house.dumbs_mint),
+            create_token_2022_ata(&user, &user, &self.house.rapr_mint),
+            self.house.place_bet(&user, amount, fight_id, fighter, odds, token_type),
+        ]
+    }
+
+    pub fn place_bet(&self, amount: u64, fight_id: u64, fighter: Pubkey, odds: Odds, token_type: TokenType) -> Result<Signature> {
+        self.send(&self.place_bet_instructions(amount, fight_id, fighter, odds, token_type), &[])
+    }
+
+    pub fn place_bet_from_balance(&self, amount: u64, fight_id: u64, fighter: Pubkey, odds: Odds, token_type: TokenType) -> Result<Signature> {
+        let user = self.payer.pubkey();
+        self.send(&[self.house.place_bet_from_balance(&user, amount, fight_id, fighter, odds, token_type)], &[])
+    }
+
+    pub fn deposit_to_balance(&self, amount: u64, token_type: TokenType) -> Result<Signature> {
+        let user = self.payer.pubkey();
+        self.send(&[self.house.deposit_to_balance(&user, amount, token_type)], &[])
+    }
+
+    pub fn withdraw_from_balance_instructions(&self, amount: u64, token_type: TokenType) -> Vec<Instruction> {
+        let user = self.payer.pubkey();
+        vec![
+            create_token_2022_ata(&user, &user, &self.house.mint(token_type)),
+            self.house.withdraw_from_balance(&user, amount, token_type),
+        ]
+    }
+
+    pub fn withdraw_from_balance(&self, amount: u64, token_type: TokenType) -> Result<Signature> {
+        self.send(&self.withdraw_from_balance_instructions(amount, token_type), &[])
+    }
+
+    /// Tightened limits apply at once; looser ones after `LIMIT_LOOSEN_DELAY`.
+    pub fn set_limits(&self, limits: LimitSettings) -> Result<Signature> {
+        let user = self.payer.pubkey();
+        self.send(&[self.house.set_limits(&user, limits)], &[])
+    }
+
+    pub fn create_bet_history(&self) -> Result<Signature> {
+        let payer = self.payer.pubkey();
+        self.send(&[self.house.create_bet_history(&payer)], &[])
+    }
+
+    pub fn create_holder_volume(&self) -> Result<Signature> {
+        let payer = self.payer.pubkey();
+        self.send(&[instructions::create_holder_volume(&payer, &payer)], &[])
+    }
+
+    /// Pays what this epoch's emission budget allows of the payer's queued winnings.
+    pub fn claim_queued_winnings(&self) -> Result<Signature> {
+        let bettor = self.payer.pubkey();
+        self.send(&[self.house.claim_queued_winnings(&bettor)], &[])
+    }
+
+    pub fn lock_rapr(&self, amount: u64, period: LockPeriod) -> Result<Signature> {
+        let owner = self.payer.pubkey();
+        self.send(&[self.house.lock_rapr(&owner, amount, period)], &[])
+    }
+
+    /// Early unlocks pay `EARLY_UNLOCK_PENALTY` to the treasury.
+    pub fn unlock_rapr(&self) -> Result<Signature> {
+        let owner = self.payer.pubkey();
+        self.send(&[self.house.unlock_rapr(&owner)], &[])
+    }
+
+    pub fn place_asset_bet(&self, mint: &Pubkey, amount: u64, fight_id: u64, fighter: Pubkey, odds: Odds) -> Result<Signature> {
+        let bettor = self.payer.pubkey();
+        let asset = self.supported_mint(mint)?.ok_or(ClientError::AccountNotFound(*mint))?;
+        self.send(&[self.house.place_asset_bet(&bettor, &asset, amount, fight_id, fighter, odds)], &[])
+    }
+
+    pub fn cash_out_asset(&self, mint: &Pubkey, amount: u64) -> Result<Signature> {
+        let user = self.payer.pubkey();
+        let asset = self.supported_mint(mint)?.ok_or(ClientError::AccountNotFound(*mint))?;
+        self.send(&[self.house.cash_out_asset(&user, &asset, amount)], &[])
+    }
+
+    pub fn self_exclude(&self, duration: Option<i64>) -> Result<Signature> {
+        let user = self.payer.pubkey();
+        self.send(&[self.house.self_exclude(&user, duration)], &[])
+    }
+
+    pub fn cash_out_instructions(&self, amount: u64, token_type: TokenType) -> Result<Vec<Instruction>> {
+        let user = self.payer.pubkey();
+        let quoted = self.quote_cash_out(amount, token_type)?.sol_returned;
+        Ok(vec![
+            create_token_2022_ata(&user, &user, &self.house.dumbs_mint),
+            create_token_2022_ata(&user, &user, &self.house.rapr_mint),
+            self.house.cash_out_with_slippage(&user, amount, token_type, quoted, DEFAULT_MAX_SLIPPAGE),
+        ])
+    }
+
+    pub fn cash_out(&self, amount: u64, token_type: TokenType) -> Result<Signature> {
+        self.send(&self.cash_out_instructions(amount, token_type)?, &[])
+    }
+
+    /// Settles `bettor`'s bet against the posted result, with the payer
+    /// acting as settler.
+    pub fn settle_bet_instructions(&self, bettor: &Pubkey, fight_id: u64) -> Vec<Instruction> {
+        let settler = self.payer.pubkey();
+        vec![
+            create_token_2022_ata(&settler, bettor, &self.house.dumbs_mint),
+            create_token_2022_ata(&settler, bettor, &self.house.rapr_mint),
+            self.house.settle_bet(&settler, bettor, fight_id),
+        ]
+    }
+
+    pub fn settle_bet(&self, bettor: &Pubkey, fight_id: u64) -> Result<Signature> {
+        self.send(&self.settle_bet_instructions(bettor, fight_id), &[])
+    }
+
+    /// Settles an open asset bet against the posted result. The registry
+    /// entry is looked up from the bet.
+    pub fn settle_asset_bet_instruction(&self, bet: &AssetBet) -> Result<Instruction> {
+        let asset: SupportedMint = self.account(&bet.supported_mint)?;
+        Ok(self.house.settle_asset_bet(&self.payer.pubkey(), &bet.bettor, &asset, bet.fight_id))
+    }
+
+    pub fn settle_asset_bet(&self, bet: &AssetBet) -> Result<Signature> {
+        self.send(&[self.settle_asset_bet_instruction(bet)?], &[])
+    }
+}
+
+fn fetch<T: AccountDeserialize>(rpc: &RpcClient, address: &Pubkey) -> Result<T> {
+    let account = rpc
+        .get_account_with_commitment(address, rpc.commitment())?
+        .value
+        .ok_or(ClientError::AccountNotFound(*address))?;
+    accounts::decode(address, &account.data)
+}
@@ -0,0 +1,143 @@
+use betting_program::errors::error_code::ErrorCode;
+use solana_sdk::{instruction::InstructionError, transaction::TransactionError};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ClientError {
+    #[error("rpc error: {0}")]
+    Rpc(Box<solana_client::client_error::ClientError>),
+
+    #[error("account {0} does not exist")]
+    AccountNotFound(solana_sdk::pubkey::Pubkey),
+
+    #[error("failed to deserialize account {address}: {source}")]
+    Deserialize {
+        address: solana_sdk::pubkey::Pubkey,
+        source: Box<anchor_lang::error::Error>,
+    },
+
+    #[error("simulation failed: {0}")]
+    Simulation(TransactionError),
+
+    #[error("instruction returned no data")]
+    MissingReturnData,
+
+    #[error("invalid amount or odds: {0:?}")]
+    Math(casino_math::MathError),
+}
+
+impl From<solana_client::client_error::ClientError> for ClientError {
+    fn from(err: solana_client::client_error::ClientError) -> Self {
+        ClientError::Rpc(Box::new(err))
+    }
+}
+
+impl From<casino_math::MathError> for ClientError {
+    fn from(err: casino_math::MathError) -> Self {
+        ClientError::Math(err)
+    }
+}
+
+impl ClientError {
+    /// The betting program error that failed the transaction, if any.
+    pub fn program_error(&self) -> Option<ErrorCode> {
+        match self {
+            ClientError::Rpc(err) => err.get_transaction_error().as_ref().and_then(program_error),
+            ClientError::Simulation(err) => program_error(err),
+            _ => None,
+        }
+    }
+}
+
+pub type Result<T> = std::result::Result<T, ClientError>;
+
+/// Every betting program error in declaration order, so a custom error
+/// number can be mapped back to its variant. Keep in sync with `error_code.rs`.
+const BETTING_ERRORS: &[ErrorCode] = &[
+    ErrorCode::Unauthorized,
+    ErrorCode::AccountAlreadyInitialized,
+    ErrorCode::NotInitialized,
+    ErrorCode::InvalidAccountData,
+    ErrorCode::CalculationOverflow,
+    ErrorCode::InvalidAccount,
+    ErrorCode::InvalidBettor,
+    ErrorCode::BetAlreadyPlaced,
+    ErrorCode::BetAlreadySettled,
+    ErrorCode::BetNotSettled,
+    ErrorCode::BetNotFound,
+    ErrorCode::InvalidFightId,
+    ErrorCode::InsufficientBalance,
+    ErrorCode::SwapAmountTooLow,
+    ErrorCode::SwapAmountTooHigh,
+    ErrorCode::NoRaprToStake,
+    ErrorCode::InvalidRaprMultiplier,
+    ErrorCode::InsufficientFunds,
+    ErrorCode::BetTooLarge,
+    ErrorCode::InvalidAmount,
+    ErrorCode::InvalidOwner,
+    ErrorCode::InvalidTreasury,
+    ErrorCode::ProgramPaused,
+    ErrorCode::InvalidProgramId,
+    ErrorCode::InvalidAssociatedTokenAccount,
+    ErrorCode::MissingAccountBump,
+    ErrorCode::DepositAmountTooLow,
+    ErrorCode::DepositAmountTooHigh,
+    ErrorCode::InvalidOdds,
+    ErrorCode::InsufficientSolBalance,
+    ErrorCode::AmountTooSmall,
+    ErrorCode::AmountTooLarge,
+    ErrorCode::InvalidTokenType,
+    ErrorCode::InvalidMint,
+    ErrorCode::MissingRole,
+    ErrorCode::RoleRegistryFull,
+    ErrorCode::CannotRevokeLastAdmin,
+    ErrorCode::InvalidConfigValue,
+    ErrorCode::TimelockNotExpired,
+    ErrorCode::FightAlreadyResolved,
+    ErrorCode::InvalidWinner,
+    ErrorCode::OpenBetExists,
+    ErrorCode::AccountNotIdle,
+    ErrorCode::BalanceNotWithdrawn,
+    ErrorCode::InvalidLimit,
+    ErrorCode::SelfExcluded,
+    ErrorCode::DepositLimitExceeded,
+    ErrorCode::WagerLimitExceeded,
+    ErrorCode::LossLimitExceeded,
+    ErrorCode::BetCooldownActive,
+    ErrorCode::InvalidTransferFeeAuthority,
+    ErrorCode::TransferFeeNotEnabled,
+    ErrorCode::FightNotResolved,
+    ErrorCode::FightNotReversed,
+    ErrorCode::ClawbackNotEnabled,
+    ErrorCode::ClawbackExceedsPayout,
+    ErrorCode::DumbsMaxSupplyExceeded,
+    ErrorCode::NoQueuedWinnings,
+    ErrorCode::EmissionBudgetExhausted,
+    ErrorCode::WinningsQueued,
+    ErrorCode::LockTooSmall,
+    ErrorCode::UnsupportedMint,
+    ErrorCode::MintDisabled,
+    ErrorCode::InsufficientVaultLiquidity,
+    ErrorCode::InvalidOracle,
+    ErrorCode::StaleOraclePrice,
+    ErrorCode::OracleConfidenceTooWide,
+    ErrorCode::SlippageExceeded,
+    ErrorCode::InvalidFighter,
+    ErrorCode::BetNotWon,
+    ErrorCode::MintNotPriced,
+    ErrorCode::LockBacksOpenBet,
+    ErrorCode::InsufficientMintLiquidity,
+];
+
+/// Maps a custom program error number back to the betting program error.
+pub fn betting_error(code: u32) -> Option<ErrorCode> {
+    BETTING_ERRORS.iter().copied().find(|e| u32::from(*e) == code)
+}
+
+/// Extracts the betting program error from a failed transaction.
+pub fn program_error(err: &TransactionError) -> Option<ErrorCode> {
+    match err {
+        TransactionError::InstructionError(_, InstructionError::Custom(code)) => betting_error(*code),
+        _ => None,
+    }
+}
@@ -0,0 +1,1267 @@
+//! Typed instruction builders. Every account list is derived from a [`House`]
+//! and the caller's wallet, so callers never assemble PDAs by hand.
+
+use anchor_lang::{system_program, InstructionData, ToAccountMetas};
+use betting_program::state::{
+    betting_state::TokenType, BettingState, ConfigChange, LimitSettings, LockPeriod, MetadataField, OracleParams, Role,
+    SupportedMint, SupportedMintParams, ORACLE_CHANGE_KIND,
+};
+use casino_math::Odds;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    sysvar,
+};
+
+use crate::pda;
+
+fn betting_ix(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: betting_program::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+fn hook_ix(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: rapr_transfer_hook::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+fn agent_ix(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: nft_game_agent_program::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+/// How far below its quote `CasinoClient` lets a deposit, swap or cash-out
+/// fill, in basis points.
+pub const DEFAULT_MAX_SLIPPAGE: u16 = 100;
+
+/// Price accounts a house with oracle rates prices deposits, swaps and
+/// cash-outs through.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OracleFeeds {
+    pub sol_usd: Pubkey,
+    pub dumbs_usd: Pubkey,
+    pub rapr_usd: Pubkey,
+}
+
+impl OracleFeeds {
+    pub fn from_params(params: &OracleParams) -> Self {
+        Self { sol_usd: params.sol_usd_feed, dumbs_usd: params.dumbs_usd_feed, rapr_usd: params.rapr_usd_feed }
+    }
+
+    pub fn token_usd(&self, token_type: TokenType) -> Pubkey {
+        match token_type {
+            TokenType::DUMBS => self.dumbs_usd,
+            TokenType::RAPR => self.rapr_usd,
+        }
+    }
+}
+
+/// Addresses that identify one house of the betting program.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct House {
+    pub authority: Pubkey,
+    pub betting_state: Pubkey,
+    pub dumbs_mint: Pubkey,
+    pub rapr_mint: Pubkey,
+    pub rapr_transfer_hook: bool,   // RAPR transfers run the rapr_transfer_hook program
+    pub oracle_feeds: Option<OracleFeeds>, // Set when the house prices through oracle rates
+}
+
+impl House {
+    pub fn new(authority: Pubkey, rapr_mint: Pubkey) -> Self {
+        Self {
+            authority,
+            betting_state: pda::betting_state(&authority).0,
+            dumbs_mint: pda::dumbs_mint().0,
+            rapr_mint,
+            rapr_transfer_hook: false,
+            oracle_feeds: None,
+        }
+    }
+
+    /// The same house, forwarding the transfer hook's accounts with every
+    /// instruction that moves RAPR.
+    pub fn with_rapr_transfer_hook(self) -> Self {
+        Self { rapr_transfer_hook: true, ..self }
+    }
+
+    /// The same house, passing `feeds` to every deposit, swap and cash-out
+    /// so they can be priced at oracle rates.
+    pub fn with_oracle_feeds(self, feeds: OracleFeeds) -> Self {
+        Self { oracle_feeds: Some(feeds), ..self }
+    }
+
+    pub fn from_state(state: &BettingState) -> Self {
+        Self {
+            authority: state.authority,
+            betting_state: pda::betting_state(&state.authority).0,
+            dumbs_mint: state.dumbs_mint,
+            rapr_mint: state.rapr_mint,
+            rapr_transfer_hook: false,
+            oracle_feeds: None,
+        }
+    }
+
+    pub fn roles(&self) -> Pubkey {
+        pda::roles(&self.betting_state).0
+    }
+
+    pub fn dumbs_ata(&self, owner: &Pubkey) -> Pubkey {
+        pda::token_2022_ata(owner, &self.dumbs_mint)
+    }
+
+    pub fn rapr_ata(&self, owner: &Pubkey) -> Pubkey {
+        pda::token_2022_ata(owner, &self.rapr_mint)
+    }
+
+    pub fn mint(&self, token_type: TokenType) -> Pubkey {
+        match token_type {
+            TokenType::DUMBS => self.dumbs_mint,
+            TokenType::RAPR => self.rapr_mint,
+        }
+    }
+
+    pub fn token_ata(&self, owner: &Pubkey, token_type: TokenType) -> Pubkey {
+        pda::token_2022_ata(owner, &self.mint(token_type))
+    }
+
+    /// Vault holding stakes and internal balances of `token_type`.
+    pub fn vault(&self, token_type: TokenType) -> Pubkey {
+        match token_type {
+            TokenType::DUMBS => pda::bet_vault().0,
+            TokenType::RAPR => pda::rapr_vault().0,
+        }
+    }
+
+    /// Accounts Token-2022 needs to run the RAPR transfer hook for a transfer
+    /// out of an account owned by `sender`, or none without a hook.
+    pub fn rapr_hook_accounts(&self, sender: &Pubkey) -> Vec<AccountMeta> {
+        if !self.rapr_transfer_hook {
+            return Vec::new();
+        }
+        vec![
+            AccountMeta::new_readonly(rapr_transfer_hook::ID, false),
+            AccountMeta::new_readonly(pda::extra_account_metas(&self.rapr_mint).0, false),
+            AccountMeta::new_readonly(betting_program::ID, false),
+            AccountMeta::new_readonly(pda::user_limits(sender).0, false),
+            AccountMeta::new(pda::holder_volume(sender).0, false),
+        ]
+    }
+
+    pub fn oracle_config(&self) -> Pubkey {
+        pda::oracle_config(&self.betting_state).0
+    }
+
+    /// SOL/USD and `token_type`/USD feeds for pricing a trade. Without oracle
+    /// feeds the program never reads them, so the oracle config stands in.
+    fn rate_feeds(&self, token_type: TokenType) -> (Pubkey, Pubkey) {
+        match self.oracle_feeds {
+            Some(feeds) => (feeds.sol_usd, feeds.token_usd(token_type)),
+            None => (self.oracle_config(), self.oracle_config()),
+        }
+    }
+
+    /// Appends the hook accounts when `token_type` is RAPR.
+    fn forward_hook_accounts(&self, mut ix: Instruction, token_type: TokenType, sender: &Pubkey) -> Instruction {
+        if token_type == TokenType::RAPR {
+            ix.accounts.extend(self.rapr_hook_accounts(sender));
+        }
+        ix
+    }
+
+    // House bootstrap, in the order the instructions must run
+
+    pub fn initialize_betting_state_base(&self) -> Instruction {
+        betting_ix(
+            betting_program::accounts::InitializeBettingStateBase {
+                authority: self.authority,
+                betting_state: self.betting_state,
+                system_program: system_program::ID,
+            },
+            betting_program::instruction::InitializeBettingStateBase {},
+        )
+    }
+
+    /// Creates DUMBS, as non-transferable play money when `non_transferable`,
+    /// and with the governance PDA as permanent delegate when `clawback`.
+    pub fn initialize_dumbs_mint(&self, non_transferable: bool, clawback: bool) -> Instruction {
+        betting_ix(
+            betting_program::accounts::InitializeDumbsMint {
+                authority: self.authority,
+                dumbs_mint: self.dumbs_mint,
+                betting_state: self.betting_state,
+                rent: sysvar::rent::ID,
+                token_program: spl_token_2022::ID,
+                system_program: system_program::ID,
+            },
+            betting_program::instruction::InitializeDumbsMint { non_transferable, clawback },
+        )
+    }
+
+    pub fn initialize_state_accounts(&self) -> Instruction {
+        betting_ix(
+            betting_program::accounts::InitializeStateAccounts {
+                authority: self.authority,
+                betting_state: self.betting_state,
+                dumbs_mint: self.dumbs_mint,
+                user_token_account: self.dumbs_ata(&self.authority),
+                rapr_mint: self.rapr_mint,
+                token_program: spl_token_2022::ID,
+                associated_token_program: spl_associated_token_account::ID,
+                system_program: system_program::ID,
+                rent: sysvar::rent::ID,
+            },
+            betting_program::instruction::InitializeStateAccounts {},
+        )
+    }
+
+    pub fn initialize_bet_vault(&self) -> Instruction {
+        betting_ix(
+            betting_program::accounts::InitializeBetVault {
+                authority: self.authority,
+                bet_vault: pda::bet_vault().0,
+                dumbs_mint: self.dumbs_mint,
+                betting_state: self.betting_state,
+                token_program: spl_token_2022::ID,
+                system_program: system_program::ID,
+            },
+            betting_program::instruction::InitializeBetVault {},
+        )
+    }
+
+    pub fn initialize_rapr_vault(&self) -> Instruction {
+        betting_ix(
+            betting_program::accounts::InitializeRaprVault {
+                authority: self.authority,
+                rapr_vault: pda::rapr_vault().0,
+                rapr_mint: self.rapr_mint,
+                betting_state: self.betting_state,
+                token_program: spl_token_2022::ID,
+                system_program: system_program::ID,
+            },
+            betting_program::instruction::InitializeRaprVault {},
+        )
+    }
+
+    pub fn initialize_sol_vault(&self) -> Instruction {
+        betting_ix(
+            betting_program::accounts::InitializeSolVault {
+                authority: self.authority,
+                sol_vault: pda::sol_vault().0,
+                betting_state: self.betting_state,
+                system_program: system_program::ID,
+            },
+            betting_program::instruction::InitializeSolVault {},
+        )
+    }
+
+    pub fn initialize_treasury(&self) -> Instruction {
+        betting_ix(
+            betting_program::accounts::InitializeTreasury {
+                authority: self.authority,
+                treasury: pda::treasury().0,
+                betting_state: self.betting_state,
+                system_program: system_program::ID,
+                rent: sysvar::rent::ID,
+            },
+            betting_program::instruction::InitializeTreasury {},
+        )
+    }
+
+    pub fn initialize_betting_state(&self) -> Instruction {
+        betting_ix(
+            betting_program::accounts::InitializeBettingState {
+                authority: self.authority,
+                betting_state: self.betting_state,
+                bet_vault: pda::bet_vault().0,
+                rapr_vault: pda::rapr_vault().0,
+                treasury: pda::treasury().0,
+                sol_vault: pda::sol_vault().0,
+            },
+            betting_program::instruction::InitializeBettingState {},
+        )
+    }
+
+    /// Writes the metadata of the `token_type` mint. Run after
+    /// `initialize_state_accounts`, which sets the SOL rate it records.
+    pub fn initialize_token_metadata(&self, token_type: TokenType, name: &str, symbol: &str, uri: &str) -> Instruction {
+        betting_ix(
+            betting_program::accounts::InitializeTokenMetadata {
+                authority: self.authority,
+                betting_state: self.betting_state,
+                mint: self.mint(token_type),
+                token_program: spl_token_2022::ID,
+                system_program: system_program::ID,
+            },
+            betting_program::instruction::InitializeTokenMetadata {
+                token_type,
+                name: name.to_string(),
+                symbol: symbol.to_string(),
+                uri: uri.to_string(),
+            },
+        )
+    }
+
+    /// Registers the accounts the RAPR transfer hook reads. The RAPR mint
+    /// must already name `rapr_transfer_hook` in its TransferHook extension.
+    pub fn initialize_rapr_transfer_hook(&self, payer: &Pubkey) -> Instruction {
+        hook_ix(
+            rapr_transfer_hook::accounts::InitializeExtraAccountMetaList {
+                payer: *payer,
+                authority: self.authority,
+                betting_state: self.betting_state,
+                mint: self.rapr_mint,
+                extra_account_meta_list: pda::extra_account_metas(&self.rapr_mint).0,
+                system_program: system_program::ID,
+            },
+            rapr_transfer_hook::instruction::InitializeExtraAccountMetaList {},
+        )
+    }
+
+    pub fn initialize_roles(&self) -> Instruction {
+        betting_ix(
+            betting_program::accounts::InitializeRoles {
+                authority: self.authority,
+                betting_state: self.betting_state,
+                roles: self.roles(),
+                system_program: system_program::ID,
+            },
+            betting_program::instruction::InitializeRoles {},
+        )
+    }
+
+    // User instructions
+
+    pub fn create_user_betting_account(&self, user: &Pubkey) -> Instruction {
+        betting_ix(
+            betting_program::accounts::CreateUserBettingAccount {
+                payer: *user,
+                user_betting_account: pda::user_betting_account(user).0,
+                system_program: system_program::ID,
+            },
+            betting_program::instruction::CreateUserBettingAccount {},
+        )
+    }
+
+    pub fn close_user_betting_account(&self, owner: &Pubkey) -> Instruction {
+        betting_ix(
+            betting_program::accounts::CloseUserBettingAccount {
+                owner: *owner,
+                user_betting_account: pda::user_betting_account(owner).0,
+            },
+            betting_program::instruction::CloseUserBettingAccount {},
+        )
+    }
+
+    /// Closes `owner`'s idle betting account on their behalf; anyone may sign.
+    pub fn close_idle_user_betting_account(&self, cranker: &Pubkey, owner: &Pubkey) -> Instruction {
+        betting_ix(
+            betting_program::accounts::CloseIdleUserBettingAccount {
+                cranker: *cranker,
+                owner: *owner,
+                user_betting_account: pda::user_betting_account(owner).0,
+            },
+            betting_program::instruction::CloseIdleUserBettingAccount {},
+        )
+    }
+
+    /// Deposits at whatever rate applies; see `deposit_sol_with_slippage`.
+    pub fn deposit_sol(&self, depositor: &Pubkey, amount: u64) -> Instruction {
+        self.deposit_sol_with_slippage(depositor, amount, 0, 0)
+    }
+
+    /// Fails with `SlippageExceeded` if the deposit would mint more than
+    /// `max_slippage` basis points fewer DUMBS than `quoted_dumbs`.
+    pub fn deposit_sol_with_slippage(&self, depositor: &Pubkey, amount: u64, quoted_dumbs: u64, max_slippage: u16) -> Instruction {
+        let (sol_usd_feed, token_usd_feed) = self.rate_feeds(TokenType::DUMBS);
+        betting_ix(
+            betting_program::accounts::DepositSol {
+                depositor: *depositor,
+                sol_vault: pda::sol_vault().0,
+                bet_vault_dumbs: pda::bet_vault().0,
+                treasury: pda::treasury().0,
+                dumbs_mint: self.dumbs_mint,
+                user_dumbs_account: self.dumbs_ata(depositor),
+                betting_state: self.betting_state,
+                user_limits: pda::user_limits(depositor).0,
+                oracle_config: self.oracle_config(),
+                sol_usd_feed,
+                token_usd_feed,
+                token_program: spl_token_2022::ID,
+                associated_token_program: spl_associated_token_account::ID,
+                system_program: system_program::ID,
+                rent: sysvar::rent::ID,
+            },
+            betting_program::instruction::DepositSol { amount, quoted_dumbs, max_slippage },
+        )
+    }
+
+    pub fn swap_sol_for_rapr(&self, user: &Pubkey, sol_amount: u64) -> Instruction {
+        self.swap_sol_for_rapr_with_slippage(user, sol_amount, 0, 0)
+    }
+
+    /// Fails with `SlippageExceeded` if the swap would mint more than
+    /// `max_slippage` basis points less RAPR than `quoted_rapr`.
+    pub fn swap_sol_for_rapr_with_slippage(&self, user: &Pubkey, sol_amount: u64, quoted_rapr: u64, max_slippage: u16) -> Instruction {
+        let (sol_usd_feed, token_usd_feed) = self.rate_feeds(TokenType::RAPR);
+        betting_ix(
+            betting_program::accounts::SwapSolForRapr {
+                user: *user,
+                user_rapr_account: self.rapr_ata(user),
+                sol_vault: pda::sol_vault().0,
+                rapr_mint: self.rapr_mint,
+                betting_state: self.betting_state,
+                user_limits: pda::user_limits(user).0,
+                oracle_config: self.oracle_config(),
+                sol_usd_feed,
+                token_usd_feed,
+                token_program: spl_token_2022::ID,
+                system_program: system_program::ID,
+            },
+            betting_program::instruction::SwapSolForRapr { sol_amount, quoted_rapr, max_slippage },
+        )
+    }
+
+    pub fn place_bet(
+        &self,
+        bettor: &Pubkey,
+        amount: u64,
+        fight_id: u64,
+        fighter: Pubkey,
+        odds: Odds,
+        token_type: TokenType,
+    ) -> Instruction {
+        let ix = betting_ix(
+            betting_program::accounts::PlaceBet {
+                bettor: *bettor,
+                user_betting_account: pda::user_betting_account(bettor).0,
+                user_dumbs_account: self.dumbs_ata(bettor),
+                user_rapr_account: self.rapr_ata(bettor),
+                bet_vault_dumbs: pda::bet_vault().0,
+                bet_vault_rapr: pda::rapr_vault().0,
+                treasury: pda::treasury().0,
+                betting_state: self.betting_state,
+                dumbs_mint: self.dumbs_mint,
+                rapr_mint: self.rapr_mint,
+                fight: pda::fight(&self.betting_state, fight_id).0,
+                user_limits: pda::user_limits(bettor).0,
+                rapr_lock: pda::rapr_lock(bettor).0,
+                token_program: spl_token_2022::ID,
+                system_program: system_program::ID,
+            },
+            betting_program::instruction::PlaceBet { amount, fight_id, fighter, odds: odds.bps() as u64, token_type },
+        );
+        self.forward_hook_accounts(ix, token_type, bettor)
+    }
+
+    /// Opts `payer` into keeping their settled bets on chain.
+    pub fn create_bet_history(&self, payer: &Pubkey) -> Instruction {
+        betting_ix(
+            betting_program::accounts::CreateBetHistory {
+                payer: *payer,
+                bet_history: pda::bet_history(payer).0,
+                system_program: system_program::ID,
+            },
+            betting_program::instruction::CreateBetHistory {},
+        )
+    }
+
+    pub fn set_limits(&self, user: &Pubkey, limits: LimitSettings) -> Instruction {
+        betting_ix(
+            betting_program::accounts::SetLimits {
+                user: *user,
+                user_limits: pda::user_limits(user).0,
+                system_program: system_program::ID,
+            },
+            betting_program::instruction::SetLimits { limits },
+        )
+    }
+
+    /// Self-excludes `user` for `duration` seconds, or for good when `None`.
+    pub fn self_exclude(&self, user: &Pubkey, duration: Option<i64>) -> Instruction {
+        betting_ix(
+            betting_program::accounts::SelfExclude {
+                user: *user,
+                user_limits: pda::user_limits(user).0,
+                system_program: system_program::ID,
+            },
+            betting_program::instruction::SelfExclude { duration },
+        )
+    }
+
+    /// Locks `amount` RAPR of `owner`'s for `period`, boosting their RAPR bets.
+    pub fn lock_rapr(&self, owner: &Pubkey, amount: u64, period: LockPeriod) -> Instruction {
+        let ix = betting_ix(
+            betting_program::accounts::LockRapr {
+                owner: *owner,
+                rapr_lock: pda::rapr_lock(owner).0,
+                user_rapr_account: self.rapr_ata(owner),
+                rapr_vault: pda::rapr_vault().0,
+                rapr_mint: self.rapr_mint,
+                betting_state: self.betting_state,
+                token_program: spl_token_2022::ID,
+                system_program: system_program::ID,
+            },
+            betting_program::instruction::LockRapr { amount, period },
+        );
+        self.forward_hook_accounts(ix, TokenType::RAPR, owner)
+    }
+
+    /// Returns `owner`'s locked RAPR, less the penalty if the lock has not ended.
+    pub fn unlock_rapr(&self, owner: &Pubkey) -> Instruction {
+        let ix = betting_ix(
+            betting_program::accounts::UnlockRapr {
+                owner: *owner,
+                rapr_lock: pda::rapr_lock(owner).0,
+                user_betting_account: pda::user_betting_account(owner).0,
+                user_rapr_account: self.rapr_ata(owner),
+                rapr_vault: pda::rapr_vault().0,
+                rapr_mint: self.rapr_mint,
+                treasury: pda::treasury().0,
+                betting_state: self.betting_state,
+                token_program: spl_token_2022::ID,
+            },
+            betting_program::instruction::UnlockRapr {},
+        );
+        self.forward_hook_accounts(ix, TokenType::RAPR, &self.betting_state)
+    }
+
+    /// Stakes a bet from `bettor`'s internal balance instead of their token accounts.
+    pub fn place_bet_from_balance(
+        &self,
+        bettor: &Pubkey,
+        amount: u64,
+        fight_id: u64,
+        fighter: Pubkey,
+        odds: Odds,
+        token_type: TokenType,
+    ) -> Instruction {
+        betting_ix(
+            betting_program::accounts::PlaceBetFromBalance {
+                bettor: *bettor,
+                user_betting_account: pda::user_betting_account(bettor).0,
+                treasury: pda::treasury().0,
+                betting_state: self.betting_state,
+                fight: pda::fight(&self.betting_state, fight_id).0,
+                user_limits: pda::user_limits(bettor).0,
+                rapr_lock: pda::rapr_lock(bettor).0,
+            },
+            betting_program::instruction::PlaceBetFromBalance { amount, fight_id, fighter, odds: odds.bps() as u64, token_type },
+        )
+    }
+
+    /// Stakes `amount` of the registered mint `asset` on `fighter` winning `fight_id`.
+    pub fn place_asset_bet(
+        &self,
+        bettor: &Pubkey,
+        asset: &SupportedMint,
+        amount: u64,
+        fight_id: u64,
+        fighter: Pubkey,
+        odds: Odds,
+    ) -> Instruction {
+        let supported_mint = pda::supported_mint(&self.betting_state, &asset.mint).0;
+        betting_ix(
+            betting_program::accounts::PlaceAssetBet {
+                bettor: *bettor,
+                asset_bet: pda::asset_bet(&supported_mint, bettor).0,
+                supported_mint,
+                mint: asset.mint,
+                user_token_account: pda::token_ata(bettor, &asset.mint, &asset.token_program),
+                vault: asset.vault,
+                betting_state: self.betting_state,
+                fight: pda::fight(&self.betting_state, fight_id).0,
+                user_limits: pda::user_limits(bettor).0,
+                token_program: asset.token_program,
+                system_program: system_program::ID,
+            },
+            betting_program::instruction::PlaceAssetBet { amount, fight_id, fighter, odds: odds.bps() as u64 },
+        )
+    }
+
+    /// Settles `bettor`'s bet in `asset` against the posted result of `fight_id`.
+    pub fn settle_asset_bet(&self, settler: &Pubkey, bettor: &Pubkey, asset: &SupportedMint, fight_id: u64) -> Instruction {
+        let supported_mint = pda::supported_mint(&self.betting_state, &asset.mint).0;
+        betting_ix(
+            betting_program::accounts::SettleAssetBet {
+                authority: *settler,
+                bettor: *bettor,
+                asset_bet: pda::asset_bet(&supported_mint, bettor).0,
+                supported_mint,
+                mint: asset.mint,
+                user_token_account: pda::token_ata(bettor, &asset.mint, &asset.token_program),
+                vault: asset.vault,
+                betting_state: self.betting_state,
+                fight: pda::fight(&self.betting_state, fight_id).0,
+                roles: self.roles(),
+                user_limits: pda::user_limits(bettor).0,
+                bet_history: pda::bet_history(bettor).0,
+                token_program: asset.token_program,
+            },
+            betting_program::instruction::SettleAssetBet { fight_id },
+        )
+    }
+
+    /// Sells `amount` of `asset` back to the house for SOL. A mint with a
+    /// USD feed is priced against the house's SOL/USD feed.
+    pub fn cash_out_asset(&self, user: &Pubkey, asset: &SupportedMint, amount: u64) -> Instruction {
+        self.cash_out_asset_with_min_out(user, asset, amount, 0)
+    }
+
+    /// Fails with `SlippageExceeded` if the cash-out would return fewer than
+    /// `min_out` lamports.
+    pub fn cash_out_asset_with_min_out(&self, user: &Pubkey, asset: &SupportedMint, amount: u64, min_out: u64) -> Instruction {
+        let (sol_usd_feed, token_usd_feed) = match (self.oracle_feeds, asset.usd_feed != Pubkey::default()) {
+            (Some(feeds), true) => (feeds.sol_usd, asset.usd_feed),
+            _ => (self.oracle_config(), self.oracle_config()),
+        };
+        betting_ix(
+            betting_program::accounts::CashOutAsset {
+                user: *user,
+                supported_mint: pda::supported_mint(&self.betting_state, &asset.mint).0,
+                mint: asset.mint,
+                user_token_account: pda::token_ata(user, &asset.mint, &asset.token_program),
+                vault: asset.vault,
+                sol_vault: pda::sol_vault().0,
+                betting_state: self.betting_state,
+                oracle_config: self.oracle_config(),
+                sol_usd_feed,
+                token_usd_feed,
+                token_program: asset.token_program,
+            },
+            betting_program::instruction::CashOutAsset { amount, min_out },
+        )
+    }
+
+    pub fn deposit_to_balance(&self, user: &Pubkey, amount: u64, token_type: TokenType) -> Instruction {
+        let ix = betting_ix(
+            betting_program::accounts::DepositToBalance {
+                user: *user,
+                user_betting_account: pda::user_betting_account(user).0,
+                user_token_account: self.token_ata(user, token_type),
+                vault: self.vault(token_type),
+                mint: self.mint(token_type),
+                betting_state: self.betting_state,
+                token_program: spl_token_2022::ID,
+            },
+            betting_program::instruction::DepositToBalance { amount, token_type },
+        );
+        self.forward_hook_accounts(ix, token_type, user)
+    }
+
+    pub fn withdraw_from_balance(&self, user: &Pubkey, amount: u64, token_type: TokenType) -> Instruction {
+        let ix = betting_ix(
+            betting_program::accounts::WithdrawFromBalance {
+                user: *user,
+                user_betting_account: pda::user_betting_account(user).0,
+                user_token_account: self.token_ata(user, token_type),
+                vault: self.vault(token_type),
+                mint: self.mint(token_type),
+                betting_state: self.betting_state,
+                token_program: spl_token_2022::ID,
+            },
+            betting_program::instruction::WithdrawFromBalance { amount, token_type },
+        );
+        self.forward_hook_accounts(ix, token_type, &self.betting_state)
+    }
+
+    pub fn cash_out(&self, user: &Pubkey, amount: u64, token_type: TokenType) -> Instruction {
+        self.cash_out_with_slippage(user, amount, token_type, 0, 0)
+    }
+
+    /// Fails with `SlippageExceeded` if the cash-out would return more than
+    /// `max_slippage` basis points fewer lamports than `quoted_sol`.
+    pub fn cash_out_with_slippage(
+        &self,
+        user: &Pubkey,
+        amount: u64,
+        token_type: TokenType,
+        quoted_sol: u64,
+        max_slippage: u16,
+    ) -> Instruction {
+        let (sol_usd_feed, token_usd_feed) = self.rate_feeds(token_type);
+        let ix = betting_ix(
+            betting_program::accounts::CashOut {
+                user: *user,
+                user_betting_account: pda::user_betting_account(user).0,
+                user_dumbs_account: self.dumbs_ata(user),
+                user_rapr_account: self.rapr_ata(user),
+                sol_vault: pda::sol_vault().0,
+                bet_vault_dumbs: pda::bet_vault().0,
+                bet_vault_rapr: pda::rapr_vault().0,
+                dumbs_mint: self.dumbs_mint,
+                rapr_mint: self.rapr_mint,
+                treasury: pda::treasury().0,
+                betting_state: self.betting_state,
+                oracle_config: self.oracle_config(),
+                sol_usd_feed,
+                token_usd_feed,
+                token_program: spl_token_2022::ID,
+                system_program: system_program::ID,
+            },
+            betting_program::instruction::CashOut { amount, token_type, quoted_sol, max_slippage },
+        );
+        self.forward_hook_accounts(ix, token_type, user)
+    }
+
+    // Quotes: read-only, the result comes back as return data
+
+    /// Quotes a bet for `bettor`, whose RAPR lock boosts RAPR odds.
+    pub fn quote_bet(&self, bettor: &Pubkey, amount: u64, odds: Odds, token_type: TokenType) -> Instruction {
+        betting_ix(
+            betting_program::accounts::QuoteBet {
+                betting_state: self.betting_state,
+                rapr_mint: self.rapr_mint,
+                bettor: *bettor,
+                rapr_lock: pda::rapr_lock(bettor).0,
+            },
+            betting_program::instruction::QuoteBet { amount, odds: odds.bps() as u64, token_type },
+        )
+    }
+
+    pub fn quote_deposit(&self, amount: u64) -> Instruction {
+        let (sol_usd_feed, token_usd_feed) = self.rate_feeds(TokenType::DUMBS);
+        betting_ix(
+            betting_program::accounts::QuoteDeposit {
+                sol_vault: pda::sol_vault().0,
+                betting_state: self.betting_state,
+                oracle_config: self.oracle_config(),
+                sol_usd_feed,
+                token_usd_feed,
+            },
+            betting_program::instruction::QuoteDeposit { amount },
+        )
+    }
+
+    pub fn quote_cash_out(&self, amount: u64, token_type: TokenType) -> Instruction {
+        let (sol_usd_feed, token_usd_feed) = self.rate_feeds(token_type);
+        betting_ix(
+            betting_program::accounts::QuoteCashOut {
+                sol_vault: pda::sol_vault().0,
+                betting_state: self.betting_state,
+                rapr_mint: self.rapr_mint,
+                oracle_config: self.oracle_config(),
+                sol_usd_feed,
+                token_usd_feed,
+            },
+            betting_program::instruction::QuoteCashOut { amount, token_type },
+        )
+    }
+
+    pub fn quote_swap(&self, sol_amount: u64) -> Instruction {
+        let (sol_usd_feed, token_usd_feed) = self.rate_feeds(TokenType::RAPR);
+        betting_ix(
+            betting_program::accounts::QuoteSwap {
+                sol_vault: pda::sol_vault().0,
+                betting_state: self.betting_state,
+                oracle_config: self.oracle_config(),
+                sol_usd_feed,
+                token_usd_feed,
+            },
+            betting_program::instruction::QuoteSwap { sol_amount },
+        )
+    }
+
+    /// Values the house in SOL and USD at the price in `sol_usd_feed`, which
+    /// must be the feed in the house's oracle config.
+    pub fn quote_house_pnl(&self, sol_usd_feed: &Pubkey) -> Instruction {
+        betting_ix(
+            betting_program::accounts::QuoteHousePnl {
+                betting_state: self.betting_state,
+                treasury: pda::treasury().0,
+                sol_vault: pda::sol_vault().0,
+                oracle_config: self.oracle_config(),
+                sol_usd_feed: *sol_usd_feed,
+            },
+            betting_program::instruction::QuoteHousePnl {},
+        )
+    }
+
+    // Settler instructions
+
+    /// Settles `bettor`'s bet against the result posted for `fight_id`.
+    pub fn settle_bet(&self, settler: &Pubkey, bettor: &Pubkey, fight_id: u64) -> Instruction {
+        let ix = betting_ix(
+            betting_program::accounts::SettleBet {
+                authority: *settler,
+                user_betting_account: pda::user_betting_account(bettor).0,
+                bettor: *bettor,
+                user_dumbs_account: self.dumbs_ata(bettor),
+                user_rapr_account: self.rapr_ata(bettor),
+                bet_vault_dumbs: pda::bet_vault().0,
+                bet_vault_rapr: pda::rapr_vault().0,
+                dumbs_mint: self.dumbs_mint,
+                rapr_mint: self.rapr_mint,
+                treasury: pda::treasury().0,
+                betting_state: self.betting_state,
+                roles: self.roles(),
+                fight: pda::fight(&self.betting_state, fight_id).0,
+                user_limits: pda::user_limits(bettor).0,
+                bet_history: pda::bet_history(bettor).0,
+                win_record: pda::win_record(&self.betting_state, fight_id, bettor).0,
+                token_program: spl_token_2022::ID,
+                system_program: system_program::ID,
+            },
+            betting_program::instruction::SettleBet { fight_id },
+        );
+        // A won RAPR bet returns its stake from the vault
+        self.forward_hook_accounts(ix, TokenType::RAPR, &self.betting_state)
+    }
+
+    pub fn mint_dumbs_for_win(&self, settler: &Pubkey, bettor: &Pubkey, fight_id: u64) -> Instruction {
+        betting_ix(
+            betting_program::accounts::MintDumbsForWin {
+                authority: *settler,
+                user_betting_account: pda::user_betting_account(bettor).0,
+                bettor: *bettor,
+                user_dumbs_account: self.dumbs_ata(bettor),
+                bet_vault_dumbs: pda::bet_vault().0,
+                rapr_vault: pda::rapr_vault().0,
+                dumbs_mint: self.dumbs_mint,
+                betting_state: self.betting_state,
+                roles: self.roles(),
+                fight: pda::fight(&self.betting_state, fight_id).0,
+                user_limits: pda::user_limits(bettor).0,
+                bet_history: pda::bet_history(bettor).0,
+                win_record: pda::win_record(&self.betting_state, fight_id, bettor).0,
+                token2022_program: spl_token_2022::ID,
+                system_program: system_program::ID,
+            },
+            betting_program::instruction::MintDumbsForWin { secure_fight_id: fight_id },
+        )
+    }
+
+    /// Pays what it can of `bettor`'s queued winnings to their wallet.
+    pub fn claim_queued_winnings(&self, bettor: &Pubkey) -> Instruction {
+        betting_ix(
+            betting_program::accounts::ClaimQueuedWinnings {
+                bettor: *bettor,
+                user_betting_account: pda::user_betting_account(bettor).0,
+                user_dumbs_account: self.dumbs_ata(bettor),
+                bet_vault_dumbs: pda::bet_vault().0,
+                dumbs_mint: self.dumbs_mint,
+                betting_state: self.betting_state,
+                token_program: spl_token_2022::ID,
+            },
+            betting_program::instruction::ClaimQueuedWinnings {},
+        )
+    }
+
+    // Fight lifecycle
+
+    pub fn create_fight(&self, odds_maker: &Pubkey, fight_id: u64, fighter_one: Pubkey, fighter_two: Pubkey) -> Instruction {
+        betting_ix(
+            betting_program::accounts::CreateFight {
+                odds_maker: *odds_maker,
+                betting_state: self.betting_state,
+                roles: self.roles(),
+                fight: pda::fight(&self.betting_state, fight_id).0,
+                system_program: system_program::ID,
+            },
+            betting_program::instruction::CreateFight { fight_id, fighter_one, fighter_two },
+        )
+    }
+
+    pub fn post_fight_result(&self, settler: &Pubkey, fight_id: u64, winner: Pubkey) -> Instruction {
+        betting_ix(
+            betting_program::accounts::PostFightResult {
+                settler: *settler,
+                betting_state: self.betting_state,
+                roles: self.roles(),
+                fight: pda::fight(&self.betting_state, fight_id).0,
+            },
+            betting_program::instruction::PostFightResult { winner },
+        )
+    }
+
+    /// Overturns a posted result, the end of a dispute.
+    pub fn reverse_fight_result(&self, admin: &Pubkey, fight_id: u64, winner: Pubkey) -> Instruction {
+        betting_ix(
+            betting_program::accounts::ReverseFightResult {
+                admin: *admin,
+                betting_state: self.betting_state,
+                roles: self.roles(),
+                fight: pda::fight(&self.betting_state, fight_id).0,
+            },
+            betting_program::instruction::ReverseFightResult { winner },
+        )
+    }
+
+    // Role-gated administration
+
+    pub fn grant_role(&self, admin: &Pubkey, member: Pubkey, role: Role) -> Instruction {
+        betting_ix(
+            betting_program::accounts::GrantRole {
+                admin: *admin,
+                roles: self.roles(),
+            },
+            betting_program::instruction::GrantRole { member, role },
+        )
+    }
+
+    pub fn revoke_role(&self, admin: &Pubkey, member: Pubkey, role: Role) -> Instruction {
+        betting_ix(
+            betting_program::accounts::RevokeRole {
+                admin: *admin,
+                roles: self.roles(),
+            },
+            betting_program::instruction::RevokeRole { member, role },
+        )
+    }
+
+    pub fn update_token_metadata(&self, admin: &Pubkey, token_type: TokenType, field: MetadataField, value: &str) -> Instruction {
+        betting_ix(
+            betting_program::accounts::UpdateTokenMetadata {
+                admin: *admin,
+                betting_state: self.betting_state,
+                roles: self.roles(),
+                mint: self.mint(token_type),
+                token_program: spl_token_2022::ID,
+                system_program: system_program::ID,
+            },
+            betting_program::instruction::UpdateTokenMetadata { token_type, field, value: value.to_string() },
+        )
+    }
+
+    pub fn set_paused(&self, pauser: &Pubkey, paused: bool) -> Instruction {
+        betting_ix(
+            betting_program::accounts::SetPaused {
+                pauser: *pauser,
+                betting_state: self.betting_state,
+                roles: self.roles(),
+            },
+            betting_program::instruction::SetPaused { paused },
+        )
+    }
+
+    pub fn withdraw_treasury(&self, treasurer: &Pubkey, recipient: &Pubkey, amount: u64) -> Instruction {
+        betting_ix(
+            betting_program::accounts::WithdrawTreasury {
+                treasurer: *treasurer,
+                recipient: *recipient,
+                treasury: pda::treasury().0,
+                betting_state: self.betting_state,
+                roles: self.roles(),
+            },
+            betting_program::instruction::WithdrawTreasury { amount },
+        )
+    }
+
+    /// Harvests RAPR transfer fees withheld in the RAPR vault and in `sources`,
+    /// other RAPR token accounts, into the RAPR vault.
+    pub fn harvest_and_withdraw_fees(&self, treasurer: &Pubkey, sources: &[Pubkey]) -> Instruction {
+        let mut ix = betting_ix(
+            betting_program::accounts::HarvestAndWithdrawFees {
+                treasurer: *treasurer,
+                treasury: pda::treasury().0,
+                betting_state: self.betting_state,
+                roles: self.roles(),
+                rapr_mint: self.rapr_mint,
+                rapr_vault: pda::rapr_vault().0,
+                token_program: spl_token_2022::ID,
+            },
+            betting_program::instruction::HarvestAndWithdrawFees {},
+        );
+        ix.accounts.extend(sources.iter().map(|source| AccountMeta::new(*source, false)));
+        ix
+    }
+
+    /// Claws back `amount` of `bettor`'s winnings from `fight_id`, whose result
+    /// was reversed.
+    pub fn clawback(&self, admin: &Pubkey, bettor: &Pubkey, fight_id: u64, token_type: TokenType, amount: u64) -> Instruction {
+        let mint = self.mint(token_type);
+        let ix = betting_ix(
+            betting_program::accounts::Clawback {
+                admin: *admin,
+                betting_state: self.betting_state,
+                roles: self.roles(),
+                fight: pda::fight(&self.betting_state, fight_id).0,
+                bettor: *bettor,
+                win_record: pda::win_record(&self.betting_state, fight_id, bettor).0,
+                user_betting_account: pda::user_betting_account(bettor).0,
+                user_token_account: pda::token_2022_ata(bettor, &mint),
+                vault: self.vault(token_type),
+                mint,
+                governance: pda::governance(&self.betting_state).0,
+                token_program: spl_token_2022::ID,
+            },
+            betting_program::instruction::Clawback { fight_id, token_type, amount },
+        );
+        self.forward_hook_accounts(ix, token_type, bettor)
+    }
+
+    /// Registers `mint`, owned by `token_program`, as a betting asset with its own vault.
+    pub fn register_mint(&self, admin: &Pubkey, mint: &Pubkey, token_program: &Pubkey, params: SupportedMintParams) -> Instruction {
+        betting_ix(
+            betting_program::accounts::RegisterMint {
+                admin: *admin,
+                betting_state: self.betting_state,
+                roles: self.roles(),
+                mint: *mint,
+                supported_mint: pda::supported_mint(&self.betting_state, mint).0,
+                vault: pda::mint_vault(&self.betting_state, mint).0,
+                token_program: *token_program,
+                system_program: system_program::ID,
+            },
+            betting_program::instruction::RegisterMint { params },
+        )
+    }
+
+    /// Sets the house's price feeds, creating its oracle config on first use.
+    /// Creates the house's empty oracle config; queue a `ConfigChange::Oracle`
+    /// to set it.
+    pub fn create_oracle_config(&self, admin: &Pubkey) -> Instruction {
+        betting_ix(
+            betting_program::accounts::CreateOracleConfig {
+                admin: *admin,
+                betting_state: self.betting_state,
+                roles: self.roles(),
+                oracle_config: self.oracle_config(),
+                system_program: system_program::ID,
+            },
+            betting_program::instruction::CreateOracleConfig {},
+        )
+    }
+
+    pub fn update_supported_mint(&self, admin: &Pubkey, mint: &Pubkey, params: SupportedMintParams) -> Instruction {
+        betting_ix(
+            betting_program::accounts::UpdateSupportedMint {
+                admin: *admin,
+                roles: self.roles(),
+                supported_mint: pda::supported_mint(&self.betting_state, mint).0,
+            },
+            betting_program::instruction::UpdateSupportedMint { params },
+        )
+    }
+
+    /// Moves `amount` lamports from `treasurer` into the SOL vault as `mint`'s
+    /// cash-out liquidity.
+    pub fn fund_mint_liquidity(&self, treasurer: &Pubkey, mint: &Pubkey, amount: u64) -> Instruction {
+        betting_ix(
+            betting_program::accounts::FundMintLiquidity {
+                treasurer: *treasurer,
+                roles: self.roles(),
+                supported_mint: pda::supported_mint(&self.betting_state, mint).0,
+                sol_vault: pda::sol_vault().0,
+                betting_state: self.betting_state,
+                system_program: system_program::ID,
+            },
+            betting_program::instruction::FundMintLiquidity { amount },
+        )
+    }
+
+    pub fn queue_change(&self, admin: &Pubkey, change: ConfigChange) -> Instruction {
+        betting_ix(
+            betting_program::accounts::QueueChange {
+                admin: *admin,
+                betting_state: self.betting_state,
+                roles: self.roles(),
+                pending_change: pda::pending_change_for(&self.betting_state, &change).0,
+                system_program: system_program::ID,
+            },
+            betting_program::instruction::QueueChange { change },
+        )
+    }
+
+    pub fn execute_change(&self, executor: &Pubkey, proposer: &Pubkey, kind: u8) -> Instruction {
+        betting_ix(
+            betting_program::accounts::ExecuteChange {
+                executor: *executor,
+                proposer: *proposer,
+                betting_state: self.betting_state,
+                pending_change: pda::pending_change(&self.betting_state, kind).0,
+            },
+            betting_program::instruction::ExecuteChange {},
+        )
+    }
+
+    pub fn cancel_change(&self, admin: &Pubkey, proposer: &Pubkey, kind: u8) -> Instruction {
+        betting_ix(
+            betting_program::accounts::CancelChange {
+                admin: *admin,
+                proposer: *proposer,
+                betting_state: self.betting_state,
+                roles: self.roles(),
+                pending_change: pda::pending_change(&self.betting_state, kind).0,
+            },
+            betting_program::instruction::CancelChange {},
+        )
+    }
+
+    /// Executes the queued price change of the registered `mint`.
+    pub fn execute_mint_price_change(&self, executor: &Pubkey, proposer: &Pubkey, mint: &Pubkey) -> Instruction {
+        let mut ix = betting_ix(
+            betting_program::accounts::ExecuteChange {
+                executor: *executor,
+                proposer: *proposer,
+                betting_state: self.betting_state,
+                pending_change: pda::pending_mint_price(&self.betting_state, mint).0,
+            },
+            betting_program::instruction::ExecuteChange {},
+        );
+        ix.accounts.push(AccountMeta::new(pda::supported_mint(&self.betting_state, mint).0, false));
+        ix
+    }
+
+    /// Executes the queued `ConfigChange::Oracle`.
+    pub fn execute_oracle_change(&self, executor: &Pubkey, proposer: &Pubkey) -> Instruction {
+        let mut ix = self.execute_change(executor, proposer, ORACLE_CHANGE_KIND);
+        ix.accounts.push(AccountMeta::new(self.oracle_config(), false));
+        ix
+    }
+
+    pub fn cancel_mint_price_change(&self, admin: &Pubkey, proposer: &Pubkey, mint: &Pubkey) -> Instruction {
+        betting_ix(
+            betting_program::accounts::CancelChange {
+                admin: *admin,
+                proposer: *proposer,
+                betting_state: self.betting_state,
+                roles: self.roles(),
+                pending_change: pda::pending_mint_price(&self.betting_state, mint).0,
+            },
+            betting_program::instruction::CancelChange {},
+        )
+    }
+}
+
+/// Creates the Token-2022 associated token account if it does not exist yet.
+pub fn create_token_2022_ata(payer: &Pubkey, owner: &Pubkey, mint: &Pubkey) -> Instruction {
+    spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+        payer,
+        owner,
+        mint,
+        &spl_token_2022::ID,
+    )
+}
+
+/// Creates the associated token account of a mint owned by either token
+/// program, such as a registered betting asset, if it does not exist yet.
+pub fn create_ata(payer: &Pubkey, owner: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Instruction {
+    spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+        payer,
+        owner,
+        mint,
+        token_program,
+    )
+}
+
+// RAPR transfer hook program
+
+/// Opts `owner` into loyalty volume tracking by the RAPR transfer hook.
+pub fn create_holder_volume(payer: &Pubkey, owner: &Pubkey) -> Instruction {
+    hook_ix(
+        rapr_transfer_hook::accounts::CreateHolderVolume {
+            payer: *payer,
+            holder_volume: pda::holder_volume(owner).0,
+            system_program: system_program::ID,
+        },
+        rapr_transfer_hook::instruction::CreateHolderVolume { owner: *owner },
+    )
+}
+
+// NFT game agent program
+
+pub fn initialize_agent_treasury(authority: &Pubkey) -> Instruction {
+    agent_ix(
+        nft_game_agent_program::accounts::InitializeTreasury {
+            treasury: pda::agent_treasury().0,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        nft_game_agent_program::instruction::InitializeTreasury {},
+    )
+}
+
+pub fn create_collection(
+    authority: &Pubkey,
+    collection_id: Pubkey,
+    name: String,
+    symbol: String,
+    strategy: String,
+) -> Instruction {
+    agent_ix(
+        nft_game_agent_program::accounts::CreateCollection {
+            collection: pda::collection(&collection_id).0,
+            authority: *authority,
+            treasury: pda::agent_treasury().0,
+            system_program: system_program::ID,
+        },
+        nft_game_agent_program::instruction::CreateCollection {
+            name,
+            symbol,
+            strategy,
+            collection_id,
+        },
+    )
+}
+
+/// `mint` must be a fresh keypair that signs the transaction.
+pub fn initialize_ai_agent_accounts(authority: &Pubkey, payer: &Pubkey, mint: &Pubkey, id: Pubkey) -> Instruction {
+    agent_ix(
+        nft_game_agent_program::accounts::InitializeAIAgentAccounts {
+            authority: *authority,
+            payer: *payer,
+            ai_agent: pda::ai_agent(&id).0,
+            mint: *mint,
+            system_program: system_program::ID,
+            token_program: anchor_spl::token::ID,
+            rent: sysvar::rent::ID,
+        },
+        nft_game_agent_program::instruction::InitializeAiAgentAccounts { id },
+    )
+}
+
+/// Metadata for an agent NFT minted by [`mint_ai_agent`].
+pub struct AgentMetadata {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub model_hash: [u8; 32],
+}
+
+pub fn mint_ai_agent(
+    authority: &Pubkey,
+    payer: &Pubkey,
+    mint: &Pubkey,
+    id: Pubkey,
+    collection_id: Pubkey,
+    metadata: AgentMetadata,
+) -> Instruction {
+    let (collection, collection_bump) = pda::collection(&collection_id);
+    agent_ix(
+        nft_game_agent_program::accounts::MintAIAgent {
+            authority: *authority,
+            payer: *payer,
+            ai_agent: pda::ai_agent(&id).0,
+            mint: *mint,
+            token_account: spl_associated_token_account::get_associated_token_address(authority, mint),
+            associated_token_program: spl_associated_token_account::ID,
+            rent: sysvar::rent::ID,
+            system_program: system_program::ID,
+            token_program: anchor_spl::token::ID,
+            metadata_program: mpl_token_metadata::ID,
+            collection,
+            metadata: mpl_token_metadata::accounts::Metadata::find_pda(mint).0,
+            master_edition: mpl_token_metadata::accounts::MasterEdition::find_pda(mint).0,
+            treasury: pda::agent_treasury().0,
+        },
+        nft_game_agent_program::instruction::MintAiAgent {
+            id,
+            name: metadata.name,
+            symbol: metadata.symbol,
+            uri: metadata.uri,
+            model_hash: metadata.model_hash,
+            collection_id,
+            collection_bump,
+        },
+    )
+}
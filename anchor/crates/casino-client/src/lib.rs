@@ -0,0 +1,19 @@
+//! Rust client for the betting, NFT game agent and RAPR transfer hook programs.
+//!
+//! * [`pda`] derives every program address from its seeds.
+//! * [`instructions`] builds typed instructions from a [`House`].
+//! * [`accounts`] decodes program accounts.
+//! * [`CasinoClient`] ties these to an RPC endpoint and sends transactions,
+//!   creating Token-2022 ATAs as needed.
+
+pub mod accounts;
+pub mod client;
+pub mod error;
+pub mod instructions;
+pub mod pda;
+
+pub use betting_program::state::{betting_state::TokenType, BetQuote, CashOutQuote, ConfigChange, DepositQuote, HousePnl, LimitSettings, LockPeriod, MetadataField, OracleParams, OraclePrice, Role, SupportedMintParams, SwapQuote, ORACLE_CHANGE_KIND};
+pub use casino_math::{BasisPoints, Dust, Lamports, Odds};
+pub use client::CasinoClient;
+pub use error::{betting_error, program_error, ClientError, Result};
+pub use instructions::{House, OracleFeeds, DEFAULT_MAX_SLIPPAGE};
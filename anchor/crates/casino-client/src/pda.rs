@@ -0,0 +1,163 @@
+//! PDA derivation for every seed used by the programs.
+
+use betting_program::state::{
+    ConfigChange, ASSET_BET_SEED, BET_HISTORY_SEED, BET_VAULT_SEED, FIGHT_SEED, GOVERNANCE_SEED, MINT_VAULT_SEED, ORACLE_CONFIG_SEED,
+    PENDING_CHANGE_SEED, RAPR_LOCK_SEED, RAPR_VAULT_SEED, ROLES_SEED, SOL_VAULT_SEED, SUPPORTED_MINT_SEED, TREASURY_SEED,
+    USER_BETTING_ACCOUNT_SEED, USER_LIMITS_SEED, WIN_RECORD_SEED,
+};
+use rapr_transfer_hook::state::{EXTRA_ACCOUNT_METAS_SEED, HOLDER_VOLUME_SEED};
+use solana_sdk::pubkey::Pubkey;
+
+pub const BETTING_STATE_SEED: &[u8] = b"betting_state";
+pub const DUMBS_MINT_SEED: &[u8] = b"dumbs_mint";
+pub const AI_AGENT_SEED: &[u8] = b"ai_agent";
+pub const COLLECTION_SEED: &[u8] = b"collection";
+
+fn betting_pda(seeds: &[&[u8]]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(seeds, &betting_program::ID)
+}
+
+fn agent_pda(seeds: &[&[u8]]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(seeds, &nft_game_agent_program::ID)
+}
+
+fn hook_pda(seeds: &[&[u8]]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(seeds, &rapr_transfer_hook::ID)
+}
+
+/// House state, one per house authority.
+pub fn betting_state(authority: &Pubkey) -> (Pubkey, u8) {
+    betting_pda(&[BETTING_STATE_SEED, authority.as_ref()])
+}
+
+/// DUMBS Token-2022 vault holding staked DUMBS.
+pub fn bet_vault() -> (Pubkey, u8) {
+    betting_pda(&[BET_VAULT_SEED])
+}
+
+/// RAPR Token-2022 vault holding staked RAPR.
+pub fn rapr_vault() -> (Pubkey, u8) {
+    betting_pda(&[RAPR_VAULT_SEED])
+}
+
+pub fn sol_vault() -> (Pubkey, u8) {
+    betting_pda(&[SOL_VAULT_SEED])
+}
+
+/// Betting program treasury.
+pub fn treasury() -> (Pubkey, u8) {
+    betting_pda(&[TREASURY_SEED])
+}
+
+pub fn dumbs_mint() -> (Pubkey, u8) {
+    betting_pda(&[DUMBS_MINT_SEED])
+}
+
+pub fn user_betting_account(owner: &Pubkey) -> (Pubkey, u8) {
+    betting_pda(&[USER_BETTING_ACCOUNT_SEED, owner.as_ref()])
+}
+
+/// Responsible gambling limits a user has set on themselves.
+pub fn user_limits(owner: &Pubkey) -> (Pubkey, u8) {
+    betting_pda(&[USER_LIMITS_SEED, owner.as_ref()])
+}
+
+/// Settled bets a user has opted to keep on chain.
+pub fn bet_history(owner: &Pubkey) -> (Pubkey, u8) {
+    betting_pda(&[BET_HISTORY_SEED, owner.as_ref()])
+}
+
+/// RAPR a user has locked for boosted RAPR odds.
+pub fn rapr_lock(owner: &Pubkey) -> (Pubkey, u8) {
+    betting_pda(&[RAPR_LOCK_SEED, owner.as_ref()])
+}
+
+pub fn roles(betting_state: &Pubkey) -> (Pubkey, u8) {
+    betting_pda(&[ROLES_SEED, betting_state.as_ref()])
+}
+
+/// Pending timelocked change for the parameter identified by `ConfigChange::kind`.
+pub fn pending_change(betting_state: &Pubkey, kind: u8) -> (Pubkey, u8) {
+    betting_pda(&[PENDING_CHANGE_SEED, betting_state.as_ref(), &[kind]])
+}
+
+/// Pending timelocked `change`, keyed by its kind and target.
+pub fn pending_change_for(betting_state: &Pubkey, change: &ConfigChange) -> (Pubkey, u8) {
+    betting_pda(&[PENDING_CHANGE_SEED, betting_state.as_ref(), &[change.kind()], change.target_seed()])
+}
+
+/// Pending timelocked price change for the registered `mint`.
+pub fn pending_mint_price(betting_state: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    let change = ConfigChange::MintPrice { mint: *mint, lamports_per_token: 0, usd_feed: Pubkey::default() };
+    pending_change_for(betting_state, &change)
+}
+
+pub fn fight(betting_state: &Pubkey, fight_id: u64) -> (Pubkey, u8) {
+    betting_pda(&[FIGHT_SEED, betting_state.as_ref(), &fight_id.to_le_bytes()])
+}
+
+/// Governance PDA, the permanent delegate of mints created for clawbacks.
+pub fn governance(betting_state: &Pubkey) -> (Pubkey, u8) {
+    betting_pda(&[GOVERNANCE_SEED, betting_state.as_ref()])
+}
+
+pub fn win_record(betting_state: &Pubkey, fight_id: u64, bettor: &Pubkey) -> (Pubkey, u8) {
+    betting_pda(&[WIN_RECORD_SEED, betting_state.as_ref(), &fight_id.to_le_bytes(), bettor.as_ref()])
+}
+
+/// Price feed settings of a house.
+pub fn oracle_config(betting_state: &Pubkey) -> (Pubkey, u8) {
+    betting_pda(&[ORACLE_CONFIG_SEED, betting_state.as_ref()])
+}
+
+/// Registry entry for a mint the house takes bets in besides DUMBS and RAPR.
+pub fn supported_mint(betting_state: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    betting_pda(&[SUPPORTED_MINT_SEED, betting_state.as_ref(), mint.as_ref()])
+}
+
+/// House vault for a registered mint.
+pub fn mint_vault(betting_state: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    betting_pda(&[MINT_VAULT_SEED, betting_state.as_ref(), mint.as_ref()])
+}
+
+/// A bettor's open bet in a registered mint.
+pub fn asset_bet(supported_mint: &Pubkey, bettor: &Pubkey) -> (Pubkey, u8) {
+    betting_pda(&[ASSET_BET_SEED, supported_mint.as_ref(), bettor.as_ref()])
+}
+
+pub fn ai_agent(id: &Pubkey) -> (Pubkey, u8) {
+    agent_pda(&[AI_AGENT_SEED, id.as_ref()])
+}
+
+pub fn collection(collection_id: &Pubkey) -> (Pubkey, u8) {
+    agent_pda(&[COLLECTION_SEED, collection_id.as_ref()])
+}
+
+/// NFT game agent program treasury.
+pub fn agent_treasury() -> (Pubkey, u8) {
+    agent_pda(&[nft_game_agent_program::state::TREASURY_SEED])
+}
+
+/// Accounts the RAPR transfer hook needs, resolved by Token-2022 on transfer.
+pub fn extra_account_metas(mint: &Pubkey) -> (Pubkey, u8) {
+    hook_pda(&[EXTRA_ACCOUNT_METAS_SEED, mint.as_ref()])
+}
+
+/// RAPR a holder has sent, tracked for loyalty tiers once they opt in.
+pub fn holder_volume(owner: &Pubkey) -> (Pubkey, u8) {
+    hook_pda(&[HOLDER_VOLUME_SEED, owner.as_ref()])
+}
+
+/// Token-2022 associated token account, as used for DUMBS and RAPR.
+/// Associated token account of `owner` for a mint of either token program.
+pub fn token_ata(owner: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    spl_associated_token_account::get_associated_token_address_with_program_id(owner, mint, token_program)
+}
+
+pub fn token_2022_ata(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    spl_associated_token_account::get_associated_token_address_with_program_id(
+        owner,
+        mint,
+        &spl_token_2022::ID,
+    )
+}
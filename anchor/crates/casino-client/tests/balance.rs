@@ -0,0 +1,96 @@
+//! Internal balance mode: tokens deposited once into the ledger on
+//! `UserBettingAccount`, bets staked from it and settled back into it, and
+//! withdrawn to the user's token accounts on request.
+
+mod common;
+
+use betting_program::errors::error_code::ErrorCode;
+use casino_client::{pda, Odds, TokenType};
+use common::{assert_program_error, Harness, FIGHTER_ONE, LAMPORTS_PER_SOL};
+use solana_sdk::signer::Signer;
+
+const DEPOSIT: u64 = 10_000_000_000;
+
+const BET: u64 = 1_000_000_000;
+const BET_STAKE: u64 = BET - BET * 250 / 10_000;
+const BET_PAYOUT: u64 = BET_STAKE * 20_000 / 10_000; // Odds 2.0x
+
+const SWAP: u64 = 10_000_000;
+const RAPR_DEPOSIT: u64 = 100_000_000_000;
+const RAPR_BET: u64 = 10_000_000_000;
+const RAPR_BET_STAKE: u64 = RAPR_BET - RAPR_BET * 250 / 10_000;
+const RAPR_BET_PAYOUT: u64 = RAPR_BET_STAKE * 150_000 / 10_000; // 1.5x with the 10x RAPR multiplier
+
+#[tokio::test]
+async fn dumbs_bets_settle_into_the_ledger() {
+    let mut h = Harness::start().await;
+    let house = h.house;
+    let user = h.user.insecure_clone();
+    let bettor = user.pubkey();
+
+    h.send(&[house.deposit_sol(&bettor, LAMPORTS_PER_SOL)], &[&user]).await.unwrap();
+    h.send(&[house.deposit_to_balance(&bettor, DEPOSIT, TokenType::DUMBS)], &[&user]).await.unwrap();
+    let start = h.snapshot().await;
+    assert_eq!(h.user_betting_account().await.dumbs_balance, DEPOSIT);
+
+    // Bets can only spend what the ledger holds
+    h.open_fight(1).await;
+    let result = h
+        .send(&[house.place_bet_from_balance(&bettor, DEPOSIT + 1, 1, FIGHTER_ONE, Odds::EVENS, TokenType::DUMBS)], &[&user])
+        .await;
+    assert_program_error(result, ErrorCode::InsufficientBalance);
+
+    h.send(&[house.place_bet_from_balance(&bettor, BET, 1, FIGHTER_ONE, Odds::EVENS, TokenType::DUMBS)], &[&user]).await.unwrap();
+    let account = h.user_betting_account().await;
+    assert_eq!(account.dumbs_balance, DEPOSIT - BET);
+    assert!(account.active_bet.unwrap().from_balance);
+    assert_eq!(h.betting_state().await.total_potential_payout, BET_PAYOUT);
+
+    // Winnings are minted into the vault and credited, not transferred
+    h.post_and_settle(&bettor, 1, FIGHTER_ONE).await.unwrap();
+    let after = h.snapshot().await;
+    assert_eq!(h.user_betting_account().await.dumbs_balance, DEPOSIT - BET + BET_PAYOUT);
+    assert_eq!(after.user_dumbs, start.user_dumbs);
+    assert_eq!(after.bet_vault, start.bet_vault + BET_PAYOUT);
+
+    let result = h.send(&[house.close_user_betting_account(&bettor)], &[&user]).await;
+    assert_program_error(result, ErrorCode::BalanceNotWithdrawn);
+
+    let balance = DEPOSIT - BET + BET_PAYOUT;
+    h.send(&[house.withdraw_from_balance(&bettor, balance, TokenType::DUMBS)], &[&user]).await.unwrap();
+    let end = h.snapshot().await;
+    assert_eq!(end.user_dumbs, start.user_dumbs + balance);
+    assert_eq!(end.bet_vault, start.bet_vault + BET_PAYOUT - balance);
+    assert_eq!(h.user_betting_account().await.dumbs_balance, 0);
+
+    h.send(&[house.close_user_betting_account(&bettor)], &[&user]).await.unwrap();
+}
+
+#[tokio::test]
+async fn rapr_stake_returns_to_the_ledger_on_a_win() {
+    let mut h = Harness::start().await;
+    let house = h.house;
+    let user = h.user.insecure_clone();
+    let bettor = user.pubkey();
+
+    h.send(&[house.swap_sol_for_rapr(&bettor, SWAP)], &[&user]).await.unwrap();
+    h.send(&[house.deposit_to_balance(&bettor, RAPR_DEPOSIT, TokenType::RAPR)], &[&user]).await.unwrap();
+    assert_eq!(h.token_balance(&pda::rapr_vault().0).await, RAPR_DEPOSIT);
+
+    h.open_fight(2).await;
+    h.send(&[house.place_bet_from_balance(&bettor, RAPR_BET, 2, FIGHTER_ONE, Odds::from_american(-200).unwrap(), TokenType::RAPR)], &[&user])
+        .await
+        .unwrap();
+    h.post_and_settle(&bettor, 2, FIGHTER_ONE).await.unwrap();
+
+    let account = h.user_betting_account().await;
+    assert_eq!(account.rapr_balance, RAPR_DEPOSIT - RAPR_BET + RAPR_BET_STAKE);
+    assert_eq!(account.dumbs_balance, RAPR_BET_PAYOUT);
+    assert_eq!(h.token_balance(&pda::bet_vault().0).await, RAPR_BET_PAYOUT);
+
+    // Withdrawals cannot exceed the ledger balance
+    let result = h
+        .send(&[house.withdraw_from_balance(&bettor, account.rapr_balance + 1, TokenType::RAPR)], &[&user])
+        .await;
+    assert_program_error(result, ErrorCode::InsufficientBalance);
+}
@@ -0,0 +1,149 @@
+//! Clawbacks through a permanent delegate: mints name the governance PDA,
+//! wins settle into a `WinRecord`, and once a result is reversed an admin can
+//! recover at most what the record paid, from the internal balance first and
+//! then the wallet.
+
+mod common;
+
+use betting_program::errors::error_code::ErrorCode;
+use betting_program::state::FightStatus;
+use casino_client::{accounts::{Fight, WinRecord}, pda, Odds, TokenType};
+use common::{assert_program_error, Harness, Setup, FIGHTER_ONE, FIGHTER_TWO, LAMPORTS_PER_SOL};
+use solana_sdk::signer::Signer;
+
+const DEPOSIT: u64 = 10_000_000_000;
+
+const BET: u64 = 1_000_000_000;
+const BET_STAKE: u64 = BET - BET * 250 / 10_000;
+const BET_PAYOUT: u64 = BET_STAKE * 20_000 / 10_000; // Odds 2.0x
+
+const SWAP: u64 = 10_000_000;
+const RAPR_BET: u64 = 10_000_000_000;
+const RAPR_BET_STAKE: u64 = RAPR_BET - RAPR_BET * 250 / 10_000;
+
+async fn clawback_house() -> Harness {
+    let mut h = Harness::start_with(Setup { clawback: true, ..Setup::default() }).await;
+    let user = h.user.insecure_clone();
+    let house = h.house;
+    h.send(&[house.deposit_sol(&user.pubkey(), LAMPORTS_PER_SOL)], &[&user]).await.unwrap();
+    h
+}
+
+impl Harness {
+    /// Posts `FIGHTER_ONE`, whom the user's open bet backs, as winner of
+    /// fight `fight_id` and settles the bet as a win.
+    async fn win_fight(&mut self, fight_id: u64) {
+        let bettor = self.user.pubkey();
+        self.post_and_settle(&bettor, fight_id, FIGHTER_ONE).await.unwrap();
+    }
+
+    /// Overturns the result for `FIGHTER_TWO`.
+    async fn reverse(&mut self, fight_id: u64) {
+        let authority = self.authority.insecure_clone();
+        let ix = self.house.reverse_fight_result(&authority.pubkey(), fight_id, FIGHTER_TWO);
+        self.send(&[ix], &[&authority]).await.unwrap();
+    }
+
+    async fn win_record(&mut self, fight_id: u64) -> WinRecord {
+        let address = pda::win_record(&self.house.betting_state, fight_id, &self.user.pubkey()).0;
+        self.account(&address).await
+    }
+
+    async fn claw_back(&mut self, fight_id: u64, token_type: TokenType, amount: u64) -> Result<(), solana_program_test::BanksClientError> {
+        let authority = self.authority.insecure_clone();
+        let ix = self.house.clawback(&authority.pubkey(), &self.user.pubkey(), fight_id, token_type, amount);
+        self.send(&[ix], &[&authority]).await
+    }
+}
+
+#[tokio::test]
+async fn clawbacks_need_a_reversed_result_and_stop_at_the_payout() {
+    let mut h = clawback_house().await;
+    let house = h.house;
+    let user = h.user.insecure_clone();
+    let bettor = user.pubkey();
+
+    h.open_fight(1).await;
+    h.send(&[house.place_bet(&bettor, BET, 1, FIGHTER_ONE, Odds::EVENS, TokenType::DUMBS)], &[&user]).await.unwrap();
+    h.win_fight(1).await;
+    let record = h.win_record(1).await;
+    assert_eq!(record.winner, FIGHTER_ONE);
+    assert_eq!(record.dumbs_payout, BET_PAYOUT);
+
+    assert_program_error(h.claw_back(1, TokenType::DUMBS, BET_PAYOUT).await, ErrorCode::FightNotReversed);
+
+    h.reverse(1).await;
+    let fight = h.account::<Fight>(&pda::fight(&house.betting_state, 1).0).await;
+    assert_eq!(fight.status, FightStatus::Reversed);
+    assert_eq!(fight.overturned_winner, FIGHTER_ONE);
+    assert_eq!(fight.winner, FIGHTER_TWO);
+
+    assert_program_error(h.claw_back(1, TokenType::DUMBS, BET_PAYOUT + 1).await, ErrorCode::ClawbackExceedsPayout);
+    assert_program_error(h.claw_back(1, TokenType::RAPR, 1).await, ErrorCode::ClawbackExceedsPayout);
+
+    // The permanent delegate burns straight out of the wallet
+    let before = h.snapshot().await;
+    h.claw_back(1, TokenType::DUMBS, BET_PAYOUT).await.unwrap();
+    let after = h.snapshot().await;
+    assert_eq!(after.user_dumbs, before.user_dumbs - BET_PAYOUT);
+    assert_eq!(after.dumbs_supply, before.dumbs_supply - BET_PAYOUT);
+    assert_eq!(h.win_record(1).await.dumbs_clawed_back, BET_PAYOUT);
+
+    assert_program_error(h.claw_back(1, TokenType::DUMBS, 1).await, ErrorCode::ClawbackExceedsPayout);
+}
+
+#[tokio::test]
+async fn clawbacks_take_the_balance_first_and_recover_returned_rapr() {
+    let mut h = clawback_house().await;
+    let house = h.house;
+    let user = h.user.insecure_clone();
+    let bettor = user.pubkey();
+    h.send(
+        &[house.swap_sol_for_rapr(&bettor, SWAP), house.deposit_to_balance(&bettor, DEPOSIT, TokenType::DUMBS)],
+        &[&user],
+    )
+    .await
+    .unwrap();
+
+    h.open_fight(1).await;
+    h.send(&[house.place_bet_from_balance(&bettor, BET, 1, FIGHTER_ONE, Odds::EVENS, TokenType::DUMBS)], &[&user]).await.unwrap();
+    h.win_fight(1).await;
+    h.open_fight(2).await;
+    h.send(&[house.place_bet(&bettor, RAPR_BET, 2, FIGHTER_ONE, Odds::EVENS, TokenType::RAPR)], &[&user]).await.unwrap();
+    h.win_fight(2).await;
+    assert_eq!(h.win_record(2).await.rapr_returned, RAPR_BET_STAKE);
+    h.reverse(1).await;
+    h.reverse(2).await;
+
+    // Balance winnings are burned from the vault and debited from the ledger
+    let before = h.snapshot().await;
+    h.claw_back(1, TokenType::DUMBS, BET_PAYOUT).await.unwrap();
+    let after = h.snapshot().await;
+    assert_eq!(h.user_betting_account().await.dumbs_balance, DEPOSIT - BET);
+    assert_eq!(after.bet_vault, before.bet_vault - BET_PAYOUT);
+    assert_eq!(after.user_dumbs, before.user_dumbs);
+
+    // A returned RAPR stake goes back to the RAPR vault
+    h.claw_back(2, TokenType::RAPR, RAPR_BET_STAKE).await.unwrap();
+    let end = h.snapshot().await;
+    assert_eq!(end.user_rapr, after.user_rapr - RAPR_BET_STAKE);
+    assert_eq!(end.rapr_vault, after.rapr_vault + RAPR_BET_STAKE);
+}
+
+#[tokio::test]
+async fn houses_without_a_permanent_delegate_keep_no_win_records() {
+    let mut h = Harness::start().await;
+    let house = h.house;
+    let user = h.user.insecure_clone();
+    let bettor = user.pubkey();
+    h.send(&[house.deposit_sol(&bettor, LAMPORTS_PER_SOL)], &[&user]).await.unwrap();
+
+    h.open_fight(1).await;
+    h.send(&[house.place_bet(&bettor, BET, 1, FIGHTER_ONE, Odds::EVENS, TokenType::DUMBS)], &[&user]).await.unwrap();
+    h.win_fight(1).await;
+    assert!(!h.account_exists(&pda::win_record(&house.betting_state, 1, &bettor).0).await);
+
+    // Without a record there is nothing to claw back against
+    h.reverse(1).await;
+    assert!(h.claw_back(1, TokenType::DUMBS, BET_PAYOUT).await.is_err());
+}
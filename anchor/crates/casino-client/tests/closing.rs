@@ -0,0 +1,86 @@
+//! Rent reclamation: owners close betting accounts once nothing is open, and
+//! anyone may close an account left idle past the grace period, with the rent
+//! always returned to the owner.
+
+mod common;
+
+use betting_program::errors::error_code::ErrorCode;
+use betting_program::state::IDLE_ACCOUNT_GRACE_PERIOD;
+use casino_client::{pda, Odds, TokenType};
+use common::{assert_program_error, Harness, FIGHTER_ONE, LAMPORTS_PER_SOL, TX_FEE};
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+#[tokio::test]
+async fn owner_closes_account_once_bet_settles() {
+    let mut h = Harness::start().await;
+    let house = h.house;
+    let user = h.user.insecure_clone();
+    let bettor = user.pubkey();
+    let account = pda::user_betting_account(&bettor).0;
+
+    h.send(&[house.deposit_sol(&bettor, LAMPORTS_PER_SOL)], &[&user]).await.unwrap();
+    h.open_fight(1).await;
+    h.send(&[house.place_bet(&bettor, LAMPORTS_PER_SOL, 1, FIGHTER_ONE, Odds::EVENS, TokenType::DUMBS)], &[&user]).await.unwrap();
+
+    let result = h.send(&[house.close_user_betting_account(&bettor)], &[&user]).await;
+    assert_program_error(result, ErrorCode::OpenBetExists);
+
+    h.post_and_settle(&bettor, 1, FIGHTER_ONE).await.unwrap();
+
+    let rent = h.lamports(&account).await;
+    let before = h.lamports(&bettor).await;
+    h.send(&[house.close_user_betting_account(&bettor)], &[&user]).await.unwrap();
+    assert!(!h.account_exists(&account).await);
+    assert_eq!(h.lamports(&bettor).await, before + rent - TX_FEE);
+
+    // The account can be opened again afterwards
+    h.send(&[house.create_user_betting_account(&bettor)], &[&user]).await.unwrap();
+    assert_eq!(h.user_betting_account().await.total_bets_placed, 0);
+}
+
+#[tokio::test]
+async fn crank_closes_idle_accounts_for_their_owner() {
+    let mut h = Harness::start().await;
+    let house = h.house;
+    let user = h.user.insecure_clone();
+    let cranker = h.authority.insecure_clone();
+    let owner = user.pubkey();
+    let account = pda::user_betting_account(&owner).0;
+
+    let result = h.send(&[house.close_idle_user_betting_account(&cranker.pubkey(), &owner)], &[&cranker]).await;
+    assert_program_error(result, ErrorCode::AccountNotIdle);
+
+    h.advance_clock(IDLE_ACCOUNT_GRACE_PERIOD).await;
+    h.refresh_blockhash().await;
+
+    // Rent can only go to the account's owner
+    let stranger = Keypair::new().pubkey();
+    let result = h.send(&[house.close_idle_user_betting_account(&cranker.pubkey(), &stranger)], &[&cranker]).await;
+    assert!(result.is_err());
+
+    let rent = h.lamports(&account).await;
+    let owner_before = h.lamports(&owner).await;
+    let cranker_before = h.lamports(&cranker.pubkey()).await;
+    h.send(&[house.close_idle_user_betting_account(&cranker.pubkey(), &owner)], &[&cranker]).await.unwrap();
+    assert!(!h.account_exists(&account).await);
+    assert_eq!(h.lamports(&owner).await, owner_before + rent);
+    assert_eq!(h.lamports(&cranker.pubkey()).await, cranker_before - TX_FEE);
+}
+
+#[tokio::test]
+async fn crank_leaves_open_bets_alone() {
+    let mut h = Harness::start().await;
+    let house = h.house;
+    let user = h.user.insecure_clone();
+    let cranker = h.authority.insecure_clone();
+    let bettor = user.pubkey();
+
+    h.send(&[house.deposit_sol(&bettor, LAMPORTS_PER_SOL)], &[&user]).await.unwrap();
+    h.open_fight(1).await;
+    h.send(&[house.place_bet(&bettor, LAMPORTS_PER_SOL, 1, FIGHTER_ONE, Odds::EVENS, TokenType::DUMBS)], &[&user]).await.unwrap();
+    h.advance_clock(IDLE_ACCOUNT_GRACE_PERIOD).await;
+
+    let result = h.send(&[house.close_idle_user_betting_account(&cranker.pubkey(), &bettor)], &[&cranker]).await;
+    assert_program_error(result, ErrorCode::OpenBetExists);
+    assert!(h.user_betting_account().await.active_bet.is_some());
+}
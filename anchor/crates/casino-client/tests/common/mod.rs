@@ -0,0 +1,526 @@
+//! In-process test harness: runs the betting program natively under
+//! solana-program-test with Token-2022 and the ATA program loaded, and
+//! bootstraps a house before handing control to the test.
+
+#![allow(dead_code)]
+
+use anchor_lang::{AccountDeserialize, AnchorDeserialize};
+use betting_program::errors::error_code::ErrorCode;
+use betting_program::state::{
+    BettingState, ConfigChange, OracleParams, OraclePrice, Role, SolVault, Treasury, UserBettingAccount,
+    CONFIG_CHANGE_DELAY, PYTH_MAGIC, PYTH_PRICE_ACCOUNT, PYTH_PRICE_ACCOUNT_LEN, PYTH_VERSION,
+};
+use casino_client::{
+    accounts::{decode_token_metadata, TokenMetadata},
+    instructions::create_token_2022_ata,
+    pda, program_error, House, TokenType,
+};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::Account,
+    account_info::AccountInfo,
+    clock::Clock,
+    entrypoint::ProgramResult,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    transaction::{Transaction, TransactionError},
+};
+use spl_token_2022::{
+    extension::{
+        metadata_pointer::MetadataPointer,
+        permanent_delegate::PermanentDelegate,
+        transfer_fee::{TransferFee, TransferFeeConfig},
+        transfer_hook::TransferHook,
+        ExtensionType,
+        StateWithExtensions, StateWithExtensionsMut,
+    },
+    solana_program::{program_option::COption, program_pack::Pack},
+    state::{Account as TokenAccount, Mint},
+};
+
+pub const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+/// Transfer fee on RAPR minted with `Setup::rapr_transfer_fee`, in basis points.
+pub const RAPR_TRANSFER_FEE_BPS: u16 = 100;
+
+/// Lamports charged per signature by the test bank.
+pub const TX_FEE: u64 = 5_000;
+
+/// Fighters in every fight `Harness::open_fight` registers.
+pub const FIGHTER_ONE: Pubkey = Pubkey::new_from_array([1; 32]);
+pub const FIGHTER_TWO: Pubkey = Pubkey::new_from_array([2; 32]);
+
+// Anchor's generated entry ties the account slice and AccountInfo lifetimes
+// together, which the plain fn pointer expected by `processor!` cannot express.
+fn process_instruction<'a, 'b, 'c, 'd>(
+    program_id: &'a Pubkey,
+    accounts: &'b [AccountInfo<'c>],
+    data: &'d [u8],
+) -> ProgramResult {
+    // SAFETY: the runtime keeps `accounts` alive for the whole instruction
+    let accounts: &'c [AccountInfo<'c>] = unsafe { std::mem::transmute(accounts) };
+    betting_program::entry(program_id, accounts, data)
+}
+
+fn process_hook_instruction<'a, 'b, 'c, 'd>(
+    program_id: &'a Pubkey,
+    accounts: &'b [AccountInfo<'c>],
+    data: &'d [u8],
+) -> ProgramResult {
+    // SAFETY: as for `process_instruction`
+    let accounts: &'c [AccountInfo<'c>] = unsafe { std::mem::transmute(accounts) };
+    rapr_transfer_hook::entry(program_id, accounts, data)
+}
+
+/// Asserts that a transaction failed with the given betting program error.
+pub fn assert_program_error(result: Result<(), BanksClientError>, expected: ErrorCode) {
+    let err = result.expect_err("transaction should fail");
+    let code = err.unwrap();
+    assert_eq!(
+        program_error(&code).map(u32::from),
+        Some(u32::from(expected)),
+        "expected {}, got {:?}",
+        expected.name(),
+        code
+    );
+}
+
+/// Asserts that a quote failed with the given betting program error.
+pub fn assert_quote_error<T: std::fmt::Debug>(result: Result<T, TransactionError>, expected: ErrorCode) {
+    let err = result.expect_err("quote should fail");
+    assert_eq!(
+        program_error(&err).map(u32::from),
+        Some(u32::from(expected)),
+        "expected {}, got {:?}",
+        expected.name(),
+        err
+    );
+}
+
+pub struct Harness {
+    pub ctx: ProgramTestContext,
+    pub house: House,
+    pub authority: Keypair,
+    pub user: Keypair,
+}
+
+/// House options a test can bootstrap with.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Setup {
+    pub non_transferable_dumbs: bool,
+    pub rapr_transfer_hook: bool,   // RAPR runs the rapr_transfer_hook program
+    pub rapr_transfer_fee: bool,    // RAPR withholds RAPR_TRANSFER_FEE_BPS on every transfer
+    pub clawback: bool,             // Both mints name the governance PDA as permanent delegate
+}
+
+impl Harness {
+    /// Starts a bank with a bootstrapped house whose authority also holds the
+    /// Settler and OddsMaker roles, and a funded user with a betting account
+    /// and both ATAs.
+    pub async fn start() -> Self {
+        Self::start_with(Setup::default()).await
+    }
+
+    /// Same as `start`, bootstrapping the house with `setup`.
+    pub async fn start_with(setup: Setup) -> Self {
+        let authority = Keypair::new();
+        let user = Keypair::new();
+        let rapr_mint = Pubkey::new_unique();
+        let mut house = House::new(authority.pubkey(), rapr_mint);
+        if setup.rapr_transfer_hook {
+            house = house.with_rapr_transfer_hook();
+        }
+
+        let mut program_test = ProgramTest::new(
+            "betting_program",
+            betting_program::ID,
+            processor!(process_instruction),
+        );
+        program_test.add_program(
+            "rapr_transfer_hook",
+            rapr_transfer_hook::ID,
+            processor!(process_hook_instruction),
+        );
+        program_test.prefer_bpf(false);
+
+        for wallet in [&authority, &user] {
+            program_test.add_account(
+                wallet.pubkey(),
+                Account::new(100 * LAMPORTS_PER_SOL, 0, &solana_sdk::system_program::ID),
+            );
+        }
+
+        // RAPR is minted by the house, so its mint authority is the BettingState
+        // PDA, and it points its metadata at itself like DUMBS does
+        let mut extensions = vec![ExtensionType::MetadataPointer];
+        if setup.rapr_transfer_hook {
+            extensions.push(ExtensionType::TransferHook);
+        }
+        if setup.rapr_transfer_fee {
+            extensions.push(ExtensionType::TransferFeeConfig);
+        }
+        if setup.clawback {
+            extensions.push(ExtensionType::PermanentDelegate);
+        }
+        let mint_len = ExtensionType::try_calculate_account_len::<Mint>(&extensions).unwrap();
+        let mut mint_data = vec![0; mint_len];
+        let mut mint = StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut mint_data).unwrap();
+        let pointer = mint.init_extension::<MetadataPointer>(true).unwrap();
+        pointer.authority = Some(house.betting_state).try_into().unwrap();
+        pointer.metadata_address = Some(rapr_mint).try_into().unwrap();
+        if setup.rapr_transfer_hook {
+            let hook = mint.init_extension::<TransferHook>(true).unwrap();
+            hook.authority = Some(house.betting_state).try_into().unwrap();
+            hook.program_id = Some(rapr_transfer_hook::ID).try_into().unwrap();
+        }
+        if setup.rapr_transfer_fee {
+            let fee = TransferFee {
+                epoch: 0.into(),
+                maximum_fee: u64::MAX.into(),
+                transfer_fee_basis_points: RAPR_TRANSFER_FEE_BPS.into(),
+            };
+            let config = mint.init_extension::<TransferFeeConfig>(true).unwrap();
+            config.transfer_fee_config_authority = Some(house.betting_state).try_into().unwrap();
+            config.withdraw_withheld_authority = Some(house.betting_state).try_into().unwrap();
+            config.older_transfer_fee = fee;
+            config.newer_transfer_fee = fee;
+        }
+        if setup.clawback {
+            let delegate = mint.init_extension::<PermanentDelegate>(true).unwrap();
+            delegate.delegate = Some(pda::governance(&house.betting_state).0).try_into().unwrap();
+        }
+        mint.base = Mint {
+            mint_authority: COption::Some(house.betting_state),
+            supply: 0,
+            decimals: 9,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        };
+        mint.pack_base();
+        mint.init_account_type().unwrap();
+        program_test.add_account(
+            rapr_mint,
+            Account {
+                lamports: LAMPORTS_PER_SOL,
+                data: mint_data,
+                owner: spl_token_2022::ID,
+                executable: false,
+                rent_epoch: 0,
+            },
+        );
+
+        let ctx = program_test.start_with_context().await;
+        let mut harness = Self { ctx, house, authority, user };
+        harness.bootstrap(setup).await;
+        harness
+    }
+
+    async fn bootstrap(&mut self, setup: Setup) {
+        let house = self.house;
+        let authority = self.authority.insecure_clone();
+        let mut steps = vec![
+            house.initialize_betting_state_base(),
+            house.initialize_dumbs_mint(setup.non_transferable_dumbs, setup.clawback),
+            house.initialize_state_accounts(),
+            house.initialize_bet_vault(),
+            house.initialize_rapr_vault(),
+            house.initialize_sol_vault(),
+            house.initialize_treasury(),
+            house.initialize_betting_state(),
+            house.initialize_token_metadata(TokenType::DUMBS, "freeDUMBS", "DUMBS", "https://example.com/dumbs.json"),
+            house.initialize_token_metadata(TokenType::RAPR, "RAPR", "RAPR", "https://example.com/rapr.json"),
+            house.initialize_roles(),
+            house.grant_role(&authority.pubkey(), authority.pubkey(), Role::Settler),
+            house.grant_role(&authority.pubkey(), authority.pubkey(), Role::OddsMaker),
+        ];
+        if setup.rapr_transfer_hook {
+            steps.push(house.initialize_rapr_transfer_hook(&authority.pubkey()));
+        }
+        for ix in steps {
+            self.send(&[ix], &[&authority]).await.expect("bootstrap step failed");
+        }
+
+        let user = self.user.insecure_clone();
+        let owner = user.pubkey();
+        self.send(
+            &[
+                house.create_user_betting_account(&owner),
+                create_token_2022_ata(&owner, &owner, &house.dumbs_mint),
+                create_token_2022_ata(&owner, &owner, &house.rapr_mint),
+            ],
+            &[&user],
+        )
+        .await
+        .expect("user setup failed");
+    }
+
+    /// Sends `instructions` with `signers[0]` as fee payer.
+    pub async fn send(&mut self, instructions: &[Instruction], signers: &[&Keypair]) -> Result<(), BanksClientError> {
+        let blockhash = self.ctx.banks_client.get_latest_blockhash().await?;
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&signers[0].pubkey()),
+            signers,
+            blockhash,
+        );
+        self.ctx.banks_client.process_transaction(transaction).await
+    }
+
+    /// Simulates a quote instruction and decodes the value it returned.
+    pub async fn quote<T: AnchorDeserialize>(&mut self, instruction: Instruction) -> Result<T, TransactionError> {
+        let payer = self.user.insecure_clone();
+        let blockhash = self.ctx.banks_client.get_latest_blockhash().await.expect("blockhash");
+        let transaction = Transaction::new_signed_with_payer(&[instruction], Some(&payer.pubkey()), &[&payer], blockhash);
+        let simulation = self.ctx.banks_client.simulate_transaction(transaction).await.expect("banks client");
+        simulation.result.expect("simulation result")?;
+        let return_data = simulation
+            .simulation_details
+            .and_then(|details| details.return_data)
+            .expect("quote returns data");
+        assert_eq!(return_data.program_id, betting_program::ID);
+        Ok(T::try_from_slice(&return_data.data).expect("return data deserializes"))
+    }
+
+    /// Writes a mint owned by `token_program` with the authority as mint
+    /// authority. Token-2022 mints may carry `TransferFeeConfig` or
+    /// `PermanentDelegate`, both controlled by the authority.
+    pub fn create_mint(&mut self, token_program: &Pubkey, decimals: u8, extensions: &[ExtensionType]) -> Pubkey {
+        let mint = Pubkey::new_unique();
+        let authority = self.authority.pubkey();
+        let mint_len = ExtensionType::try_calculate_account_len::<Mint>(extensions).unwrap();
+        let mut data = vec![0; mint_len];
+        let base = Mint {
+            mint_authority: COption::Some(authority),
+            supply: 0,
+            decimals,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        };
+        if extensions.is_empty() {
+            Mint::pack(base, &mut data).unwrap();
+        } else {
+            let mut state = StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut data).unwrap();
+            for extension in extensions {
+                match extension {
+                    ExtensionType::TransferFeeConfig => {
+                        let config = state.init_extension::<TransferFeeConfig>(true).unwrap();
+                        config.transfer_fee_config_authority = Some(authority).try_into().unwrap();
+                    }
+                    ExtensionType::PermanentDelegate => {
+                        let delegate = state.init_extension::<PermanentDelegate>(true).unwrap();
+                        delegate.delegate = Some(authority).try_into().unwrap();
+                    }
+                    other => panic!("create_mint does not set up {:?}", other),
+                }
+            }
+            state.base = base;
+            state.pack_base();
+            state.init_account_type().unwrap();
+        }
+        self.ctx.set_account(
+            &mint,
+            &Account {
+                lamports: LAMPORTS_PER_SOL,
+                data,
+                owner: *token_program,
+                executable: false,
+                rent_epoch: 0,
+            }
+            .into(),
+        );
+        mint
+    }
+
+    /// Mints `amount` of a mint from `create_mint` into `destination`.
+    pub async fn mint_to(&mut self, mint: &Pubkey, token_program: &Pubkey, destination: &Pubkey, amount: u64) {
+        let authority = self.authority.insecure_clone();
+        let ix = spl_token_2022::instruction::mint_to(token_program, mint, destination, &authority.pubkey(), &[], amount)
+            .unwrap();
+        self.send(&[ix], &[&authority]).await.expect("mint_to failed");
+    }
+
+    /// Writes a mock Pyth v2 price account at `address`, owned by
+    /// `oracle_program`, holding `price` as its aggregate with `status`.
+    pub fn set_price_feed(&mut self, address: &Pubkey, oracle_program: &Pubkey, price: OraclePrice, status: u32) {
+        let mut data = vec![0; PYTH_PRICE_ACCOUNT_LEN];
+        data[0..4].copy_from_slice(&PYTH_MAGIC.to_le_bytes());
+        data[4..8].copy_from_slice(&PYTH_VERSION.to_le_bytes());
+        data[8..12].copy_from_slice(&PYTH_PRICE_ACCOUNT.to_le_bytes());
+        data[12..16].copy_from_slice(&(PYTH_PRICE_ACCOUNT_LEN as u32).to_le_bytes());
+        data[20..24].copy_from_slice(&price.expo.to_le_bytes());
+        data[96..104].copy_from_slice(&price.publish_time.to_le_bytes());
+        data[208..216].copy_from_slice(&price.price.to_le_bytes());
+        data[216..224].copy_from_slice(&price.conf.to_le_bytes());
+        data[224..228].copy_from_slice(&status.to_le_bytes());
+        self.ctx.set_account(
+            address,
+            &Account {
+                lamports: LAMPORTS_PER_SOL,
+                data,
+                owner: *oracle_program,
+                executable: false,
+                rent_epoch: 0,
+            }
+            .into(),
+        );
+    }
+
+    /// The bank's current unix time.
+    pub async fn now(&mut self) -> i64 {
+        let clock: Clock = self.ctx.banks_client.get_sysvar().await.expect("clock sysvar");
+        clock.unix_timestamp
+    }
+
+    /// Moves the bank's clock forward by `seconds`.
+    pub async fn advance_clock(&mut self, seconds: i64) {
+        let mut clock: Clock = self.ctx.banks_client.get_sysvar().await.expect("clock sysvar");
+        clock.unix_timestamp += seconds;
+        self.ctx.set_sysvar(&clock);
+    }
+
+    /// Waits for a fresh blockhash, so a transaction identical to one already
+    /// sent (even a failed one) is processed again rather than deduplicated.
+    pub async fn refresh_blockhash(&mut self) {
+        self.ctx.get_new_latest_blockhash().await.expect("new blockhash");
+    }
+
+    /// Moves the bank's clock into the next epoch.
+    pub async fn advance_epoch(&mut self) {
+        let mut clock: Clock = self.ctx.banks_client.get_sysvar().await.expect("clock sysvar");
+        clock.epoch += 1;
+        self.ctx.set_sysvar(&clock);
+    }
+
+    pub async fn account_exists(&mut self, address: &Pubkey) -> bool {
+        self.ctx.banks_client.get_account(*address).await.expect("banks client").is_some()
+    }
+
+    async fn raw_account(&mut self, address: &Pubkey) -> Account {
+        self.ctx
+            .banks_client
+            .get_account(*address)
+            .await
+            .expect("banks client")
+            .unwrap_or_else(|| panic!("account {} does not exist", address))
+    }
+
+    pub async fn account<T: AccountDeserialize>(&mut self, address: &Pubkey) -> T {
+        let account = self.raw_account(address).await;
+        T::try_deserialize(&mut account.data.as_slice()).expect("account deserializes")
+    }
+
+    pub async fn lamports(&mut self, address: &Pubkey) -> u64 {
+        self.raw_account(address).await.lamports
+    }
+
+    pub async fn token_balance(&mut self, address: &Pubkey) -> u64 {
+        let account = self.raw_account(address).await;
+        StateWithExtensions::<TokenAccount>::unpack(&account.data)
+            .expect("token account")
+            .base
+            .amount
+    }
+
+    pub async fn mint_supply(&mut self, mint: &Pubkey) -> u64 {
+        let account = self.raw_account(mint).await;
+        StateWithExtensions::<Mint>::unpack(&account.data)
+            .expect("mint")
+            .base
+            .supply
+    }
+
+    pub async fn token_metadata(&mut self, mint: &Pubkey) -> TokenMetadata {
+        let account = self.raw_account(mint).await;
+        decode_token_metadata(mint, &account.data)
+            .expect("mint")
+            .expect("mint has metadata")
+    }
+
+    pub async fn betting_state(&mut self) -> BettingState {
+        let address = self.house.betting_state;
+        self.account(&address).await
+    }
+
+    pub async fn treasury(&mut self) -> Treasury {
+        self.account(&pda::treasury().0).await
+    }
+
+    pub async fn sol_vault(&mut self) -> SolVault {
+        self.account(&pda::sol_vault().0).await
+    }
+
+    /// Registers fight `fight_id` between `FIGHTER_ONE` and `FIGHTER_TWO`.
+    pub async fn open_fight(&mut self, fight_id: u64) {
+        let authority = self.authority.insecure_clone();
+        let ix = self.house.create_fight(&authority.pubkey(), fight_id, FIGHTER_ONE, FIGHTER_TWO);
+        self.send(&[ix], &[&authority]).await.expect("create_fight failed");
+    }
+
+    /// Creates the house's oracle config if needed and sets `params` through
+    /// the timelock, advancing the clock past `CONFIG_CHANGE_DELAY`.
+    pub async fn configure_oracle(&mut self, params: OracleParams) {
+        let authority = self.authority.insecure_clone();
+        let admin = authority.pubkey();
+        let house = self.house;
+        if !self.account_exists(&house.oracle_config()).await {
+            self.send(&[house.create_oracle_config(&admin)], &[&authority]).await.expect("create_oracle_config failed");
+        }
+        self.send(&[house.queue_change(&admin, ConfigChange::Oracle(params))], &[&authority])
+            .await
+            .expect("queue_change failed");
+        self.advance_clock(CONFIG_CHANGE_DELAY).await;
+        self.send(&[house.execute_oracle_change(&admin, &admin)], &[&authority]).await.expect("execute_change failed");
+    }
+
+    /// Posts `winner` as fight `fight_id`'s result and settles `bettor`'s bet
+    /// against it.
+    pub async fn post_and_settle(&mut self, bettor: &Pubkey, fight_id: u64, winner: Pubkey) -> Result<(), BanksClientError> {
+        let authority = self.authority.insecure_clone();
+        let house = self.house;
+        self.send(
+            &[
+                house.post_fight_result(&authority.pubkey(), fight_id, winner),
+                house.settle_bet(&authority.pubkey(), bettor, fight_id),
+            ],
+            &[&authority],
+        )
+        .await
+    }
+
+    pub async fn user_betting_account(&mut self) -> UserBettingAccount {
+        let address = pda::user_betting_account(&self.user.pubkey()).0;
+        self.account(&address).await
+    }
+
+    /// Balances the lifecycle test checks after every step.
+    pub async fn snapshot(&mut self) -> Snapshot {
+        let user = self.user.pubkey();
+        let house = self.house;
+        Snapshot {
+            user_lamports: self.lamports(&user).await,
+            user_dumbs: self.token_balance(&house.dumbs_ata(&user)).await,
+            user_rapr: self.token_balance(&house.rapr_ata(&user)).await,
+            bet_vault: self.token_balance(&pda::bet_vault().0).await,
+            rapr_vault: self.token_balance(&pda::rapr_vault().0).await,
+            dumbs_supply: self.mint_supply(&house.dumbs_mint).await,
+            rapr_supply: self.mint_supply(&house.rapr_mint).await,
+            sol_vault_lamports: self.lamports(&pda::sol_vault().0).await,
+            treasury_lamports: self.lamports(&pda::treasury().0).await,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Snapshot {
+    pub user_lamports: u64,
+    pub user_dumbs: u64,
+    pub user_rapr: u64,
+    pub bet_vault: u64,
+    pub rapr_vault: u64,
+    pub dumbs_supply: u64,
+    pub rapr_supply: u64,
+    pub sol_vault_lamports: u64,
+    pub treasury_lamports: u64,
+}
@@ -0,0 +1,119 @@
+//! DUMBS emission limits: wins mint within the max supply and the epoch's
+//! budget, the house's settled DUMBS stakes in the bet vault pay the rest
+//! they can, and whatever is left is queued until a later epoch.
+
+mod common;
+
+use betting_program::errors::error_code::ErrorCode;
+use betting_program::state::{ConfigChange, CONFIG_CHANGE_DELAY};
+use casino_client::{Odds, TokenType};
+use common::{assert_program_error, Harness, FIGHTER_ONE, LAMPORTS_PER_SOL};
+use solana_sdk::signer::Signer;
+
+const DEPOSIT: u64 = LAMPORTS_PER_SOL;
+const DEPOSIT_DUMBS: u64 = (DEPOSIT - DEPOSIT * 250 / 10_000) * 1_000;
+
+const BET: u64 = 1_000_000_000;
+const BET_STAKE: u64 = BET - BET * 250 / 10_000;
+const BET_PAYOUT: u64 = BET_STAKE * 20_000 / 10_000; // Odds 2.0x
+
+const EPOCH_BUDGET: u64 = 500_000_000;
+
+impl Harness {
+    /// Queues `change` and executes it once the timelock has passed.
+    async fn configure(&mut self, change: ConfigChange) {
+        let authority = self.authority.insecure_clone();
+        let house = self.house;
+        self.send(&[house.queue_change(&authority.pubkey(), change)], &[&authority]).await.unwrap();
+        self.advance_clock(CONFIG_CHANGE_DELAY).await;
+        self.send(&[house.execute_change(&authority.pubkey(), &authority.pubkey(), change.kind())], &[&authority])
+            .await
+            .unwrap();
+    }
+
+    /// Places a DUMBS bet on fight `fight_id` and settles it as a win.
+    async fn win_dumbs_bet(&mut self, fight_id: u64) {
+        let user = self.user.insecure_clone();
+        let bettor = user.pubkey();
+        let house = self.house;
+        self.open_fight(fight_id).await;
+        self.send(&[house.place_bet(&bettor, BET, fight_id, FIGHTER_ONE, Odds::EVENS, TokenType::DUMBS)], &[&user])
+            .await
+            .unwrap();
+        self.post_and_settle(&bettor, fight_id, FIGHTER_ONE).await.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn wins_past_the_budget_draw_on_house_dumbs_then_queue() {
+    let mut h = Harness::start().await;
+    let house = h.house;
+    let user = h.user.insecure_clone();
+    let bettor = user.pubkey();
+    h.send(&[house.deposit_sol(&bettor, DEPOSIT)], &[&user]).await.unwrap();
+    h.configure(ConfigChange::DumbsEpochBudget(EPOCH_BUDGET)).await;
+
+    // The budget mints part of the win and the settled stake pays some more
+    let before = h.snapshot().await;
+    h.win_dumbs_bet(1).await;
+    let after = h.snapshot().await;
+    let queued = BET_PAYOUT - EPOCH_BUDGET - BET_STAKE;
+    assert_eq!(after.user_dumbs, before.user_dumbs - BET + EPOCH_BUDGET + BET_STAKE);
+    assert_eq!(after.dumbs_supply, before.dumbs_supply + EPOCH_BUDGET);
+    assert_eq!(after.bet_vault, before.bet_vault + BET - BET_STAKE);
+    assert_eq!(h.user_betting_account().await.queued_winnings, queued);
+    let state = h.betting_state().await;
+    assert_eq!(state.dumbs_emitted, EPOCH_BUDGET);
+    assert_eq!(state.house_dumbs, 0);
+    assert_eq!(state.total_dumbs_queued, queued);
+    assert_eq!(state.total_dumbs_won, BET_PAYOUT);
+
+    // Nothing is left to pay with this epoch, and the account must stay open
+    assert_program_error(h.send(&[house.claim_queued_winnings(&bettor)], &[&user]).await, ErrorCode::EmissionBudgetExhausted);
+    assert_program_error(h.send(&[house.close_user_betting_account(&bettor)], &[&user]).await, ErrorCode::WinningsQueued);
+
+    // The next epoch's budget pays the rest
+    h.advance_epoch().await;
+    h.refresh_blockhash().await;
+    h.send(&[house.claim_queued_winnings(&bettor)], &[&user]).await.unwrap();
+    assert_eq!(h.snapshot().await.user_dumbs, after.user_dumbs + queued);
+    assert_eq!(h.user_betting_account().await.queued_winnings, 0);
+    let state = h.betting_state().await;
+    assert_eq!(state.dumbs_emitted, queued);
+    assert_eq!(state.total_dumbs_queued, 0);
+
+    h.refresh_blockhash().await;
+    assert_program_error(h.send(&[house.claim_queued_winnings(&bettor)], &[&user]).await, ErrorCode::NoQueuedWinnings);
+}
+
+#[tokio::test]
+async fn max_supply_caps_deposits_and_win_minting() {
+    let mut h = Harness::start().await;
+    let house = h.house;
+    let user = h.user.insecure_clone();
+    let bettor = user.pubkey();
+    h.configure(ConfigChange::DumbsMaxSupply(DEPOSIT_DUMBS)).await;
+
+    h.send(&[house.deposit_sol(&bettor, DEPOSIT)], &[&user]).await.unwrap();
+    let result = h.send(&[house.deposit_sol(&bettor, DEPOSIT / 10)], &[&user]).await;
+    assert_program_error(result, ErrorCode::DumbsMaxSupplyExceeded);
+
+    // At the cap a win mints nothing: the stake is paid back, the rest queued
+    let before = h.snapshot().await;
+    h.win_dumbs_bet(1).await;
+    let after = h.snapshot().await;
+    assert_eq!(after.dumbs_supply, DEPOSIT_DUMBS);
+    assert_eq!(after.user_dumbs, before.user_dumbs - BET + BET_STAKE);
+    assert_eq!(h.user_betting_account().await.queued_winnings, BET_PAYOUT - BET_STAKE);
+
+    // A new epoch brings a new budget but no room under the cap
+    h.advance_epoch().await;
+    assert_program_error(h.send(&[house.claim_queued_winnings(&bettor)], &[&user]).await, ErrorCode::EmissionBudgetExhausted);
+
+    // Raising the cap lets the queue be paid
+    h.configure(ConfigChange::DumbsMaxSupply(u64::MAX)).await;
+    h.refresh_blockhash().await;
+    h.send(&[house.claim_queued_winnings(&bettor)], &[&user]).await.unwrap();
+    assert_eq!(h.snapshot().await.user_dumbs, after.user_dumbs + BET_PAYOUT - BET_STAKE);
+    assert_eq!(h.betting_state().await.total_dumbs_in_circulation, DEPOSIT_DUMBS + BET_PAYOUT - BET_STAKE);
+}
@@ -0,0 +1,56 @@
+//! On-chain bet history: settlements are recorded in the bettor's ring buffer
+//! once they create one, with win, loss and streak counters.
+
+mod common;
+
+use betting_program::state::BetHistory;
+use casino_client::{pda, Odds, TokenType};
+use common::{Harness, FIGHTER_ONE, FIGHTER_TWO, LAMPORTS_PER_SOL};
+use solana_sdk::signer::Signer;
+
+const BET: u64 = 1_000_000_000;
+
+impl Harness {
+    async fn bet_history(&mut self) -> BetHistory {
+        let address = pda::bet_history(&self.user.pubkey()).0;
+        self.account(&address).await
+    }
+
+    async fn bet_and_settle(&mut self, fight_id: u64, won: bool) {
+        let user = self.user.insecure_clone();
+        let bettor = user.pubkey();
+        self.open_fight(fight_id).await;
+        let ix = self.house.place_bet(&bettor, BET, fight_id, FIGHTER_ONE, Odds::EVENS, TokenType::DUMBS);
+        self.send(&[ix], &[&user]).await.unwrap();
+        let winner = if won { FIGHTER_ONE } else { FIGHTER_TWO };
+        self.post_and_settle(&bettor, fight_id, winner).await.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn settlements_are_recorded_newest_first_with_streaks() {
+    let mut h = Harness::start().await;
+    let user = h.user.insecure_clone();
+    h.send(&[h.house.deposit_sol(&user.pubkey(), LAMPORTS_PER_SOL)], &[&user]).await.unwrap();
+
+    // Bets settled before opting in are not recorded
+    h.bet_and_settle(1, true).await;
+    h.send(&[h.house.create_bet_history(&user.pubkey())], &[&user]).await.unwrap();
+    assert_eq!(h.bet_history().await.total_settled, 0);
+
+    h.bet_and_settle(2, true).await;
+    h.bet_and_settle(3, false).await;
+    h.bet_and_settle(4, false).await;
+
+    let history = h.bet_history().await;
+    assert_eq!(history.owner, user.pubkey());
+    assert_eq!((history.total_settled, history.wins, history.losses), (3, 1, 2));
+    assert_eq!(history.current_streak, -2);
+    assert_eq!((history.longest_win_streak, history.longest_loss_streak), (1, 2));
+
+    let recent: Vec<_> = history.recent().map(|entry| (entry.fight_id, entry.won())).collect();
+    assert_eq!(recent, vec![(4, false), (3, false), (2, true)]);
+    let win = history.recent().last().unwrap();
+    assert!(win.payout > 0 && win.settled_at >= win.placed_at);
+    assert_eq!(win.token_type(), Some(TokenType::DUMBS));
+}
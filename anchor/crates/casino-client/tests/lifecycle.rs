@@ -0,0 +1,275 @@
+//! Full betting lifecycle against an in-process bank: bootstrap, deposit SOL,
+//! swap to RAPR, bet in both tokens, settle wins and losses and cash out,
+//! checking vaults, mint supply and `BettingState` counters after every step.
+
+mod common;
+
+use betting_program::errors::error_code::ErrorCode;
+use casino_client::{pda, Odds, Role, TokenType};
+use common::{assert_program_error, Harness, Snapshot, FIGHTER_ONE, FIGHTER_TWO, LAMPORTS_PER_SOL, TX_FEE};
+use solana_sdk::signer::Signer;
+
+const DEPOSIT: u64 = LAMPORTS_PER_SOL;
+const DEPOSIT_FEE: u64 = DEPOSIT * 250 / 10_000;
+const DEPOSIT_DUMBS: u64 = (DEPOSIT - DEPOSIT_FEE) * 1_000;
+
+const SWAP: u64 = 10_000_000; // Minimum deposit, 0.01 SOL
+const SWAP_RAPR: u64 = SWAP * 10_000_000;
+
+const DUMBS_BET: u64 = 1_000_000_000;
+const DUMBS_BET_FEE: u64 = DUMBS_BET * 250 / 10_000;
+const DUMBS_BET_STAKE: u64 = DUMBS_BET - DUMBS_BET_FEE;
+const DUMBS_BET_PAYOUT: u64 = DUMBS_BET_STAKE * 20_000 / 10_000; // Odds 2.0x
+
+// RAPR bets at 1.5x get the default 10x RAPR multiplier on their odds only
+const RAPR_BET: u64 = 10_000_000_000;
+const RAPR_BET_STAKE: u64 = RAPR_BET - RAPR_BET * 250 / 10_000;
+const RAPR_BET_PAYOUT: u64 = RAPR_BET_STAKE * 150_000 / 10_000;
+const RAPR_WIN_DUMBS: u64 = RAPR_BET_PAYOUT;
+
+const DUMBS_CASH_OUT: u64 = 100_000_000_000;
+const DUMBS_CASH_OUT_SOL: u64 = (DUMBS_CASH_OUT - DUMBS_CASH_OUT * 250 / 10_000) / 1_000;
+
+const RAPR_CASH_OUT: u64 = 1_000_000_000_000;
+const RAPR_CASH_OUT_SOL: u64 = (RAPR_CASH_OUT - RAPR_CASH_OUT * 250 / 10_000) / 10_000_000;
+
+#[tokio::test]
+async fn full_betting_lifecycle() {
+    let mut h = Harness::start().await;
+    let house = h.house;
+    let user = h.user.insecure_clone();
+    let bettor = user.pubkey();
+
+    // Bootstrap
+    let state = h.betting_state().await;
+    assert!(state.is_initialized());
+    assert_eq!(state.dumbs_mint, house.dumbs_mint);
+    assert_eq!(state.rapr_mint, house.rapr_mint);
+    assert_eq!(state.bet_vault, pda::bet_vault().0);
+    assert_eq!(state.house_fee, 250);
+    assert_eq!(state.total_bets_placed, 0);
+    assert_eq!(state.total_dumbs_in_circulation, 0);
+    let start = h.snapshot().await;
+    assert_eq!((start.dumbs_supply, start.rapr_supply), (0, 0));
+    assert_eq!((start.bet_vault, start.rapr_vault), (0, 0));
+
+    // Deposit SOL for DUMBS
+    h.send(&[house.deposit_sol(&bettor, DEPOSIT)], &[&user]).await.unwrap();
+    let after = h.snapshot().await;
+    assert_eq!(after, Snapshot {
+        user_lamports: start.user_lamports - DEPOSIT - TX_FEE,
+        user_dumbs: DEPOSIT_DUMBS,
+        dumbs_supply: DEPOSIT_DUMBS,
+        sol_vault_lamports: start.sol_vault_lamports + DEPOSIT - DEPOSIT_FEE,
+        treasury_lamports: start.treasury_lamports + DEPOSIT_FEE,
+        ..start
+    });
+    let state = h.betting_state().await;
+    assert_eq!(state.total_fees_collected, DEPOSIT_FEE);
+    assert_eq!(state.total_dumbs_in_circulation, DEPOSIT_DUMBS);
+    let vault = h.sol_vault().await;
+    assert_eq!(vault.balance, DEPOSIT - DEPOSIT_FEE);
+    assert_eq!(vault.total_deposits, 1);
+    assert_eq!(vault.total_dumbs_minted, DEPOSIT_DUMBS);
+    assert_eq!(h.treasury().await.sol_fees_collected, DEPOSIT_FEE);
+
+    // Swap SOL for RAPR
+    let before = after;
+    h.send(&[house.swap_sol_for_rapr(&bettor, SWAP)], &[&user]).await.unwrap();
+    let after = h.snapshot().await;
+    assert_eq!(after, Snapshot {
+        user_lamports: before.user_lamports - SWAP - TX_FEE,
+        user_rapr: SWAP_RAPR,
+        rapr_supply: SWAP_RAPR,
+        sol_vault_lamports: before.sol_vault_lamports + SWAP,
+        ..before
+    });
+    assert_eq!(h.betting_state().await.total_rapr_in_circulation, SWAP_RAPR);
+    assert_eq!(h.sol_vault().await.balance, DEPOSIT - DEPOSIT_FEE + SWAP);
+
+    // DUMBS bet that wins
+    let before = after;
+    h.open_fight(1).await;
+    h.send(&[house.place_bet(&bettor, DUMBS_BET, 1, FIGHTER_ONE, Odds::EVENS, TokenType::DUMBS)], &[&user]).await.unwrap();
+    let after = h.snapshot().await;
+    assert_eq!(after, Snapshot {
+        user_lamports: before.user_lamports - TX_FEE,
+        user_dumbs: before.user_dumbs - DUMBS_BET,
+        bet_vault: before.bet_vault + DUMBS_BET,
+        ..before
+    });
+    let state = h.betting_state().await;
+    assert_eq!(state.total_bets_placed, 1);
+    assert_eq!(state.total_dumbs_wagered, DUMBS_BET_STAKE);
+    assert_eq!(state.total_potential_payout, DUMBS_BET_PAYOUT);
+    assert_eq!(h.treasury().await.dumbs_fees_collected, DUMBS_BET_FEE);
+    let bet = h.user_betting_account().await.active_bet.expect("active bet");
+    assert_eq!((bet.fight_id, bet.amount), (1, DUMBS_BET_STAKE));
+    assert_eq!(bet.potential_payout, DUMBS_BET_PAYOUT);
+
+    let before = after;
+    h.post_and_settle(&bettor, 1, FIGHTER_ONE).await.unwrap();
+    let after = h.snapshot().await;
+    assert_eq!(after, Snapshot {
+        user_dumbs: before.user_dumbs + DUMBS_BET_PAYOUT,
+        dumbs_supply: before.dumbs_supply + DUMBS_BET_PAYOUT,
+        ..before
+    });
+    let state = h.betting_state().await;
+    assert_eq!(state.total_bets_settled, 1);
+    assert_eq!(state.total_dumbs_won, DUMBS_BET_PAYOUT);
+    assert_eq!(state.total_potential_payout, 0);
+    assert_eq!(state.total_dumbs_in_circulation, DEPOSIT_DUMBS + DUMBS_BET_PAYOUT);
+    let account = h.user_betting_account().await;
+    assert!(account.active_bet.is_none());
+    assert_eq!(account.total_winnings, DUMBS_BET_PAYOUT);
+
+    // RAPR bet that loses
+    let before = after;
+    h.open_fight(2).await;
+    h.send(&[house.place_bet(&bettor, RAPR_BET, 2, FIGHTER_ONE, Odds::from_american(-200).unwrap(), TokenType::RAPR)], &[&user]).await.unwrap();
+    let after = h.snapshot().await;
+    assert_eq!(after, Snapshot {
+        user_lamports: before.user_lamports - TX_FEE,
+        user_rapr: before.user_rapr - RAPR_BET,
+        rapr_vault: before.rapr_vault + RAPR_BET,
+        ..before
+    });
+    let state = h.betting_state().await;
+    assert_eq!(state.total_bets_placed, 2);
+    assert_eq!(state.total_rapr_wagered, RAPR_BET_STAKE);
+    assert_eq!(state.total_potential_payout, RAPR_BET_PAYOUT);
+
+    let before = after;
+    h.post_and_settle(&bettor, 2, FIGHTER_TWO).await.unwrap();
+    let after = h.snapshot().await;
+    assert_eq!(after, before);
+    let state = h.betting_state().await;
+    assert_eq!(state.total_bets_settled, 2);
+    assert_eq!(state.total_dumbs_won, DUMBS_BET_PAYOUT);
+    assert_eq!(state.total_potential_payout, 0);
+
+    // RAPR bet that wins: paid in DUMBS, stake returned
+    let before = after;
+    h.open_fight(3).await;
+    h.send(&[house.place_bet(&bettor, RAPR_BET, 3, FIGHTER_ONE, Odds::from_american(-200).unwrap(), TokenType::RAPR)], &[&user]).await.unwrap();
+    h.post_and_settle(&bettor, 3, FIGHTER_ONE).await.unwrap();
+    let after = h.snapshot().await;
+    assert_eq!(after, Snapshot {
+        user_lamports: before.user_lamports - TX_FEE,
+        user_dumbs: before.user_dumbs + RAPR_WIN_DUMBS,
+        user_rapr: before.user_rapr - RAPR_BET + RAPR_BET_STAKE,
+        rapr_vault: before.rapr_vault + RAPR_BET - RAPR_BET_STAKE,
+        dumbs_supply: before.dumbs_supply + RAPR_WIN_DUMBS,
+        ..before
+    });
+    let state = h.betting_state().await;
+    assert_eq!(state.total_bets_placed, 3);
+    assert_eq!(state.total_bets_settled, 3);
+    assert_eq!(state.total_rapr_wagered, 2 * RAPR_BET_STAKE);
+    assert_eq!(state.total_dumbs_won, DUMBS_BET_PAYOUT + RAPR_WIN_DUMBS);
+    assert_eq!(state.total_potential_payout, 0);
+    assert_eq!(state.total_rapr_in_circulation, SWAP_RAPR);
+
+    // Cash out DUMBS: burned, SOL paid from the vault
+    let before = after;
+    h.send(&[house.cash_out(&bettor, DUMBS_CASH_OUT, TokenType::DUMBS)], &[&user]).await.unwrap();
+    let after = h.snapshot().await;
+    assert_eq!(after, Snapshot {
+        user_lamports: before.user_lamports + DUMBS_CASH_OUT_SOL - TX_FEE,
+        user_dumbs: before.user_dumbs - DUMBS_CASH_OUT,
+        dumbs_supply: before.dumbs_supply - DUMBS_CASH_OUT,
+        sol_vault_lamports: before.sol_vault_lamports - DUMBS_CASH_OUT_SOL,
+        ..before
+    });
+    let state = h.betting_state().await;
+    assert_eq!(state.total_dumbs_in_circulation, after.dumbs_supply);
+    assert_eq!(h.sol_vault().await.balance, DEPOSIT - DEPOSIT_FEE + SWAP - DUMBS_CASH_OUT_SOL);
+
+    // Cash out RAPR: returned to the house vault, SOL paid from the vault
+    let before = after;
+    h.send(&[house.cash_out(&bettor, RAPR_CASH_OUT, TokenType::RAPR)], &[&user]).await.unwrap();
+    let after = h.snapshot().await;
+    assert_eq!(after, Snapshot {
+        user_lamports: before.user_lamports + RAPR_CASH_OUT_SOL - TX_FEE,
+        user_rapr: before.user_rapr - RAPR_CASH_OUT,
+        rapr_vault: before.rapr_vault + RAPR_CASH_OUT,
+        sol_vault_lamports: before.sol_vault_lamports - RAPR_CASH_OUT_SOL,
+        ..before
+    });
+    let state = h.betting_state().await;
+    assert_eq!(state.total_rapr_in_circulation, SWAP_RAPR - RAPR_CASH_OUT);
+    assert_eq!(
+        h.sol_vault().await.balance,
+        DEPOSIT - DEPOSIT_FEE + SWAP - DUMBS_CASH_OUT_SOL - RAPR_CASH_OUT_SOL
+    );
+}
+
+#[tokio::test]
+async fn paused_house_rejects_bets_and_cash_outs() {
+    let mut h = Harness::start().await;
+    let house = h.house;
+    let user = h.user.insecure_clone();
+    let authority = h.authority.insecure_clone();
+
+    h.send(
+        &[house.grant_role(&authority.pubkey(), authority.pubkey(), Role::Pauser)],
+        &[&authority],
+    )
+    .await
+    .unwrap();
+    h.send(&[house.deposit_sol(&user.pubkey(), DEPOSIT)], &[&user]).await.unwrap();
+    h.send(&[house.set_paused(&authority.pubkey(), true)], &[&authority]).await.unwrap();
+
+    h.open_fight(1).await;
+    let before = h.snapshot().await;
+    let result = h
+        .send(&[house.place_bet(&user.pubkey(), DUMBS_BET, 1, FIGHTER_ONE, Odds::EVENS, TokenType::DUMBS)], &[&user])
+        .await;
+    assert_program_error(result, ErrorCode::ProgramPaused);
+    // Only the transaction fee may have moved
+    let after = h.snapshot().await;
+    assert_eq!(after, Snapshot { user_lamports: after.user_lamports, ..before });
+    assert_eq!(h.betting_state().await.total_bets_placed, 0);
+
+    let result = h.send(&[house.cash_out(&user.pubkey(), DUMBS_BET, TokenType::DUMBS)], &[&user]).await;
+    assert_program_error(result, ErrorCode::ProgramPaused);
+}
+
+#[tokio::test]
+async fn bets_back_a_fighter_and_settle_against_the_posted_result() {
+    let mut h = Harness::start().await;
+    let house = h.house;
+    let user = h.user.insecure_clone();
+    let settler = h.authority.insecure_clone();
+    let bettor = user.pubkey();
+    h.send(&[house.deposit_sol(&bettor, DEPOSIT)], &[&user]).await.unwrap();
+
+    // Bets need a registered fight and one of its fighters
+    let bet = |fighter| house.place_bet(&bettor, DUMBS_BET, 1, fighter, Odds::EVENS, TokenType::DUMBS);
+    assert!(h.send(&[bet(FIGHTER_ONE)], &[&user]).await.is_err());
+    h.open_fight(1).await;
+    assert_program_error(h.send(&[bet(settler.pubkey())], &[&user]).await, ErrorCode::InvalidFighter);
+    h.send(&[bet(FIGHTER_TWO)], &[&user]).await.unwrap();
+    assert_eq!(h.user_betting_account().await.active_bet.unwrap().fighter, FIGHTER_TWO);
+
+    // Nothing settles before the result is posted
+    let result = h.send(&[house.settle_bet(&settler.pubkey(), &bettor, 1)], &[&settler]).await;
+    assert_program_error(result, ErrorCode::FightNotResolved);
+    let result = h.send(&[house.mint_dumbs_for_win(&settler.pubkey(), &bettor, 1)], &[&settler]).await;
+    assert_program_error(result, ErrorCode::FightNotResolved);
+
+    // A bet on the loser cannot be paid as a win and settles as a loss
+    h.send(&[house.post_fight_result(&settler.pubkey(), 1, FIGHTER_ONE)], &[&settler]).await.unwrap();
+    let result = h.send(&[house.mint_dumbs_for_win(&settler.pubkey(), &bettor, 1)], &[&settler]).await;
+    assert_program_error(result, ErrorCode::BetNotWon);
+    let before = h.snapshot().await;
+    h.send(&[house.settle_bet(&settler.pubkey(), &bettor, 1)], &[&settler]).await.unwrap();
+    assert_eq!(h.snapshot().await.user_dumbs, before.user_dumbs);
+    let account = h.user_betting_account().await;
+    assert!(account.active_bet.is_none());
+    assert_eq!(account.total_winnings, 0);
+
+    // A posted fight takes no more bets
+    assert_program_error(h.send(&[bet(FIGHTER_ONE)], &[&user]).await, ErrorCode::FightAlreadyResolved);
+}
@@ -0,0 +1,130 @@
+//! Responsible gambling limits: deposit, wager and loss limits per day and
+//! week, bet cooldowns and self-exclusion, with tightening applied at once and
+//! loosening only after `LIMIT_LOOSEN_DELAY`.
+
+mod common;
+
+use betting_program::errors::error_code::ErrorCode;
+use betting_program::state::{UserLimits, LIMIT_LOOSEN_DELAY, PERMANENT_EXCLUSION};
+use casino_client::{pda, LimitSettings, Odds, TokenType};
+use common::{assert_program_error, Harness, FIGHTER_ONE, FIGHTER_TWO, LAMPORTS_PER_SOL};
+use solana_sdk::signer::Signer;
+
+const BET: u64 = 1_000_000_000;
+const BET_VALUE: u64 = BET / 1_000; // Lamports at 1000 DUMBS per SOL
+
+impl Harness {
+    async fn user_limits(&mut self) -> UserLimits {
+        let address = pda::user_limits(&self.user.pubkey()).0;
+        self.account(&address).await
+    }
+
+    async fn set_limits(&mut self, limits: LimitSettings) {
+        let user = self.user.insecure_clone();
+        self.send(&[self.house.set_limits(&user.pubkey(), limits)], &[&user]).await.unwrap();
+    }
+
+    async fn bet(&mut self, fight_id: u64) -> Result<(), solana_program_test::BanksClientError> {
+        let user = self.user.insecure_clone();
+        if !self.account_exists(&pda::fight(&self.house.betting_state, fight_id).0).await {
+            self.open_fight(fight_id).await;
+        }
+        let ix = self.house.place_bet(&user.pubkey(), BET, fight_id, FIGHTER_ONE, Odds::EVENS, TokenType::DUMBS);
+        self.send(&[ix], &[&user]).await
+    }
+
+    async fn settle(&mut self, fight_id: u64, won: bool) {
+        let bettor = self.user.pubkey();
+        let winner = if won { FIGHTER_ONE } else { FIGHTER_TWO };
+        self.post_and_settle(&bettor, fight_id, winner).await.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn deposit_limit_covers_deposits_and_swaps() {
+    let mut h = Harness::start().await;
+    let house = h.house;
+    let user = h.user.insecure_clone();
+    let owner = user.pubkey();
+
+    h.set_limits(LimitSettings { daily_deposit: Some(LAMPORTS_PER_SOL), ..LimitSettings::default() }).await;
+
+    h.send(&[house.deposit_sol(&owner, LAMPORTS_PER_SOL / 2)], &[&user]).await.unwrap();
+    let result = h.send(&[house.deposit_sol(&owner, LAMPORTS_PER_SOL)], &[&user]).await;
+    assert_program_error(result, ErrorCode::DepositLimitExceeded);
+    h.send(&[house.swap_sol_for_rapr(&owner, LAMPORTS_PER_SOL / 4)], &[&user]).await.unwrap();
+    let result = h.send(&[house.swap_sol_for_rapr(&owner, LAMPORTS_PER_SOL / 2)], &[&user]).await;
+    assert_program_error(result, ErrorCode::DepositLimitExceeded);
+    assert_eq!(h.user_limits().await.day.deposited, LAMPORTS_PER_SOL * 3 / 4);
+}
+
+#[tokio::test]
+async fn loss_limit_counts_settled_losses_and_the_next_stake() {
+    let mut h = Harness::start().await;
+    let user = h.user.insecure_clone();
+    h.send(&[h.house.deposit_sol(&user.pubkey(), LAMPORTS_PER_SOL)], &[&user]).await.unwrap();
+
+    h.set_limits(LimitSettings { daily_loss: Some(BET_VALUE * 3 / 2), ..LimitSettings::default() }).await;
+
+    h.bet(1).await.unwrap();
+    h.settle(1, false).await;
+    assert_eq!(h.user_limits().await.day.net_loss(), BET_VALUE);
+
+    // A second losing bet could take the day's losses past the limit
+    assert_program_error(h.bet(2).await, ErrorCode::LossLimitExceeded);
+}
+
+#[tokio::test]
+async fn limits_tighten_at_once_and_loosen_after_the_delay() {
+    let mut h = Harness::start().await;
+    let user = h.user.insecure_clone();
+    h.send(&[h.house.deposit_sol(&user.pubkey(), LAMPORTS_PER_SOL)], &[&user]).await.unwrap();
+
+    let cooldown = 2 * LIMIT_LOOSEN_DELAY;
+    h.set_limits(LimitSettings { bet_cooldown: cooldown, ..LimitSettings::default() }).await;
+    h.bet(1).await.unwrap();
+    h.settle(1, true).await;
+    assert_program_error(h.bet(2).await, ErrorCode::BetCooldownActive);
+
+    // Dropping the cooldown is a loosening, so it waits for the delay
+    h.set_limits(LimitSettings::default()).await;
+    let limits = h.user_limits().await;
+    assert_eq!(limits.limits.bet_cooldown, cooldown);
+    assert_eq!(limits.pending, Some(LimitSettings::default()));
+    assert_program_error(h.bet(2).await, ErrorCode::BetCooldownActive);
+
+    h.advance_clock(LIMIT_LOOSEN_DELAY).await;
+    h.bet(3).await.unwrap();
+    let limits = h.user_limits().await;
+    assert_eq!(limits.limits, LimitSettings::default());
+    assert_eq!(limits.pending, None);
+}
+
+#[tokio::test]
+async fn self_exclusion_blocks_play_and_cannot_be_shortened() {
+    let mut h = Harness::start().await;
+    let house = h.house;
+    let user = h.user.insecure_clone();
+    let owner = user.pubkey();
+    h.send(&[house.deposit_sol(&owner, LAMPORTS_PER_SOL)], &[&user]).await.unwrap();
+
+    h.send(&[house.self_exclude(&owner, Some(7 * 24 * 60 * 60))], &[&user]).await.unwrap();
+    let until = h.user_limits().await.excluded_until;
+
+    assert_program_error(h.bet(1).await, ErrorCode::SelfExcluded);
+    let result = h.send(&[house.deposit_sol(&owner, LAMPORTS_PER_SOL)], &[&user]).await;
+    assert_program_error(result, ErrorCode::SelfExcluded);
+    let result = h.send(&[house.swap_sol_for_rapr(&owner, LAMPORTS_PER_SOL / 10)], &[&user]).await;
+    assert_program_error(result, ErrorCode::SelfExcluded);
+
+    // Cashing out stays open
+    h.send(&[house.cash_out(&owner, BET, TokenType::DUMBS)], &[&user]).await.unwrap();
+
+    h.send(&[house.self_exclude(&owner, Some(60))], &[&user]).await.unwrap();
+    assert_eq!(h.user_limits().await.excluded_until, until);
+
+    h.send(&[house.self_exclude(&owner, None)], &[&user]).await.unwrap();
+    assert_eq!(h.user_limits().await.excluded_until, PERMANENT_EXCLUSION);
+    h.advance_clock(365 * 24 * 60 * 60).await;
+    assert_program_error(h.bet(1).await, ErrorCode::SelfExcluded);
+}
@@ -0,0 +1,57 @@
+//! Token-2022 metadata on the DUMBS and RAPR mints: written at bootstrap with
+//! the house and its SOL rate as custom fields, then editable by admins.
+
+mod common;
+
+use betting_program::errors::error_code::ErrorCode;
+use casino_client::{MetadataField, TokenType};
+use common::{assert_program_error, Harness};
+use solana_sdk::signer::Signer;
+
+fn field<'a>(fields: &'a [(String, String)], key: &str) -> Option<&'a str> {
+    fields.iter().find(|(k, _)| k == key).map(|(_, value)| value.as_str())
+}
+
+#[tokio::test]
+async fn bootstrap_writes_metadata_for_both_mints() {
+    let mut h = Harness::start().await;
+    let house = h.house;
+    let state = h.betting_state().await;
+
+    let dumbs = h.token_metadata(&house.dumbs_mint).await;
+    assert_eq!((dumbs.name.as_str(), dumbs.symbol.as_str()), ("freeDUMBS", "DUMBS"));
+    assert_eq!(dumbs.uri, "https://example.com/dumbs.json");
+    assert_eq!(Option::<solana_sdk::pubkey::Pubkey>::from(dumbs.update_authority), Some(house.betting_state));
+    assert_eq!(field(&dumbs.additional_metadata, "house"), Some(house.betting_state.to_string().as_str()));
+    assert_eq!(field(&dumbs.additional_metadata, "rate"), Some(state.sol_dumbs_rate.to_string().as_str()));
+
+    let rapr = h.token_metadata(&house.rapr_mint).await;
+    assert_eq!(rapr.symbol, "RAPR");
+    assert_eq!(field(&rapr.additional_metadata, "rate"), Some(state.sol_rapr_rate.to_string().as_str()));
+}
+
+#[tokio::test]
+async fn admins_update_fields_and_fund_the_growth() {
+    let mut h = Harness::start().await;
+    let house = h.house;
+    let admin = h.authority.insecure_clone();
+    let user = h.user.insecure_clone();
+
+    let uri = format!("https://example.com/{}.json", "dumbs".repeat(40));
+    h.send(&[house.update_token_metadata(&admin.pubkey(), TokenType::DUMBS, MetadataField::Uri, &uri)], &[&admin])
+        .await
+        .unwrap();
+    let key = MetadataField::Key("rate".to_string());
+    h.send(&[house.update_token_metadata(&admin.pubkey(), TokenType::DUMBS, key, "2000")], &[&admin])
+        .await
+        .unwrap();
+
+    let dumbs = h.token_metadata(&house.dumbs_mint).await;
+    assert_eq!(dumbs.uri, uri);
+    assert_eq!(field(&dumbs.additional_metadata, "rate"), Some("2000"));
+
+    let result = h
+        .send(&[house.update_token_metadata(&user.pubkey(), TokenType::DUMBS, MetadataField::Name, "scam")], &[&user])
+        .await;
+    assert_program_error(result, ErrorCode::MissingRole);
+}
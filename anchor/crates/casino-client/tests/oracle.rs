@@ -0,0 +1,126 @@
+//! Price oracle: treasury totals are kept in SOL value, and `quote_house_pnl`
+//! values them in USD through a Pyth-style SOL/USD price account, refusing
+//! stale, uncertain or foreign prices.
+
+mod common;
+
+use betting_program::errors::error_code::ErrorCode;
+use betting_program::state::{OracleConfig, PYTH_STATUS_TRADING};
+use casino_client::{pda, ConfigChange, HousePnl, Odds, OracleParams, OraclePrice, TokenType};
+use common::{assert_program_error, assert_quote_error, Harness, FIGHTER_ONE, LAMPORTS_PER_SOL};
+use solana_sdk::{pubkey::Pubkey, signer::Signer};
+
+const DUMBS_BET: u64 = 1_000_000_000;
+const MAX_AGE: i64 = 60;
+const MAX_CONFIDENCE: u16 = 100; // 1%
+
+/// $150.00 per SOL at Pyth's usual exponent.
+const SOL_USD: i64 = 15_000_000_000;
+const EXPO: i32 = -8;
+
+/// A harness with a deposit, a DUMBS bet and an oracle config whose feed
+/// holds a fresh $150 price. Returns the feed's address and owner.
+async fn priced_house() -> (Harness, Pubkey, Pubkey) {
+    let mut h = Harness::start().await;
+    let house = h.house;
+    let user = h.user.insecure_clone();
+    let bettor = user.pubkey();
+    h.send(&[house.deposit_sol(&bettor, LAMPORTS_PER_SOL)], &[&user]).await.unwrap();
+    h.open_fight(1).await;
+    h.send(&[house.place_bet(&bettor, DUMBS_BET, 1, FIGHTER_ONE, Odds::EVENS, TokenType::DUMBS)], &[&user]).await.unwrap();
+
+    let oracle_program = Pubkey::new_unique();
+    let feed = Pubkey::new_unique();
+    h.configure_oracle(params(oracle_program, feed)).await;
+    let now = h.now().await;
+    h.set_price_feed(&feed, &oracle_program, price(SOL_USD, SOL_USD as u64 / 1_000, now), PYTH_STATUS_TRADING);
+    (h, feed, oracle_program)
+}
+
+/// Settings valuing the house through `feed`, leaving rates fixed.
+fn params(oracle_program: Pubkey, feed: Pubkey) -> OracleParams {
+    OracleParams {
+        oracle_program,
+        sol_usd_feed: feed,
+        dumbs_usd_feed: Pubkey::default(),
+        rapr_usd_feed: Pubkey::default(),
+        max_age: MAX_AGE,
+        max_confidence: MAX_CONFIDENCE,
+        oracle_rates: false,
+    }
+}
+
+fn price(price: i64, conf: u64, publish_time: i64) -> OraclePrice {
+    OraclePrice { price, conf, expo: EXPO, publish_time }
+}
+
+#[tokio::test]
+async fn house_pnl_values_sol_denominated_treasury_totals_in_usd() {
+    let (mut h, feed, oracle_program) = priced_house().await;
+    let house = h.house;
+    let user = h.user.insecure_clone();
+
+    let params = params(oracle_program, feed);
+    let config: OracleConfig = h.account(&pda::oracle_config(&house.betting_state).0).await;
+    assert_eq!(config.params(), params);
+    let result = h.send(&[house.queue_change(&user.pubkey(), ConfigChange::Oracle(params))], &[&user]).await;
+    assert_program_error(result, ErrorCode::MissingRole);
+    let result = h.send(&[house.create_oracle_config(&user.pubkey())], &[&user]).await;
+    assert!(result.is_err(), "the config is created once, by an admin");
+
+    // DUMBS fees count at their SOL value, not their raw amount
+    let state = h.betting_state().await;
+    let treasury = h.treasury().await;
+    let fee_value = treasury.dumbs_fees_collected / state.sol_dumbs_rate;
+    assert!(fee_value > 0);
+    assert_eq!(treasury.total_fees_collected, treasury.sol_fees_collected + fee_value);
+
+    let pnl: HousePnl = h.quote(house.quote_house_pnl(&feed)).await.unwrap();
+    assert_eq!(pnl.sol_usd.price, SOL_USD);
+    assert_eq!(pnl.fees, treasury.total_fees_collected);
+    // $150 per 10^9 lamports is 150,000,000 micro-USD per 10^9 lamports
+    assert_eq!(pnl.fees_usd, treasury.total_fees_collected * 150 / 1_000);
+    let backing = h.sol_vault().await.balance;
+    let liabilities = state.total_dumbs_in_circulation.div_ceil(state.sol_dumbs_rate);
+    assert_eq!(pnl.backing, backing);
+    assert_eq!(pnl.liabilities, liabilities);
+    assert_eq!(pnl.net, backing as i64 - liabilities as i64);
+    assert_eq!(pnl.net_usd, pnl.net * 150 / 1_000);
+}
+
+#[tokio::test]
+async fn stale_uncertain_and_foreign_prices_are_rejected() {
+    let (mut h, feed, oracle_program) = priced_house().await;
+    let house = h.house;
+    let now = h.now().await;
+
+    // A price past its max age
+    h.advance_clock(MAX_AGE + 1).await;
+    let result = h.quote::<HousePnl>(house.quote_house_pnl(&feed)).await;
+    assert_quote_error(result, ErrorCode::StaleOraclePrice);
+    let now = now + MAX_AGE + 1;
+
+    // A confidence interval wider than 1% of the price
+    h.set_price_feed(&feed, &oracle_program, price(SOL_USD, SOL_USD as u64 / 50, now), PYTH_STATUS_TRADING);
+    let result = h.quote::<HousePnl>(house.quote_house_pnl(&feed)).await;
+    assert_quote_error(result, ErrorCode::OracleConfidenceTooWide);
+
+    // A feed that is halted, not positive, owned by another program or not the configured one
+    h.set_price_feed(&feed, &oracle_program, price(SOL_USD, 0, now), PYTH_STATUS_TRADING + 1);
+    let result = h.quote::<HousePnl>(house.quote_house_pnl(&feed)).await;
+    assert_quote_error(result, ErrorCode::InvalidOracle);
+    h.set_price_feed(&feed, &oracle_program, price(0, 0, now), PYTH_STATUS_TRADING);
+    let result = h.quote::<HousePnl>(house.quote_house_pnl(&feed)).await;
+    assert_quote_error(result, ErrorCode::InvalidOracle);
+    h.set_price_feed(&feed, &Pubkey::new_unique(), price(SOL_USD, 0, now), PYTH_STATUS_TRADING);
+    let result = h.quote::<HousePnl>(house.quote_house_pnl(&feed)).await;
+    assert_quote_error(result, ErrorCode::InvalidOracle);
+    let other = Pubkey::new_unique();
+    h.set_price_feed(&other, &oracle_program, price(SOL_USD, 0, now), PYTH_STATUS_TRADING);
+    let result = h.quote::<HousePnl>(house.quote_house_pnl(&other)).await;
+    assert_quote_error(result, ErrorCode::InvalidOracle);
+
+    // A fresh, tight price from the configured feed is accepted again
+    h.set_price_feed(&feed, &oracle_program, price(SOL_USD, 0, now), PYTH_STATUS_TRADING);
+    h.quote::<HousePnl>(house.quote_house_pnl(&feed)).await.unwrap();
+}
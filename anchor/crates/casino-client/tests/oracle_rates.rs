@@ -0,0 +1,177 @@
+//! Oracle rates: with the mode on, deposits, swaps and cash-outs trade at the
+//! ratio of the SOL/USD and token/USD prices, at the edge of their confidence
+//! intervals that favours the house, and fail if they fill further below the
+//! caller's quote than its `max_slippage`.
+
+mod common;
+
+use betting_program::errors::error_code::ErrorCode;
+use betting_program::state::{CONFIG_CHANGE_DELAY, PYTH_STATUS_TRADING};
+use casino_client::{
+    CashOutQuote, ConfigChange, DepositQuote, House, OracleFeeds, OracleParams, OraclePrice, SwapQuote, TokenType,
+};
+use common::{assert_program_error, Harness, LAMPORTS_PER_SOL, TX_FEE};
+use solana_sdk::{pubkey::Pubkey, signer::Signer};
+
+const DEPOSIT: u64 = LAMPORTS_PER_SOL;
+const SWAP: u64 = 10_000_000;
+const MAX_AGE: i64 = 60;
+const MAX_CONFIDENCE: u16 = 100; // 1%
+
+/// $150.00 per SOL ± 0.1%, and $0.10 per DUMBS ± 0.5%, at exponent -8.
+const SOL_USD: i64 = 15_000_000_000;
+const SOL_CONF: u64 = 15_000_000;
+const DUMBS_USD: i64 = 10_000_000;
+const DUMBS_CONF: u64 = 50_000;
+/// $0.00001 per RAPR at exponent -10, so rates must line up the exponents.
+const RAPR_USD: i64 = 100_000;
+
+struct Oracle {
+    program: Pubkey,
+    params: OracleParams,
+}
+
+/// A harness whose house trades at oracle rates, and the house with its feeds.
+async fn oracle_house() -> (Harness, House, Oracle) {
+    let mut h = Harness::start().await;
+    let house = h.house;
+    let program = Pubkey::new_unique();
+    let params = OracleParams {
+        oracle_program: program,
+        sol_usd_feed: Pubkey::new_unique(),
+        dumbs_usd_feed: Pubkey::new_unique(),
+        rapr_usd_feed: Pubkey::new_unique(),
+        max_age: MAX_AGE,
+        max_confidence: MAX_CONFIDENCE,
+        oracle_rates: true,
+    };
+    let oracle = Oracle { program, params };
+    h.configure_oracle(params).await;
+    let now = h.now().await;
+    set_prices(&mut h, &oracle, now);
+    let house = house.with_oracle_feeds(OracleFeeds::from_params(&params));
+    (h, house, oracle)
+}
+
+fn set_prices(h: &mut Harness, oracle: &Oracle, publish_time: i64) {
+    let prices = [
+        (oracle.params.sol_usd_feed, OraclePrice { price: SOL_USD, conf: SOL_CONF, expo: -8, publish_time }),
+        (oracle.params.dumbs_usd_feed, OraclePrice { price: DUMBS_USD, conf: DUMBS_CONF, expo: -8, publish_time }),
+        (oracle.params.rapr_usd_feed, OraclePrice { price: RAPR_USD, conf: 0, expo: -10, publish_time }),
+    ];
+    for (feed, price) in prices {
+        h.set_price_feed(&feed, &oracle.program, price, PYTH_STATUS_TRADING);
+    }
+}
+
+/// `amount · numerator / denominator`, rounded down.
+fn convert(amount: u64, numerator: u64, denominator: u64) -> u64 {
+    (amount as u128 * numerator as u128 / denominator as u128) as u64
+}
+
+#[tokio::test]
+async fn deposits_swaps_and_cash_outs_trade_at_oracle_rates() {
+    let (mut h, house, _) = oracle_house().await;
+    let user = h.user.insecure_clone();
+    let bettor = user.pubkey();
+
+    // Buying DUMBS takes the low SOL price and the high DUMBS price
+    let quote: DepositQuote = h.quote(house.quote_deposit(DEPOSIT)).await.unwrap();
+    let dumbs = convert(quote.net_amount, SOL_USD as u64 - SOL_CONF, DUMBS_USD as u64 + DUMBS_CONF);
+    assert_eq!(quote.dumbs_minted, dumbs);
+    let before = h.snapshot().await;
+    h.send(&[house.deposit_sol(&bettor, DEPOSIT)], &[&user]).await.unwrap();
+    let after = h.snapshot().await;
+    assert_eq!(after.user_dumbs - before.user_dumbs, dumbs);
+    assert_eq!(after.sol_vault_lamports - before.sol_vault_lamports, quote.net_amount);
+
+    // RAPR's price is two decimal places finer than SOL's
+    let quote: SwapQuote = h.quote(house.quote_swap(SWAP)).await.unwrap();
+    let rapr = convert(SWAP, (SOL_USD as u64 - SOL_CONF) * 100, RAPR_USD as u64);
+    assert_eq!(quote.rapr_minted, rapr);
+    let before = h.snapshot().await;
+    h.send(&[house.swap_sol_for_rapr(&bettor, SWAP)], &[&user]).await.unwrap();
+    let after = h.snapshot().await;
+    assert_eq!(after.user_rapr - before.user_rapr, rapr);
+
+    // Selling DUMBS takes the high SOL price and the low DUMBS price
+    let amount = dumbs / 2;
+    let quote: CashOutQuote = h.quote(house.quote_cash_out(amount, TokenType::DUMBS)).await.unwrap();
+    let sol_returned = convert(quote.net_amount, DUMBS_USD as u64 - DUMBS_CONF, SOL_USD as u64 + SOL_CONF);
+    assert_eq!(quote.sol_returned, sol_returned);
+    let before = h.snapshot().await;
+    h.send(&[house.cash_out(&bettor, amount, TokenType::DUMBS)], &[&user]).await.unwrap();
+    let after = h.snapshot().await;
+    assert_eq!(after.user_lamports, before.user_lamports + sol_returned - TX_FEE);
+    assert_eq!(after.sol_vault_lamports, before.sol_vault_lamports - sol_returned);
+}
+
+#[tokio::test]
+async fn oracle_rates_enforce_max_slippage_and_feeds_and_only_turn_off_after_the_timelock() {
+    let (mut h, house, oracle) = oracle_house().await;
+    let user = h.user.insecure_clone();
+    let authority = h.authority.insecure_clone();
+    let admin = authority.pubkey();
+    let bettor = user.pubkey();
+
+    // A trade fills at its quote, and with no slippage allowed it fails short of it
+    let quote: DepositQuote = h.quote(house.quote_deposit(DEPOSIT)).await.unwrap();
+    let result = h.send(&[house.deposit_sol_with_slippage(&bettor, DEPOSIT, quote.dumbs_minted + 1, 0)], &[&user]).await;
+    assert_program_error(result, ErrorCode::SlippageExceeded);
+    let result = h.send(&[house.deposit_sol_with_slippage(&bettor, DEPOSIT, quote.dumbs_minted, 10_001)], &[&user]).await;
+    assert_program_error(result, ErrorCode::InvalidConfigValue);
+    h.send(&[house.deposit_sol_with_slippage(&bettor, DEPOSIT, quote.dumbs_minted, 0)], &[&user]).await.unwrap();
+    let quote: SwapQuote = h.quote(house.quote_swap(SWAP)).await.unwrap();
+    h.send(&[house.swap_sol_for_rapr_with_slippage(&bettor, SWAP, quote.rapr_minted, 0)], &[&user]).await.unwrap();
+
+    // A 1% price move after the quote fails within 0.5% slippage but fills within 2%
+    let quote: CashOutQuote = h.quote(house.quote_cash_out(DEPOSIT, TokenType::DUMBS)).await.unwrap();
+    let now = h.now().await;
+    let lower = OraclePrice { price: DUMBS_USD - DUMBS_USD / 100, conf: DUMBS_CONF, expo: -8, publish_time: now };
+    h.set_price_feed(&oracle.params.dumbs_usd_feed, &oracle.program, lower, PYTH_STATUS_TRADING);
+    let cash_out = |max_slippage| house.cash_out_with_slippage(&bettor, DEPOSIT, TokenType::DUMBS, quote.sol_returned, max_slippage);
+    assert_program_error(h.send(&[cash_out(50)], &[&user]).await, ErrorCode::SlippageExceeded);
+    let before = h.snapshot().await;
+    h.send(&[cash_out(200)], &[&user]).await.unwrap();
+    let returned = h.snapshot().await.user_lamports + TX_FEE - before.user_lamports;
+    assert!(returned < quote.sol_returned && returned >= quote.sol_returned * 98 / 100);
+    set_prices(&mut h, &oracle, now);
+
+    // Feeds other than the configured ones, and stale prices, are refused
+    let fixed_house = h.house;
+    let result = h.send(&[fixed_house.deposit_sol(&bettor, DEPOSIT)], &[&user]).await;
+    assert_program_error(result, ErrorCode::InvalidOracle);
+    let swapped = OracleFeeds { dumbs_usd: oracle.params.rapr_usd_feed, ..OracleFeeds::from_params(&oracle.params) };
+    let result = h.send(&[house.with_oracle_feeds(swapped).deposit_sol(&bettor, DEPOSIT)], &[&user]).await;
+    assert_program_error(result, ErrorCode::InvalidOracle);
+    h.advance_clock(MAX_AGE + 1).await;
+    let result = h.send(&[house.cash_out(&bettor, DEPOSIT, TokenType::DUMBS)], &[&user]).await;
+    assert_program_error(result, ErrorCode::StaleOraclePrice);
+
+    // Oracle rates need both token feeds, and only admins change the settings
+    let missing = OracleParams { rapr_usd_feed: Pubkey::default(), ..oracle.params };
+    let result = h.send(&[house.queue_change(&admin, ConfigChange::Oracle(missing))], &[&authority]).await;
+    assert_program_error(result, ErrorCode::InvalidConfigValue);
+    let fixed = OracleParams { oracle_rates: false, ..oracle.params };
+    let result = h.send(&[house.queue_change(&bettor, ConfigChange::Oracle(fixed))], &[&user]).await;
+    assert_program_error(result, ErrorCode::MissingRole);
+
+    // Turning them off waits out the timelock like any other setting
+    h.send(&[house.queue_change(&admin, ConfigChange::Oracle(fixed))], &[&authority]).await.unwrap();
+    let result = h.send(&[house.execute_oracle_change(&admin, &admin)], &[&authority]).await;
+    assert_program_error(result, ErrorCode::TimelockNotExpired);
+    let result = h.send(&[house.deposit_sol(&bettor, DEPOSIT)], &[&user]).await;
+    assert_program_error(result, ErrorCode::StaleOraclePrice);
+    h.advance_clock(CONFIG_CHANGE_DELAY).await;
+    h.send(&[house.execute_oracle_change(&admin, &admin)], &[&authority]).await.unwrap();
+
+    // Turned off, the fixed rate applies whatever feeds are passed
+    let rate = h.betting_state().await.sol_dumbs_rate;
+    for house in [fixed_house, house] {
+        let quote: DepositQuote = h.quote(house.quote_deposit(DEPOSIT)).await.unwrap();
+        assert_eq!(quote.dumbs_minted, quote.net_amount * rate);
+        let before = h.snapshot().await;
+        h.send(&[house.deposit_sol_with_slippage(&bettor, DEPOSIT, quote.dumbs_minted, 0)], &[&user]).await.unwrap();
+        assert_eq!(h.snapshot().await.user_dumbs - before.user_dumbs, quote.dumbs_minted);
+    }
+}
@@ -0,0 +1,94 @@
+//! Play-money houses: DUMBS minted with the NonTransferable extension, so
+//! wallets cannot trade it while deposits, bets, settlement, balances and
+//! cash-outs keep working through program-signed burns and mints.
+
+mod common;
+
+use casino_client::{instructions::create_token_2022_ata, Odds, TokenType};
+use common::{Harness, Setup, FIGHTER_ONE, LAMPORTS_PER_SOL};
+use solana_sdk::signer::Signer;
+use spl_token_2022::{
+    extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions},
+    state::Mint,
+};
+
+const DEPOSIT_DUMBS: u64 = (LAMPORTS_PER_SOL - LAMPORTS_PER_SOL * 250 / 10_000) * 1_000;
+
+const BET: u64 = 1_000_000_000;
+const BET_STAKE: u64 = BET - BET * 250 / 10_000;
+const BET_PAYOUT: u64 = BET_STAKE * 20_000 / 10_000; // Odds 2.0x
+
+async fn play_money_house() -> Harness {
+    Harness::start_with(Setup { non_transferable_dumbs: true, ..Setup::default() }).await
+}
+
+#[tokio::test]
+async fn dumbs_cannot_move_between_wallets() {
+    let mut h = play_money_house().await;
+    let house = h.house;
+    let user = h.user.insecure_clone();
+    let other = h.authority.insecure_clone();
+
+    let mint = h.ctx.banks_client.get_account(house.dumbs_mint).await.unwrap().unwrap();
+    let extensions = StateWithExtensions::<Mint>::unpack(&mint.data).unwrap().get_extension_types().unwrap();
+    assert!(extensions.contains(&ExtensionType::NonTransferable));
+    assert!(h.betting_state().await.dumbs_non_transferable);
+
+    h.send(&[house.deposit_sol(&user.pubkey(), LAMPORTS_PER_SOL)], &[&user]).await.unwrap();
+    h.send(&[create_token_2022_ata(&other.pubkey(), &other.pubkey(), &house.dumbs_mint)], &[&other])
+        .await
+        .unwrap();
+    let transfer = spl_token_2022::instruction::transfer_checked(
+        &spl_token_2022::ID,
+        &house.dumbs_ata(&user.pubkey()),
+        &house.dumbs_mint,
+        &house.dumbs_ata(&other.pubkey()),
+        &user.pubkey(),
+        &[],
+        BET,
+        9,
+    )
+    .unwrap();
+    assert!(h.send(&[transfer], &[&user]).await.is_err());
+    assert_eq!(h.snapshot().await.user_dumbs, DEPOSIT_DUMBS);
+}
+
+#[tokio::test]
+async fn the_house_burns_and_mints_in_place_of_transfers() {
+    let mut h = play_money_house().await;
+    let house = h.house;
+    let user = h.user.insecure_clone();
+    let bettor = user.pubkey();
+
+    h.send(&[house.deposit_sol(&bettor, LAMPORTS_PER_SOL)], &[&user]).await.unwrap();
+    let start = h.snapshot().await;
+
+    // A won bet leaves balances as a transferable house would
+    h.open_fight(1).await;
+    h.send(&[house.place_bet(&bettor, BET, 1, FIGHTER_ONE, Odds::EVENS, TokenType::DUMBS)], &[&user]).await.unwrap();
+    let placed = h.snapshot().await;
+    assert_eq!(placed.user_dumbs, start.user_dumbs - BET);
+    assert_eq!(placed.bet_vault, start.bet_vault + BET);
+    assert_eq!(placed.dumbs_supply, start.dumbs_supply);
+
+    h.post_and_settle(&bettor, 1, FIGHTER_ONE).await.unwrap();
+    let settled = h.snapshot().await;
+    assert_eq!(settled.user_dumbs, placed.user_dumbs + BET_PAYOUT);
+    assert_eq!(settled.bet_vault, placed.bet_vault);
+    assert_eq!(settled.dumbs_supply, h.betting_state().await.total_dumbs_in_circulation);
+
+    // Internal balances move in and out the same way
+    h.send(&[house.deposit_to_balance(&bettor, BET, TokenType::DUMBS)], &[&user]).await.unwrap();
+    assert_eq!(h.snapshot().await.bet_vault, settled.bet_vault + BET);
+    h.send(&[house.withdraw_from_balance(&bettor, BET, TokenType::DUMBS)], &[&user]).await.unwrap();
+    let withdrawn = h.snapshot().await;
+    assert_eq!(withdrawn.user_dumbs, settled.user_dumbs);
+    assert_eq!(withdrawn.bet_vault, settled.bet_vault);
+    assert_eq!(withdrawn.dumbs_supply, settled.dumbs_supply);
+
+    // Cash-outs already burn
+    h.send(&[house.cash_out(&bettor, BET, TokenType::DUMBS)], &[&user]).await.unwrap();
+    let cashed = h.snapshot().await;
+    assert_eq!(cashed.user_dumbs, settled.user_dumbs - BET);
+    assert_eq!(cashed.dumbs_supply, settled.dumbs_supply - BET);
+}
@@ -0,0 +1,103 @@
+//! The quote instructions change nothing and return exactly what the real
+//! instruction then pays out or charges.
+
+mod common;
+
+use betting_program::errors::error_code::ErrorCode;
+use casino_client::{BetQuote, CashOutQuote, DepositQuote, Odds, Role, SwapQuote, TokenType};
+use common::{assert_quote_error, Harness, FIGHTER_ONE, FIGHTER_TWO, LAMPORTS_PER_SOL, TX_FEE};
+use solana_sdk::signer::Signer;
+
+const DEPOSIT: u64 = LAMPORTS_PER_SOL;
+const SWAP: u64 = 10_000_000;
+// Amounts that do not divide evenly, so every quote carries rounding dust
+const BET: u64 = 1_234_567_891;
+const RAPR_BET: u64 = 10_000_000_003;
+const CASH_OUT: u64 = 100_000_000_007;
+
+#[tokio::test]
+async fn quotes_match_executed_prices() {
+    let mut h = Harness::start().await;
+    let house = h.house;
+    let user = h.user.insecure_clone();
+    let bettor = user.pubkey();
+
+    // Deposit
+    let quote: DepositQuote = h.quote(house.quote_deposit(DEPOSIT)).await.unwrap();
+    let before = h.snapshot().await;
+    let state = h.betting_state().await;
+    h.send(&[house.deposit_sol(&bettor, DEPOSIT)], &[&user]).await.unwrap();
+    let after = h.snapshot().await;
+    assert_eq!(quote.net_amount, DEPOSIT - quote.fee);
+    assert_eq!(after.user_dumbs - before.user_dumbs, quote.dumbs_minted);
+    assert_eq!(after.treasury_lamports - before.treasury_lamports, quote.fee);
+    assert_eq!(after.sol_vault_lamports - before.sol_vault_lamports, quote.net_amount);
+    assert_eq!(h.betting_state().await.rounding_dust - state.rounding_dust, quote.dust);
+
+    // Swap
+    let quote: SwapQuote = h.quote(house.quote_swap(SWAP)).await.unwrap();
+    let before = h.snapshot().await;
+    h.send(&[house.swap_sol_for_rapr(&bettor, SWAP)], &[&user]).await.unwrap();
+    let after = h.snapshot().await;
+    assert_eq!(quote, SwapQuote { fee: 0, net_amount: SWAP, rapr_minted: after.user_rapr - before.user_rapr });
+
+    // Bets in both tokens, the RAPR one with boosted odds
+    for (fight_id, amount, odds, token_type) in [
+        (1, BET, Odds::from_american(150).unwrap(), TokenType::DUMBS),
+        (2, RAPR_BET, Odds::from_american(-200).unwrap(), TokenType::RAPR),
+    ] {
+        let quote: BetQuote = h.quote(house.quote_bet(&bettor, amount, odds, token_type)).await.unwrap();
+        let state = h.betting_state().await;
+        h.open_fight(fight_id).await;
+        h.send(&[house.place_bet(&bettor, amount, fight_id, FIGHTER_ONE, odds, token_type)], &[&user]).await.unwrap();
+        let bet = h.user_betting_account().await.active_bet.expect("active bet");
+        assert_eq!(quote.fee, bet.fee_amount);
+        assert_eq!(quote.net_amount, bet.amount);
+        assert_eq!(quote.odds, bet.odds as u64);
+        assert_eq!(quote.potential_payout, bet.potential_payout);
+        assert_eq!(h.betting_state().await.rounding_dust - state.rounding_dust, quote.dust);
+        if token_type == TokenType::RAPR {
+            assert_eq!(quote.odds, odds.bps() as u64 * 10);
+        }
+
+        h.post_and_settle(&bettor, fight_id, FIGHTER_TWO).await.unwrap();
+    }
+
+    // Cash out
+    let quote: CashOutQuote = h.quote(house.quote_cash_out(CASH_OUT, TokenType::DUMBS)).await.unwrap();
+    let before = h.snapshot().await;
+    let state = h.betting_state().await;
+    h.send(&[house.cash_out(&bettor, CASH_OUT, TokenType::DUMBS)], &[&user]).await.unwrap();
+    let after = h.snapshot().await;
+    assert_eq!(quote.net_amount, CASH_OUT - quote.fee);
+    assert_eq!(after.user_lamports + TX_FEE - before.user_lamports, quote.sol_returned);
+    assert_eq!(h.betting_state().await.rounding_dust - state.rounding_dust, quote.dust);
+}
+
+#[tokio::test]
+async fn quotes_fail_like_the_instructions() {
+    let mut h = Harness::start().await;
+    let house = h.house;
+    let authority = h.authority.insecure_clone();
+
+    let before = h.betting_state().await;
+    assert_quote_error(h.quote::<DepositQuote>(house.quote_deposit(1)).await, ErrorCode::AmountTooSmall);
+    let quote = h.quote::<BetQuote>(house.quote_bet(&authority.pubkey(), BET, Odds::MIN, TokenType::DUMBS)).await;
+    assert!(quote.is_ok(), "1x odds are valid");
+    assert_quote_error(h.quote::<BetQuote>(house.quote_bet(&authority.pubkey(), u64::MAX, Odds::EVENS, TokenType::DUMBS)).await, ErrorCode::BetTooLarge);
+    // Nothing has been deposited, so the vault cannot pay a cash-out
+    assert_quote_error(h.quote::<CashOutQuote>(house.quote_cash_out(CASH_OUT, TokenType::DUMBS)).await, ErrorCode::InsufficientSolBalance);
+
+    h.send(&[house.grant_role(&authority.pubkey(), authority.pubkey(), Role::Pauser)], &[&authority])
+        .await
+        .unwrap();
+    h.send(&[house.set_paused(&authority.pubkey(), true)], &[&authority]).await.unwrap();
+    assert_quote_error(h.quote::<BetQuote>(house.quote_bet(&authority.pubkey(), BET, Odds::EVENS, TokenType::DUMBS)).await, ErrorCode::ProgramPaused);
+    assert_quote_error(h.quote::<SwapQuote>(house.quote_swap(SWAP)).await, ErrorCode::ProgramPaused);
+    assert_quote_error(h.quote::<CashOutQuote>(house.quote_cash_out(CASH_OUT, TokenType::DUMBS)).await, ErrorCode::ProgramPaused);
+
+    // Quoting never writes
+    let after = h.betting_state().await;
+    assert_eq!(after.rounding_dust, before.rounding_dust);
+    assert_eq!(after.total_fees_collected, before.total_fees_collected);
+}
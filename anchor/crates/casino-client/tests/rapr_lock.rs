@@ -0,0 +1,159 @@
+//! RAPR lock-ups: locked RAPR boosts the owner's RAPR odds by the time left
+//! on the lock and the share of the stake it covers, unlocking early leaves a
+//! penalty for the treasury, and no lock is released under an open RAPR bet.
+
+mod common;
+
+use betting_program::errors::error_code::ErrorCode;
+use betting_program::state::{
+    ConfigChange, RaprLock, CONFIG_CHANGE_DELAY, EARLY_UNLOCK_PENALTY, MAX_LOCK_BOOST, MIN_RAPR_LOCK,
+};
+use casino_client::{pda, BetQuote, LockPeriod, Odds, TokenType};
+use common::{assert_program_error, Harness, FIGHTER_ONE};
+use solana_sdk::signer::Signer;
+
+const SWAP: u64 = 10_000_000; // 100,000 RAPR
+const LOCK: u64 = 10 * MIN_RAPR_LOCK;
+const PENALTY: u64 = LOCK * EARLY_UNLOCK_PENALTY as u64 / 10_000;
+const RAPR_BET: u64 = 10_000_000_000;
+const DAY: i64 = 24 * 60 * 60;
+
+/// A harness whose user has swapped SOL for RAPR.
+async fn rapr_holder() -> Harness {
+    let mut h = Harness::start().await;
+    let house = h.house;
+    let user = h.user.insecure_clone();
+    h.send(&[house.swap_sol_for_rapr(&user.pubkey(), SWAP)], &[&user]).await.unwrap();
+    h
+}
+
+#[tokio::test]
+async fn locked_rapr_boosts_rapr_odds_as_the_lock_runs_down() {
+    let mut h = rapr_holder().await;
+    let house = h.house;
+    let user = h.user.insecure_clone();
+    let bettor = user.pubkey();
+    let rapr_multiplier = h.betting_state().await.rapr_multiplier as u64;
+    let unboosted = Odds::EVENS.bps() as u64 * rapr_multiplier / 10_000;
+
+    let quote: BetQuote = h.quote(house.quote_bet(&bettor, RAPR_BET, Odds::EVENS, TokenType::RAPR)).await.unwrap();
+    assert_eq!(quote.odds, unboosted);
+
+    let result = h.send(&[house.lock_rapr(&bettor, MIN_RAPR_LOCK - 1, LockPeriod::Quarter)], &[&user]).await;
+    assert_program_error(result, ErrorCode::LockTooSmall);
+
+    // A fresh quarter lock gives the full boost, to quotes and bets alike
+    let before = h.snapshot().await;
+    h.send(&[house.lock_rapr(&bettor, LOCK, LockPeriod::Quarter)], &[&user]).await.unwrap();
+    let after = h.snapshot().await;
+    assert_eq!(after.user_rapr, before.user_rapr - LOCK);
+    assert_eq!(after.rapr_vault, before.rapr_vault + LOCK);
+    let lock: RaprLock = h.account(&pda::rapr_lock(&bettor).0).await;
+    assert_eq!(lock.amount, LOCK);
+    assert_eq!(lock.unlocks_at - lock.locked_at, LockPeriod::Quarter.duration());
+
+    let boosted = unboosted * (10_000 + MAX_LOCK_BOOST as u64) / 10_000;
+    let quote: BetQuote = h.quote(house.quote_bet(&bettor, RAPR_BET, Odds::EVENS, TokenType::RAPR)).await.unwrap();
+    assert_eq!(quote.odds, boosted);
+    h.open_fight(1).await;
+    h.send(&[house.place_bet(&bettor, RAPR_BET, 1, FIGHTER_ONE, Odds::EVENS, TokenType::RAPR)], &[&user]).await.unwrap();
+    let bet = h.user_betting_account().await.active_bet.expect("active bet");
+    assert_eq!(bet.odds as u64, boosted);
+
+    // Halfway through, half the boost is left; DUMBS odds never change
+    h.advance_clock(45 * DAY).await;
+    let quote: BetQuote = h.quote(house.quote_bet(&bettor, RAPR_BET, Odds::EVENS, TokenType::RAPR)).await.unwrap();
+    assert_eq!(quote.odds, unboosted * (10_000 + MAX_LOCK_BOOST as u64 / 2) / 10_000);
+    let quote: BetQuote = h.quote(house.quote_bet(&bettor, RAPR_BET, Odds::EVENS, TokenType::DUMBS)).await.unwrap();
+    assert_eq!(quote.odds, Odds::EVENS.bps() as u64);
+
+    // Once the lock ends there is no boost
+    h.advance_clock(45 * DAY).await;
+    let quote: BetQuote = h.quote(house.quote_bet(&bettor, RAPR_BET, Odds::EVENS, TokenType::RAPR)).await.unwrap();
+    assert_eq!(quote.odds, unboosted);
+}
+
+#[tokio::test]
+async fn locks_only_boost_the_part_of_a_stake_they_cover() {
+    let mut h = rapr_holder().await;
+    let house = h.house;
+    let user = h.user.insecure_clone();
+    let authority = h.authority.insecure_clone();
+    let bettor = user.pubkey();
+    let rapr_multiplier = h.betting_state().await.rapr_multiplier as u64;
+    let unboosted = Odds::EVENS.bps() as u64 * rapr_multiplier / 10_000;
+
+    // Let stakes grow past the smallest lock
+    let change = ConfigChange::MaxBet(4 * MIN_RAPR_LOCK);
+    h.send(&[house.queue_change(&authority.pubkey(), change)], &[&authority]).await.unwrap();
+    h.advance_clock(CONFIG_CHANGE_DELAY).await;
+    h.send(&[house.execute_change(&authority.pubkey(), &authority.pubkey(), change.kind())], &[&authority])
+        .await
+        .unwrap();
+    h.send(&[house.lock_rapr(&bettor, MIN_RAPR_LOCK, LockPeriod::Quarter)], &[&user]).await.unwrap();
+
+    let quote_odds = |stake| house.quote_bet(&bettor, stake, Odds::EVENS, TokenType::RAPR);
+    let quote: BetQuote = h.quote(quote_odds(MIN_RAPR_LOCK)).await.unwrap();
+    assert_eq!(quote.odds, unboosted * (10_000 + MAX_LOCK_BOOST as u64) / 10_000);
+    let quote: BetQuote = h.quote(quote_odds(2 * MIN_RAPR_LOCK)).await.unwrap();
+    assert_eq!(quote.odds, unboosted * (10_000 + MAX_LOCK_BOOST as u64 / 2) / 10_000);
+    let quote: BetQuote = h.quote(quote_odds(4 * MIN_RAPR_LOCK)).await.unwrap();
+    assert_eq!(quote.odds, unboosted * (10_000 + MAX_LOCK_BOOST as u64 / 4) / 10_000);
+}
+
+#[tokio::test]
+async fn early_unlocks_pay_the_treasury_and_matured_ones_return_everything() {
+    let mut h = rapr_holder().await;
+    let house = h.house;
+    let user = h.user.insecure_clone();
+    let owner = user.pubkey();
+
+    // Unlocking a day into a week-long lock keeps the penalty
+    h.send(&[house.lock_rapr(&owner, LOCK, LockPeriod::Week)], &[&user]).await.unwrap();
+    h.advance_clock(DAY).await;
+    let before = h.snapshot().await;
+    h.send(&[house.unlock_rapr(&owner)], &[&user]).await.unwrap();
+    let after = h.snapshot().await;
+    assert_eq!(after.user_rapr, before.user_rapr + LOCK - PENALTY);
+    assert_eq!(after.rapr_vault, before.rapr_vault - LOCK + PENALTY);
+    let treasury = h.treasury().await;
+    assert_eq!(treasury.rapr_lock_penalties, PENALTY);
+    assert_eq!(treasury.rapr_fees_collected, PENALTY);
+    assert!(!h.account_exists(&pda::rapr_lock(&owner).0).await);
+
+    let result = h.send(&[house.unlock_rapr(&owner)], &[&user]).await;
+    assert!(result.is_err(), "the lock is closed");
+
+    // A lock that has run its course comes back whole
+    h.send(&[house.lock_rapr(&owner, LOCK, LockPeriod::Week)], &[&user]).await.unwrap();
+    h.advance_clock(LockPeriod::Week.duration()).await;
+    let before = h.snapshot().await;
+    h.send(&[house.unlock_rapr(&owner)], &[&user]).await.unwrap();
+    let after = h.snapshot().await;
+    assert_eq!(after.user_rapr, before.user_rapr + LOCK);
+    assert_eq!(after.rapr_vault, before.rapr_vault - LOCK);
+    assert_eq!(h.treasury().await.rapr_lock_penalties, PENALTY);
+}
+
+#[tokio::test]
+async fn locks_stay_put_while_a_rapr_bet_is_open() {
+    let mut h = rapr_holder().await;
+    let house = h.house;
+    let user = h.user.insecure_clone();
+    let owner = user.pubkey();
+
+    // Lock, take the boosted odds, then try to walk away for the penalty
+    h.send(&[house.lock_rapr(&owner, LOCK, LockPeriod::Quarter)], &[&user]).await.unwrap();
+    h.open_fight(1).await;
+    h.send(&[house.place_bet(&owner, RAPR_BET, 1, FIGHTER_ONE, Odds::EVENS, TokenType::RAPR)], &[&user]).await.unwrap();
+    let result = h.send(&[house.unlock_rapr(&owner)], &[&user]).await;
+    assert_program_error(result, ErrorCode::LockBacksOpenBet);
+    assert!(h.account_exists(&pda::rapr_lock(&owner).0).await);
+
+    // Once the bet settles the lock can be released, early penalty and all
+    h.post_and_settle(&owner, 1, FIGHTER_ONE).await.unwrap();
+    let before = h.snapshot().await;
+    h.send(&[house.unlock_rapr(&owner)], &[&user]).await.unwrap();
+    assert_eq!(h.snapshot().await.user_rapr, before.user_rapr + LOCK - PENALTY);
+    assert!(!h.account_exists(&pda::rapr_lock(&owner).0).await);
+}
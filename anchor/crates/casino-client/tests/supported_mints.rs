@@ -0,0 +1,364 @@
+//! Registered mints: the house takes bets in any whitelisted legacy SPL or
+//! Token-2022 mint, paying winners from that mint's own vault and cashing
+//! it out for SOL from its own SOL liquidity at its timelocked price,
+//! through an oracle when it has a USD feed.
+
+mod common;
+
+use betting_program::errors::error_code::ErrorCode;
+use betting_program::state::{AssetBet, BetHistory, SupportedMint, CONFIG_CHANGE_DELAY, PYTH_STATUS_TRADING};
+use casino_client::instructions::create_ata;
+use casino_client::{pda, ConfigChange, Odds, OracleFeeds, OracleParams, OraclePrice, Role, SupportedMintParams};
+use common::{assert_program_error, Harness, FIGHTER_ONE, FIGHTER_TWO, LAMPORTS_PER_SOL, TX_FEE};
+use solana_sdk::{pubkey::Pubkey, signer::Signer};
+use spl_token_2022::extension::ExtensionType;
+
+const DECIMALS: u8 = 6;
+const ONE: u64 = 1_000_000; // One whole token at DECIMALS
+const BET: u64 = 10 * ONE;
+const PRICE: u64 = LAMPORTS_PER_SOL / 20;
+const PARAMS: SupportedMintParams = SupportedMintParams {
+    multiplier: 12_000,
+    min_bet: ONE,
+    max_bet: 100 * ONE,
+    enabled: true,
+};
+
+/// Queues `change` and executes it once the timelock expires.
+async fn set_price(h: &mut Harness, mint: &Pubkey, lamports_per_token: u64, usd_feed: Pubkey) {
+    let house = h.house;
+    let authority = h.authority.insecure_clone();
+    let change = ConfigChange::MintPrice { mint: *mint, lamports_per_token, usd_feed };
+    h.send(&[house.queue_change(&authority.pubkey(), change)], &[&authority]).await.unwrap();
+    h.advance_clock(CONFIG_CHANGE_DELAY).await;
+    h.send(&[house.execute_mint_price_change(&authority.pubkey(), &authority.pubkey(), mint)], &[&authority])
+        .await
+        .unwrap();
+}
+
+/// Registers a fresh, unpriced mint owned by `token_program` and gives the
+/// user 1,000 tokens in their associated account.
+async fn register_unpriced(h: &mut Harness, token_program: &Pubkey) -> SupportedMint {
+    let house = h.house;
+    let authority = h.authority.insecure_clone();
+    let user = h.user.insecure_clone();
+    let mint = h.create_mint(token_program, DECIMALS, &[]);
+    h.send(&[house.register_mint(&authority.pubkey(), &mint, token_program, PARAMS)], &[&authority])
+        .await
+        .unwrap();
+    h.send(&[create_ata(&user.pubkey(), &user.pubkey(), &mint, token_program)], &[&user]).await.unwrap();
+    let user_tokens = pda::token_ata(&user.pubkey(), &mint, token_program);
+    h.mint_to(&mint, token_program, &user_tokens, 1_000 * ONE).await;
+    h.account(&pda::supported_mint(&house.betting_state, &mint).0).await
+}
+
+/// Registers a fresh mint worth `PRICE` per whole token.
+async fn register(h: &mut Harness, token_program: &Pubkey) -> SupportedMint {
+    let asset = register_unpriced(h, token_program).await;
+    set_price(h, &asset.mint, PRICE, Pubkey::default()).await;
+    h.account(&pda::supported_mint(&h.house.betting_state, &asset.mint).0).await
+}
+
+/// Makes the authority a treasurer and funds `asset`'s cash-outs with `lamports`.
+async fn fund(h: &mut Harness, asset: &SupportedMint, lamports: u64) {
+    let house = h.house;
+    let authority = h.authority.insecure_clone();
+    h.send(
+        &[
+            house.grant_role(&authority.pubkey(), authority.pubkey(), Role::Treasurer),
+            house.fund_mint_liquidity(&authority.pubkey(), &asset.mint, lamports),
+        ],
+        &[&authority],
+    )
+    .await
+    .unwrap();
+}
+
+/// Posts `winner` as fight `fight_id`'s result and settles `bettor`'s bet in `asset`.
+async fn post_and_settle_asset(h: &mut Harness, asset: &SupportedMint, fight_id: u64, winner: Pubkey) {
+    let house = h.house;
+    let authority = h.authority.insecure_clone();
+    let bettor = h.user.pubkey();
+    h.send(
+        &[
+            house.post_fight_result(&authority.pubkey(), fight_id, winner),
+            house.settle_asset_bet(&authority.pubkey(), &bettor, asset, fight_id),
+        ],
+        &[&authority],
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn registered_mints_pay_winners_from_their_vault_and_keep_losing_stakes() {
+    let mut h = Harness::start().await;
+    let house = h.house;
+    let user = h.user.insecure_clone();
+    let bettor = user.pubkey();
+    let token_program = anchor_spl::token::ID;
+    let asset = register(&mut h, &token_program).await;
+    assert_eq!(asset.vault, pda::mint_vault(&house.betting_state, &asset.mint).0);
+    assert_eq!(asset.decimals, DECIMALS);
+    assert_eq!(asset.params(), PARAMS);
+    let user_tokens = pda::token_ata(&bettor, &asset.mint, &token_program);
+    let address = pda::supported_mint(&house.betting_state, &asset.mint).0;
+
+    h.send(&[house.create_bet_history(&bettor)], &[&user]).await.unwrap();
+
+    // An empty vault cannot cover the payout
+    h.open_fight(1).await;
+    let result = h.send(&[house.place_asset_bet(&bettor, &asset, BET, 1, FIGHTER_ONE, Odds::EVENS)], &[&user]).await;
+    assert_program_error(result, ErrorCode::InsufficientVaultLiquidity);
+
+    // Bets back one of the fight's fighters
+    h.mint_to(&asset.mint, &token_program, &asset.vault, 100 * ONE).await;
+    let stranger = Pubkey::new_unique();
+    let result = h.send(&[house.place_asset_bet(&bettor, &asset, BET, 1, stranger, Odds::EVENS)], &[&user]).await;
+    assert_program_error(result, ErrorCode::InvalidFighter);
+    h.send(&[house.place_asset_bet(&bettor, &asset, BET, 1, FIGHTER_ONE, Odds::EVENS)], &[&user]).await.unwrap();
+    let bet: AssetBet = h.account(&pda::asset_bet(&address, &bettor).0).await;
+    assert_eq!(bet.fighter, FIGHTER_ONE);
+    assert_eq!(bet.amount + bet.fee_amount, BET);
+    assert_eq!(bet.odds, Odds::EVENS.bps() * 12 / 10);
+    assert_eq!(bet.potential_payout, bet.amount * bet.odds as u64 / 10_000);
+    assert_eq!(h.token_balance(&user_tokens).await, 990 * ONE);
+    assert_eq!(h.token_balance(&asset.vault).await, 110 * ONE);
+    let registered: SupportedMint = h.account(&address).await;
+    assert_eq!(registered.open_liability, bet.potential_payout);
+    assert_eq!(registered.fees_collected, bet.fee_amount);
+
+    // Nothing settles before the result is posted
+    let authority = h.authority.insecure_clone();
+    let result = h.send(&[house.settle_asset_bet(&authority.pubkey(), &bettor, &asset, 1)], &[&authority]).await;
+    assert_program_error(result, ErrorCode::FightNotResolved);
+
+    // A win is paid in the same mint and closes the bet
+    post_and_settle_asset(&mut h, &asset, 1, FIGHTER_ONE).await;
+    assert_eq!(h.token_balance(&user_tokens).await, 990 * ONE + bet.potential_payout);
+    assert_eq!(h.token_balance(&asset.vault).await, 110 * ONE - bet.potential_payout);
+    assert!(!h.account_exists(&pda::asset_bet(&address, &bettor).0).await);
+    let registered: SupportedMint = h.account(&address).await;
+    assert_eq!(registered.open_liability, 0);
+    assert_eq!(registered.total_paid, bet.potential_payout);
+
+    // A loss leaves the stake in the vault
+    h.open_fight(2).await;
+    h.send(&[house.place_asset_bet(&bettor, &asset, BET, 2, FIGHTER_ONE, Odds::EVENS)], &[&user]).await.unwrap();
+    let vault = h.token_balance(&asset.vault).await;
+    post_and_settle_asset(&mut h, &asset, 2, FIGHTER_TWO).await;
+    assert!(!h.account_exists(&pda::asset_bet(&address, &bettor).0).await);
+    assert_eq!(h.token_balance(&asset.vault).await, vault);
+    let registered: SupportedMint = h.account(&address).await;
+    assert_eq!(registered.open_liability, 0);
+    assert_eq!(registered.total_staked, 2 * bet.amount);
+
+    // Both settlements are in the bettor's history with their mint
+    let history: BetHistory = h.account(&pda::bet_history(&bettor).0).await;
+    let recent: Vec<_> = history.recent().map(|entry| (entry.fight_id, entry.won(), entry.payout)).collect();
+    assert_eq!(recent, vec![(2, false, 0), (1, true, bet.potential_payout)]);
+    assert!(history.recent().all(|entry| entry.mint == asset.mint && entry.token_type().is_none()));
+
+    // A decided fight takes no more bets
+    let result = h.send(&[house.place_asset_bet(&bettor, &asset, BET, 2, FIGHTER_ONE, Odds::EVENS)], &[&user]).await;
+    assert_program_error(result, ErrorCode::FightAlreadyResolved);
+}
+
+#[tokio::test]
+async fn mint_settings_bound_bets_and_cash_outs_pay_sol_at_the_registered_rate() {
+    let mut h = Harness::start().await;
+    let house = h.house;
+    let user = h.user.insecure_clone();
+    let authority = h.authority.insecure_clone();
+    let bettor = user.pubkey();
+    let token_program = spl_token_2022::ID;
+    let asset = register(&mut h, &token_program).await;
+    h.mint_to(&asset.mint, &token_program, &asset.vault, 1_000 * ONE).await;
+    h.open_fight(1).await;
+
+    let bet = |amount| house.place_asset_bet(&bettor, &asset, amount, 1, FIGHTER_ONE, Odds::EVENS);
+    assert_program_error(h.send(&[bet(ONE - 1)], &[&user]).await, ErrorCode::AmountTooSmall);
+    assert_program_error(h.send(&[bet(100 * ONE + 1)], &[&user]).await, ErrorCode::BetTooLarge);
+
+    // Only admins change the settings, and a disabled mint takes no bets or cash-outs
+    let disabled = SupportedMintParams { enabled: false, ..PARAMS };
+    let result = h.send(&[house.update_supported_mint(&bettor, &asset.mint, disabled)], &[&user]).await;
+    assert_program_error(result, ErrorCode::MissingRole);
+    h.send(&[house.update_supported_mint(&authority.pubkey(), &asset.mint, disabled)], &[&authority])
+        .await
+        .unwrap();
+    assert_program_error(h.send(&[bet(BET)], &[&user]).await, ErrorCode::MintDisabled);
+    let result = h.send(&[house.cash_out_asset(&bettor, &asset, BET)], &[&user]).await;
+    assert_program_error(result, ErrorCode::MintDisabled);
+    h.send(&[house.update_supported_mint(&authority.pubkey(), &asset.mint, PARAMS)], &[&authority])
+        .await
+        .unwrap();
+
+    // The SOL backing DUMBS does not pay for the mint's cash-outs
+    h.send(&[house.deposit_sol(&bettor, LAMPORTS_PER_SOL)], &[&user]).await.unwrap();
+    let fee = BET * h.betting_state().await.house_fee as u64 / 10_000;
+    let sol_returned = (BET - fee) * PRICE / ONE;
+    let result = h.send(&[house.cash_out_asset(&bettor, &asset, BET)], &[&user]).await;
+    assert_program_error(result, ErrorCode::InsufficientMintLiquidity);
+
+    // Only treasurers fund a mint's SOL liquidity
+    let result = h.send(&[house.fund_mint_liquidity(&bettor, &asset.mint, sol_returned)], &[&user]).await;
+    assert_program_error(result, ErrorCode::MissingRole);
+    fund(&mut h, &asset, sol_returned).await;
+
+    // The caller bounds what they accept, and the mint's liquidity pays the net amount's SOL value
+    let result = h.send(&[house.cash_out_asset_with_min_out(&bettor, &asset, BET, sol_returned + 1)], &[&user]).await;
+    assert_program_error(result, ErrorCode::SlippageExceeded);
+    let backing = h.sol_vault().await.balance;
+    let before = h.snapshot().await;
+    h.send(&[house.cash_out_asset_with_min_out(&bettor, &asset, BET, sol_returned)], &[&user]).await.unwrap();
+    let after = h.snapshot().await;
+    assert_eq!(after.user_lamports, before.user_lamports + sol_returned - TX_FEE);
+    assert_eq!(after.sol_vault_lamports, before.sol_vault_lamports - sol_returned);
+    assert_eq!(h.sol_vault().await.balance, backing);
+    assert_eq!(h.token_balance(&asset.vault).await, 1_000 * ONE + BET);
+    let registered: SupportedMint = h.account(&pda::supported_mint(&house.betting_state, &asset.mint).0).await;
+    assert_eq!(registered.fees_collected, fee);
+    assert_eq!(registered.sol_liquidity, 0);
+
+    // Nothing is cashed out while the house is paused
+    h.send(&[house.grant_role(&authority.pubkey(), authority.pubkey(), Role::Pauser)], &[&authority])
+        .await
+        .unwrap();
+    h.send(&[house.set_paused(&authority.pubkey(), true)], &[&authority]).await.unwrap();
+    let result = h.send(&[house.cash_out_asset(&bettor, &asset, BET)], &[&user]).await;
+    assert_program_error(result, ErrorCode::ProgramPaused);
+}
+
+#[tokio::test]
+async fn mint_prices_only_change_through_the_timelock() {
+    let mut h = Harness::start().await;
+    let house = h.house;
+    let user = h.user.insecure_clone();
+    let authority = h.authority.insecure_clone();
+    let bettor = user.pubkey();
+    let token_program = spl_token_2022::ID;
+    let asset = register_unpriced(&mut h, &token_program).await;
+    h.mint_to(&asset.mint, &token_program, &asset.vault, 1_000 * ONE).await;
+    h.send(&[house.deposit_sol(&bettor, LAMPORTS_PER_SOL)], &[&user]).await.unwrap();
+    h.open_fight(1).await;
+
+    // A fresh registration takes no bets or cash-outs
+    let result = h.send(&[house.place_asset_bet(&bettor, &asset, BET, 1, FIGHTER_ONE, Odds::EVENS)], &[&user]).await;
+    assert_program_error(result, ErrorCode::MintNotPriced);
+    let result = h.send(&[house.cash_out_asset(&bettor, &asset, BET)], &[&user]).await;
+    assert_program_error(result, ErrorCode::MintNotPriced);
+
+    // Only admins queue a price, and it waits out the delay
+    let change = ConfigChange::MintPrice { mint: asset.mint, lamports_per_token: PRICE, usd_feed: Pubkey::default() };
+    let result = h.send(&[house.queue_change(&bettor, change)], &[&user]).await;
+    assert_program_error(result, ErrorCode::MissingRole);
+    h.send(&[house.queue_change(&authority.pubkey(), change)], &[&authority]).await.unwrap();
+    let execute = house.execute_mint_price_change(&authority.pubkey(), &authority.pubkey(), &asset.mint);
+    let result = h.send(std::slice::from_ref(&execute), &[&authority]).await;
+    assert_program_error(result, ErrorCode::TimelockNotExpired);
+
+    // Each mint has its own pending price
+    let other = register_unpriced(&mut h, &token_program).await;
+    let change = ConfigChange::MintPrice { mint: other.mint, lamports_per_token: PRICE, usd_feed: Pubkey::default() };
+    h.send(&[house.queue_change(&authority.pubkey(), change)], &[&authority]).await.unwrap();
+    h.send(&[house.cancel_mint_price_change(&authority.pubkey(), &authority.pubkey(), &other.mint)], &[&authority])
+        .await
+        .unwrap();
+
+    h.advance_clock(CONFIG_CHANGE_DELAY).await;
+    h.send(&[execute], &[&authority]).await.unwrap();
+    let asset: SupportedMint = h.account(&pda::supported_mint(&house.betting_state, &asset.mint).0).await;
+    assert_eq!(asset.lamports_per_token, PRICE);
+    h.send(&[house.place_asset_bet(&bettor, &asset, BET, 1, FIGHTER_ONE, Odds::EVENS)], &[&user]).await.unwrap();
+    fund(&mut h, &asset, BET * PRICE / ONE).await;
+    h.send(&[house.cash_out_asset(&bettor, &asset, BET)], &[&user]).await.unwrap();
+    let other: SupportedMint = h.account(&pda::supported_mint(&house.betting_state, &other.mint).0).await;
+    assert_eq!(other.lamports_per_token, 0);
+}
+
+#[tokio::test]
+async fn cash_outs_of_mints_with_a_usd_feed_trade_at_the_oracle_price() {
+    let mut h = Harness::start().await;
+    let house = h.house;
+    let user = h.user.insecure_clone();
+    let bettor = user.pubkey();
+    let token_program = anchor_spl::token::ID;
+
+    // $150.00 per SOL ± 0.1%, and $5.00 per token ± 1%, at exponent -8
+    let program = Pubkey::new_unique();
+    let usd_feed = Pubkey::new_unique();
+    let params = OracleParams {
+        oracle_program: program,
+        sol_usd_feed: Pubkey::new_unique(),
+        dumbs_usd_feed: Pubkey::default(),
+        rapr_usd_feed: Pubkey::default(),
+        max_age: 60,
+        max_confidence: 100,
+        oracle_rates: false,
+    };
+    h.configure_oracle(params).await;
+    let house = house.with_oracle_feeds(OracleFeeds::from_params(&params));
+    let asset = register_unpriced(&mut h, &token_program).await;
+    set_price(&mut h, &asset.mint, PRICE, usd_feed).await;
+
+    let publish_time = h.now().await;
+    let sol_usd = OraclePrice { price: 15_000_000_000, conf: 15_000_000, expo: -8, publish_time };
+    let token_usd = OraclePrice { price: 500_000_000, conf: 5_000_000, expo: -8, publish_time };
+    h.set_price_feed(&params.sol_usd_feed, &program, sol_usd, PYTH_STATUS_TRADING);
+    h.set_price_feed(&usd_feed, &program, token_usd, PYTH_STATUS_TRADING);
+    let asset: SupportedMint = h.account(&pda::supported_mint(&house.betting_state, &asset.mint).0).await;
+    assert_eq!(asset.usd_feed, usd_feed);
+    h.send(&[house.deposit_sol(&bettor, LAMPORTS_PER_SOL)], &[&user]).await.unwrap();
+
+    // Selling takes the high SOL price and the low token price
+    let fee = BET * h.betting_state().await.house_fee as u64 / 10_000;
+    let per_token = LAMPORTS_PER_SOL * (token_usd.price as u64 - token_usd.conf) / (sol_usd.price as u64 + sol_usd.conf);
+    let sol_returned = (BET - fee) * per_token / ONE;
+    fund(&mut h, &asset, LAMPORTS_PER_SOL).await;
+    let before = h.snapshot().await;
+    h.send(&[house.cash_out_asset(&bettor, &asset, BET)], &[&user]).await.unwrap();
+    let after = h.snapshot().await;
+    assert_eq!(after.user_lamports, before.user_lamports + sol_returned - TX_FEE);
+
+    // A feed other than the mint's is refused, and so is a stale price
+    let other = SupportedMint { usd_feed: params.sol_usd_feed, ..asset.clone() };
+    let result = h.send(&[house.cash_out_asset(&bettor, &other, BET)], &[&user]).await;
+    assert_program_error(result, ErrorCode::InvalidOracle);
+    h.advance_clock(61).await;
+    let result = h.send(&[house.cash_out_asset(&bettor, &asset, BET)], &[&user]).await;
+    assert_program_error(result, ErrorCode::StaleOraclePrice);
+}
+
+#[tokio::test]
+async fn only_plain_mints_outside_dumbs_and_rapr_can_be_registered() {
+    let mut h = Harness::start().await;
+    let house = h.house;
+    let authority = h.authority.insecure_clone();
+    let user = h.user.insecure_clone();
+    let token_program = spl_token_2022::ID;
+
+    let mint = h.create_mint(&token_program, DECIMALS, &[]);
+    let result = h.send(&[house.register_mint(&user.pubkey(), &mint, &token_program, PARAMS)], &[&user]).await;
+    assert_program_error(result, ErrorCode::MissingRole);
+
+    let result = h
+        .send(&[house.register_mint(&authority.pubkey(), &house.dumbs_mint, &token_program, PARAMS)], &[&authority])
+        .await;
+    assert_program_error(result, ErrorCode::InvalidMint);
+
+    let invalid = SupportedMintParams { min_bet: PARAMS.max_bet + 1, ..PARAMS };
+    let result = h
+        .send(&[house.register_mint(&authority.pubkey(), &mint, &token_program, invalid)], &[&authority])
+        .await;
+    assert_program_error(result, ErrorCode::InvalidConfigValue);
+
+    // Mints that can skim or seize vault tokens are refused
+    for extension in [ExtensionType::TransferFeeConfig, ExtensionType::PermanentDelegate] {
+        let mint = h.create_mint(&token_program, DECIMALS, &[extension]);
+        let result = h
+            .send(&[house.register_mint(&authority.pubkey(), &mint, &token_program, PARAMS)], &[&authority])
+            .await;
+        assert_program_error(result, ErrorCode::UnsupportedMint);
+    }
+}
@@ -0,0 +1,130 @@
+//! RAPR minted with a TransferFeeConfig whose withheld fees the house can
+//! withdraw: RAPR bets and cash-outs pay the withheld fee instead of the house
+//! fee, balance deposits pay it once for the bets they fund, secondary
+//! transfers pay it too, and `harvest_and_withdraw_fees` moves it into the
+//! RAPR vault and the treasury ledger.
+
+mod common;
+
+use betting_program::errors::error_code::ErrorCode;
+use casino_client::{instructions::create_token_2022_ata, BetQuote, Odds, Role, TokenType};
+use common::{assert_program_error, Harness, Setup, FIGHTER_ONE, LAMPORTS_PER_SOL, RAPR_TRANSFER_FEE_BPS};
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+const SWAP: u64 = 10_000_000; // 0.01 SOL buys 100,000 RAPR
+
+const RAPR_BET: u64 = 10_000_000_000;
+const RAPR_BET_FEE: u64 = RAPR_BET * RAPR_TRANSFER_FEE_BPS as u64 / 10_000;
+
+const RAPR_SENT: u64 = 50_000_000_000;
+const RAPR_SENT_FEE: u64 = RAPR_SENT * RAPR_TRANSFER_FEE_BPS as u64 / 10_000;
+
+async fn fee_house() -> Harness {
+    let mut h = Harness::start_with(Setup { rapr_transfer_fee: true, ..Setup::default() }).await;
+    let user = h.user.insecure_clone();
+    let authority = h.authority.insecure_clone();
+    let bettor = user.pubkey();
+    let house = h.house;
+    h.send(&[house.deposit_sol(&bettor, LAMPORTS_PER_SOL), house.swap_sol_for_rapr(&bettor, SWAP)], &[&user])
+        .await
+        .unwrap();
+    h.send(&[house.grant_role(&authority.pubkey(), authority.pubkey(), Role::Treasurer)], &[&authority])
+        .await
+        .unwrap();
+    h
+}
+
+#[tokio::test]
+async fn rapr_bets_pay_the_withheld_fee_until_it_is_harvested() {
+    let mut h = fee_house().await;
+    let house = h.house;
+    let user = h.user.insecure_clone();
+    let authority = h.authority.insecure_clone();
+    let bettor = user.pubkey();
+    assert!(h.betting_state().await.rapr_transfer_fee);
+
+    let quote: BetQuote = h.quote(house.quote_bet(&bettor, RAPR_BET, Odds::EVENS, TokenType::RAPR)).await.unwrap();
+    assert_eq!(quote.fee, RAPR_BET_FEE);
+    assert_eq!(quote.net_amount, RAPR_BET - RAPR_BET_FEE);
+
+    let start = h.snapshot().await;
+    h.open_fight(1).await;
+    h.send(&[house.place_bet(&bettor, RAPR_BET, 1, FIGHTER_ONE, Odds::EVENS, TokenType::RAPR)], &[&user]).await.unwrap();
+    let bet = h.user_betting_account().await.active_bet.expect("active bet");
+    assert_eq!(bet.fee_amount, RAPR_BET_FEE);
+    assert_eq!(bet.amount, RAPR_BET - RAPR_BET_FEE);
+    assert_eq!(h.snapshot().await.rapr_vault, start.rapr_vault + bet.amount);
+
+    // The fee sits in the vault's withheld amount, outside the ledger
+    assert_eq!(h.treasury().await.rapr_fees_collected, 0);
+
+    h.send(&[house.harvest_and_withdraw_fees(&authority.pubkey(), &[])], &[&authority]).await.unwrap();
+    assert_eq!(h.snapshot().await.rapr_vault, start.rapr_vault + RAPR_BET);
+    let treasury = h.treasury().await;
+    assert_eq!(treasury.rapr_fees_collected, RAPR_BET_FEE);
+    assert_eq!(treasury.rapr_fees_harvested, RAPR_BET_FEE);
+}
+
+#[tokio::test]
+async fn secondary_transfers_and_balance_deposits_are_charged_too() {
+    let mut h = fee_house().await;
+    let house = h.house;
+    let user = h.user.insecure_clone();
+    let authority = h.authority.insecure_clone();
+    let friend = Keypair::new();
+    let bettor = user.pubkey();
+    let friend_ata = house.rapr_ata(&friend.pubkey());
+
+    let transfer = spl_token_2022::instruction::transfer_checked(
+        &spl_token_2022::ID,
+        &house.rapr_ata(&bettor),
+        &house.rapr_mint,
+        &friend_ata,
+        &bettor,
+        &[],
+        RAPR_SENT,
+        9,
+    )
+    .unwrap();
+    h.send(&[create_token_2022_ata(&bettor, &friend.pubkey(), &house.rapr_mint), transfer], &[&user])
+        .await
+        .unwrap();
+    assert_eq!(h.token_balance(&friend_ata).await, RAPR_SENT - RAPR_SENT_FEE);
+
+    // The internal balance is credited with what reached the vault
+    h.send(&[house.deposit_to_balance(&bettor, RAPR_BET, TokenType::RAPR)], &[&user]).await.unwrap();
+    assert_eq!(h.user_betting_account().await.rapr_balance, RAPR_BET - RAPR_BET_FEE);
+
+    let start = h.snapshot().await;
+    h.send(&[house.harvest_and_withdraw_fees(&authority.pubkey(), &[friend_ata])], &[&authority]).await.unwrap();
+    assert_eq!(h.snapshot().await.rapr_vault, start.rapr_vault + RAPR_SENT_FEE + RAPR_BET_FEE);
+    assert_eq!(h.treasury().await.rapr_fees_harvested, RAPR_SENT_FEE + RAPR_BET_FEE);
+
+    // Having paid on the way in, a bet from the balance owes no house fee
+    let balance = RAPR_BET - RAPR_BET_FEE;
+    let collected = h.treasury().await.rapr_fees_collected;
+    h.open_fight(1).await;
+    h.send(&[house.place_bet_from_balance(&bettor, balance, 1, FIGHTER_ONE, Odds::EVENS, TokenType::RAPR)], &[&user])
+        .await
+        .unwrap();
+    let bet = h.user_betting_account().await.active_bet.expect("active bet");
+    assert_eq!(bet.fee_amount, 0);
+    assert_eq!(bet.amount, balance);
+    assert_eq!(h.treasury().await.rapr_fees_collected, collected);
+}
+
+#[tokio::test]
+async fn harvesting_needs_a_transfer_fee_and_the_treasurer() {
+    let mut h = fee_house().await;
+    let user = h.user.insecure_clone();
+    let result = h.send(&[h.house.harvest_and_withdraw_fees(&user.pubkey(), &[])], &[&user]).await;
+    assert_program_error(result, ErrorCode::MissingRole);
+
+    let mut h = Harness::start().await;
+    let authority = h.authority.insecure_clone();
+    h.send(&[h.house.grant_role(&authority.pubkey(), authority.pubkey(), Role::Treasurer)], &[&authority])
+        .await
+        .unwrap();
+    let result = h.send(&[h.house.harvest_and_withdraw_fees(&authority.pubkey(), &[])], &[&authority]).await;
+    assert_program_error(result, ErrorCode::TransferFeeNotEnabled);
+}
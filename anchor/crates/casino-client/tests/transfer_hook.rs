@@ -0,0 +1,148 @@
+//! RAPR minted with a TransferHook extension naming `rapr_transfer_hook`:
+//! bets, settlements and cash-outs forward the hook's accounts, while the hook
+//! holds RAPR of self-excluded senders and records holder volume for loyalty
+//! tiers.
+
+mod common;
+
+use casino_client::{
+    accounts::{HolderVolume, LoyaltyTier},
+    instructions::{create_holder_volume, create_token_2022_ata},
+    pda, Odds, TokenType,
+};
+use common::{Harness, Setup, FIGHTER_ONE, FIGHTER_TWO, LAMPORTS_PER_SOL};
+use rapr_transfer_hook::errors::ErrorCode as HookError;
+use rapr_transfer_hook::state::SILVER_TIER_VOLUME;
+use solana_program_test::BanksClientError;
+use solana_sdk::{
+    instruction::InstructionError, pubkey::Pubkey, signature::Keypair, signer::Signer,
+    transaction::TransactionError,
+};
+use spl_token_2022::{
+    extension::{transfer_hook::TransferHook, BaseStateWithExtensions, StateWithExtensions},
+    state::Mint,
+};
+
+const SWAP: u64 = 10_000_000; // 0.01 SOL buys 100,000 RAPR
+
+const RAPR_BET: u64 = 10_000_000_000;
+const RAPR_BET_STAKE: u64 = RAPR_BET - RAPR_BET * 250 / 10_000;
+
+const RAPR_CASH_OUT: u64 = 1_000_000_000_000;
+
+async fn hooked_house() -> Harness {
+    let mut h = Harness::start_with(Setup { rapr_transfer_hook: true, ..Setup::default() }).await;
+    let user = h.user.insecure_clone();
+    let bettor = user.pubkey();
+    let house = h.house;
+    h.send(
+        &[
+            house.deposit_sol(&bettor, LAMPORTS_PER_SOL),
+            house.swap_sol_for_rapr(&bettor, SWAP),
+            create_holder_volume(&bettor, &bettor),
+        ],
+        &[&user],
+    )
+    .await
+    .unwrap();
+    h
+}
+
+impl Harness {
+    async fn holder_volume(&mut self) -> HolderVolume {
+        let address = pda::holder_volume(&self.user.pubkey()).0;
+        self.account(&address).await
+    }
+
+    /// Sends RAPR from the user's wallet to `to`'s, outside the house.
+    async fn send_rapr(&mut self, to: &Keypair, amount: u64) -> Result<(), BanksClientError> {
+        let user = self.user.insecure_clone();
+        let house = self.house;
+        let mut transfer = spl_token_2022::instruction::transfer_checked(
+            &spl_token_2022::ID,
+            &house.rapr_ata(&user.pubkey()),
+            &house.rapr_mint,
+            &house.rapr_ata(&to.pubkey()),
+            &user.pubkey(),
+            &[],
+            amount,
+            9,
+        )
+        .unwrap();
+        transfer.accounts.extend(house.rapr_hook_accounts(&user.pubkey()));
+        self.send(&[create_token_2022_ata(&user.pubkey(), &to.pubkey(), &house.rapr_mint), transfer], &[&user])
+            .await
+    }
+}
+
+fn assert_hook_error(result: Result<(), BanksClientError>, expected: HookError) {
+    let err = result.expect_err("transfer should fail");
+    match err.unwrap() {
+        TransactionError::InstructionError(_, InstructionError::Custom(code)) => {
+            assert_eq!(code, u32::from(expected), "expected {}", expected.name())
+        }
+        other => panic!("expected {}, got {:?}", expected.name(), other),
+    }
+}
+
+#[tokio::test]
+async fn rapr_bets_and_cash_outs_run_the_hook() {
+    let mut h = hooked_house().await;
+    let house = h.house;
+    let user = h.user.insecure_clone();
+    let bettor = user.pubkey();
+
+    let mint = h.ctx.banks_client.get_account(house.rapr_mint).await.unwrap().unwrap();
+    let mint = StateWithExtensions::<Mint>::unpack(&mint.data).unwrap();
+    let hook = mint.get_extension::<TransferHook>().unwrap();
+    assert_eq!(Option::<Pubkey>::from(hook.program_id), Some(rapr_transfer_hook::ID));
+    assert!(h.account_exists(&pda::extra_account_metas(&house.rapr_mint).0).await);
+
+    let start = h.snapshot().await;
+    h.open_fight(1).await;
+    h.send(&[house.place_bet(&bettor, RAPR_BET, 1, FIGHTER_ONE, Odds::EVENS, TokenType::RAPR)], &[&user]).await.unwrap();
+    h.post_and_settle(&bettor, 1, FIGHTER_ONE).await.unwrap();
+    let settled = h.snapshot().await;
+    assert_eq!(settled.user_rapr, start.user_rapr - RAPR_BET + RAPR_BET_STAKE);
+    assert_eq!(settled.rapr_vault, start.rapr_vault + RAPR_BET - RAPR_BET_STAKE);
+
+    h.send(&[house.cash_out(&bettor, RAPR_CASH_OUT, TokenType::RAPR)], &[&user]).await.unwrap();
+    assert_eq!(h.snapshot().await.user_rapr, settled.user_rapr - RAPR_CASH_OUT);
+
+    // Only transfers out of the user's wallet count towards their volume
+    let volume = h.holder_volume().await;
+    assert_eq!(volume.owner, bettor);
+    assert_eq!(volume.total_sent, RAPR_BET + RAPR_CASH_OUT);
+    assert_eq!(volume.transfers, 2);
+    assert_eq!(volume.tier, LoyaltyTier::Bronze);
+}
+
+#[tokio::test]
+async fn the_hook_holds_self_excluded_rapr_and_the_vault_holds_staked_rapr() {
+    let mut h = hooked_house().await;
+    let house = h.house;
+    let user = h.user.insecure_clone();
+    let friend = Keypair::new();
+    let bettor = user.pubkey();
+
+    // An open RAPR bet's stake sits in the vault; the rest of the wallet moves freely
+    let before = h.snapshot().await;
+    h.open_fight(1).await;
+    h.send(&[house.place_bet(&bettor, RAPR_BET, 1, FIGHTER_ONE, Odds::EVENS, TokenType::RAPR)], &[&user]).await.unwrap();
+    assert_eq!(h.snapshot().await.rapr_vault, before.rapr_vault + RAPR_BET);
+    h.send_rapr(&friend, SILVER_TIER_VOLUME).await.unwrap();
+    assert_eq!(h.token_balance(&house.rapr_ata(&friend.pubkey())).await, SILVER_TIER_VOLUME);
+    h.post_and_settle(&bettor, 1, FIGHTER_TWO).await.unwrap();
+
+    let volume = h.holder_volume().await;
+    assert_eq!(volume.total_sent, RAPR_BET + SILVER_TIER_VOLUME);
+    assert_eq!(volume.tier, LoyaltyTier::Silver);
+
+    // Self-excluded users cannot send RAPR on, but can still cash it out
+    h.send(&[house.self_exclude(&bettor, None)], &[&user]).await.unwrap();
+    assert_hook_error(h.send_rapr(&friend, RAPR_BET).await, HookError::SenderSelfExcluded);
+
+    let before = h.snapshot().await;
+    h.send(&[house.cash_out(&bettor, RAPR_CASH_OUT, TokenType::RAPR)], &[&user]).await.unwrap();
+    assert_eq!(h.snapshot().await.user_rapr, before.user_rapr - RAPR_CASH_OUT);
+}
@@ -0,0 +1,761 @@
+use std::path::Path;
+
+use anyhow::Result;
+use rusqlite::{params, Connection, OptionalExtension, Transaction};
+
+use crate::decode::{DecodedEvent, DecodedInstruction};
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS checkpoints (
+    program_id      TEXT PRIMARY KEY,
+    last_signature  TEXT NOT NULL,
+    last_slot       INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS instructions (
+    signature   TEXT NOT NULL,
+    ix_index    INTEGER NOT NULL,
+    slot        INTEGER NOT NULL,
+    block_time  INTEGER,
+    program_id  TEXT NOT NULL,
+    name        TEXT NOT NULL,
+    signer      TEXT,
+    args        TEXT,
+    PRIMARY KEY (signature, ix_index)
+);
+
+CREATE TABLE IF NOT EXISTS events (
+    signature   TEXT NOT NULL,
+    event_index INTEGER NOT NULL,
+    slot        INTEGER NOT NULL,
+    block_time  INTEGER,
+    program_id  TEXT NOT NULL,
+    name        TEXT NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+
+CREATE TABLE IF NOT EXISTS deposits (
+    signature    TEXT NOT NULL,
+    event_index  INTEGER NOT NULL,
+    slot         INTEGER NOT NULL,
+    depositor    TEXT NOT NULL,
+    sol_amount   INTEGER NOT NULL,
+    fee          INTEGER NOT NULL,
+    dumbs_minted INTEGER NOT NULL,
+    timestamp    INTEGER NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+
+CREATE TABLE IF NOT EXISTS bets (
+    signature        TEXT NOT NULL,
+    event_index      INTEGER NOT NULL,
+    slot             INTEGER NOT NULL,
+    bettor           TEXT NOT NULL,
+    fight_id         INTEGER NOT NULL,
+    token_type       TEXT NOT NULL,
+    amount           INTEGER NOT NULL,
+    fee              INTEGER NOT NULL,
+    odds             INTEGER NOT NULL,
+    potential_payout INTEGER NOT NULL,
+    timestamp        INTEGER NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+CREATE INDEX IF NOT EXISTS bets_by_bettor ON bets (bettor, timestamp);
+CREATE INDEX IF NOT EXISTS bets_by_fight ON bets (fight_id);
+
+CREATE TABLE IF NOT EXISTS settlements (
+    signature   TEXT NOT NULL,
+    event_index INTEGER NOT NULL,
+    slot        INTEGER NOT NULL,
+    bettor      TEXT NOT NULL,
+    fight_id    INTEGER NOT NULL,
+    token_type  TEXT NOT NULL,
+    won         INTEGER NOT NULL,
+    payout      INTEGER NOT NULL,
+    timestamp   INTEGER NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+CREATE INDEX IF NOT EXISTS settlements_by_bettor ON settlements (bettor, timestamp);
+
+CREATE TABLE IF NOT EXISTS winnings (
+    signature   TEXT NOT NULL,
+    event_index INTEGER NOT NULL,
+    slot        INTEGER NOT NULL,
+    bettor      TEXT NOT NULL,
+    fight_id    INTEGER NOT NULL,
+    amount      INTEGER NOT NULL,
+    timestamp   INTEGER NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+
+CREATE TABLE IF NOT EXISTS queued_winnings (
+    signature   TEXT NOT NULL,
+    event_index INTEGER NOT NULL,
+    slot        INTEGER NOT NULL,
+    bettor      TEXT NOT NULL,
+    fight_id    INTEGER,
+    status      TEXT NOT NULL,
+    amount      INTEGER NOT NULL,
+    queued      INTEGER NOT NULL,
+    timestamp   INTEGER NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+CREATE INDEX IF NOT EXISTS queued_winnings_by_bettor ON queued_winnings (bettor, timestamp);
+
+CREATE TABLE IF NOT EXISTS cash_outs (
+    signature    TEXT NOT NULL,
+    event_index  INTEGER NOT NULL,
+    slot         INTEGER NOT NULL,
+    user         TEXT NOT NULL,
+    token_type   TEXT NOT NULL,
+    amount       INTEGER NOT NULL,
+    fee          INTEGER NOT NULL,
+    sol_returned INTEGER NOT NULL,
+    timestamp    INTEGER NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+
+CREATE TABLE IF NOT EXISTS swaps (
+    signature   TEXT NOT NULL,
+    event_index INTEGER NOT NULL,
+    slot        INTEGER NOT NULL,
+    user        TEXT NOT NULL,
+    sol_amount  INTEGER NOT NULL,
+    rapr_amount INTEGER NOT NULL,
+    timestamp   INTEGER NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+
+CREATE TABLE IF NOT EXISTS fees (
+    signature   TEXT NOT NULL,
+    event_index INTEGER NOT NULL,
+    slot        INTEGER NOT NULL,
+    payer       TEXT NOT NULL,
+    asset       TEXT NOT NULL,
+    amount      INTEGER NOT NULL,
+    timestamp   INTEGER NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+
+CREATE TABLE IF NOT EXISTS balance_movements (
+    signature   TEXT NOT NULL,
+    event_index INTEGER NOT NULL,
+    slot        INTEGER NOT NULL,
+    user        TEXT NOT NULL,
+    direction   TEXT NOT NULL,
+    token_type  TEXT NOT NULL,
+    amount      INTEGER NOT NULL,
+    balance     INTEGER NOT NULL,
+    timestamp   INTEGER NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+CREATE INDEX IF NOT EXISTS balance_movements_by_user ON balance_movements (user, timestamp);
+
+CREATE TABLE IF NOT EXISTS limit_changes (
+    signature    TEXT NOT NULL,
+    event_index  INTEGER NOT NULL,
+    slot         INTEGER NOT NULL,
+    user         TEXT NOT NULL,
+    action       TEXT NOT NULL,
+    detail       TEXT NOT NULL,
+    effective_at INTEGER NOT NULL,
+    timestamp    INTEGER NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+
+CREATE TABLE IF NOT EXISTS rapr_locks (
+    signature   TEXT NOT NULL,
+    event_index INTEGER NOT NULL,
+    slot        INTEGER NOT NULL,
+    owner       TEXT NOT NULL,
+    action      TEXT NOT NULL,
+    period      TEXT,
+    amount      INTEGER NOT NULL,
+    penalty     INTEGER NOT NULL,
+    unlocks_at  INTEGER,
+    timestamp   INTEGER NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+CREATE INDEX IF NOT EXISTS rapr_locks_by_owner ON rapr_locks (owner, timestamp);
+
+CREATE TABLE IF NOT EXISTS asset_bets (
+    signature        TEXT NOT NULL,
+    event_index      INTEGER NOT NULL,
+    slot             INTEGER NOT NULL,
+    bettor           TEXT NOT NULL,
+    mint             TEXT NOT NULL,
+    fight_id         INTEGER NOT NULL,
+    status           TEXT NOT NULL,
+    amount           INTEGER NOT NULL,
+    fee              INTEGER NOT NULL,
+    odds             INTEGER,
+    payout           INTEGER NOT NULL,
+    timestamp        INTEGER NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+CREATE INDEX IF NOT EXISTS asset_bets_by_bettor ON asset_bets (bettor, timestamp);
+CREATE INDEX IF NOT EXISTS asset_bets_by_mint ON asset_bets (mint, fight_id);
+
+CREATE TABLE IF NOT EXISTS asset_cash_outs (
+    signature    TEXT NOT NULL,
+    event_index  INTEGER NOT NULL,
+    slot         INTEGER NOT NULL,
+    user         TEXT NOT NULL,
+    mint         TEXT NOT NULL,
+    amount       INTEGER NOT NULL,
+    fee          INTEGER NOT NULL,
+    sol_returned INTEGER NOT NULL,
+    timestamp    INTEGER NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+
+CREATE TABLE IF NOT EXISTS account_closures (
+    signature     TEXT NOT NULL,
+    event_index   INTEGER NOT NULL,
+    slot          INTEGER NOT NULL,
+    owner         TEXT NOT NULL,
+    closed_by     TEXT NOT NULL,
+    rent_returned INTEGER NOT NULL,
+    timestamp     INTEGER NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+
+CREATE TABLE IF NOT EXISTS config_changes (
+    signature     TEXT NOT NULL,
+    event_index   INTEGER NOT NULL,
+    slot          INTEGER NOT NULL,
+    betting_state TEXT NOT NULL,
+    status        TEXT NOT NULL,
+    change        TEXT NOT NULL,
+    timestamp     INTEGER NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+
+CREATE TABLE IF NOT EXISTS admin_actions (
+    signature   TEXT NOT NULL,
+    event_index INTEGER NOT NULL,
+    slot        INTEGER NOT NULL,
+    action      TEXT NOT NULL,
+    subject     TEXT NOT NULL,
+    detail      TEXT NOT NULL,
+    block_time  INTEGER,
+    PRIMARY KEY (signature, event_index)
+);
+
+CREATE TABLE IF NOT EXISTS fights (
+    signature   TEXT NOT NULL,
+    event_index INTEGER NOT NULL,
+    slot        INTEGER NOT NULL,
+    fight_id    INTEGER NOT NULL,
+    action      TEXT NOT NULL,
+    fighter_one TEXT,
+    fighter_two TEXT,
+    winner      TEXT,
+    timestamp   INTEGER NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+
+CREATE TABLE IF NOT EXISTS collections (
+    signature     TEXT NOT NULL,
+    event_index   INTEGER NOT NULL,
+    slot          INTEGER NOT NULL,
+    collection    TEXT NOT NULL,
+    collection_id TEXT NOT NULL,
+    authority     TEXT NOT NULL,
+    strategy      TEXT NOT NULL,
+    fee           INTEGER NOT NULL,
+    timestamp     INTEGER NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+
+CREATE TABLE IF NOT EXISTS agent_mints (
+    signature   TEXT NOT NULL,
+    event_index INTEGER NOT NULL,
+    slot        INTEGER NOT NULL,
+    ai_agent    TEXT NOT NULL,
+    agent_id    TEXT NOT NULL,
+    mint        TEXT NOT NULL,
+    collection  TEXT NOT NULL,
+    owner       TEXT NOT NULL,
+    model_hash  BLOB NOT NULL,
+    fee         INTEGER NOT NULL,
+    timestamp   INTEGER NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+";
+
+/// Position of a decoded item inside the chain, shared by every row written for it.
+pub struct RowKey<'a> {
+    pub signature: &'a str,
+    pub index: u32,
+    pub slot: u64,
+    pub block_time: Option<i64>,
+}
+
+pub struct Database {
+    conn: Connection,
+}
+
+impl Database {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let conn = Connection::open(path)?;
+        conn.execute_batch("PRAGMA journal_mode = WAL;")?;
+        conn.execute_batch(SCHEMA)?;
+        Ok(Self { conn })
+    }
+
+    pub fn checkpoint(&self, program_id: &str) -> Result<Option<(String, u64)>> {
+        Ok(self
+            .conn
+            .query_row(
+                "SELECT last_signature, last_slot FROM checkpoints WHERE program_id = ?1",
+                params![program_id],
+                |row| Ok((row.get(0)?, row.get::<_, i64>(1)? as u64)),
+            )
+            .optional()?)
+    }
+
+    pub fn transaction(&mut self) -> Result<Transaction<'_>> {
+        Ok(self.conn.transaction()?)
+    }
+}
+
+pub fn save_checkpoint(tx: &Transaction, program_id: &str, signature: &str, slot: u64) -> Result<()> {
+    tx.execute(
+        "INSERT INTO checkpoints (program_id, last_signature, last_slot) VALUES (?1, ?2, ?3)
+         ON CONFLICT(program_id) DO UPDATE SET last_signature = ?2, last_slot = ?3",
+        params![program_id, signature, slot as i64],
+    )?;
+    Ok(())
+}
+
+pub fn insert_instruction(
+    tx: &Transaction,
+    key: &RowKey,
+    program_id: &str,
+    signer: Option<String>,
+    ix: &DecodedInstruction,
+) -> Result<()> {
+    tx.execute(
+        "INSERT OR REPLACE INTO instructions
+         (signature, ix_index, slot, block_time, program_id, name, signer, args)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        params![
+            key.signature,
+            key.index,
+            key.slot as i64,
+            key.block_time,
+            program_id,
+            ix.name,
+            signer,
+            ix.args.to_string(),
+        ],
+    )?;
+    Ok(())
+}
+
+pub fn insert_event(tx: &Transaction, key: &RowKey, program_id: &str, event: &DecodedEvent) -> Result<()> {
+    tx.execute(
+        "INSERT OR REPLACE INTO events (signature, event_index, slot, block_time, program_id, name)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![key.signature, key.index, key.slot as i64, key.block_time, program_id, event.name()],
+    )?;
+
+    let (sig, idx, slot) = (key.signature, key.index, key.slot as i64);
+    match event {
+        DecodedEvent::DepositMade(e) => {
+            tx.execute(
+                "INSERT OR REPLACE INTO deposits VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![sig, idx, slot, e.depositor.to_string(), e.sol_amount as i64, e.fee as i64, e.dumbs_minted as i64, e.timestamp],
+            )?;
+        }
+        DecodedEvent::BetPlaced(e) => {
+            tx.execute(
+                "INSERT OR REPLACE INTO bets VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+                params![
+                    sig, idx, slot,
+                    e.bettor.to_string(),
+                    e.fight_id as i64,
+                    format!("{:?}", e.token_type),
+                    e.amount as i64,
+                    e.fee as i64,
+                    e.odds as i64,
+                    e.potential_payout as i64,
+                    e.timestamp,
+                ],
+            )?;
+        }
+        DecodedEvent::BetSettled(e) => {
+            tx.execute(
+                "INSERT OR REPLACE INTO settlements VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                params![
+                    sig, idx, slot,
+                    e.bettor.to_string(),
+                    e.fight_id as i64,
+                    format!("{:?}", e.token_type),
+                    e.won,
+                    e.payout as i64,
+                    e.timestamp,
+                ],
+            )?;
+        }
+        DecodedEvent::WinningsMinted(e) => {
+            tx.execute(
+                "INSERT OR REPLACE INTO winnings VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![sig, idx, slot, e.bettor.to_string(), e.fight_id as i64, e.amount as i64, e.timestamp],
+            )?;
+        }
+        DecodedEvent::WinningsQueued(e) => {
+            tx.execute(
+                "INSERT OR REPLACE INTO queued_winnings VALUES (?1, ?2, ?3, ?4, ?5, 'queued', ?6, ?7, ?8)",
+                params![sig, idx, slot, e.bettor.to_string(), e.fight_id as i64, e.amount as i64, e.queued as i64, e.timestamp],
+            )?;
+        }
+        DecodedEvent::QueuedWinningsPaid(e) => {
+            tx.execute(
+                "INSERT OR REPLACE INTO queued_winnings VALUES (?1, ?2, ?3, ?4, NULL, 'paid', ?5, ?6, ?7)",
+                params![sig, idx, slot, e.bettor.to_string(), e.amount as i64, e.remaining as i64, e.timestamp],
+            )?;
+        }
+        DecodedEvent::CashedOut(e) => {
+            tx.execute(
+                "INSERT OR REPLACE INTO cash_outs VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                params![
+                    sig, idx, slot,
+                    e.user.to_string(),
+                    format!("{:?}", e.token_type),
+                    e.amount as i64,
+                    e.fee as i64,
+                    e.sol_returned as i64,
+                    e.timestamp,
+                ],
+            )?;
+        }
+        DecodedEvent::Swapped(e) => {
+            tx.execute(
+                "INSERT OR REPLACE INTO swaps VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![sig, idx, slot, e.user.to_string(), e.sol_amount as i64, e.rapr_amount as i64, e.timestamp],
+            )?;
+        }
+        DecodedEvent::FeeCollected(e) => {
+            tx.execute(
+                "INSERT OR REPLACE INTO fees VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![sig, idx, slot, e.payer.to_string(), format!("{:?}", e.asset), e.amount as i64, e.timestamp],
+            )?;
+        }
+        DecodedEvent::BalanceDeposited(e) => {
+            tx.execute(
+                "INSERT OR REPLACE INTO balance_movements VALUES (?1, ?2, ?3, ?4, 'deposit', ?5, ?6, ?7, ?8)",
+                params![sig, idx, slot, e.user.to_string(), format!("{:?}", e.token_type), e.amount as i64, e.balance as i64, e.timestamp],
+            )?;
+        }
+        DecodedEvent::BalanceWithdrawn(e) => {
+            tx.execute(
+                "INSERT OR REPLACE INTO balance_movements VALUES (?1, ?2, ?3, ?4, 'withdrawal', ?5, ?6, ?7, ?8)",
+                params![sig, idx, slot, e.user.to_string(), format!("{:?}", e.token_type), e.amount as i64, e.balance as i64, e.timestamp],
+            )?;
+        }
+        DecodedEvent::LimitsChanged(e) => {
+            let detail = format!("{:?}, pending {:?}", e.limits, e.pending);
+            tx.execute(
+                "INSERT OR REPLACE INTO limit_changes VALUES (?1, ?2, ?3, ?4, 'limits_changed', ?5, ?6, ?7)",
+                params![sig, idx, slot, e.user.to_string(), detail, e.effective_at, e.timestamp],
+            )?;
+        }
+        DecodedEvent::SelfExcluded(e) => {
+            tx.execute(
+                "INSERT OR REPLACE INTO limit_changes VALUES (?1, ?2, ?3, ?4, 'self_excluded', '', ?5, ?6)",
+                params![sig, idx, slot, e.user.to_string(), e.until, e.timestamp],
+            )?;
+        }
+        DecodedEvent::RaprLocked(e) => {
+            tx.execute(
+                "INSERT OR REPLACE INTO rapr_locks VALUES (?1, ?2, ?3, ?4, 'locked', ?5, ?6, 0, ?7, ?8)",
+                params![sig, idx, slot, e.owner.to_string(), format!("{:?}", e.period), e.amount as i64, e.unlocks_at, e.timestamp],
+            )?;
+        }
+        DecodedEvent::RaprUnlocked(e) => {
+            tx.execute(
+                "INSERT OR REPLACE INTO rapr_locks VALUES (?1, ?2, ?3, ?4, 'unlocked', NULL, ?5, ?6, NULL, ?7)",
+                params![sig, idx, slot, e.owner.to_string(), e.amount as i64, e.penalty as i64, e.timestamp],
+            )?;
+        }
+        DecodedEvent::AssetBetPlaced(e) => {
+            tx.execute(
+                "INSERT OR REPLACE INTO asset_bets VALUES (?1, ?2, ?3, ?4, ?5, ?6, 'placed', ?7, ?8, ?9, ?10, ?11)",
+                params![
+                    sig, idx, slot,
+                    e.bettor.to_string(),
+                    e.mint.to_string(),
+                    e.fight_id as i64,
+                    e.amount as i64,
+                    e.fee as i64,
+                    e.odds as i64,
+                    e.potential_payout as i64,
+                    e.timestamp,
+                ],
+            )?;
+        }
+        DecodedEvent::AssetBetSettled(e) => {
+            let status = if e.won { "won" } else { "lost" };
+            tx.execute(
+                "INSERT OR REPLACE INTO asset_bets VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, 0, 0, NULL, ?8, ?9)",
+                params![sig, idx, slot, e.bettor.to_string(), e.mint.to_string(), e.fight_id as i64, status, e.payout as i64, e.timestamp],
+            )?;
+        }
+        DecodedEvent::AssetCashedOut(e) => {
+            tx.execute(
+                "INSERT OR REPLACE INTO asset_cash_outs VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                params![
+                    sig, idx, slot,
+                    e.user.to_string(),
+                    e.mint.to_string(),
+                    e.amount as i64,
+                    e.fee as i64,
+                    e.sol_returned as i64,
+                    e.timestamp,
+                ],
+            )?;
+        }
+        DecodedEvent::UserBettingAccountClosed(e) => {
+            tx.execute(
+                "INSERT OR REPLACE INTO account_closures VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![sig, idx, slot, e.owner.to_string(), e.closed_by.to_string(), e.rent_returned as i64, e.timestamp],
+            )?;
+        }
+        DecodedEvent::ConfigChangeQueued(e) => {
+            insert_config_change(tx, key, &e.betting_state.to_string(), "queued", format!("{:?}", e.change), e.eta)?;
+        }
+        DecodedEvent::ConfigChangeCancelled(e) => {
+            insert_config_change(tx, key, &e.betting_state.to_string(), "cancelled", format!("{:?}", e.change), e.timestamp)?;
+        }
+        DecodedEvent::ConfigChanged(e) => {
+            insert_config_change(tx, key, &e.betting_state.to_string(), "executed", format!("{:?}", e.change), e.timestamp)?;
+        }
+        DecodedEvent::FightCreated(e) => {
+            tx.execute(
+                "INSERT OR REPLACE INTO fights VALUES (?1, ?2, ?3, ?4, 'created', ?5, ?6, NULL, ?7)",
+                params![
+                    sig, idx, slot,
+                    e.fight_id as i64,
+                    e.fighter_one.to_string(),
+                    e.fighter_two.to_string(),
+                    e.timestamp,
+                ],
+            )?;
+        }
+        DecodedEvent::FightResultPosted(e) => {
+            tx.execute(
+                "INSERT OR REPLACE INTO fights VALUES (?1, ?2, ?3, ?4, 'result_posted', NULL, NULL, ?5, ?6)",
+                params![sig, idx, slot, e.fight_id as i64, e.winner.to_string(), e.timestamp],
+            )?;
+        }
+        DecodedEvent::FightResultReversed(e) => {
+            tx.execute(
+                "INSERT OR REPLACE INTO fights VALUES (?1, ?2, ?3, ?4, 'result_reversed', NULL, NULL, ?5, ?6)",
+                params![sig, idx, slot, e.fight_id as i64, e.winner.to_string(), e.timestamp],
+            )?;
+            let detail = format!("fight {}: {} overturned for {}", e.fight_id, e.overturned_winner, e.winner);
+            insert_admin_action(tx, key, "fight_result_reversed", &e.reversed_by.to_string(), detail)?;
+        }
+        DecodedEvent::WinningsClawedBack(e) => {
+            let detail = format!(
+                "fight {}: {} {:?} ({} from queue, {} from balance, {} still clawable) by {}",
+                e.fight_id, e.amount, e.token_type, e.from_queue, e.from_balance, e.remaining, e.clawed_back_by
+            );
+            insert_admin_action(tx, key, "winnings_clawed_back", &e.bettor.to_string(), detail)?;
+        }
+        DecodedEvent::CollectionCreated(e) => {
+            tx.execute(
+                "INSERT OR REPLACE INTO collections VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                params![
+                    sig, idx, slot,
+                    e.collection.to_string(),
+                    e.collection_id.to_string(),
+                    e.authority.to_string(),
+                    e.strategy,
+                    e.fee as i64,
+                    e.timestamp,
+                ],
+            )?;
+        }
+        DecodedEvent::AgentMinted(e) => {
+            tx.execute(
+                "INSERT OR REPLACE INTO agent_mints VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+                params![
+                    sig, idx, slot,
+                    e.ai_agent.to_string(),
+                    e.agent_id.to_string(),
+                    e.mint.to_string(),
+                    e.collection.to_string(),
+                    e.owner.to_string(),
+                    &e.model_hash[..],
+                    e.fee as i64,
+                    e.timestamp,
+                ],
+            )?;
+        }
+        DecodedEvent::PauseChanged(e) => {
+            insert_admin_action(tx, key, "pause_changed", &e.betting_state.to_string(), e.paused.to_string())?;
+        }
+        DecodedEvent::RoleGranted(e) => {
+            insert_admin_action(tx, key, "role_granted", &e.member.to_string(), format!("{:?} by {}", e.role, e.granted_by))?;
+        }
+        DecodedEvent::RoleRevoked(e) => {
+            insert_admin_action(tx, key, "role_revoked", &e.member.to_string(), format!("{:?} by {}", e.role, e.revoked_by))?;
+        }
+        DecodedEvent::TokenMetadataUpdated(e) => {
+            let detail = format!("{:?} = {} by {}", e.field, e.value, e.updated_by);
+            insert_admin_action(tx, key, "token_metadata_updated", &e.mint.to_string(), detail)?;
+        }
+        DecodedEvent::TreasuryWithdrawn(e) => {
+            insert_admin_action(tx, key, "treasury_withdrawn", &e.recipient.to_string(), e.amount.to_string())?;
+        }
+        DecodedEvent::TransferFeesHarvested(e) => {
+            let detail = format!("{} from {} accounts by {}", e.amount, e.sources + 1, e.harvested_by);
+            insert_admin_action(tx, key, "transfer_fees_harvested", &e.mint.to_string(), detail)?;
+        }
+        DecodedEvent::MintRegistered(e) => {
+            let detail = format!("{:?} vault {} by {}", e.params, e.vault, e.registered_by);
+            insert_admin_action(tx, key, "mint_registered", &e.mint.to_string(), detail)?;
+        }
+        DecodedEvent::SupportedMintUpdated(e) => {
+            let detail = format!("{:?} by {}", e.params, e.updated_by);
+            insert_admin_action(tx, key, "supported_mint_updated", &e.mint.to_string(), detail)?;
+        }
+        DecodedEvent::MintLiquidityFunded(e) => {
+            let detail = format!("{} to {} by {}", e.amount, e.sol_liquidity, e.funded_by);
+            insert_admin_action(tx, key, "mint_liquidity_funded", &e.mint.to_string(), detail)?;
+        }
+        DecodedEvent::OracleConfigCreated(e) => {
+            let detail = format!("by {}", e.created_by);
+            insert_admin_action(tx, key, "oracle_config_created", &e.betting_state.to_string(), detail)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn insert_config_change(
+    tx: &Transaction,
+    key: &RowKey,
+    betting_state: &str,
+    status: &str,
+    change: String,
+    timestamp: i64,
+) -> Result<()> {
+    tx.execute(
+        "INSERT OR REPLACE INTO config_changes VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        params![key.signature, key.index, key.slot as i64, betting_state, status, change, timestamp],
+    )?;
+    Ok(())
+}
+
+fn insert_admin_action(
+    tx: &Transaction,
+    key: &RowKey,
+    action: &str,
+    subject: &str,
+    detail: String,
+) -> Result<()> {
+    tx.execute(
+        "INSERT OR REPLACE INTO admin_actions VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        params![key.signature, key.index, key.slot as i64, action, subject, detail, key.block_time],
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use betting_program::events::{BetPlaced, PauseChanged};
+    use betting_program::state::TokenType;
+    use serde_json::json;
+    use solana_sdk::pubkey::Pubkey;
+
+    fn key(index: u32) -> RowKey<'static> {
+        RowKey { signature: "sig", index, slot: 42, block_time: Some(1_700_000_000) }
+    }
+
+    fn bet(amount: u64) -> DecodedEvent {
+        DecodedEvent::BetPlaced(BetPlaced {
+            bettor: Pubkey::new_unique(),
+            fight_id: 3,
+            token_type: TokenType::DUMBS,
+            amount,
+            fee: 2,
+            odds: 150,
+            potential_payout: amount * 3 / 2,
+            timestamp: 9,
+        })
+    }
+
+    #[test]
+    fn checkpoints_round_trip_and_advance() {
+        let mut db = Database::open(":memory:").unwrap();
+        assert_eq!(db.checkpoint("program").unwrap(), None);
+
+        let tx = db.transaction().unwrap();
+        save_checkpoint(&tx, "program", "first", 10).unwrap();
+        save_checkpoint(&tx, "program", "second", 11).unwrap();
+        tx.commit().unwrap();
+        assert_eq!(db.checkpoint("program").unwrap(), Some(("second".to_string(), 11)));
+        assert_eq!(db.checkpoint("other").unwrap(), None);
+    }
+
+    #[test]
+    fn instructions_are_stored_with_their_args() {
+        let mut db = Database::open(":memory:").unwrap();
+        let tx = db.transaction().unwrap();
+        let ix = DecodedInstruction { name: "deposit_sol", args: json!({ "amount": 5 }) };
+        insert_instruction(&tx, &key(1), "program", Some("signer".to_string()), &ix).unwrap();
+        tx.commit().unwrap();
+
+        let (name, signer, args): (String, String, String) = db
+            .conn
+            .query_row(
+                "SELECT name, signer, args FROM instructions WHERE signature = 'sig' AND ix_index = 1",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .unwrap();
+        assert_eq!((name.as_str(), signer.as_str()), ("deposit_sol", "signer"));
+        assert_eq!(serde_json::from_str::<serde_json::Value>(&args).unwrap(), json!({ "amount": 5 }));
+    }
+
+    #[test]
+    fn events_fill_their_table_and_reindexing_replaces_rows() {
+        let mut db = Database::open(":memory:").unwrap();
+        let tx = db.transaction().unwrap();
+        insert_event(&tx, &key(0), "program", &bet(100)).unwrap();
+        insert_event(&tx, &key(0), "program", &bet(200)).unwrap();
+        let paused = DecodedEvent::PauseChanged(PauseChanged {
+            betting_state: Pubkey::new_unique(),
+            paused: true,
+            timestamp: 9,
+        });
+        insert_event(&tx, &key(1), "program", &paused).unwrap();
+        tx.commit().unwrap();
+
+        let count = |table: &str| -> i64 {
+            db.conn.query_row(&format!("SELECT COUNT(*) FROM {}", table), [], |row| row.get(0)).unwrap()
+        };
+        assert_eq!((count("events"), count("bets"), count("admin_actions")), (2, 1, 1));
+
+        let (amount, payout, token_type): (i64, i64, String) = db
+            .conn
+            .query_row("SELECT amount, potential_payout, token_type FROM bets", [], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+            })
+            .unwrap();
+        assert_eq!((amount, payout, token_type.as_str()), (200, 300, "DUMBS"));
+
+        let (action, detail, block_time): (String, String, i64) = db
+            .conn
+            .query_row("SELECT action, detail, block_time FROM admin_actions", [], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+            })
+            .unwrap();
+        assert_eq!((action.as_str(), detail.as_str(), block_time), ("pause_changed", "true", 1_700_000_000));
+    }
+}
@@ -0,0 +1,433 @@
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use betting_program::events as betting_events;
+use nft_game_agent_program::events as agent_events;
+use serde_json::{json, Value};
+use solana_sdk::pubkey::Pubkey;
+
+/// Every event either program can emit.
+pub enum DecodedEvent {
+    DepositMade(betting_events::DepositMade),
+    BetPlaced(betting_events::BetPlaced),
+    BetSettled(betting_events::BetSettled),
+    WinningsMinted(betting_events::WinningsMinted),
+    WinningsQueued(betting_events::WinningsQueued),
+    QueuedWinningsPaid(betting_events::QueuedWinningsPaid),
+    CashedOut(betting_events::CashedOut),
+    Swapped(betting_events::Swapped),
+    FeeCollected(betting_events::FeeCollected),
+    ConfigChangeQueued(betting_events::ConfigChangeQueued),
+    ConfigChangeCancelled(betting_events::ConfigChangeCancelled),
+    ConfigChanged(betting_events::ConfigChanged),
+    PauseChanged(betting_events::PauseChanged),
+    RoleGranted(betting_events::RoleGranted),
+    RoleRevoked(betting_events::RoleRevoked),
+    TreasuryWithdrawn(betting_events::TreasuryWithdrawn),
+    FightCreated(betting_events::FightCreated),
+    FightResultPosted(betting_events::FightResultPosted),
+    FightResultReversed(betting_events::FightResultReversed),
+    WinningsClawedBack(betting_events::WinningsClawedBack),
+    UserBettingAccountClosed(betting_events::UserBettingAccountClosed),
+    BalanceDeposited(betting_events::BalanceDeposited),
+    BalanceWithdrawn(betting_events::BalanceWithdrawn),
+    LimitsChanged(betting_events::LimitsChanged),
+    SelfExcluded(betting_events::SelfExcluded),
+    RaprLocked(betting_events::RaprLocked),
+    RaprUnlocked(betting_events::RaprUnlocked),
+    TokenMetadataUpdated(betting_events::TokenMetadataUpdated),
+    TransferFeesHarvested(betting_events::TransferFeesHarvested),
+    MintRegistered(betting_events::MintRegistered),
+    SupportedMintUpdated(betting_events::SupportedMintUpdated),
+    AssetBetPlaced(betting_events::AssetBetPlaced),
+    AssetBetSettled(betting_events::AssetBetSettled),
+    AssetCashedOut(betting_events::AssetCashedOut),
+    MintLiquidityFunded(betting_events::MintLiquidityFunded),
+    OracleConfigCreated(betting_events::OracleConfigCreated),
+    CollectionCreated(agent_events::CollectionCreated),
+    AgentMinted(agent_events::AgentMinted),
+}
+
+impl DecodedEvent {
+    pub fn name(&self) -> &'static str {
+        match self {
+            DecodedEvent::DepositMade(_) => "DepositMade",
+            DecodedEvent::BetPlaced(_) => "BetPlaced",
+            DecodedEvent::BetSettled(_) => "BetSettled",
+            DecodedEvent::WinningsMinted(_) => "WinningsMinted",
+            DecodedEvent::WinningsQueued(_) => "WinningsQueued",
+            DecodedEvent::QueuedWinningsPaid(_) => "QueuedWinningsPaid",
+            DecodedEvent::CashedOut(_) => "CashedOut",
+            DecodedEvent::Swapped(_) => "Swapped",
+            DecodedEvent::FeeCollected(_) => "FeeCollected",
+            DecodedEvent::ConfigChangeQueued(_) => "ConfigChangeQueued",
+            DecodedEvent::ConfigChangeCancelled(_) => "ConfigChangeCancelled",
+            DecodedEvent::ConfigChanged(_) => "ConfigChanged",
+            DecodedEvent::PauseChanged(_) => "PauseChanged",
+            DecodedEvent::RoleGranted(_) => "RoleGranted",
+            DecodedEvent::RoleRevoked(_) => "RoleRevoked",
+            DecodedEvent::TreasuryWithdrawn(_) => "TreasuryWithdrawn",
+            DecodedEvent::FightCreated(_) => "FightCreated",
+            DecodedEvent::FightResultPosted(_) => "FightResultPosted",
+            DecodedEvent::FightResultReversed(_) => "FightResultReversed",
+            DecodedEvent::WinningsClawedBack(_) => "WinningsClawedBack",
+            DecodedEvent::UserBettingAccountClosed(_) => "UserBettingAccountClosed",
+            DecodedEvent::BalanceDeposited(_) => "BalanceDeposited",
+            DecodedEvent::BalanceWithdrawn(_) => "BalanceWithdrawn",
+            DecodedEvent::LimitsChanged(_) => "LimitsChanged",
+            DecodedEvent::SelfExcluded(_) => "SelfExcluded",
+            DecodedEvent::RaprLocked(_) => "RaprLocked",
+            DecodedEvent::RaprUnlocked(_) => "RaprUnlocked",
+            DecodedEvent::TokenMetadataUpdated(_) => "TokenMetadataUpdated",
+            DecodedEvent::TransferFeesHarvested(_) => "TransferFeesHarvested",
+            DecodedEvent::MintRegistered(_) => "MintRegistered",
+            DecodedEvent::SupportedMintUpdated(_) => "SupportedMintUpdated",
+            DecodedEvent::AssetBetPlaced(_) => "AssetBetPlaced",
+            DecodedEvent::AssetBetSettled(_) => "AssetBetSettled",
+            DecodedEvent::AssetCashedOut(_) => "AssetCashedOut",
+            DecodedEvent::MintLiquidityFunded(_) => "MintLiquidityFunded",
+            DecodedEvent::OracleConfigCreated(_) => "OracleConfigCreated",
+            DecodedEvent::CollectionCreated(_) => "CollectionCreated",
+            DecodedEvent::AgentMinted(_) => "AgentMinted",
+        }
+    }
+}
+
+fn parse<T: AnchorDeserialize + Discriminator>(data: &[u8]) -> Option<T> {
+    let body = data.strip_prefix(&T::DISCRIMINATOR[..])?;
+    T::try_from_slice(body).ok()
+}
+
+/// Decodes a `Program data:` payload emitted by one of the two casino programs.
+pub fn decode_event(program_id: &Pubkey, data: &[u8]) -> Option<DecodedEvent> {
+    if *program_id == betting_program::ID {
+        return parse(data).map(DecodedEvent::DepositMade)
+            .or_else(|| parse(data).map(DecodedEvent::BetPlaced))
+            .or_else(|| parse(data).map(DecodedEvent::BetSettled))
+            .or_else(|| parse(data).map(DecodedEvent::WinningsMinted))
+            .or_else(|| parse(data).map(DecodedEvent::WinningsQueued))
+            .or_else(|| parse(data).map(DecodedEvent::QueuedWinningsPaid))
+            .or_else(|| parse(data).map(DecodedEvent::CashedOut))
+            .or_else(|| parse(data).map(DecodedEvent::Swapped))
+            .or_else(|| parse(data).map(DecodedEvent::FeeCollected))
+            .or_else(|| parse(data).map(DecodedEvent::ConfigChangeQueued))
+            .or_else(|| parse(data).map(DecodedEvent::ConfigChangeCancelled))
+            .or_else(|| parse(data).map(DecodedEvent::ConfigChanged))
+            .or_else(|| parse(data).map(DecodedEvent::PauseChanged))
+            .or_else(|| parse(data).map(DecodedEvent::RoleGranted))
+            .or_else(|| parse(data).map(DecodedEvent::RoleRevoked))
+            .or_else(|| parse(data).map(DecodedEvent::TreasuryWithdrawn))
+            .or_else(|| parse(data).map(DecodedEvent::FightCreated))
+            .or_else(|| parse(data).map(DecodedEvent::FightResultPosted))
+            .or_else(|| parse(data).map(DecodedEvent::FightResultReversed))
+            .or_else(|| parse(data).map(DecodedEvent::WinningsClawedBack))
+            .or_else(|| parse(data).map(DecodedEvent::UserBettingAccountClosed))
+            .or_else(|| parse(data).map(DecodedEvent::BalanceDeposited))
+            .or_else(|| parse(data).map(DecodedEvent::BalanceWithdrawn))
+            .or_else(|| parse(data).map(DecodedEvent::LimitsChanged))
+            .or_else(|| parse(data).map(DecodedEvent::SelfExcluded))
+            .or_else(|| parse(data).map(DecodedEvent::RaprLocked))
+            .or_else(|| parse(data).map(DecodedEvent::RaprUnlocked))
+            .or_else(|| parse(data).map(DecodedEvent::TokenMetadataUpdated))
+            .or_else(|| parse(data).map(DecodedEvent::TransferFeesHarvested))
+            .or_else(|| parse(data).map(DecodedEvent::MintRegistered))
+            .or_else(|| parse(data).map(DecodedEvent::SupportedMintUpdated))
+            .or_else(|| parse(data).map(DecodedEvent::AssetBetPlaced))
+            .or_else(|| parse(data).map(DecodedEvent::AssetBetSettled))
+            .or_else(|| parse(data).map(DecodedEvent::AssetCashedOut))
+            .or_else(|| parse(data).map(DecodedEvent::MintLiquidityFunded))
+            .or_else(|| parse(data).map(DecodedEvent::OracleConfigCreated));
+    }
+
+    if *program_id == nft_game_agent_program::ID {
+        return parse(data).map(DecodedEvent::CollectionCreated)
+            .or_else(|| parse(data).map(DecodedEvent::AgentMinted));
+    }
+
+    None
+}
+
+/// Instruction name and arguments decoded from top-level instruction data.
+pub struct DecodedInstruction {
+    pub name: &'static str,
+    pub args: Value,
+}
+
+fn instruction<T: AnchorDeserialize + Discriminator>(
+    data: &[u8],
+    name: &'static str,
+    args: impl FnOnce(T) -> Value,
+) -> Option<DecodedInstruction> {
+    parse::<T>(data).map(|ix| DecodedInstruction { name, args: args(ix) })
+}
+
+fn no_args<T: AnchorDeserialize + Discriminator>(
+    data: &[u8],
+    name: &'static str,
+) -> Option<DecodedInstruction> {
+    instruction::<T>(data, name, |_| Value::Null)
+}
+
+pub fn decode_instruction(program_id: &Pubkey, data: &[u8]) -> Option<DecodedInstruction> {
+    use betting_program::instruction as bp;
+    use nft_game_agent_program::instruction as ap;
+
+    if *program_id == betting_program::ID {
+        return instruction::<bp::DepositSol>(data, "deposit_sol", |ix| json!({
+                "amount": ix.amount,
+                "quoted_dumbs": ix.quoted_dumbs,
+                "max_slippage": ix.max_slippage,
+            }))
+            .or_else(|| instruction::<bp::PlaceBet>(data, "place_bet", |ix| json!({
+                "amount": ix.amount,
+                "fight_id": ix.fight_id,
+                "fighter": ix.fighter.to_string(),
+                "odds": ix.odds,
+                "token_type": format!("{:?}", ix.token_type),
+            })))
+            .or_else(|| instruction::<bp::PlaceBetFromBalance>(data, "place_bet_from_balance", |ix| json!({
+                "amount": ix.amount,
+                "fight_id": ix.fight_id,
+                "fighter": ix.fighter.to_string(),
+                "odds": ix.odds,
+                "token_type": format!("{:?}", ix.token_type),
+            })))
+            .or_else(|| instruction::<bp::DepositToBalance>(data, "deposit_to_balance", |ix| json!({
+                "amount": ix.amount,
+                "token_type": format!("{:?}", ix.token_type),
+            })))
+            .or_else(|| instruction::<bp::WithdrawFromBalance>(data, "withdraw_from_balance", |ix| json!({
+                "amount": ix.amount,
+                "token_type": format!("{:?}", ix.token_type),
+            })))
+            .or_else(|| instruction::<bp::SetLimits>(data, "set_limits", |ix| json!({
+                "limits": format!("{:?}", ix.limits),
+            })))
+            .or_else(|| instruction::<bp::SelfExclude>(data, "self_exclude", |ix| json!({ "duration": ix.duration })))
+            .or_else(|| instruction::<bp::LockRapr>(data, "lock_rapr", |ix| json!({
+                "amount": ix.amount,
+                "period": format!("{:?}", ix.period),
+            })))
+            .or_else(|| no_args::<bp::UnlockRapr>(data, "unlock_rapr"))
+            .or_else(|| instruction::<bp::SettleBet>(data, "settle_bet", |ix| json!({
+                "fight_id": ix.fight_id,
+            })))
+            .or_else(|| instruction::<bp::CashOut>(data, "cash_out", |ix| json!({
+                "amount": ix.amount,
+                "token_type": format!("{:?}", ix.token_type),
+                "quoted_sol": ix.quoted_sol,
+                "max_slippage": ix.max_slippage,
+            })))
+            .or_else(|| instruction::<bp::MintDumbsForWin>(data, "mint_dumbs_for_win", |ix| json!({
+                "secure_fight_id": ix.secure_fight_id,
+            })))
+            .or_else(|| instruction::<bp::CreateFight>(data, "create_fight", |ix| json!({
+                "fight_id": ix.fight_id,
+                "fighter_one": ix.fighter_one.to_string(),
+                "fighter_two": ix.fighter_two.to_string(),
+            })))
+            .or_else(|| instruction::<bp::PostFightResult>(data, "post_fight_result", |ix| json!({
+                "winner": ix.winner.to_string(),
+            })))
+            .or_else(|| instruction::<bp::ReverseFightResult>(data, "reverse_fight_result", |ix| json!({
+                "winner": ix.winner.to_string(),
+            })))
+            .or_else(|| instruction::<bp::Clawback>(data, "clawback", |ix| json!({
+                "fight_id": ix.fight_id,
+                "token_type": format!("{:?}", ix.token_type),
+                "amount": ix.amount,
+            })))
+            .or_else(|| instruction::<bp::PlaceAssetBet>(data, "place_asset_bet", |ix| json!({
+                "amount": ix.amount,
+                "fight_id": ix.fight_id,
+                "fighter": ix.fighter.to_string(),
+                "odds": ix.odds,
+            })))
+            .or_else(|| instruction::<bp::SettleAssetBet>(data, "settle_asset_bet", |ix| json!({ "fight_id": ix.fight_id })))
+            .or_else(|| instruction::<bp::CashOutAsset>(data, "cash_out_asset", |ix| json!({
+                "amount": ix.amount,
+                "min_out": ix.min_out,
+            })))
+            .or_else(|| instruction::<bp::SwapSolForRapr>(data, "swap_sol_for_rapr", |ix| json!({
+                "sol_amount": ix.sol_amount,
+                "quoted_rapr": ix.quoted_rapr,
+                "max_slippage": ix.max_slippage,
+            })))
+            .or_else(|| instruction::<bp::QuoteBet>(data, "quote_bet", |ix| json!({
+                "amount": ix.amount,
+                "odds": ix.odds,
+                "token_type": format!("{:?}", ix.token_type),
+            })))
+            .or_else(|| instruction::<bp::QuoteDeposit>(data, "quote_deposit", |ix| json!({ "amount": ix.amount })))
+            .or_else(|| instruction::<bp::QuoteCashOut>(data, "quote_cash_out", |ix| json!({
+                "amount": ix.amount,
+                "token_type": format!("{:?}", ix.token_type),
+            })))
+            .or_else(|| instruction::<bp::QuoteSwap>(data, "quote_swap", |ix| json!({ "sol_amount": ix.sol_amount })))
+            .or_else(|| no_args::<bp::QuoteHousePnl>(data, "quote_house_pnl"))
+            .or_else(|| instruction::<bp::GrantRole>(data, "grant_role", |ix| json!({
+                "member": ix.member.to_string(),
+                "role": format!("{:?}", ix.role),
+            })))
+            .or_else(|| instruction::<bp::RevokeRole>(data, "revoke_role", |ix| json!({
+                "member": ix.member.to_string(),
+                "role": format!("{:?}", ix.role),
+            })))
+            .or_else(|| instruction::<bp::SetPaused>(data, "set_paused", |ix| json!({ "paused": ix.paused })))
+            .or_else(|| instruction::<bp::WithdrawTreasury>(data, "withdraw_treasury", |ix| json!({ "amount": ix.amount })))
+            .or_else(|| no_args::<bp::HarvestAndWithdrawFees>(data, "harvest_and_withdraw_fees"))
+            .or_else(|| instruction::<bp::UpdateTokenMetadata>(data, "update_token_metadata", |ix| json!({
+                "token_type": format!("{:?}", ix.token_type),
+                "field": format!("{:?}", ix.field),
+                "value": ix.value,
+            })))
+            .or_else(|| instruction::<bp::InitializeTokenMetadata>(data, "initialize_token_metadata", |ix| json!({
+                "token_type": format!("{:?}", ix.token_type),
+                "name": ix.name,
+                "symbol": ix.symbol,
+                "uri": ix.uri,
+            })))
+            .or_else(|| instruction::<bp::RegisterMint>(data, "register_mint", |ix| json!({
+                "params": format!("{:?}", ix.params),
+            })))
+            .or_else(|| instruction::<bp::UpdateSupportedMint>(data, "update_supported_mint", |ix| json!({
+                "params": format!("{:?}", ix.params),
+            })))
+            .or_else(|| instruction::<bp::FundMintLiquidity>(data, "fund_mint_liquidity", |ix| json!({ "amount": ix.amount })))
+            .or_else(|| no_args::<bp::CreateOracleConfig>(data, "create_oracle_config"))
+            .or_else(|| instruction::<bp::QueueChange>(data, "queue_change", |ix| json!({
+                "change": format!("{:?}", ix.change),
+            })))
+            .or_else(|| no_args::<bp::ExecuteChange>(data, "execute_change"))
+            .or_else(|| no_args::<bp::CancelChange>(data, "cancel_change"))
+            .or_else(|| no_args::<bp::CreateUserBettingAccount>(data, "create_user_betting_account"))
+            .or_else(|| no_args::<bp::CreateBetHistory>(data, "create_bet_history"))
+            .or_else(|| no_args::<bp::CloseUserBettingAccount>(data, "close_user_betting_account"))
+            .or_else(|| no_args::<bp::CloseIdleUserBettingAccount>(data, "close_idle_user_betting_account"))
+            .or_else(|| no_args::<bp::ClaimQueuedWinnings>(data, "claim_queued_winnings"))
+            .or_else(|| no_args::<bp::InitializeUserAccount>(data, "initialize_user_account"))
+            .or_else(|| no_args::<bp::InitializeBettingStateBase>(data, "initialize_betting_state_base"))
+            .or_else(|| no_args::<bp::InitializeStateAccounts>(data, "initialize_state_accounts"))
+            .or_else(|| no_args::<bp::InitializeDumbsMint>(data, "initialize_dumbs_mint"))
+            .or_else(|| no_args::<bp::InitializeBettingState>(data, "initialize_betting_state"))
+            .or_else(|| no_args::<bp::InitializeBetVault>(data, "initialize_bet_vault"))
+            .or_else(|| no_args::<bp::InitializeRaprVault>(data, "initialize_rapr_vault"))
+            .or_else(|| no_args::<bp::InitializeSolVault>(data, "initialize_sol_vault"))
+            .or_else(|| no_args::<bp::InitializeTreasury>(data, "initialize_treasury"))
+            .or_else(|| no_args::<bp::InitializeRoles>(data, "initialize_roles"));
+    }
+
+    if *program_id == nft_game_agent_program::ID {
+        return instruction::<ap::CreateCollection>(data, "create_collection", |ix| json!({
+            "name": ix.name,
+            "symbol": ix.symbol,
+            "strategy": ix.strategy,
+            "collection_id": ix.collection_id.to_string(),
+        }))
+        .or_else(|| instruction::<ap::MintAiAgent>(data, "mint_ai_agent", |ix| json!({
+            "id": ix.id.to_string(),
+            "name": ix.name,
+            "symbol": ix.symbol,
+            "uri": ix.uri,
+            "collection_id": ix.collection_id.to_string(),
+        })))
+        .or_else(|| instruction::<ap::InitializeAiAgentAccounts>(data, "initialize_ai_agent_accounts", |ix| json!({
+            "id": ix.id.to_string(),
+        })))
+        .or_else(|| no_args::<ap::InitializeTreasury>(data, "initialize_treasury"));
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::{Event, InstructionData};
+    use betting_program::state::TokenType;
+
+    #[test]
+    fn events_decode_for_their_own_program_only() {
+        let depositor = Pubkey::new_unique();
+        let data = betting_events::DepositMade {
+            depositor,
+            sol_amount: 1_000,
+            fee: 25,
+            dumbs_minted: 975_000,
+            timestamp: 7,
+        }
+        .data();
+
+        let Some(DecodedEvent::DepositMade(event)) = decode_event(&betting_program::ID, &data) else {
+            panic!("DepositMade did not decode");
+        };
+        assert_eq!((event.depositor, event.sol_amount, event.fee, event.dumbs_minted), (depositor, 1_000, 25, 975_000));
+
+        assert!(decode_event(&nft_game_agent_program::ID, &data).is_none());
+        assert!(decode_event(&Pubkey::new_unique(), &data).is_none());
+    }
+
+    #[test]
+    fn events_are_told_apart_by_discriminator() {
+        let data = betting_events::BetPlaced {
+            bettor: Pubkey::new_unique(),
+            fight_id: 3,
+            token_type: TokenType::RAPR,
+            amount: 100,
+            fee: 2,
+            odds: 150,
+            potential_payout: 150,
+            timestamp: 9,
+        }
+        .data();
+        let event = decode_event(&betting_program::ID, &data).expect("BetPlaced did not decode");
+        assert_eq!(event.name(), "BetPlaced");
+
+        let agent = agent_events::AgentMinted {
+            ai_agent: Pubkey::new_unique(),
+            agent_id: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            collection: Pubkey::new_unique(),
+            owner: Pubkey::new_unique(),
+            model_hash: [7; 32],
+            fee: 1,
+            timestamp: 2,
+        }
+        .data();
+        let event = decode_event(&nft_game_agent_program::ID, &agent).expect("AgentMinted did not decode");
+        assert_eq!(event.name(), "AgentMinted");
+    }
+
+    #[test]
+    fn truncated_or_unknown_data_is_ignored() {
+        let data = betting_events::DepositMade {
+            depositor: Pubkey::new_unique(),
+            sol_amount: 1,
+            fee: 0,
+            dumbs_minted: 1,
+            timestamp: 0,
+        }
+        .data();
+        assert!(decode_event(&betting_program::ID, &data[..data.len() - 1]).is_none());
+        assert!(decode_event(&betting_program::ID, &[0; 8]).is_none());
+        assert!(decode_instruction(&betting_program::ID, &[]).is_none());
+    }
+
+    #[test]
+    fn instructions_decode_with_their_args() {
+        use betting_program::instruction as bp;
+
+        let data = bp::DepositSol { amount: 5_000, quoted_dumbs: 4_900, max_slippage: 100 }.data();
+        let ix = decode_instruction(&betting_program::ID, &data).expect("deposit_sol did not decode");
+        assert_eq!(ix.name, "deposit_sol");
+        assert_eq!(ix.args, json!({ "amount": 5_000, "quoted_dumbs": 4_900, "max_slippage": 100 }));
+
+        let data = bp::CashOut { amount: 10, token_type: TokenType::DUMBS, quoted_sol: 0, max_slippage: 0 }.data();
+        let ix = decode_instruction(&betting_program::ID, &data).expect("cash_out did not decode");
+        assert_eq!(ix.name, "cash_out");
+        assert_eq!(ix.args["token_type"], "DUMBS");
+
+        let ix = decode_instruction(&betting_program::ID, &bp::ExecuteChange.data()).expect("execute_change did not decode");
+        assert_eq!((ix.name, ix.args), ("execute_change", Value::Null));
+
+        assert!(decode_instruction(&nft_game_agent_program::ID, &data).is_none());
+    }
+}
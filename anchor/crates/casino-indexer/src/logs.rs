@@ -0,0 +1,115 @@
+use std::str::FromStr;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use solana_sdk::pubkey::Pubkey;
+
+const PROGRAM_DATA: &str = "Program data: ";
+
+/// Raw `emit!` payload together with the program that was executing when it was logged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmittedData {
+    pub program_id: Pubkey,
+    pub data: Vec<u8>,
+}
+
+/// Walks the transaction log and attributes every `Program data:` line to the
+/// program on top of the invoke stack, so events emitted inside CPIs are kept.
+pub fn extract_program_data(logs: &[String]) -> Vec<EmittedData> {
+    let mut stack: Vec<Pubkey> = Vec::new();
+    let mut emitted = Vec::new();
+
+    for line in logs {
+        if let Some(encoded) = line.strip_prefix(PROGRAM_DATA) {
+            let (Some(program_id), Ok(data)) = (stack.last(), STANDARD.decode(encoded.trim())) else {
+                continue;
+            };
+            emitted.push(EmittedData {
+                program_id: *program_id,
+                data,
+            });
+            continue;
+        }
+
+        let Some(rest) = line.strip_prefix("Program ") else {
+            continue;
+        };
+        let mut parts = rest.split_whitespace();
+        let (Some(id), Some(action)) = (parts.next(), parts.next()) else {
+            continue;
+        };
+        let Ok(program_id) = Pubkey::from_str(id) else {
+            continue;
+        };
+
+        match action {
+            "invoke" => stack.push(program_id),
+            "success" | "failed:" => {
+                stack.pop();
+            }
+            _ => {}
+        }
+    }
+
+    emitted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn invoke(program_id: &Pubkey, depth: u8) -> String {
+        format!("Program {} invoke [{}]", program_id, depth)
+    }
+
+    fn success(program_id: &Pubkey) -> String {
+        format!("Program {} success", program_id)
+    }
+
+    fn data(bytes: &[u8]) -> String {
+        format!("{}{}", PROGRAM_DATA, STANDARD.encode(bytes))
+    }
+
+    #[test]
+    fn data_is_attributed_to_the_program_on_top_of_the_stack() {
+        let (outer, inner) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let logs = vec![
+            invoke(&outer, 1),
+            data(&[1]),
+            invoke(&inner, 2),
+            "Program log: Instruction: DepositSol".to_string(),
+            data(&[2]),
+            format!("Program {} consumed 1200 of 190000 compute units", inner),
+            success(&inner),
+            data(&[3]),
+            success(&outer),
+        ];
+
+        let emitted = extract_program_data(&logs);
+        assert_eq!(
+            emitted,
+            vec![
+                EmittedData { program_id: outer, data: vec![1] },
+                EmittedData { program_id: inner, data: vec![2] },
+                EmittedData { program_id: outer, data: vec![3] },
+            ]
+        );
+    }
+
+    #[test]
+    fn failed_programs_are_popped_and_bad_lines_skipped() {
+        let (first, second) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let logs = vec![
+            data(&[0]),
+            invoke(&first, 1),
+            format!("{}not base64!", PROGRAM_DATA),
+            format!("Program {} failed: custom program error: 0x1", first),
+            "Program notakey invoke [1]".to_string(),
+            invoke(&second, 1),
+            data(&[4]),
+            success(&second),
+        ];
+
+        let emitted = extract_program_data(&logs);
+        assert_eq!(emitted, vec![EmittedData { program_id: second, data: vec![4] }]);
+    }
+}
@@ -0,0 +1,83 @@
+//! Follows the betting and NFT game agent programs over RPC and writes their
+//! decoded events and instructions into SQLite for the dashboard.
+
+mod db;
+mod decode;
+mod logs;
+mod sync;
+
+use std::{path::PathBuf, thread, time::Duration};
+
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+use log::{error, info};
+
+use crate::db::Database;
+use crate::sync::{Indexer, SyncMode};
+
+#[derive(Parser)]
+#[command(name = "casino-indexer", about = "Index casino program history into SQLite")]
+struct Cli {
+    /// RPC endpoint, defaults to a local solana-test-validator
+    #[arg(long, env = "RPC_URL", default_value = "http://127.0.0.1:8899")]
+    rpc_url: String,
+
+    /// SQLite database file
+    #[arg(long, env = "INDEXER_DB", default_value = "casino-index.sqlite")]
+    db: PathBuf,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Index from the stored checkpoints and keep polling for new transactions
+    Run {
+        /// Seconds between polls
+        #[arg(long, default_value_t = 5)]
+        interval: u64,
+
+        /// Exit after catching up instead of polling
+        #[arg(long)]
+        once: bool,
+    },
+    /// Re-index the full history of both programs, ignoring checkpoints
+    Backfill,
+}
+
+fn sync_all(indexer: &mut Indexer, mode: SyncMode) -> Result<usize> {
+    let mut total = 0;
+    for program_id in [betting_program::ID, nft_game_agent_program::ID] {
+        total += indexer.sync_program(&program_id, mode)?;
+    }
+    Ok(total)
+}
+
+fn main() -> Result<()> {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+    let cli = Cli::parse();
+
+    let mut indexer = Indexer::new(cli.rpc_url, Database::open(&cli.db)?);
+
+    match cli.command {
+        Command::Backfill => {
+            let total = sync_all(&mut indexer, SyncMode::Backfill)?;
+            info!("Backfill complete, {} transactions indexed", total);
+        }
+        Command::Run { interval, once } => loop {
+            match sync_all(&mut indexer, SyncMode::Resume) {
+                Ok(total) if total > 0 => info!("Indexed {} transactions", total),
+                Ok(_) => {}
+                Err(err) if !once => error!("Sync failed, retrying: {:#}", err),
+                Err(err) => return Err(err),
+            }
+            if once {
+                break;
+            }
+            thread::sleep(Duration::from_secs(interval));
+        },
+    }
+
+    Ok(())
+}
@@ -0,0 +1,247 @@
+use std::str::FromStr;
+
+use anyhow::{Context, Result};
+use log::{debug, info, warn};
+use solana_client::{
+    rpc_client::{GetConfirmedSignaturesForAddress2Config, RpcClient},
+    rpc_config::RpcTransactionConfig,
+    rpc_response::RpcConfirmedTransactionStatusWithSignature,
+};
+use solana_sdk::{
+    commitment_config::CommitmentConfig, instruction::CompiledInstruction, pubkey::Pubkey,
+    signature::Signature,
+};
+use solana_transaction_status::{
+    option_serializer::OptionSerializer, EncodedConfirmedTransactionWithStatusMeta,
+    UiInnerInstructions, UiInstruction, UiTransactionEncoding,
+};
+
+use crate::db::{self, Database, RowKey};
+use crate::decode::{decode_event, decode_instruction};
+use crate::logs::extract_program_data;
+
+const SIGNATURE_PAGE_SIZE: usize = 1_000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SyncMode {
+    /// Continue from the last stored checkpoint
+    Resume,
+    /// Re-read the full history of the program, rewriting existing rows
+    Backfill,
+}
+
+pub struct Indexer {
+    rpc: RpcClient,
+    db: Database,
+    commitment: CommitmentConfig,
+}
+
+impl Indexer {
+    pub fn new(rpc_url: String, db: Database) -> Self {
+        let commitment = CommitmentConfig::confirmed();
+        Self {
+            rpc: RpcClient::new_with_commitment(rpc_url, commitment),
+            db,
+            commitment,
+        }
+    }
+
+    /// Indexes every new transaction touching `program_id`, oldest first.
+    /// Returns the number of transactions processed.
+    pub fn sync_program(&mut self, program_id: &Pubkey, mode: SyncMode) -> Result<usize> {
+        let program_key = program_id.to_string();
+        let until = match mode {
+            SyncMode::Resume => self
+                .db
+                .checkpoint(&program_key)?
+                .map(|(signature, _)| Signature::from_str(&signature))
+                .transpose()
+                .context("stored checkpoint is not a valid signature")?,
+            SyncMode::Backfill => None,
+        };
+
+        let signatures = self.fetch_signatures(program_id, until)?;
+        if signatures.is_empty() {
+            debug!("{}: no new transactions", program_key);
+            return Ok(0);
+        }
+        info!("{}: indexing {} transactions", program_key, signatures.len());
+
+        for status in &signatures {
+            let signature = Signature::from_str(&status.signature)?;
+            let tx = self.db.transaction()?;
+
+            // Failed transactions emit no events but still advance the checkpoint
+            if status.err.is_none() {
+                let confirmed = self.rpc.get_transaction_with_config(
+                    &signature,
+                    RpcTransactionConfig {
+                        encoding: Some(UiTransactionEncoding::Base64),
+                        commitment: Some(self.commitment),
+                        max_supported_transaction_version: Some(0),
+                    },
+                )?;
+                index_transaction(&tx, &status.signature, &confirmed)?;
+            }
+
+            db::save_checkpoint(&tx, &program_key, &status.signature, status.slot)?;
+            tx.commit()?;
+        }
+
+        Ok(signatures.len())
+    }
+
+    /// Pages backwards from the newest signature until `until` (exclusive) and
+    /// returns the statuses in chronological order.
+    fn fetch_signatures(
+        &self,
+        program_id: &Pubkey,
+        until: Option<Signature>,
+    ) -> Result<Vec<RpcConfirmedTransactionStatusWithSignature>> {
+        let mut all = Vec::new();
+        let mut before = None;
+
+        loop {
+            let page = self.rpc.get_signatures_for_address_with_config(
+                program_id,
+                GetConfirmedSignaturesForAddress2Config {
+                    before,
+                    until,
+                    limit: Some(SIGNATURE_PAGE_SIZE),
+                    commitment: Some(self.commitment),
+                },
+            )?;
+            let Some(last) = page.last() else {
+                break;
+            };
+            before = Some(Signature::from_str(&last.signature)?);
+            let done = page.len() < SIGNATURE_PAGE_SIZE;
+            all.extend(page);
+            if done {
+                break;
+            }
+        }
+
+        all.reverse();
+        Ok(all)
+    }
+}
+
+fn index_transaction(
+    tx: &rusqlite::Transaction,
+    signature: &str,
+    confirmed: &EncodedConfirmedTransactionWithStatusMeta,
+) -> Result<()> {
+    let slot = confirmed.slot;
+    let block_time = confirmed.block_time;
+    let Some(decoded) = confirmed.transaction.transaction.decode() else {
+        warn!("{}: could not decode transaction", signature);
+        return Ok(());
+    };
+    let meta = confirmed.transaction.meta.as_ref();
+
+    // Static keys followed by any keys loaded from lookup tables
+    let mut account_keys = decoded.message.static_account_keys().to_vec();
+    if let Some(OptionSerializer::Some(loaded)) = meta.map(|m| &m.loaded_addresses) {
+        for key in loaded.writable.iter().chain(loaded.readonly.iter()) {
+            account_keys.push(Pubkey::from_str(key)?);
+        }
+    }
+    let signer = account_keys.first().map(|k| k.to_string());
+
+    let inner = match meta.map(|m| &m.inner_instructions) {
+        Some(OptionSerializer::Some(inner)) => inner.as_slice(),
+        _ => &[],
+    };
+    let executed = executed_instructions(decoded.message.instructions(), inner);
+    for (index, (program_id_index, data)) in executed.iter().enumerate() {
+        let Some(program_id) = account_keys.get(*program_id_index as usize) else {
+            continue;
+        };
+        if let Some(decoded_ix) = decode_instruction(program_id, data) {
+            let key = RowKey { signature, index: index as u32, slot, block_time };
+            db::insert_instruction(tx, &key, &program_id.to_string(), signer.clone(), &decoded_ix)?;
+        }
+    }
+
+    let logs = match meta.map(|m| &m.log_messages) {
+        Some(OptionSerializer::Some(logs)) => logs.as_slice(),
+        _ => &[],
+    };
+    for (index, emitted) in extract_program_data(logs).iter().enumerate() {
+        if let Some(event) = decode_event(&emitted.program_id, &emitted.data) {
+            let key = RowKey { signature, index: index as u32, slot, block_time };
+            db::insert_event(tx, &key, &emitted.program_id.to_string(), &event)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Every instruction the transaction ran as `(program id index, data)`, in
+/// execution order: each top-level instruction followed by the CPIs it made,
+/// so calls into the casino programs from other programs are indexed too.
+fn executed_instructions(
+    top_level: &[CompiledInstruction],
+    inner: &[UiInnerInstructions],
+) -> Vec<(u8, Vec<u8>)> {
+    let mut executed = Vec::new();
+    for (index, ix) in top_level.iter().enumerate() {
+        executed.push((ix.program_id_index, ix.data.clone()));
+
+        let cpis = inner
+            .iter()
+            .filter(|group| group.index as usize == index)
+            .flat_map(|group| &group.instructions);
+        for cpi in cpis {
+            // Binary encodings always return compiled inner instructions
+            let UiInstruction::Compiled(cpi) = cpi else {
+                continue;
+            };
+            let Ok(data) = bs58::decode(&cpi.data).into_vec() else {
+                continue;
+            };
+            executed.push((cpi.program_id_index, data));
+        }
+    }
+    executed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_transaction_status::UiCompiledInstruction;
+
+    fn cpi(program_id_index: u8, data: &[u8]) -> UiInstruction {
+        UiInstruction::Compiled(UiCompiledInstruction {
+            program_id_index,
+            accounts: vec![],
+            data: bs58::encode(data).into_string(),
+            stack_height: Some(2),
+        })
+    }
+
+    #[test]
+    fn inner_instructions_follow_the_instruction_that_made_them() {
+        let top_level = [
+            CompiledInstruction::new_from_raw_parts(1, vec![10], vec![]),
+            CompiledInstruction::new_from_raw_parts(2, vec![20], vec![]),
+        ];
+        let inner = [UiInnerInstructions { index: 0, instructions: vec![cpi(3, &[11]), cpi(4, &[12])] }];
+
+        let executed = executed_instructions(&top_level, &inner);
+        assert_eq!(executed, vec![(1, vec![10]), (3, vec![11]), (4, vec![12]), (2, vec![20])]);
+    }
+
+    #[test]
+    fn undecodable_inner_instructions_are_skipped() {
+        let top_level = [CompiledInstruction::new_from_raw_parts(1, vec![10], vec![])];
+        let mut bad = cpi(3, &[11]);
+        if let UiInstruction::Compiled(ix) = &mut bad {
+            ix.data = "0OIl".to_string();
+        }
+        let inner = [UiInnerInstructions { index: 0, instructions: vec![bad, cpi(4, &[12])] }];
+
+        assert_eq!(executed_instructions(&top_level, &inner), vec![(1, vec![10]), (4, vec![12])]);
+    }
+}
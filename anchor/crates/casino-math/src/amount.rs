@@ -0,0 +1,85 @@
+use core::fmt;
+use core::str::FromStr;
+
+use crate::{BasisPoints, MathError, Result, Rounded, Rounding};
+
+/// An amount in base units of a token with `DECIMALS` decimal places.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TokenAmount<const DECIMALS: u8>(u64);
+
+/// SOL, DUMBS and RAPR all use 9 decimals.
+pub type Lamports = TokenAmount<9>;
+
+impl<const DECIMALS: u8> TokenAmount<DECIMALS> {
+    pub const ZERO: Self = Self(0);
+    /// Base units in one whole token.
+    pub const ONE: Self = Self(10u64.pow(DECIMALS as u32));
+
+    pub const fn new(base_units: u64) -> Self {
+        Self(base_units)
+    }
+
+    pub const fn base_units(self) -> u64 {
+        self.0
+    }
+
+    pub fn checked_add(self, other: Self) -> Result<Self> {
+        self.0.checked_add(other.0).map(Self).ok_or(MathError::Overflow)
+    }
+
+    pub fn checked_sub(self, other: Self) -> Result<Self> {
+        self.0.checked_sub(other.0).map(Self).ok_or(MathError::Overflow)
+    }
+
+    /// This amount scaled by `ratio`.
+    pub fn scale(self, ratio: BasisPoints, rounding: Rounding) -> Result<Rounded> {
+        ratio.apply(self.0, rounding)
+    }
+}
+
+/// Formats in whole tokens without trailing zeros, e.g. `1.5`.
+impl<const DECIMALS: u8> fmt::Display for TokenAmount<DECIMALS> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let scale = Self::ONE.0;
+        let (whole, mut frac) = (self.0 / scale, self.0 % scale);
+        if frac == 0 {
+            return write!(f, "{}", whole);
+        }
+        let mut digits = DECIMALS as usize;
+        while frac.rem_euclid(10) == 0 {
+            frac /= 10;
+            digits -= 1;
+        }
+        write!(f, "{}.{:0width$}", whole, frac, width = digits)
+    }
+}
+
+/// Parses whole tokens such as `1.5` exactly, without going through floats.
+impl<const DECIMALS: u8> FromStr for TokenAmount<DECIMALS> {
+    type Err = MathError;
+
+    fn from_str(input: &str) -> Result<Self> {
+        let (whole, frac) = input.split_once('.').unwrap_or((input, ""));
+        if (whole.is_empty() && frac.is_empty()) || frac.len() > DECIMALS as usize {
+            return Err(MathError::InvalidAmount);
+        }
+        let digits = |s: &str| -> Result<u64> {
+            if !s.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(MathError::InvalidAmount);
+            }
+            s.bytes().try_fold(0u64, |acc, b| {
+                acc.checked_mul(10)
+                    .and_then(|acc| acc.checked_add((b - b'0') as u64))
+                    .ok_or(MathError::Overflow)
+            })
+        };
+
+        let padding = 10u64.pow((DECIMALS as usize - frac.len()) as u32);
+        let frac = digits(frac)? * padding;
+        digits(whole)?
+            .checked_mul(Self::ONE.0)
+            .and_then(|whole| whole.checked_add(frac))
+            .map(Self)
+            .ok_or(MathError::Overflow)
+    }
+}
@@ -0,0 +1,55 @@
+use core::fmt;
+
+use crate::{mul_div, MathError, Result, Rounded, Rounding};
+
+/// A ratio in basis points, where [`BasisPoints::ONE`] is 1x.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BasisPoints(u32);
+
+impl BasisPoints {
+    pub const SCALE: u32 = 10_000;
+    pub const ZERO: Self = Self(0);
+    pub const ONE: Self = Self(Self::SCALE);
+
+    pub const fn new(bps: u32) -> Self {
+        Self(bps)
+    }
+
+    /// Accepts only ratios of at most 1x, as fees must be.
+    pub fn fraction(bps: u32) -> Result<Self> {
+        if bps > Self::SCALE {
+            return Err(MathError::InvalidBasisPoints);
+        }
+        Ok(Self(bps))
+    }
+
+    /// Converts a stored `u64` setting.
+    pub fn from_u64(bps: u64) -> Result<Self> {
+        u32::try_from(bps).map(Self).map_err(|_| MathError::InvalidBasisPoints)
+    }
+
+    pub const fn get(self) -> u32 {
+        self.0
+    }
+
+    /// `amount` scaled by this ratio.
+    pub fn apply(self, amount: u64, rounding: Rounding) -> Result<Rounded> {
+        mul_div(amount, self.0 as u64, Self::SCALE as u64, rounding)
+    }
+
+    /// The fee this rate charges on `amount`. Rounds up, so the fee never
+    /// undercharges, and never exceeds `amount`.
+    pub fn fee_on(self, amount: u64) -> Result<Rounded> {
+        if self > Self::ONE {
+            return Err(MathError::InvalidBasisPoints);
+        }
+        self.apply(amount, Rounding::Up)
+    }
+}
+
+/// Formats as a percentage, e.g. `250` -> `2.50%`.
+impl fmt::Display for BasisPoints {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{:02}%", self.0 / 100, self.0 % 100)
+    }
+}
@@ -0,0 +1,102 @@
+//! Fixed-point math shared by the betting program and the off-chain tools.
+//!
+//! * [`BasisPoints`] are ratios where `10_000` is 1x: house fees, the RAPR
+//!   multiplier.
+//! * [`Odds`] are decimal odds in basis points, so `15_000` is 1.5x and pays
+//!   1.5 times the stake including the stake itself. They convert to and from
+//!   American and fractional odds.
+//! * [`TokenAmount`] is a base-unit amount tagged with its decimals.
+//!
+//! # Rounding policy
+//!
+//! Every division rounds in the house's favour: amounts the house collects
+//! (fees) round up, amounts it pays out (payouts, cash-outs, boosted odds)
+//! round down. Each rounded result is returned as a [`Rounded`] carrying the
+//! dust the house kept, in [`DUST_SCALE`]ths of a base unit, so callers can
+//! account for it with a [`Dust`] ledger instead of losing it silently.
+
+#![no_std]
+
+mod amount;
+mod bps;
+mod odds;
+
+pub use amount::{Lamports, TokenAmount};
+pub use bps::BasisPoints;
+pub use odds::Odds;
+
+/// Dust is measured in ten-thousandths of a base unit.
+pub const DUST_SCALE: u64 = 10_000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MathError {
+    Overflow,
+    DivideByZero,
+    InvalidOdds,
+    InvalidBasisPoints,
+    InvalidAmount,
+}
+
+pub type Result<T> = core::result::Result<T, MathError>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rounding {
+    /// Towards zero, for amounts the house pays out.
+    Down,
+    /// Away from zero, for amounts the house collects.
+    Up,
+}
+
+/// A rounded result and the dust the house kept by rounding it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Rounded {
+    pub value: u64,
+    /// In [`DUST_SCALE`]ths of a base unit. For rounding down this is the
+    /// fraction withheld; for rounding up, the fraction charged on top.
+    pub dust: u64,
+}
+
+/// Computes `value * numerator / denominator` in 128 bits and rounds it.
+pub fn mul_div(value: u64, numerator: u64, denominator: u64, rounding: Rounding) -> Result<Rounded> {
+    if denominator == 0 {
+        return Err(MathError::DivideByZero);
+    }
+    let product = value as u128 * numerator as u128;
+    let denominator = denominator as u128;
+    let quotient = product / denominator;
+    let remainder = product % denominator;
+
+    let (value, dust_numerator) = match rounding {
+        Rounding::Down => (quotient, remainder),
+        Rounding::Up if remainder == 0 => (quotient, 0),
+        Rounding::Up => (quotient + 1, denominator - remainder),
+    };
+    let value = u64::try_from(value).map_err(|_| MathError::Overflow)?;
+    // Exact whenever the denominator divides DUST_SCALE, as basis points do
+    let dust = (dust_numerator * DUST_SCALE as u128 / denominator) as u64;
+    Ok(Rounded { value, dust })
+}
+
+/// Running total of rounding dust kept by the house.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Dust(pub u64);
+
+impl Dust {
+    /// Records `rounded.dust` and returns the rounded value.
+    pub fn keep(&mut self, rounded: Rounded) -> Result<u64> {
+        self.0 = self.0.checked_add(rounded.dust).ok_or(MathError::Overflow)?;
+        Ok(rounded.value)
+    }
+
+    /// Whole base units the recorded dust adds up to.
+    pub fn whole_units(&self) -> u64 {
+        self.0 / DUST_SCALE
+    }
+}
+
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
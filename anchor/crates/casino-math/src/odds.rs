@@ -0,0 +1,112 @@
+use core::fmt;
+
+use crate::{gcd, mul_div, BasisPoints, MathError, Result, Rounded, Rounding};
+
+/// Decimal odds in basis points: `15_000` is 1.5x and returns 1.5 times the
+/// stake, stake included. Odds below 1x would pay back less than the stake
+/// and are rejected.
+///
+/// Conversions into `Odds` round in the house's favour. Conversions out of
+/// it (American, fractional) are for display and round to nearest so that
+/// quotes survive a round trip.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Odds(u32);
+
+impl Odds {
+    pub const SCALE: u32 = 10_000;
+    /// 1x: the stake back and nothing more.
+    pub const MIN: Self = Self(Self::SCALE);
+    /// 2x, i.e. +100 American or 1/1 fractional.
+    pub const EVENS: Self = Self(2 * Self::SCALE);
+
+    pub fn from_bps(bps: u32) -> Result<Self> {
+        if bps < Self::SCALE {
+            return Err(MathError::InvalidOdds);
+        }
+        Ok(Self(bps))
+    }
+
+    /// Converts an instruction argument.
+    pub fn from_u64(bps: u64) -> Result<Self> {
+        Self::from_bps(u32::try_from(bps).map_err(|_| MathError::InvalidOdds)?)
+    }
+
+    pub const fn bps(self) -> u32 {
+        self.0
+    }
+
+    /// What a winning `stake` returns, rounded down.
+    pub fn payout(self, stake: u64) -> Result<Rounded> {
+        mul_div(stake, self.0 as u64, Self::SCALE as u64, Rounding::Down)
+    }
+
+    /// These odds scaled by `multiplier`, rounded down.
+    pub fn boost(self, multiplier: BasisPoints) -> Result<Self> {
+        let boosted = mul_div(self.0 as u64, multiplier.get() as u64, BasisPoints::SCALE as u64, Rounding::Down)?;
+        Self::from_bps(u32::try_from(boosted.value).map_err(|_| MathError::Overflow)?)
+    }
+
+    /// From American odds: `+150` is 2.5x, `-200` is 1.5x. Values strictly
+    /// between -100 and +100 are not valid American odds.
+    pub fn from_american(american: i32) -> Result<Self> {
+        let profit = match american {
+            100.. => american as u64 * Self::SCALE as u64 / 100,
+            ..=-100 => mul_div(100, Self::SCALE as u64, american.unsigned_abs() as u64, Rounding::Down)?.value,
+            _ => return Err(MathError::InvalidOdds),
+        };
+        Self::from_profit(profit)
+    }
+
+    /// To American odds. 1x has no American equivalent.
+    pub fn to_american(self) -> Result<i32> {
+        let profit = self.profit();
+        let american = if profit >= Self::SCALE {
+            round_div(profit as u64 * 100, Self::SCALE as u64) as i64
+        } else if profit > 0 {
+            -(round_div(100 * Self::SCALE as u64, profit as u64) as i64)
+        } else {
+            return Err(MathError::InvalidOdds);
+        };
+        i32::try_from(american).map_err(|_| MathError::Overflow)
+    }
+
+    /// From fractional odds `numerator/denominator`: `1/2` is 1.5x.
+    pub fn from_fractional(numerator: u32, denominator: u32) -> Result<Self> {
+        let profit = mul_div(numerator as u64, Self::SCALE as u64, denominator as u64, Rounding::Down)?;
+        Self::from_profit(profit.value)
+    }
+
+    /// To fractional odds in lowest terms.
+    pub fn to_fractional(self) -> (u32, u32) {
+        let profit = self.profit() as u64;
+        let divisor = gcd(profit, Self::SCALE as u64).max(1);
+        ((profit / divisor) as u32, (Self::SCALE as u64 / divisor) as u32)
+    }
+
+    fn profit(self) -> u32 {
+        self.0 - Self::SCALE
+    }
+
+    fn from_profit(profit: u64) -> Result<Self> {
+        let bps = profit.checked_add(Self::SCALE as u64).ok_or(MathError::Overflow)?;
+        Self::from_bps(u32::try_from(bps).map_err(|_| MathError::Overflow)?)
+    }
+}
+
+fn round_div(numerator: u64, denominator: u64) -> u64 {
+    (numerator + denominator / 2) / denominator
+}
+
+/// Formats as a multiplier with at least two decimals, e.g. `1.50x`, `1.909x`.
+impl fmt::Display for Odds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let whole = self.0 / Self::SCALE;
+        let mut frac = self.0 % Self::SCALE;
+        let mut digits = 4;
+        while digits > 2 && frac.rem_euclid(10) == 0 {
+            frac /= 10;
+            digits -= 1;
+        }
+        write!(f, "{}.{:0width$}x", whole, frac, width = digits)
+    }
+}
@@ -0,0 +1,61 @@
+use casino_math::{mul_div, BasisPoints, Dust, Lamports, MathError, Odds, Rounded, Rounding};
+
+#[test]
+fn american_odds_round_trip() {
+    for american in [100, 150, 250, 1_000, -110, -150, -200, -1_000] {
+        let odds = Odds::from_american(american).unwrap();
+        assert_eq!(odds.to_american().unwrap(), american, "{}", odds);
+    }
+    assert_eq!(Odds::from_american(150).unwrap().bps(), 25_000);
+    assert_eq!(Odds::from_american(-200).unwrap().bps(), 15_000);
+    // 1 + 100/110 = 1.90909..., rounded down in the house's favour
+    assert_eq!(Odds::from_american(-110).unwrap().bps(), 19_090);
+    assert_eq!(Odds::from_american(99), Err(MathError::InvalidOdds));
+    assert_eq!(Odds::from_american(-99), Err(MathError::InvalidOdds));
+    assert_eq!(Odds::MIN.to_american(), Err(MathError::InvalidOdds));
+}
+
+#[test]
+fn fractional_odds_round_trip() {
+    assert_eq!(Odds::from_fractional(1, 2).unwrap().bps(), 15_000);
+    assert_eq!(Odds::from_fractional(5, 2).unwrap().to_fractional(), (5, 2));
+    assert_eq!(Odds::EVENS.to_fractional(), (1, 1));
+    assert_eq!(Odds::from_fractional(1, 3).unwrap().bps(), 13_333);
+    assert_eq!(Odds::from_fractional(1, 0), Err(MathError::DivideByZero));
+}
+
+#[test]
+fn odds_reject_less_than_the_stake() {
+    assert_eq!(Odds::from_bps(9_999), Err(MathError::InvalidOdds));
+    assert_eq!(Odds::from_u64(u64::MAX), Err(MathError::InvalidOdds));
+    assert_eq!(Odds::EVENS.boost(BasisPoints::new(4_000)), Err(MathError::InvalidOdds));
+    assert_eq!(Odds::from_bps(15_000).unwrap().boost(BasisPoints::new(100_000)).unwrap().bps(), 150_000);
+}
+
+#[test]
+fn rounding_favours_the_house() {
+    // 2.5% of 101 is 2.525: the fee rounds up and the payout rounds down
+    assert_eq!(BasisPoints::new(250).fee_on(101).unwrap(), Rounded { value: 3, dust: 4_750 });
+    assert_eq!(Odds::from_bps(15_001).unwrap().payout(3).unwrap(), Rounded { value: 4, dust: 5_003 });
+    assert_eq!(mul_div(10, 1, 4, Rounding::Down).unwrap(), Rounded { value: 2, dust: 5_000 });
+    assert_eq!(BasisPoints::new(10_001).fee_on(1), Err(MathError::InvalidBasisPoints));
+
+    let mut dust = Dust::default();
+    assert_eq!(dust.keep(Rounded { value: 7, dust: 6_000 }).unwrap(), 7);
+    dust.keep(Rounded { value: 1, dust: 6_000 }).unwrap();
+    assert_eq!(dust.whole_units(), 1);
+}
+
+#[test]
+fn amounts_format_and_parse_exactly() {
+    assert_eq!("1.5".parse::<Lamports>().unwrap().base_units(), 1_500_000_000);
+    assert_eq!(".000000001".parse::<Lamports>().unwrap().base_units(), 1);
+    assert_eq!(Lamports::new(1_500_000_000).to_string(), "1.5");
+    assert_eq!(Lamports::new(2 * Lamports::ONE.base_units()).to_string(), "2");
+    assert_eq!("1.0000000001".parse::<Lamports>(), Err(MathError::InvalidAmount));
+    assert_eq!("1e9".parse::<Lamports>(), Err(MathError::InvalidAmount));
+    assert_eq!("18446744074".parse::<Lamports>(), Err(MathError::Overflow));
+    assert_eq!(Odds::from_bps(15_000).unwrap().to_string(), "1.50x");
+    assert_eq!(Odds::from_bps(19_090).unwrap().to_string(), "1.909x");
+    assert_eq!(BasisPoints::new(250).to_string(), "2.50%");
+}
@@ -0,0 +1,185 @@
+use std::sync::Once;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::SUCCESS;
+use anchor_lang::solana_program::program_stubs::{set_syscall_stubs, SyscallStubs};
+use betting_program::errors::error_code::ErrorCode;
+use betting_program::state::bet::Bet;
+use betting_program::state::betting_state::{BetTerms, TokenType};
+use betting_program::state::BettingState;
+use casino_math::{mul_div, Rounding};
+
+struct ClockStub;
+
+impl SyscallStubs for ClockStub {
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        // SAFETY: the sysvar getter passes a pointer to a `Clock`
+        unsafe { *(var_addr as *mut Clock) = Clock::default() };
+        SUCCESS
+    }
+}
+
+/// `Bet::initialize` and `Bet::settle` read the clock sysvar, which has no
+/// off-chain implementation unless stubbed.
+fn install_clock() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        set_syscall_stubs(Box::new(ClockStub));
+    });
+}
+
+/// A bet the house has accepted, with the gross amount the bettor sent.
+pub struct OpenBet {
+    pub bet: Bet,
+    pub gross: u64,
+    pub backs_fighter_one: bool,
+}
+
+/// A house driven through the same `BettingState` and `Bet` methods as the
+/// `place_bet` and `settle_bet` handlers. Token movements are not modelled;
+/// every flow is valued in DUMBS base units instead.
+pub struct House {
+    pub state: BettingState,
+}
+
+impl House {
+    pub fn new(state: BettingState) -> Self {
+        install_clock();
+        Self { state }
+    }
+
+    /// Runs `place_bet`'s arithmetic. Fails exactly when the handler would.
+    pub fn place_bet(&mut self, amount: u64, fight_id: u64, base_odds: u64, token_type: TokenType, backs_fighter_one: bool) -> Result<OpenBet> {
+        let terms = self.state.price_bet(amount, base_odds, token_type)?;
+        let BetTerms { stake, potential_payout, .. } = terms;
+
+        let mut bet = Bet::default();
+        bet.initialize(
+            Pubkey::default(),
+            token_type,
+            u32::try_from(fight_id).map_err(|_| ErrorCode::InvalidFightId)?,
+            Pubkey::default(),
+            &terms,
+            0,
+        )?;
+        self.state.record_bet_placed(stake, token_type, potential_payout)?;
+        Ok(OpenBet { bet, gross: amount, backs_fighter_one })
+    }
+
+    /// Runs `settle_bet`'s arithmetic and returns the DUMBS minted to the
+    /// winner, if any.
+    pub fn settle_bet(&mut self, open: &mut OpenBet, won: bool) -> Result<u64> {
+        open.bet.settle(won)?;
+        let payout = if open.bet.won {
+            self.state.mint_dumbs_for_win(open.bet.potential_payout)?
+        } else {
+            0
+        };
+        self.state.record_bet_settled(open.bet.potential_payout, payout)?;
+        Ok(payout)
+    }
+
+    /// Values an amount of `token_type` in DUMBS base units through the
+    /// SOL rates, rounding down.
+    pub fn dumbs_value(&self, amount: u64, token_type: TokenType) -> u64 {
+        match token_type {
+            TokenType::DUMBS => amount,
+            TokenType::RAPR => mul_div(amount, self.state.sol_dumbs_rate, self.state.sol_rapr_rate, Rounding::Down)
+                .map_or(u64::MAX, |rounded| rounded.value),
+        }
+    }
+
+    /// Converts a DUMBS value into `token_type` base units, rounding down.
+    pub fn tokens_for_dumbs(&self, dumbs: u64, token_type: TokenType) -> u64 {
+        match token_type {
+            TokenType::DUMBS => dumbs,
+            TokenType::RAPR => mul_div(dumbs, self.state.sol_rapr_rate, self.state.sol_dumbs_rate, Rounding::Down)
+                .map_or(u64::MAX, |rounded| rounded.value),
+        }
+    }
+
+    /// What settling `open` costs or earns the house, in DUMBS base units.
+    /// The house keeps the gross amount sent, mints the payout to a winner,
+    /// and hands a winning RAPR stake back.
+    pub fn house_result(&self, open: &OpenBet, payout: u64) -> i128 {
+        let token_type = open.bet.token_type;
+        let mut result = self.dumbs_value(open.gross, token_type) as i128 - payout as i128;
+        if open.bet.won && token_type == TokenType::RAPR {
+            result -= self.dumbs_value(open.bet.amount, token_type) as i128;
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DUMBS: u64 = 1_000_000_000;
+
+    fn house() -> House {
+        House::new(BettingState {
+            house_fee: 250,
+            rapr_multiplier: 100_000,
+            sol_dumbs_rate: 1_000,
+            sol_rapr_rate: 10_000_000,
+            max_bet: 100 * DUMBS,
+            ..BettingState::default()
+        })
+    }
+
+    #[test]
+    fn winning_bets_pay_their_potential_payout() {
+        let mut house = house();
+        let mut open = house.place_bet(10 * DUMBS, 1, 20_000, TokenType::DUMBS, true).unwrap();
+        let payout_due = open.bet.potential_payout;
+        assert_eq!(house.state.total_potential_payout, payout_due);
+
+        let payout = house.settle_bet(&mut open, true).unwrap();
+        assert_eq!(payout, payout_due);
+        assert!(open.bet.won);
+        assert_eq!(house.state.total_potential_payout, 0);
+        assert_eq!((house.state.total_bets_settled, house.state.total_dumbs_won), (1, payout));
+        assert_eq!(house.house_result(&open, payout), 10 * DUMBS as i128 - payout as i128);
+    }
+
+    #[test]
+    fn losing_bets_pay_nothing_and_the_house_keeps_the_stake() {
+        let mut house = house();
+        let mut open = house.place_bet(10 * DUMBS, 1, 20_000, TokenType::DUMBS, false).unwrap();
+
+        assert_eq!(house.settle_bet(&mut open, false).unwrap(), 0);
+        assert!(!open.bet.won);
+        assert_eq!(house.state.total_potential_payout, 0);
+        assert_eq!(house.state.total_dumbs_won, 0);
+        assert_eq!(house.house_result(&open, 0), 10 * DUMBS as i128);
+    }
+
+    #[test]
+    fn winning_rapr_stakes_are_handed_back() {
+        let mut house = house();
+        let mut open = house.place_bet(10 * DUMBS, 1, 20_000, TokenType::RAPR, true).unwrap();
+
+        let payout = house.settle_bet(&mut open, true).unwrap();
+        let gross = house.dumbs_value(10 * DUMBS, TokenType::RAPR) as i128;
+        let stake = house.dumbs_value(open.bet.amount, TokenType::RAPR) as i128;
+        assert!(stake > 0);
+        assert_eq!(house.house_result(&open, payout), gross - payout as i128 - stake);
+    }
+
+    #[test]
+    fn settled_bets_cannot_be_settled_again() {
+        let mut house = house();
+        let mut open = house.place_bet(10 * DUMBS, 1, 20_000, TokenType::DUMBS, true).unwrap();
+        house.settle_bet(&mut open, true).unwrap();
+        assert!(house.settle_bet(&mut open, true).is_err());
+    }
+
+    #[test]
+    fn values_convert_through_the_sol_rates() {
+        let house = house();
+        assert_eq!(house.dumbs_value(10_000, TokenType::RAPR), 1);
+        assert_eq!(house.tokens_for_dumbs(1, TokenType::RAPR), 10_000);
+        assert_eq!(house.dumbs_value(7, TokenType::DUMBS), 7);
+    }
+}
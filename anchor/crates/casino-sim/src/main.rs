@@ -0,0 +1,180 @@
+//! Monte Carlo simulator for house economics: runs synthetic bettors through
+//! the betting program's own pricing and settlement code and reports P&L,
+//! variance, risk of ruin and peak exposure.
+
+mod house;
+mod report;
+mod sim;
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
+
+use anyhow::{anyhow, bail, Context, Result};
+use betting_program::state::BettingState;
+use casino_math::Lamports;
+use clap::{Parser, ValueEnum};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use crate::report::Summary;
+use crate::sim::Scenario;
+
+#[derive(Parser)]
+#[command(name = "casino-sim", about = "Simulate house P&L for a betting program configuration")]
+struct Cli {
+    /// Independent histories to simulate
+    #[arg(long, default_value_t = 1000)]
+    runs: u32,
+    /// Fights per history
+    #[arg(long, default_value_t = 100)]
+    fights: u32,
+    /// Bettors in each history's population
+    #[arg(long, default_value_t = 200)]
+    bettors: u32,
+    /// Chance a bettor bets on any given fight
+    #[arg(long, default_value_t = 0.5)]
+    participation: f64,
+    /// Smallest typical stake, in DUMBS
+    #[arg(long, default_value = "1")]
+    min_stake: String,
+    /// Largest typical stake, in DUMBS
+    #[arg(long, default_value = "50")]
+    max_stake: String,
+    /// Share of bettors staking RAPR
+    #[arg(long, default_value_t = 0.1)]
+    rapr_share: f64,
+    /// Share of bettors who only take bets with a positive expected return
+    #[arg(long, default_value_t = 0.0)]
+    sharp_share: f64,
+    /// Lowest chance of fighter one winning
+    #[arg(long, default_value_t = 0.2)]
+    min_win_probability: f64,
+    /// Highest chance of fighter one winning
+    #[arg(long, default_value_t = 0.8)]
+    max_win_probability: f64,
+    /// Overround built into quoted odds, in basis points
+    #[arg(long, default_value_t = 500)]
+    margin_bps: u32,
+    /// House bankroll in DUMBS; losing more than this counts as ruin
+    #[arg(long, default_value = "100000")]
+    bankroll: String,
+
+    /// House fee in basis points
+    #[arg(long, default_value_t = 250)]
+    house_fee: u32,
+    /// RAPR odds multiplier in basis points
+    #[arg(long, default_value_t = 100_000)]
+    rapr_multiplier: u64,
+    /// DUMBS per SOL
+    #[arg(long, default_value_t = 1000)]
+    sol_dumbs_rate: u64,
+    /// RAPR per SOL
+    #[arg(long, default_value_t = 10_000_000)]
+    sol_rapr_rate: u64,
+    /// Largest bet the program accepts, in tokens
+    #[arg(long, default_value = "100")]
+    max_bet: String,
+
+    /// Seed for reproducible runs
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+    #[arg(long, value_enum, default_value_t = Format::Table)]
+    format: Format,
+    /// Write the report here instead of stdout
+    #[arg(long)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    /// Summary for a terminal
+    Table,
+    /// One row per run
+    Csv,
+    /// Scenario, config, summary and every run
+    Json,
+}
+
+fn parse_amount(input: &str) -> Result<u64> {
+    input
+        .parse::<Lamports>()
+        .map(Lamports::base_units)
+        .map_err(|err| anyhow!("invalid amount {:?}: {:?}", input, err))
+}
+
+fn probability(label: &str, value: f64) -> Result<f64> {
+    if !(0.0..=1.0).contains(&value) {
+        bail!("{} must be between 0 and 1, got {}", label, value);
+    }
+    Ok(value)
+}
+
+impl Cli {
+    fn scenario(&self) -> Result<Scenario> {
+        let scenario = Scenario {
+            fights: self.fights,
+            bettors: self.bettors,
+            participation: probability("participation", self.participation)?,
+            min_stake: parse_amount(&self.min_stake)?,
+            max_stake: parse_amount(&self.max_stake)?,
+            rapr_share: probability("rapr-share", self.rapr_share)?,
+            sharp_share: probability("sharp-share", self.sharp_share)?,
+            min_win_probability: probability("min-win-probability", self.min_win_probability)?,
+            max_win_probability: probability("max-win-probability", self.max_win_probability)?,
+            margin_bps: self.margin_bps,
+            bankroll: parse_amount(&self.bankroll)?,
+        };
+        if scenario.min_stake > scenario.max_stake {
+            bail!("min-stake exceeds max-stake");
+        }
+        // Both sides need a chance of winning for their odds to exist
+        if scenario.min_win_probability <= 0.0
+            || scenario.max_win_probability >= 1.0
+            || scenario.min_win_probability > scenario.max_win_probability
+        {
+            bail!("win probabilities must satisfy 0 < min <= max < 1");
+        }
+        Ok(scenario)
+    }
+
+    fn config(&self) -> Result<BettingState> {
+        Ok(BettingState {
+            house_fee: self.house_fee,
+            rapr_multiplier: self.rapr_multiplier,
+            sol_dumbs_rate: self.sol_dumbs_rate,
+            sol_rapr_rate: self.sol_rapr_rate,
+            max_bet: parse_amount(&self.max_bet)?,
+            ..BettingState::default()
+        })
+    }
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let scenario = cli.scenario()?;
+    let config = cli.config()?;
+    if config.sol_dumbs_rate == 0 || config.sol_rapr_rate == 0 {
+        bail!("rates must be non-zero");
+    }
+
+    let mut rng = StdRng::seed_from_u64(cli.seed);
+    let results: Vec<_> = (0..cli.runs).map(|run| sim::run(run, &scenario, &config, &mut rng)).collect();
+    let summary = Summary::new(&results);
+
+    let out: Box<dyn Write> = match &cli.output {
+        Some(path) => Box::new(File::create(path).with_context(|| format!("failed to create {}", path.display()))?),
+        None => Box::new(io::stdout().lock()),
+    };
+    let mut out = BufWriter::new(out);
+    match cli.format {
+        Format::Table => report::write_table(&mut out, &scenario, &config, &summary)?,
+        Format::Csv => report::write_csv(&mut out, &results)?,
+        Format::Json => {
+            report::write_json(&mut out, &scenario, &config, &summary, &results)?;
+            writeln!(out)?;
+        }
+    }
+    out.flush()?;
+    Ok(())
+}
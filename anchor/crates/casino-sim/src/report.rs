@@ -0,0 +1,217 @@
+use std::io::Write;
+
+use anyhow::Result;
+use betting_program::state::BettingState;
+use casino_math::{BasisPoints, Lamports};
+use serde::Serialize;
+
+use crate::sim::{RunResult, Scenario};
+
+/// House settings the runs used.
+#[derive(Debug, Serialize)]
+pub struct Config {
+    pub house_fee: u32,
+    pub rapr_multiplier: u64,
+    pub sol_dumbs_rate: u64,
+    pub sol_rapr_rate: u64,
+    pub max_bet: u64,
+}
+
+impl From<&BettingState> for Config {
+    fn from(state: &BettingState) -> Self {
+        Self {
+            house_fee: state.house_fee,
+            rapr_multiplier: state.rapr_multiplier,
+            sol_dumbs_rate: state.sol_dumbs_rate,
+            sol_rapr_rate: state.sol_rapr_rate,
+            max_bet: state.max_bet,
+        }
+    }
+}
+
+/// Statistics across every run. Amounts are in DUMBS base units.
+#[derive(Debug, Serialize)]
+pub struct Summary {
+    pub runs: u32,
+    pub mean_pnl: f64,
+    pub pnl_std_dev: f64,
+    pub pnl_p5: i64,
+    pub pnl_p50: i64,
+    pub pnl_p95: i64,
+    /// House P&L over the total amount wagered.
+    pub realised_edge: f64,
+    pub fight_pnl_std_dev: f64,
+    pub risk_of_ruin: f64,
+    pub mean_peak_exposure: f64,
+    pub max_peak_exposure: u64,
+    pub mean_max_drawdown: f64,
+    pub rejected_bets: u64,
+}
+
+fn mean(values: impl Iterator<Item = f64> + Clone) -> f64 {
+    let count = values.clone().count();
+    if count == 0 {
+        return 0.0;
+    }
+    values.sum::<f64>() / count as f64
+}
+
+fn std_dev(values: impl Iterator<Item = f64> + Clone) -> f64 {
+    let count = values.clone().count();
+    if count < 2 {
+        return 0.0;
+    }
+    let mean = mean(values.clone());
+    let squares: f64 = values.map(|value| (value - mean).powi(2)).sum();
+    (squares / (count - 1) as f64).sqrt()
+}
+
+/// Nearest-rank percentile of sorted values.
+fn percentile(sorted: &[i64], percent: usize) -> i64 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let rank = (percent * sorted.len()).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+impl Summary {
+    pub fn new(results: &[RunResult]) -> Self {
+        let pnls = results.iter().map(|result| result.pnl as f64);
+        let fight_pnls = results.iter().flat_map(|result| &result.fight_pnls).map(|&pnl| pnl as f64);
+        let mut sorted: Vec<i64> = results.iter().map(|result| result.pnl).collect();
+        sorted.sort_unstable();
+
+        let wagered: f64 = results.iter().map(|result| result.wagered as f64).sum();
+        let total_pnl: f64 = pnls.clone().sum();
+        let ruined = results.iter().filter(|result| result.ruined).count();
+
+        Self {
+            runs: results.len() as u32,
+            mean_pnl: mean(pnls.clone()),
+            pnl_std_dev: std_dev(pnls),
+            pnl_p5: percentile(&sorted, 5),
+            pnl_p50: percentile(&sorted, 50),
+            pnl_p95: percentile(&sorted, 95),
+            realised_edge: if wagered > 0.0 { total_pnl / wagered } else { 0.0 },
+            fight_pnl_std_dev: std_dev(fight_pnls),
+            risk_of_ruin: if results.is_empty() { 0.0 } else { ruined as f64 / results.len() as f64 },
+            mean_peak_exposure: mean(results.iter().map(|result| result.peak_exposure as f64)),
+            max_peak_exposure: results.iter().map(|result| result.peak_exposure).max().unwrap_or(0),
+            mean_max_drawdown: mean(results.iter().map(|result| result.max_drawdown as f64)),
+            rejected_bets: results.iter().map(|result| result.rejected).sum(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct JsonReport<'a> {
+    scenario: &'a Scenario,
+    config: Config,
+    summary: &'a Summary,
+    runs: &'a [RunResult],
+}
+
+pub fn write_json(out: impl Write, scenario: &Scenario, state: &BettingState, summary: &Summary, results: &[RunResult]) -> Result<()> {
+    let report = JsonReport { scenario, config: state.into(), summary, runs: results };
+    serde_json::to_writer_pretty(out, &report)?;
+    Ok(())
+}
+
+/// One row per run.
+pub fn write_csv(out: impl Write, results: &[RunResult]) -> Result<()> {
+    let mut writer = csv::Writer::from_writer(out);
+    for result in results {
+        writer.serialize(result)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+fn signed_amount(amount: f64) -> String {
+    let sign = if amount < 0.0 { "-" } else { "" };
+    format!("{}{}", sign, Lamports::new(amount.abs().round() as u64))
+}
+
+fn row(out: &mut impl Write, label: &str, value: impl std::fmt::Display) -> Result<()> {
+    writeln!(out, "  {:<28} {}", label, value)?;
+    Ok(())
+}
+
+pub fn write_table(mut out: impl Write, scenario: &Scenario, state: &BettingState, summary: &Summary) -> Result<()> {
+    let out = &mut out;
+    writeln!(out, "Config")?;
+    row(out, "house fee", BasisPoints::new(state.house_fee))?;
+    row(out, "RAPR odds multiplier", BasisPoints::from_u64(state.rapr_multiplier).map_or_else(|_| state.rapr_multiplier.to_string(), |bps| bps.to_string()))?;
+    row(out, "DUMBS per SOL", state.sol_dumbs_rate)?;
+    row(out, "RAPR per SOL", state.sol_rapr_rate)?;
+    row(out, "max bet", Lamports::new(state.max_bet))?;
+    row(out, "odds margin", BasisPoints::new(scenario.margin_bps))?;
+    row(out, "bankroll (DUMBS)", Lamports::new(scenario.bankroll))?;
+    writeln!(out)?;
+    writeln!(out, "Results over {} runs of {} fights", summary.runs, scenario.fights)?;
+    row(out, "mean P&L (DUMBS)", signed_amount(summary.mean_pnl))?;
+    row(out, "P&L std dev", signed_amount(summary.pnl_std_dev))?;
+    row(out, "P&L 5th percentile", signed_amount(summary.pnl_p5 as f64))?;
+    row(out, "P&L median", signed_amount(summary.pnl_p50 as f64))?;
+    row(out, "P&L 95th percentile", signed_amount(summary.pnl_p95 as f64))?;
+    row(out, "realised edge", format!("{:.3}%", summary.realised_edge * 100.0))?;
+    row(out, "per-fight P&L std dev", signed_amount(summary.fight_pnl_std_dev))?;
+    row(out, "risk of ruin", format!("{:.2}%", summary.risk_of_ruin * 100.0))?;
+    row(out, "mean peak exposure", signed_amount(summary.mean_peak_exposure))?;
+    row(out, "max peak exposure", Lamports::new(summary.max_peak_exposure))?;
+    row(out, "mean max drawdown", signed_amount(summary.mean_max_drawdown))?;
+    row(out, "rejected bets", summary.rejected_bets)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mean_and_std_dev_match_the_sample_formulas() {
+        let values = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+        assert_eq!(mean(values.iter().copied()), 5.0);
+        // Sample variance is 32 / 7
+        assert!((std_dev(values.iter().copied()) - (32.0f64 / 7.0).sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn too_few_values_have_no_spread() {
+        assert_eq!(mean(std::iter::empty()), 0.0);
+        assert_eq!(std_dev(std::iter::empty()), 0.0);
+        assert_eq!(std_dev(std::iter::once(3.0)), 0.0);
+    }
+
+    #[test]
+    fn percentiles_take_the_nearest_rank() {
+        let sorted: Vec<i64> = (1..=20).collect();
+        assert_eq!(percentile(&sorted, 5), 1);
+        assert_eq!(percentile(&sorted, 50), 10);
+        assert_eq!(percentile(&sorted, 95), 19);
+        assert_eq!(percentile(&sorted, 100), 20);
+        assert_eq!(percentile(&sorted, 0), 1);
+
+        assert_eq!(percentile(&[-5, 7], 50), -5);
+        assert_eq!(percentile(&[], 50), 0);
+    }
+
+    #[test]
+    fn summary_aggregates_runs() {
+        let results = [
+            RunResult { pnl: 100, wagered: 1_000, peak_exposure: 50, rejected: 1, ..RunResult::default() },
+            RunResult { pnl: -40, wagered: 1_000, peak_exposure: 80, ruined: true, ..RunResult::default() },
+        ];
+        let summary = Summary::new(&results);
+        assert_eq!(summary.runs, 2);
+        assert_eq!(summary.mean_pnl, 30.0);
+        assert_eq!((summary.pnl_p5, summary.pnl_p50, summary.pnl_p95), (-40, -40, 100));
+        assert_eq!(summary.realised_edge, 0.03);
+        assert_eq!(summary.risk_of_ruin, 0.5);
+        assert_eq!((summary.max_peak_exposure, summary.rejected_bets), (80, 1));
+
+        let empty = Summary::new(&[]);
+        assert_eq!((empty.runs, empty.realised_edge, empty.risk_of_ruin), (0, 0.0, 0.0));
+    }
+}
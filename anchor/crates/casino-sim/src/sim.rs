@@ -0,0 +1,162 @@
+use betting_program::state::betting_state::TokenType;
+use betting_program::state::BettingState;
+use casino_math::BasisPoints;
+use rand::Rng;
+use serde::Serialize;
+
+use crate::house::{House, OpenBet};
+
+/// Who bets, how much, and how fights turn out.
+#[derive(Clone, Debug, Serialize)]
+pub struct Scenario {
+    pub fights: u32,
+    pub bettors: u32,
+    /// Chance that a given bettor bets on a given fight.
+    pub participation: f64,
+    /// Typical stakes are drawn log-uniformly from this range, in DUMBS
+    /// base units.
+    pub min_stake: u64,
+    pub max_stake: u64,
+    /// Share of bettors who stake RAPR instead of DUMBS.
+    pub rapr_share: f64,
+    /// Share of bettors who know the true odds and only bet with an edge.
+    /// The rest pick a side at random.
+    pub sharp_share: f64,
+    /// Fighter one's chance of winning is drawn uniformly from this range.
+    pub min_win_probability: f64,
+    pub max_win_probability: f64,
+    /// Overround the odds maker builds into quoted odds, in basis points.
+    pub margin_bps: u32,
+    /// The run is ruined once losses exceed this, in DUMBS base units.
+    pub bankroll: u64,
+}
+
+struct Bettor {
+    token_type: TokenType,
+    sharp: bool,
+    /// Typical stake in DUMBS base units.
+    stake: f64,
+}
+
+/// One simulated history of `Scenario::fights` fights. Amounts are in DUMBS
+/// base units, with RAPR valued through the SOL rates.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct RunResult {
+    pub run: u32,
+    pub bets: u64,
+    /// Bets `place_bet` would have rejected.
+    pub rejected: u64,
+    pub wagered: u64,
+    pub fees: u64,
+    pub payouts: u64,
+    pub pnl: i64,
+    pub worst_fight: i64,
+    pub max_drawdown: u64,
+    /// Largest `total_potential_payout` the program carried after a fight's
+    /// bets were placed.
+    pub peak_exposure: u64,
+    pub rounding_dust: u64,
+    pub ruined: bool,
+    #[serde(skip)]
+    pub fight_pnls: Vec<i64>,
+}
+
+fn saturate(value: i128) -> i64 {
+    i64::try_from(value).unwrap_or(if value < 0 { i64::MIN } else { i64::MAX })
+}
+
+/// Quoted decimal odds in basis points for a side that wins with
+/// `probability`, after the margin and never below 1x.
+fn quote(probability: f64, margin_bps: u32) -> u64 {
+    let fair = 10_000.0 / probability;
+    let quoted = fair * 10_000.0 / (10_000.0 + margin_bps as f64);
+    (quoted.floor() as u64).max(10_000)
+}
+
+fn population(scenario: &Scenario, rng: &mut impl Rng) -> Vec<Bettor> {
+    let (low, high) = ((scenario.min_stake.max(1) as f64).ln(), (scenario.max_stake.max(1) as f64).ln());
+    (0..scenario.bettors)
+        .map(|_| Bettor {
+            token_type: if rng.gen_bool(scenario.rapr_share) { TokenType::RAPR } else { TokenType::DUMBS },
+            sharp: rng.gen_bool(scenario.sharp_share),
+            stake: if high > low { rng.gen_range(low..=high).exp() } else { low.exp() },
+        })
+        .collect()
+}
+
+/// The side a bettor backs, or `None` if they sit the fight out.
+fn pick_side(house: &House, bettor: &Bettor, probability: f64, odds: [u64; 2], rng: &mut impl Rng) -> Option<bool> {
+    if !bettor.sharp {
+        return Some(rng.gen_bool(0.5));
+    }
+    // Sharps back whichever side has the better expected return on the
+    // odds the program would actually give them, if it beats breaking even
+    let expected = |side: usize, chance: f64| {
+        house
+            .state
+            .calculate_odds(odds[side], bettor.token_type, BasisPoints::ZERO)
+            .map_or(0.0, |odds| chance * odds.bps() as f64 / 10_000.0)
+    };
+    let (one, two) = (expected(0, probability), expected(1, 1.0 - probability));
+    match one.max(two) {
+        best if best <= 1.0 => None,
+        _ => Some(one >= two),
+    }
+}
+
+pub fn run(run: u32, scenario: &Scenario, config: &BettingState, rng: &mut impl Rng) -> RunResult {
+    let mut house = House::new(config.clone());
+    let bettors = population(scenario, rng);
+    let mut result = RunResult { run, ..RunResult::default() };
+    let (mut cumulative, mut high_water) = (0i128, 0i128);
+
+    for fight_id in 0..scenario.fights as u64 {
+        let probability = rng.gen_range(scenario.min_win_probability..=scenario.max_win_probability);
+        let odds = [quote(probability, scenario.margin_bps), quote(1.0 - probability, scenario.margin_bps)];
+
+        let mut open: Vec<OpenBet> = Vec::new();
+        for bettor in &bettors {
+            if !rng.gen_bool(scenario.participation) {
+                continue;
+            }
+            let Some(backs_fighter_one) = pick_side(&house, bettor, probability, odds, rng) else {
+                continue;
+            };
+            let stake = (bettor.stake * rng.gen_range(0.5..1.5)) as u64;
+            let amount = house.tokens_for_dumbs(stake, bettor.token_type).min(house.state.max_bet);
+            let side_odds = odds[usize::from(!backs_fighter_one)];
+            match house.place_bet(amount, fight_id, side_odds, bettor.token_type, backs_fighter_one) {
+                Ok(bet) => {
+                    result.bets += 1;
+                    result.wagered = result.wagered.saturating_add(house.dumbs_value(amount, bettor.token_type));
+                    result.fees = result.fees.saturating_add(house.dumbs_value(bet.bet.fee_amount, bettor.token_type));
+                    open.push(bet);
+                }
+                Err(_) => result.rejected += 1,
+            }
+        }
+        result.peak_exposure = result.peak_exposure.max(house.state.total_potential_payout);
+
+        let fighter_one_wins = rng.gen_bool(probability);
+        let mut fight_pnl = 0i128;
+        for bet in &mut open {
+            let won = bet.backs_fighter_one == fighter_one_wins;
+            let payout = house.settle_bet(bet, won).expect("settling an accepted bet");
+            result.payouts = result.payouts.saturating_add(payout);
+            fight_pnl += house.house_result(bet, payout);
+        }
+
+        cumulative += fight_pnl;
+        high_water = high_water.max(cumulative);
+        result.fight_pnls.push(saturate(fight_pnl));
+        result.worst_fight = result.worst_fight.min(saturate(fight_pnl));
+        result.max_drawdown = result.max_drawdown.max(u64::try_from(high_water - cumulative).unwrap_or(u64::MAX));
+        if cumulative < -(scenario.bankroll as i128) {
+            result.ruined = true;
+        }
+    }
+
+    result.pnl = saturate(cumulative);
+    result.rounding_dust = house.state.rounding_dust;
+    result
+}
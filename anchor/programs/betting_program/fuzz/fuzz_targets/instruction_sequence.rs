@@ -0,0 +1,42 @@
+//! Replays arbitrary instruction sequences against the house model shared with
+//! the property tests, checking the books after every step.
+
+#![no_main]
+
+#[path = "../../tests/model/mod.rs"]
+mod model;
+
+use arbitrary::{Result, Unstructured};
+use betting_program::state::betting_state::TokenType;
+use libfuzzer_sys::fuzz_target;
+use model::{Model, Op};
+
+fn token_type(u: &mut Unstructured) -> Result<TokenType> {
+    Ok(if u.arbitrary()? { TokenType::RAPR } else { TokenType::DUMBS })
+}
+
+fn op(u: &mut Unstructured) -> Result<Op> {
+    Ok(match u.int_in_range(0..=4u8)? {
+        0 => Op::Deposit { amount: u.arbitrary()? },
+        1 => Op::Swap { sol_amount: u.arbitrary()? },
+        2 => Op::PlaceBet {
+            amount: u.arbitrary()?,
+            fight_id: u.arbitrary()?,
+            odds: u.arbitrary()?,
+            token_type: token_type(u)?,
+        },
+        3 => Op::Settle { won: u.arbitrary()? },
+        _ => Op::CashOut { amount: u.arbitrary()?, token_type: token_type(u)? },
+    })
+}
+
+fuzz_target!(|data: &[u8]| {
+    let mut u = Unstructured::new(data);
+    let mut model = Model::default();
+    // Unstructured keeps yielding zeroes once drained, so stop on empty input
+    while !u.is_empty() {
+        let Ok(op) = op(&mut u) else { break };
+        let _ = model.apply(op);
+        model.check_invariants();
+    }
+});
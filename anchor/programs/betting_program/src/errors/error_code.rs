@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use casino_math::MathError;
 
 #[error_code]
 pub enum ErrorCode {
@@ -104,4 +105,132 @@ pub enum ErrorCode {
 
     #[msg("Invalid Mint")]
     InvalidMint,
+
+    #[msg("Signer does not hold the required role")]
+    MissingRole,
+
+    #[msg("Role registry is full")]
+    RoleRegistryFull,
+
+    #[msg("Cannot revoke the last admin")]
+    CannotRevokeLastAdmin,
+
+    #[msg("Invalid config value")]
+    InvalidConfigValue,
+
+    #[msg("Timelock has not expired")]
+    TimelockNotExpired,
+
+    #[msg("Fight result already posted")]
+    FightAlreadyResolved,
+
+    #[msg("Winner is not a fighter in this fight")]
+    InvalidWinner,
+
+    #[msg("Account still has an open bet")]
+    OpenBetExists,
+
+    #[msg("Account has not been idle for the grace period")]
+    AccountNotIdle,
+
+    #[msg("Internal balance must be withdrawn first")]
+    BalanceNotWithdrawn,
+
+    #[msg("Invalid limit")]
+    InvalidLimit,
+
+    #[msg("User is self-excluded")]
+    SelfExcluded,
+
+    #[msg("Deposit limit exceeded")]
+    DepositLimitExceeded,
+
+    #[msg("Wager limit exceeded")]
+    WagerLimitExceeded,
+
+    #[msg("Loss limit exceeded")]
+    LossLimitExceeded,
+
+    #[msg("Bet cooldown has not elapsed")]
+    BetCooldownActive,
+
+    #[msg("Transfer fees must be withdrawable by the betting state")]
+    InvalidTransferFeeAuthority,
+
+    #[msg("RAPR is not minted with a transfer fee")]
+    TransferFeeNotEnabled,
+
+    #[msg("Fight result has not been posted")]
+    FightNotResolved,
+
+    #[msg("Fight result has not been reversed")]
+    FightNotReversed,
+
+    #[msg("Mint has no permanent delegate for clawbacks")]
+    ClawbackNotEnabled,
+
+    #[msg("Clawback exceeds the disputed payout")]
+    ClawbackExceedsPayout,
+
+    #[msg("Minting would exceed the DUMBS max supply")]
+    DumbsMaxSupplyExceeded,
+
+    #[msg("No queued winnings to pay")]
+    NoQueuedWinnings,
+
+    #[msg("Emission budget and house DUMBS are exhausted for this epoch")]
+    EmissionBudgetExhausted,
+
+    #[msg("Queued winnings must be paid before closing")]
+    WinningsQueued,
+
+    #[msg("RAPR lock is below the minimum")]
+    LockTooSmall,
+
+    #[msg("Mint has extensions the house cannot take bets in")]
+    UnsupportedMint,
+
+    #[msg("Betting in this mint is disabled")]
+    MintDisabled,
+
+    #[msg("Mint vault cannot cover the open bets")]
+    InsufficientVaultLiquidity,
+
+    #[msg("Price account is not a valid, trading oracle price")]
+    InvalidOracle,
+
+    #[msg("Oracle price is too old")]
+    StaleOraclePrice,
+
+    #[msg("Oracle price confidence interval is too wide")]
+    OracleConfidenceTooWide,
+
+    #[msg("Trade returns less than the caller allows")]
+    SlippageExceeded,
+
+    #[msg("Fighter is not in this fight")]
+    InvalidFighter,
+
+    #[msg("Bet did not back the posted winner")]
+    BetNotWon,
+
+    #[msg("Mint has no price until its first price change executes")]
+    MintNotPriced,
+
+    #[msg("RAPR lock cannot be released while a RAPR bet is open")]
+    LockBacksOpenBet,
+
+    #[msg("Mint has too little SOL liquidity for this cash-out")]
+    InsufficientMintLiquidity,
+}
+
+impl From<MathError> for ErrorCode {
+    fn from(err: MathError) -> Self {
+        match err {
+            MathError::Overflow | MathError::DivideByZero => ErrorCode::CalculationOverflow,
+            MathError::InvalidOdds => ErrorCode::InvalidOdds,
+            MathError::InvalidBasisPoints => ErrorCode::InvalidConfigValue,
+            MathError::InvalidAmount => ErrorCode::InvalidAmount,
+        }
+    }
 }
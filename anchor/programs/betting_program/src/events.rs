@@ -0,0 +1,338 @@
+use anchor_lang::prelude::*;
+use crate::state::{ConfigChange, LimitSettings, LockPeriod, MetadataField, Role, SupportedMintParams};
+use crate::state::betting_state::TokenType;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FeeAsset {
+    Sol,
+    Dumbs,
+    Rapr,
+}
+
+impl From<TokenType> for FeeAsset {
+    fn from(token_type: TokenType) -> Self {
+        match token_type {
+            TokenType::DUMBS => FeeAsset::Dumbs,
+            TokenType::RAPR => FeeAsset::Rapr,
+        }
+    }
+}
+
+#[event]
+pub struct DepositMade {
+    pub depositor: Pubkey,
+    pub sol_amount: u64,
+    pub fee: u64,
+    pub dumbs_minted: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BetPlaced {
+    pub bettor: Pubkey,
+    pub fight_id: u64,
+    pub token_type: TokenType,
+    pub amount: u64,            // Stake after fees
+    pub fee: u64,
+    pub odds: u64,              // Adjusted odds, 100 = 1.0x
+    pub potential_payout: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BetSettled {
+    pub bettor: Pubkey,
+    pub fight_id: u64,
+    pub token_type: TokenType,
+    pub won: bool,
+    pub payout: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WinningsMinted {
+    pub bettor: Pubkey,
+    pub fight_id: u64,
+    pub amount: u64,            // Paid now, whether minted or drawn from house DUMBS
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WinningsQueued {
+    pub bettor: Pubkey,
+    pub fight_id: u64,
+    pub amount: u64,            // Part of the win past the emission budget and house DUMBS
+    pub queued: u64,            // Bettor's queued winnings after this win
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct QueuedWinningsPaid {
+    pub bettor: Pubkey,
+    pub amount: u64,
+    pub minted: u64,
+    pub from_vault: u64,
+    pub remaining: u64,         // Bettor's winnings still queued
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CashedOut {
+    pub user: Pubkey,
+    pub token_type: TokenType,
+    pub amount: u64,
+    pub fee: u64,
+    pub sol_returned: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct Swapped {
+    pub user: Pubkey,
+    pub sol_amount: u64,
+    pub rapr_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FeeCollected {
+    pub payer: Pubkey,
+    pub asset: FeeAsset,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ConfigChangeQueued {
+    pub betting_state: Pubkey,
+    pub proposer: Pubkey,
+    pub change: ConfigChange,
+    pub eta: i64,
+}
+
+#[event]
+pub struct ConfigChangeCancelled {
+    pub betting_state: Pubkey,
+    pub change: ConfigChange,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ConfigChanged {
+    pub betting_state: Pubkey,
+    pub change: ConfigChange,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PauseChanged {
+    pub betting_state: Pubkey,
+    pub paused: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RoleGranted {
+    pub member: Pubkey,
+    pub role: Role,
+    pub granted_by: Pubkey,
+}
+
+#[event]
+pub struct RoleRevoked {
+    pub member: Pubkey,
+    pub role: Role,
+    pub revoked_by: Pubkey,
+}
+
+#[event]
+pub struct TreasuryWithdrawn {
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TransferFeesHarvested {
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub sources: u32,       // Token accounts harvested besides the RAPR vault
+    pub harvested_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FightCreated {
+    pub betting_state: Pubkey,
+    pub fight_id: u64,
+    pub fighter_one: Pubkey,
+    pub fighter_two: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FightResultPosted {
+    pub betting_state: Pubkey,
+    pub fight_id: u64,
+    pub winner: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FightResultReversed {
+    pub betting_state: Pubkey,
+    pub fight_id: u64,
+    pub overturned_winner: Pubkey,
+    pub winner: Pubkey,
+    pub reversed_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WinningsClawedBack {
+    pub betting_state: Pubkey,
+    pub fight_id: u64,
+    pub bettor: Pubkey,
+    pub token_type: TokenType,
+    pub amount: u64,
+    pub from_queue: u64,        // Part cancelled from queued winnings, never paid out
+    pub from_balance: u64,      // Part taken from the internal balance rather than the wallet
+    pub remaining: u64,         // Disputed payout in this token still clawable
+    pub clawed_back_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UserBettingAccountClosed {
+    pub owner: Pubkey,
+    pub closed_by: Pubkey,
+    pub rent_returned: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BalanceDeposited {
+    pub user: Pubkey,
+    pub token_type: TokenType,
+    pub amount: u64,
+    pub balance: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BalanceWithdrawn {
+    pub user: Pubkey,
+    pub token_type: TokenType,
+    pub amount: u64,
+    pub balance: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LimitsChanged {
+    pub user: Pubkey,
+    pub limits: LimitSettings,
+    pub pending: Option<LimitSettings>,
+    pub effective_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TokenMetadataUpdated {
+    pub mint: Pubkey,
+    pub field: MetadataField,
+    pub value: String,
+    pub updated_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SelfExcluded {
+    pub user: Pubkey,
+    pub until: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RaprLocked {
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub period: LockPeriod,
+    pub unlocks_at: i64,
+    pub boost: u32,             // Starting boost on a stake the lock covers, in basis points
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RaprUnlocked {
+    pub owner: Pubkey,
+    pub amount: u64,            // Returned to the owner
+    pub penalty: u64,           // Kept for the treasury when unlocked early
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MintRegistered {
+    pub betting_state: Pubkey,
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub params: SupportedMintParams,
+    pub registered_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SupportedMintUpdated {
+    pub mint: Pubkey,
+    pub params: SupportedMintParams,
+    pub updated_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AssetBetPlaced {
+    pub bettor: Pubkey,
+    pub mint: Pubkey,
+    pub fight_id: u64,
+    pub amount: u64,            // Stake after the fee
+    pub fee: u64,
+    pub odds: u32,
+    pub potential_payout: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AssetBetSettled {
+    pub bettor: Pubkey,
+    pub mint: Pubkey,
+    pub fight_id: u64,
+    pub won: bool,
+    pub payout: u64,            // Paid from the mint's vault in the same mint
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AssetCashedOut {
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub fee: u64,
+    pub sol_returned: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MintLiquidityFunded {
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub sol_liquidity: u64,
+    pub funded_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OracleConfigCreated {
+    pub betting_state: Pubkey,
+    pub created_by: Pubkey,
+    pub timestamp: i64,
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::error_code::ErrorCode;
+use crate::events::ConfigChangeCancelled;
+
+#[derive(Accounts)]
+pub struct CancelChange<'info> {
+    pub admin: Signer<'info>,
+
+    /// CHECK: Receives the pending change rent, verified against pending_change.proposer
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+
+    pub betting_state: Account<'info, BettingState>,
+
+    #[account(
+        seeds = [ROLES_SEED, betting_state.key().as_ref()],
+        bump = roles.bump,
+        constraint = roles.has_role(&admin.key(), Role::Admin) @ ErrorCode::MissingRole
+    )]
+    pub roles: Account<'info, RoleRegistry>,
+
+    #[account(
+        mut,
+        close = proposer,
+        has_one = betting_state @ ErrorCode::InvalidAccount,
+        has_one = proposer @ ErrorCode::InvalidAccount
+    )]
+    pub pending_change: Account<'info, PendingConfigChange>,
+}
+
+pub fn handler(ctx: Context<CancelChange>) -> Result<()> {
+    emit!(ConfigChangeCancelled {
+        betting_state: ctx.accounts.betting_state.key(),
+        change: ctx.accounts.pending_change.change,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
@@ -0,0 +1,208 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::{self, Token2022};
+use anchor_spl::token_2022::spl_token_2022::{self, onchain::invoke_transfer_checked};
+use anchor_spl::token_interface::{Mint as MintInterface, TokenAccount as TokenAccountInterface};
+use crate::state::*;
+use crate::errors::error_code::ErrorCode;
+use crate::events::WinningsClawedBack;
+
+#[derive(Accounts)]
+#[instruction(fight_id: u64, token_type: TokenType)]
+pub struct Clawback<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"betting_state", betting_state.authority.as_ref()],
+        bump = betting_state.bump,
+        constraint = betting_state.is_initialized() @ ErrorCode::NotInitialized
+    )]
+    pub betting_state: Account<'info, BettingState>,
+
+    #[account(
+        seeds = [ROLES_SEED, betting_state.key().as_ref()],
+        bump = roles.bump,
+        constraint = roles.has_role(&admin.key(), Role::Admin) @ ErrorCode::MissingRole
+    )]
+    pub roles: Box<Account<'info, RoleRegistry>>,
+
+    #[account(
+        seeds = [FIGHT_SEED, betting_state.key().as_ref(), &fight_id.to_le_bytes()],
+        bump = fight.bump,
+        has_one = betting_state,
+        constraint = fight.status == FightStatus::Reversed @ ErrorCode::FightNotReversed
+    )]
+    pub fight: Box<Account<'info, Fight>>,
+
+    /// CHECK: Bettor whose winnings are clawed back, checked through win_record
+    pub bettor: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [WIN_RECORD_SEED, betting_state.key().as_ref(), &fight_id.to_le_bytes(), bettor.key().as_ref()],
+        bump = win_record.bump,
+        constraint = win_record.winner == fight.overturned_winner @ ErrorCode::FightNotReversed
+    )]
+    pub win_record: Box<Account<'info, WinRecord>>,
+
+    /// CHECK: the bettor's betting account, which may have been closed since
+    #[account(
+        mut,
+        seeds = [USER_BETTING_ACCOUNT_SEED, bettor.key().as_ref()],
+        bump,
+    )]
+    pub user_betting_account: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = user_token_account.owner == bettor.key() @ ErrorCode::InvalidAccount,
+        constraint = user_token_account.mint == mint.key() @ ErrorCode::InvalidAccount
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccountInterface>,
+
+    #[account(
+        mut,
+        constraint = vault.key() == betting_state.vault(token_type) @ ErrorCode::InvalidAccount
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccountInterface>,
+
+    #[account(
+        mut,
+        address = betting_state.mint(token_type) @ ErrorCode::InvalidAccount
+    )]
+    pub mint: InterfaceAccount<'info, MintInterface>,
+
+    /// CHECK: Governance PDA, the mint's permanent delegate
+    #[account(
+        seeds = [GOVERNANCE_SEED, betting_state.key().as_ref()],
+        bump,
+    )]
+    pub governance: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+/// Recovers up to the disputed payout of a win settled against a reversed
+/// result. DUMBS winnings still queued are cancelled first and winnings in
+/// the internal balance taken next; the rest comes out of the bettor's wallet
+/// through the mint's permanent delegate. DUMBS is burned, RAPR returns to
+/// the RAPR vault.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, Clawback<'info>>,
+    fight_id: u64,
+    token_type: TokenType,
+    amount: u64,
+) -> Result<()> {
+    require!(amount > 0, ErrorCode::InvalidAmount);
+    require!(ctx.accounts.betting_state.clawback_enabled(token_type), ErrorCode::ClawbackNotEnabled);
+    ctx.accounts.win_record.record_clawback(amount, token_type)?;
+
+    let (from_queue, from_balance) = take_from_account(&ctx.accounts.user_betting_account, amount, token_type)?;
+    let from_wallet = amount - from_queue - from_balance;
+
+    let authority = ctx.accounts.betting_state.authority;
+    let betting_state_seeds = &[
+        b"betting_state".as_ref(),
+        authority.as_ref(),
+        &[ctx.accounts.betting_state.bump],
+    ];
+    let betting_state_key = ctx.accounts.betting_state.key();
+    let governance_seeds = &[
+        GOVERNANCE_SEED,
+        betting_state_key.as_ref(),
+        &[ctx.bumps.governance],
+    ];
+
+    match token_type {
+        TokenType::DUMBS => {
+            // Balance winnings sit in the bet vault, which the BettingState owns
+            if from_balance > 0 {
+                token_2022::burn(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        token_2022::Burn {
+                            mint: ctx.accounts.mint.to_account_info(),
+                            from: ctx.accounts.vault.to_account_info(),
+                            authority: ctx.accounts.betting_state.to_account_info(),
+                        },
+                        &[&betting_state_seeds[..]],
+                    ),
+                    from_balance,
+                )?;
+            }
+            if from_wallet > 0 {
+                token_2022::burn(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        token_2022::Burn {
+                            mint: ctx.accounts.mint.to_account_info(),
+                            from: ctx.accounts.user_token_account.to_account_info(),
+                            authority: ctx.accounts.governance.to_account_info(),
+                        },
+                        &[&governance_seeds[..]],
+                    ),
+                    from_wallet,
+                )?;
+            }
+            // Queued winnings were never minted, so only the queue shrinks
+            ctx.accounts.betting_state.cancel_queued(from_queue)?;
+            ctx.accounts.betting_state.total_dumbs_in_circulation = ctx.accounts.betting_state
+                .total_dumbs_in_circulation
+                .checked_sub(from_balance + from_wallet)
+                .ok_or(ErrorCode::CalculationOverflow)?;
+        }
+        TokenType::RAPR => {
+            // Balance RAPR is already in the vault, so only the ledger changes
+            if from_wallet > 0 {
+                invoke_transfer_checked(
+                    &spl_token_2022::ID,
+                    ctx.accounts.user_token_account.to_account_info(),
+                    ctx.accounts.mint.to_account_info(),
+                    ctx.accounts.vault.to_account_info(),
+                    ctx.accounts.governance.to_account_info(),
+                    ctx.remaining_accounts,
+                    from_wallet,
+                    ctx.accounts.mint.decimals,
+                    &[&governance_seeds[..]],
+                )?;
+            }
+            ctx.accounts.betting_state.total_rapr_in_circulation = ctx.accounts.betting_state
+                .total_rapr_in_circulation
+                .checked_sub(amount)
+                .ok_or(ErrorCode::CalculationOverflow)?;
+        }
+    }
+
+    emit!(WinningsClawedBack {
+        betting_state: betting_state_key,
+        fight_id,
+        bettor: ctx.accounts.bettor.key(),
+        token_type,
+        amount,
+        from_queue,
+        from_balance,
+        remaining: ctx.accounts.win_record.clawable(token_type),
+        clawed_back_by: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
+
+/// Takes up to `amount` from the bettor's queued DUMBS winnings and then
+/// their internal balance, if their betting account still exists, returning
+/// what came from each.
+fn take_from_account(account: &AccountInfo, amount: u64, token_type: TokenType) -> Result<(u64, u64)> {
+    if account.owner != &crate::ID {
+        return Ok((0, 0));
+    }
+    let mut user_account = UserBettingAccount::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+    let from_queue = match token_type {
+        TokenType::DUMBS => amount.min(user_account.queued_winnings),
+        TokenType::RAPR => 0,
+    };
+    user_account.unqueue_winnings(from_queue)?;
+    let from_balance = (amount - from_queue).min(user_account.balance(token_type));
+    user_account.debit_balance(from_balance, token_type)?;
+    user_account.try_serialize(&mut &mut account.try_borrow_mut_data()?[..])?;
+    Ok((from_queue, from_balance))
+}
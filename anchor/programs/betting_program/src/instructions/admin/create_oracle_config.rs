@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::error_code::ErrorCode;
+use crate::events::OracleConfigCreated;
+
+#[derive(Accounts)]
+pub struct CreateOracleConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        constraint = betting_state.is_initialized() @ ErrorCode::NotInitialized
+    )]
+    pub betting_state: Account<'info, BettingState>,
+
+    #[account(
+        seeds = [ROLES_SEED, betting_state.key().as_ref()],
+        bump = roles.bump,
+        constraint = roles.has_role(&admin.key(), Role::Admin) @ ErrorCode::MissingRole
+    )]
+    pub roles: Account<'info, RoleRegistry>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = OracleConfig::LEN,
+        seeds = [ORACLE_CONFIG_SEED, betting_state.key().as_ref()],
+        bump
+    )]
+    pub oracle_config: Account<'info, OracleConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates the house's oracle config with no feeds and oracle rates off.
+/// Its settings are then set by a timelocked `ConfigChange::Oracle`.
+pub fn handler(ctx: Context<CreateOracleConfig>) -> Result<()> {
+    let oracle_config = &mut ctx.accounts.oracle_config;
+    oracle_config.betting_state = ctx.accounts.betting_state.key();
+    oracle_config.bump = ctx.bumps.oracle_config;
+
+    emit!(OracleConfigCreated {
+        betting_state: oracle_config.betting_state,
+        created_by: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
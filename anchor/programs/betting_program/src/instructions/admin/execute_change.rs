@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::error_code::ErrorCode;
+use crate::events::ConfigChanged;
+
+#[derive(Accounts)]
+pub struct ExecuteChange<'info> {
+    /// Anyone may execute a change once its timelock has expired
+    pub executor: Signer<'info>,
+
+    /// CHECK: Receives the pending change rent, verified against pending_change.proposer
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = betting_state.is_initialized() @ ErrorCode::NotInitialized
+    )]
+    pub betting_state: Account<'info, BettingState>,
+
+    #[account(
+        mut,
+        close = proposer,
+        has_one = betting_state @ ErrorCode::InvalidAccount,
+        has_one = proposer @ ErrorCode::InvalidAccount
+    )]
+    pub pending_change: Account<'info, PendingConfigChange>,
+}
+
+/// Applies a change whose timelock has expired. A mint price change also
+/// takes the mint's `SupportedMint` as the first remaining account, and an
+/// oracle change the house's `OracleConfig`.
+pub fn handler(ctx: Context<ExecuteChange>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let pending_change = &ctx.accounts.pending_change;
+    require!(pending_change.is_ready(now), ErrorCode::TimelockNotExpired);
+
+    match pending_change.change {
+        ConfigChange::MintPrice { mint, lamports_per_token, usd_feed } => {
+            let target = ctx.remaining_accounts.first().ok_or(ErrorCode::InvalidAccount)?;
+            require_keys_eq!(*target.owner, crate::ID, ErrorCode::InvalidAccount);
+            let mut supported_mint = SupportedMint::try_deserialize(&mut &target.try_borrow_data()?[..])?;
+            require_keys_eq!(supported_mint.betting_state, ctx.accounts.betting_state.key(), ErrorCode::InvalidAccount);
+            require_keys_eq!(supported_mint.mint, mint, ErrorCode::InvalidMint);
+            supported_mint.set_price(lamports_per_token, usd_feed);
+            supported_mint.try_serialize(&mut &mut target.try_borrow_mut_data()?[..])?;
+        }
+        ConfigChange::Oracle(params) => {
+            let target = ctx.remaining_accounts.first().ok_or(ErrorCode::InvalidAccount)?;
+            require_keys_eq!(*target.owner, crate::ID, ErrorCode::InvalidAccount);
+            let mut oracle_config = OracleConfig::try_deserialize(&mut &target.try_borrow_data()?[..])?;
+            require_keys_eq!(oracle_config.betting_state, ctx.accounts.betting_state.key(), ErrorCode::InvalidAccount);
+            oracle_config.set_params(params);
+            oracle_config.try_serialize(&mut &mut target.try_borrow_mut_data()?[..])?;
+        }
+        change => change.apply(&mut ctx.accounts.betting_state),
+    }
+
+    emit!(ConfigChanged {
+        betting_state: ctx.accounts.betting_state.key(),
+        change: pending_change.change,
+        timestamp: now,
+    });
+    Ok(())
+}
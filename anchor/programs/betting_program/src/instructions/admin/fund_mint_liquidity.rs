@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use crate::state::*;
+use crate::errors::error_code::ErrorCode;
+use crate::events::MintLiquidityFunded;
+
+#[derive(Accounts)]
+pub struct FundMintLiquidity<'info> {
+    #[account(mut)]
+    pub treasurer: Signer<'info>,
+
+    #[account(
+        seeds = [ROLES_SEED, betting_state.key().as_ref()],
+        bump = roles.bump,
+        constraint = roles.has_role(&treasurer.key(), Role::Treasurer) @ ErrorCode::MissingRole
+    )]
+    pub roles: Account<'info, RoleRegistry>,
+
+    #[account(
+        mut,
+        seeds = [SUPPORTED_MINT_SEED, betting_state.key().as_ref(), supported_mint.mint.as_ref()],
+        bump = supported_mint.bump,
+        has_one = betting_state @ ErrorCode::InvalidAccount
+    )]
+    pub supported_mint: Account<'info, SupportedMint>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_vault"],
+        bump
+    )]
+    pub sol_vault: Account<'info, SolVault>,
+
+    #[account(
+        constraint = betting_state.is_initialized() @ ErrorCode::NotInitialized
+    )]
+    pub betting_state: Account<'info, BettingState>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Moves `amount` lamports from the treasurer into the SOL vault as the
+/// registered mint's cash-out liquidity. They sit outside the vault's DUMBS
+/// `balance`, so the mint's cash-outs and DUMBS cash-outs never draw on each
+/// other's SOL.
+pub fn handler(ctx: Context<FundMintLiquidity>, amount: u64) -> Result<()> {
+    require!(amount > 0, ErrorCode::InvalidAmount);
+
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.treasurer.to_account_info(),
+                to: ctx.accounts.sol_vault.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+    let sol_liquidity = ctx.accounts.supported_mint.add_sol_liquidity(amount)?;
+
+    emit!(MintLiquidityFunded {
+        mint: ctx.accounts.supported_mint.mint,
+        amount,
+        sol_liquidity,
+        funded_by: ctx.accounts.treasurer.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::error_code::ErrorCode;
+use crate::events::RoleGranted;
+
+#[derive(Accounts)]
+#[instruction(member: Pubkey, role: Role)]
+pub struct GrantRole<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ROLES_SEED, roles.betting_state.as_ref()],
+        bump = roles.bump,
+        constraint = roles.has_role(&admin.key(), Role::Admin) @ ErrorCode::MissingRole
+    )]
+    pub roles: Account<'info, RoleRegistry>,
+}
+
+pub fn handler(ctx: Context<GrantRole>, member: Pubkey, role: Role) -> Result<()> {
+    ctx.accounts.roles.grant(member, role)?;
+
+    emit!(RoleGranted {
+        member,
+        role,
+        granted_by: ctx.accounts.admin.key(),
+    });
+    Ok(())
+}
@@ -0,0 +1,101 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_2022_extensions::transfer_fee::{
+    harvest_withheld_tokens_to_mint, withdraw_withheld_tokens_from_mint, HarvestWithheldTokensToMint,
+    WithdrawWithheldTokensFromMint,
+};
+use anchor_spl::token_interface::TokenAccount as TokenAccountInterface;
+use casino_math::Rounding;
+use crate::state::*;
+use crate::errors::error_code::ErrorCode;
+use crate::events::TransferFeesHarvested;
+
+#[derive(Accounts)]
+pub struct HarvestAndWithdrawFees<'info> {
+    pub treasurer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        has_one = treasury,
+        has_one = rapr_mint @ ErrorCode::InvalidAccount,
+        has_one = rapr_vault @ ErrorCode::InvalidAccount,
+        constraint = betting_state.is_initialized() @ ErrorCode::NotInitialized,
+        constraint = betting_state.rapr_transfer_fee @ ErrorCode::TransferFeeNotEnabled
+    )]
+    pub betting_state: Account<'info, BettingState>,
+
+    #[account(
+        seeds = [ROLES_SEED, betting_state.key().as_ref()],
+        bump = roles.bump,
+        constraint = roles.has_role(&treasurer.key(), Role::Treasurer) @ ErrorCode::MissingRole
+    )]
+    pub roles: Account<'info, RoleRegistry>,
+
+    /// CHECK: RAPR mint, checked against betting_state
+    #[account(mut)]
+    pub rapr_mint: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub rapr_vault: InterfaceAccount<'info, TokenAccountInterface>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+/// Harvests the RAPR withheld in the RAPR vault and in any RAPR token
+/// accounts passed as remaining accounts, then withdraws everything the mint
+/// holds, including fees harvested earlier by anyone, into the RAPR vault.
+/// The treasury records what the vault received.
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, HarvestAndWithdrawFees<'info>>) -> Result<()> {
+    let token_program = ctx.accounts.token_program.to_account_info();
+    let mint = ctx.accounts.rapr_mint.to_account_info();
+
+    let mut sources = vec![ctx.accounts.rapr_vault.to_account_info()];
+    sources.extend(ctx.remaining_accounts.iter().cloned());
+    harvest_withheld_tokens_to_mint(
+        CpiContext::new(
+            token_program.clone(),
+            HarvestWithheldTokensToMint { token_program_id: token_program.clone(), mint: mint.clone() },
+        ),
+        sources,
+    )?;
+
+    let authority = ctx.accounts.betting_state.authority;
+    let betting_state_seeds = &[
+        b"betting_state".as_ref(),
+        authority.as_ref(),
+        &[ctx.accounts.betting_state.bump],
+    ];
+    let before = ctx.accounts.rapr_vault.amount;
+    withdraw_withheld_tokens_from_mint(CpiContext::new_with_signer(
+        token_program.clone(),
+        WithdrawWithheldTokensFromMint {
+            token_program_id: token_program,
+            mint,
+            destination: ctx.accounts.rapr_vault.to_account_info(),
+            authority: ctx.accounts.betting_state.to_account_info(),
+        },
+        &[&betting_state_seeds[..]],
+    ))?;
+    ctx.accounts.rapr_vault.reload()?;
+
+    let amount = ctx.accounts.rapr_vault.amount
+        .checked_sub(before)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+    let fee_value = ctx.accounts.betting_state.sol_value(amount, TokenType::RAPR, Rounding::Down)?;
+    ctx.accounts.treasury.collect_harvested_fees(amount, fee_value)?;
+
+    emit!(TransferFeesHarvested {
+        mint: ctx.accounts.rapr_mint.key(),
+        amount,
+        sources: ctx.remaining_accounts.len() as u32,
+        harvested_by: ctx.accounts.treasurer.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::error_code::ErrorCode;
+
+#[derive(Accounts)]
+pub struct InitializeRoles<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"betting_state", authority.key().as_ref()],
+        bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub betting_state: Account<'info, BettingState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = RoleRegistry::LEN,
+        seeds = [ROLES_SEED, betting_state.key().as_ref()],
+        bump
+    )]
+    pub roles: Account<'info, RoleRegistry>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializeRoles>) -> Result<()> {
+    // The house authority becomes the first admin
+    ctx.accounts.roles.initialize(
+        ctx.accounts.betting_state.key(),
+        ctx.accounts.authority.key(),
+        ctx.bumps.roles,
+    );
+
+    msg!("Role registry initialized with admin: {}", ctx.accounts.authority.key());
+    Ok(())
+}
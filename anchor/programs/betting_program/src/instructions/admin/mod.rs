@@ -0,0 +1,78 @@
+pub mod initialize_roles;
+pub mod grant_role;
+pub mod revoke_role;
+pub mod set_paused;
+pub mod withdraw_treasury;
+pub mod queue_change;
+pub mod execute_change;
+pub mod cancel_change;
+pub mod update_token_metadata;
+pub mod harvest_and_withdraw_fees;
+pub mod clawback;
+pub mod register_mint;
+pub mod update_supported_mint;
+pub mod create_oracle_config;
+pub mod fund_mint_liquidity;
+
+pub use initialize_roles::InitializeRoles;
+pub use grant_role::GrantRole;
+pub use revoke_role::RevokeRole;
+pub use set_paused::SetPaused;
+pub use withdraw_treasury::WithdrawTreasury;
+pub use queue_change::QueueChange;
+pub use execute_change::ExecuteChange;
+pub use cancel_change::CancelChange;
+pub use update_token_metadata::UpdateTokenMetadata;
+pub use harvest_and_withdraw_fees::HarvestAndWithdrawFees;
+pub use clawback::Clawback;
+pub use register_mint::RegisterMint;
+pub use update_supported_mint::UpdateSupportedMint;
+pub use create_oracle_config::CreateOracleConfig;
+pub use fund_mint_liquidity::FundMintLiquidity;
+
+pub(crate) use initialize_roles::__client_accounts_initialize_roles;
+pub(crate) use grant_role::__client_accounts_grant_role;
+pub(crate) use revoke_role::__client_accounts_revoke_role;
+pub(crate) use set_paused::__client_accounts_set_paused;
+pub(crate) use withdraw_treasury::__client_accounts_withdraw_treasury;
+pub(crate) use queue_change::__client_accounts_queue_change;
+pub(crate) use execute_change::__client_accounts_execute_change;
+pub(crate) use cancel_change::__client_accounts_cancel_change;
+pub(crate) use update_token_metadata::__client_accounts_update_token_metadata;
+pub(crate) use harvest_and_withdraw_fees::__client_accounts_harvest_and_withdraw_fees;
+pub(crate) use clawback::__client_accounts_clawback;
+pub(crate) use register_mint::__client_accounts_register_mint;
+pub(crate) use update_supported_mint::__client_accounts_update_supported_mint;
+pub(crate) use create_oracle_config::__client_accounts_create_oracle_config;
+pub(crate) use fund_mint_liquidity::__client_accounts_fund_mint_liquidity;
+
+#[cfg(feature = "cpi")]
+pub(crate) use initialize_roles::__cpi_client_accounts_initialize_roles;
+#[cfg(feature = "cpi")]
+pub(crate) use grant_role::__cpi_client_accounts_grant_role;
+#[cfg(feature = "cpi")]
+pub(crate) use revoke_role::__cpi_client_accounts_revoke_role;
+#[cfg(feature = "cpi")]
+pub(crate) use set_paused::__cpi_client_accounts_set_paused;
+#[cfg(feature = "cpi")]
+pub(crate) use withdraw_treasury::__cpi_client_accounts_withdraw_treasury;
+#[cfg(feature = "cpi")]
+pub(crate) use queue_change::__cpi_client_accounts_queue_change;
+#[cfg(feature = "cpi")]
+pub(crate) use execute_change::__cpi_client_accounts_execute_change;
+#[cfg(feature = "cpi")]
+pub(crate) use cancel_change::__cpi_client_accounts_cancel_change;
+#[cfg(feature = "cpi")]
+pub(crate) use update_token_metadata::__cpi_client_accounts_update_token_metadata;
+#[cfg(feature = "cpi")]
+pub(crate) use harvest_and_withdraw_fees::__cpi_client_accounts_harvest_and_withdraw_fees;
+#[cfg(feature = "cpi")]
+pub(crate) use clawback::__cpi_client_accounts_clawback;
+#[cfg(feature = "cpi")]
+pub(crate) use register_mint::__cpi_client_accounts_register_mint;
+#[cfg(feature = "cpi")]
+pub(crate) use update_supported_mint::__cpi_client_accounts_update_supported_mint;
+#[cfg(feature = "cpi")]
+pub(crate) use create_oracle_config::__cpi_client_accounts_create_oracle_config;
+#[cfg(feature = "cpi")]
+pub(crate) use fund_mint_liquidity::__cpi_client_accounts_fund_mint_liquidity;
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::error_code::ErrorCode;
+use crate::events::ConfigChangeQueued;
+
+#[derive(Accounts)]
+#[instruction(change: ConfigChange)]
+pub struct QueueChange<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        constraint = betting_state.is_initialized() @ ErrorCode::NotInitialized
+    )]
+    pub betting_state: Account<'info, BettingState>,
+
+    #[account(
+        seeds = [ROLES_SEED, betting_state.key().as_ref()],
+        bump = roles.bump,
+        constraint = roles.has_role(&admin.key(), Role::Admin) @ ErrorCode::MissingRole
+    )]
+    pub roles: Account<'info, RoleRegistry>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = PendingConfigChange::LEN,
+        seeds = [PENDING_CHANGE_SEED, betting_state.key().as_ref(), &[change.kind()], change.target_seed()],
+        bump
+    )]
+    pub pending_change: Account<'info, PendingConfigChange>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<QueueChange>, change: ConfigChange) -> Result<()> {
+    ctx.accounts.pending_change.initialize(
+        ctx.accounts.betting_state.key(),
+        ctx.accounts.admin.key(),
+        change,
+        ctx.bumps.pending_change,
+    )?;
+
+    emit!(ConfigChangeQueued {
+        betting_state: ctx.accounts.betting_state.key(),
+        proposer: ctx.accounts.admin.key(),
+        change,
+        eta: ctx.accounts.pending_change.eta,
+    });
+    Ok(())
+}
@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::*;
+use crate::errors::error_code::ErrorCode;
+use crate::events::MintRegistered;
+
+#[derive(Accounts)]
+pub struct RegisterMint<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        constraint = betting_state.is_initialized() @ ErrorCode::NotInitialized,
+        constraint = mint.key() != betting_state.dumbs_mint @ ErrorCode::InvalidMint,
+        constraint = mint.key() != betting_state.rapr_mint @ ErrorCode::InvalidMint
+    )]
+    pub betting_state: Account<'info, BettingState>,
+
+    #[account(
+        seeds = [ROLES_SEED, betting_state.key().as_ref()],
+        bump = roles.bump,
+        constraint = roles.has_role(&admin.key(), Role::Admin) @ ErrorCode::MissingRole
+    )]
+    pub roles: Box<Account<'info, RoleRegistry>>,
+
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = SupportedMint::LEN,
+        seeds = [SUPPORTED_MINT_SEED, betting_state.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub supported_mint: Account<'info, SupportedMint>,
+
+    #[account(
+        init,
+        payer = admin,
+        seeds = [MINT_VAULT_SEED, betting_state.key().as_ref(), mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = betting_state,
+        token::token_program = token_program
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Adds `mint` to the house's registry with its own vault, so bets can be
+/// placed, settled and cashed out in it once its first price change executes.
+pub fn handler(ctx: Context<RegisterMint>, params: SupportedMintParams) -> Result<()> {
+    check_supported_mint(&ctx.accounts.mint.to_account_info())?;
+
+    let supported_mint = &mut ctx.accounts.supported_mint;
+    supported_mint.betting_state = ctx.accounts.betting_state.key();
+    supported_mint.mint = ctx.accounts.mint.key();
+    supported_mint.token_program = ctx.accounts.token_program.key();
+    supported_mint.vault = ctx.accounts.vault.key();
+    supported_mint.decimals = ctx.accounts.mint.decimals;
+    supported_mint.bump = ctx.bumps.supported_mint;
+    supported_mint.vault_bump = ctx.bumps.vault;
+    supported_mint.set_params(params)?;
+
+    emit!(MintRegistered {
+        betting_state: supported_mint.betting_state,
+        mint: supported_mint.mint,
+        vault: supported_mint.vault,
+        params,
+        registered_by: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
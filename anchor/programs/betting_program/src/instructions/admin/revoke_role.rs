@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::error_code::ErrorCode;
+use crate::events::RoleRevoked;
+
+#[derive(Accounts)]
+#[instruction(member: Pubkey, role: Role)]
+pub struct RevokeRole<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ROLES_SEED, roles.betting_state.as_ref()],
+        bump = roles.bump,
+        constraint = roles.has_role(&admin.key(), Role::Admin) @ ErrorCode::MissingRole
+    )]
+    pub roles: Account<'info, RoleRegistry>,
+}
+
+pub fn handler(ctx: Context<RevokeRole>, member: Pubkey, role: Role) -> Result<()> {
+    ctx.accounts.roles.revoke(member, role)?;
+
+    emit!(RoleRevoked {
+        member,
+        role,
+        revoked_by: ctx.accounts.admin.key(),
+    });
+    Ok(())
+}
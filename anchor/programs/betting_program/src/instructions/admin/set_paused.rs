@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::error_code::ErrorCode;
+use crate::events::PauseChanged;
+
+#[derive(Accounts)]
+#[instruction(paused: bool)]
+pub struct SetPaused<'info> {
+    pub pauser: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = betting_state.is_initialized() @ ErrorCode::NotInitialized
+    )]
+    pub betting_state: Account<'info, BettingState>,
+
+    #[account(
+        seeds = [ROLES_SEED, betting_state.key().as_ref()],
+        bump = roles.bump,
+        constraint = roles.has_role(&pauser.key(), Role::Pauser) @ ErrorCode::MissingRole
+    )]
+    pub roles: Account<'info, RoleRegistry>,
+}
+
+pub fn handler(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+    ctx.accounts.betting_state.is_paused = paused;
+
+    emit!(PauseChanged {
+        betting_state: ctx.accounts.betting_state.key(),
+        paused,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::error_code::ErrorCode;
+use crate::events::SupportedMintUpdated;
+
+#[derive(Accounts)]
+pub struct UpdateSupportedMint<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [ROLES_SEED, supported_mint.betting_state.as_ref()],
+        bump = roles.bump,
+        constraint = roles.has_role(&admin.key(), Role::Admin) @ ErrorCode::MissingRole
+    )]
+    pub roles: Account<'info, RoleRegistry>,
+
+    #[account(
+        mut,
+        seeds = [SUPPORTED_MINT_SEED, supported_mint.betting_state.as_ref(), supported_mint.mint.as_ref()],
+        bump = supported_mint.bump
+    )]
+    pub supported_mint: Account<'info, SupportedMint>,
+}
+
+/// Changes a registered mint's multiplier, bet bounds or enabled flag. Open
+/// bets keep the terms they were placed at. Its price only changes through
+/// the timelock.
+pub fn handler(ctx: Context<UpdateSupportedMint>, params: SupportedMintParams) -> Result<()> {
+    ctx.accounts.supported_mint.set_params(params)?;
+
+    emit!(SupportedMintUpdated {
+        mint: ctx.accounts.supported_mint.mint,
+        params,
+        updated_by: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{token_metadata_update_field, Mint, Token2022, TokenMetadataUpdateField};
+use crate::state::*;
+use crate::errors::error_code::ErrorCode;
+use crate::events::TokenMetadataUpdated;
+
+/// Sets one field of the DUMBS or RAPR mint's metadata. The admin pays for
+/// any extra rent the longer value needs.
+#[derive(Accounts)]
+#[instruction(token_type: TokenType)]
+pub struct UpdateTokenMetadata<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        constraint = betting_state.is_initialized() @ ErrorCode::NotInitialized
+    )]
+    pub betting_state: Account<'info, BettingState>,
+
+    #[account(
+        seeds = [ROLES_SEED, betting_state.key().as_ref()],
+        bump = roles.bump,
+        constraint = roles.has_role(&admin.key(), Role::Admin) @ ErrorCode::MissingRole
+    )]
+    pub roles: Account<'info, RoleRegistry>,
+
+    #[account(
+        mut,
+        address = betting_state.mint(token_type) @ ErrorCode::InvalidAccount,
+        mint::token_program = token_program,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<UpdateTokenMetadata>,
+    _token_type: TokenType,
+    field: MetadataField,
+    value: String,
+) -> Result<()> {
+    let mint = ctx.accounts.mint.to_account_info();
+    let mut metadata = read_token_metadata(&mint)?.ok_or(ErrorCode::InvalidAccount)?;
+    metadata.update(field.clone().into(), value.clone());
+    fund_token_metadata(
+        &ctx.accounts.admin.to_account_info(),
+        &mint,
+        &ctx.accounts.system_program.to_account_info(),
+        &metadata,
+    )?;
+
+    let authority = ctx.accounts.betting_state.authority;
+    let betting_state_seeds = &[
+        b"betting_state".as_ref(),
+        authority.as_ref(),
+        &[ctx.accounts.betting_state.bump],
+    ];
+    token_metadata_update_field(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TokenMetadataUpdateField {
+                token_program_id: ctx.accounts.token_program.to_account_info(),
+                metadata: mint,
+                update_authority: ctx.accounts.betting_state.to_account_info(),
+            },
+            &[&betting_state_seeds[..]],
+        ),
+        field.clone().into(),
+        value.clone(),
+    )?;
+
+    emit!(TokenMetadataUpdated {
+        mint: ctx.accounts.mint.key(),
+        field,
+        value,
+        updated_by: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::error_code::ErrorCode;
+use crate::events::TreasuryWithdrawn;
+
+#[derive(Accounts)]
+#[instruction(amount: u64)]
+pub struct WithdrawTreasury<'info> {
+    pub treasurer: Signer<'info>,
+
+    #[account(mut)]
+    pub recipient: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        has_one = treasury,
+        constraint = betting_state.is_initialized() @ ErrorCode::NotInitialized
+    )]
+    pub betting_state: Account<'info, BettingState>,
+
+    #[account(
+        seeds = [ROLES_SEED, betting_state.key().as_ref()],
+        bump = roles.bump,
+        constraint = roles.has_role(&treasurer.key(), Role::Treasurer) @ ErrorCode::MissingRole
+    )]
+    pub roles: Account<'info, RoleRegistry>,
+}
+
+pub fn handler(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
+    require!(amount > 0, ErrorCode::InvalidAmount);
+
+    let treasury_info = ctx.accounts.treasury.to_account_info();
+    let rent_exempt = Rent::get()?.minimum_balance(treasury_info.data_len());
+    let available = treasury_info
+        .lamports()
+        .checked_sub(rent_exempt)
+        .ok_or(ErrorCode::InsufficientFunds)?;
+    require!(amount <= available, ErrorCode::InsufficientFunds);
+
+    // The treasury is program-owned, so lamports are moved directly
+    **treasury_info.try_borrow_mut_lamports()? -= amount;
+    **ctx.accounts.recipient.to_account_info().try_borrow_mut_lamports()? += amount;
+
+    ctx.accounts.treasury.record_withdrawal(amount)?;
+
+    emit!(TreasuryWithdrawn {
+        recipient: ctx.accounts.recipient.key(),
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
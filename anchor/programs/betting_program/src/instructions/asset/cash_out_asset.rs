@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use casino_math::Rounded;
+use crate::state::*;
+use crate::errors::error_code::ErrorCode;
+use crate::events::AssetCashedOut;
+
+#[derive(Accounts)]
+#[instruction(amount: u64, min_out: u64)]
+pub struct CashOutAsset<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SUPPORTED_MINT_SEED, betting_state.key().as_ref(), mint.key().as_ref()],
+        bump = supported_mint.bump,
+        has_one = betting_state @ ErrorCode::InvalidAccount,
+        has_one = mint @ ErrorCode::InvalidMint,
+        has_one = vault @ ErrorCode::InvalidAccount,
+        has_one = token_program @ ErrorCode::InvalidProgramId
+    )]
+    pub supported_mint: Account<'info, SupportedMint>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = user,
+        token::token_program = token_program
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_vault"],
+        bump
+    )]
+    pub sol_vault: Account<'info, SolVault>,
+
+    #[account(
+        mut,
+        seeds = [b"betting_state", betting_state.authority.as_ref()],
+        bump = betting_state.bump,
+        constraint = betting_state.is_initialized() @ ErrorCode::NotInitialized,
+        constraint = !betting_state.is_paused @ ErrorCode::ProgramPaused
+    )]
+    pub betting_state: Account<'info, BettingState>,
+
+    /// CHECK: the house's oracle config PDA, which only holds data once an admin configures it
+    #[account(
+        seeds = [ORACLE_CONFIG_SEED, betting_state.key().as_ref()],
+        bump,
+    )]
+    pub oracle_config: UncheckedAccount<'info>,
+
+    /// CHECK: SOL/USD price account, only read when the mint has a USD feed and then checked against oracle_config
+    pub sol_usd_feed: UncheckedAccount<'info>,
+
+    /// CHECK: the mint's USD price account, only read when it has one and then checked against supported_mint
+    pub token_usd_feed: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Sells `amount` of a registered mint back to the house for SOL at the
+/// mint's oracle price, or its fixed value without a USD feed, less the house
+/// fee. The tokens go into the mint's vault, and the SOL comes out of the
+/// mint's own liquidity, so a mispriced mint cannot reach what backs DUMBS.
+/// Fails with `SlippageExceeded` below `min_out` lamports.
+pub fn handler(ctx: Context<CashOutAsset>, amount: u64, min_out: u64) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let lamports_per_token = ctx.accounts.supported_mint.cash_out_price(
+        &ctx.accounts.oracle_config,
+        &ctx.accounts.sol_usd_feed,
+        &ctx.accounts.token_usd_feed,
+        now,
+    )?;
+    let quote = ctx.accounts.supported_mint.quote_cash_out(&ctx.accounts.betting_state, amount, lamports_per_token)?;
+    require!(quote.sol_returned >= min_out, ErrorCode::SlippageExceeded);
+    ctx.accounts.supported_mint.spend_sol_liquidity(quote.sol_returned)?;
+    ctx.accounts.betting_state.keep_dust(Rounded { value: 0, dust: quote.dust })?;
+
+    token_interface::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.user_token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        amount,
+        ctx.accounts.mint.decimals,
+    )?;
+    move_sol_vault_lamports(&ctx.accounts.sol_vault.to_account_info(), &ctx.accounts.user.to_account_info(), quote.sol_returned)?;
+    ctx.accounts.supported_mint.collect_fee(quote.fee)?;
+
+    emit!(AssetCashedOut {
+        user: ctx.accounts.user.key(),
+        mint: ctx.accounts.mint.key(),
+        amount,
+        fee: quote.fee,
+        sol_returned: quote.sol_returned,
+        timestamp: now,
+    });
+    Ok(())
+}
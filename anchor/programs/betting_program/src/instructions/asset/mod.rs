@@ -0,0 +1,18 @@
+pub mod place_asset_bet;
+pub mod settle_asset_bet;
+pub mod cash_out_asset;
+
+pub use place_asset_bet::PlaceAssetBet;
+pub use settle_asset_bet::SettleAssetBet;
+pub use cash_out_asset::CashOutAsset;
+
+pub(crate) use place_asset_bet::__client_accounts_place_asset_bet;
+pub(crate) use settle_asset_bet::__client_accounts_settle_asset_bet;
+pub(crate) use cash_out_asset::__client_accounts_cash_out_asset;
+
+#[cfg(feature = "cpi")]
+pub(crate) use place_asset_bet::__cpi_client_accounts_place_asset_bet;
+#[cfg(feature = "cpi")]
+pub(crate) use settle_asset_bet::__cpi_client_accounts_settle_asset_bet;
+#[cfg(feature = "cpi")]
+pub(crate) use cash_out_asset::__cpi_client_accounts_cash_out_asset;
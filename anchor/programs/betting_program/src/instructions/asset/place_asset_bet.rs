@@ -0,0 +1,125 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use casino_math::{Rounded, Rounding};
+use crate::state::*;
+use crate::errors::error_code::ErrorCode;
+use crate::events::AssetBetPlaced;
+
+#[derive(Accounts)]
+#[instruction(amount: u64, fight_id: u64)]
+pub struct PlaceAssetBet<'info> {
+    #[account(mut)]
+    pub bettor: Signer<'info>,
+
+    #[account(
+        init,
+        payer = bettor,
+        space = AssetBet::LEN,
+        seeds = [ASSET_BET_SEED, supported_mint.key().as_ref(), bettor.key().as_ref()],
+        bump
+    )]
+    pub asset_bet: Account<'info, AssetBet>,
+
+    #[account(
+        mut,
+        seeds = [SUPPORTED_MINT_SEED, betting_state.key().as_ref(), mint.key().as_ref()],
+        bump = supported_mint.bump,
+        has_one = betting_state @ ErrorCode::InvalidAccount,
+        has_one = mint @ ErrorCode::InvalidMint,
+        has_one = vault @ ErrorCode::InvalidAccount,
+        has_one = token_program @ ErrorCode::InvalidProgramId
+    )]
+    pub supported_mint: Account<'info, SupportedMint>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = bettor,
+        token::token_program = token_program
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"betting_state", betting_state.authority.as_ref()],
+        bump = betting_state.bump,
+        constraint = betting_state.is_initialized() @ ErrorCode::NotInitialized,
+        constraint = !betting_state.is_paused @ ErrorCode::ProgramPaused
+    )]
+    pub betting_state: Account<'info, BettingState>,
+
+    #[account(
+        seeds = [FIGHT_SEED, betting_state.key().as_ref(), &fight_id.to_le_bytes()],
+        bump = fight.bump,
+        has_one = betting_state
+    )]
+    pub fight: Box<Account<'info, Fight>>,
+
+    /// CHECK: the bettor's limits PDA, which only holds data once they set limits
+    #[account(
+        mut,
+        seeds = [USER_LIMITS_SEED, bettor.key().as_ref()],
+        bump,
+    )]
+    pub user_limits: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Stakes `amount` of a registered mint on `fighter` winning `fight_id`. The
+/// stake and fee go into the mint's vault, which must be able to pay every
+/// open bet.
+pub fn handler(ctx: Context<PlaceAssetBet>, amount: u64, fight_id: u64, fighter: Pubkey, odds: u64) -> Result<()> {
+    ctx.accounts.fight.check_backable(fighter)?;
+    let terms = ctx.accounts.supported_mint.quote_bet(&ctx.accounts.betting_state, amount, odds)?;
+    ctx.accounts.betting_state.keep_dust(Rounded { value: 0, dust: terms.dust })?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let value = ctx.accounts.supported_mint.sol_value(amount, Rounding::Up)?;
+    with_user_limits(&ctx.accounts.user_limits, |limits| limits.record_bet(value, now))?;
+
+    token_interface::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.user_token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+                authority: ctx.accounts.bettor.to_account_info(),
+            },
+        ),
+        amount,
+        ctx.accounts.mint.decimals,
+    )?;
+    ctx.accounts.vault.reload()?;
+    ctx.accounts.supported_mint.record_bet(&terms, ctx.accounts.vault.amount)?;
+
+    let supported_mint = ctx.accounts.supported_mint.key();
+    ctx.accounts.asset_bet.initialize(
+        ctx.accounts.bettor.key(),
+        supported_mint,
+        fight_id,
+        fighter,
+        &terms,
+        now,
+        ctx.bumps.asset_bet,
+    );
+
+    emit!(AssetBetPlaced {
+        bettor: ctx.accounts.bettor.key(),
+        mint: ctx.accounts.mint.key(),
+        fight_id,
+        amount: terms.stake,
+        fee: terms.fee,
+        odds: terms.odds.bps(),
+        potential_payout: terms.potential_payout,
+        timestamp: now,
+    });
+    Ok(())
+}
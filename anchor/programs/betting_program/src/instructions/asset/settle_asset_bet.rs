@@ -0,0 +1,148 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use casino_math::Rounding;
+use crate::state::*;
+use crate::errors::error_code::ErrorCode;
+use crate::events::AssetBetSettled;
+
+#[derive(Accounts)]
+#[instruction(fight_id: u64)]
+pub struct SettleAssetBet<'info> {
+    pub authority: Signer<'info>,
+
+    /// CHECK: Verified through the asset bet's bettor; receives its rent
+    #[account(mut)]
+    pub bettor: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        close = bettor,
+        seeds = [ASSET_BET_SEED, supported_mint.key().as_ref(), bettor.key().as_ref()],
+        bump = asset_bet.bump,
+        has_one = bettor @ ErrorCode::InvalidBettor,
+        constraint = asset_bet.fight_id == fight_id @ ErrorCode::InvalidFightId
+    )]
+    pub asset_bet: Account<'info, AssetBet>,
+
+    #[account(
+        mut,
+        seeds = [SUPPORTED_MINT_SEED, betting_state.key().as_ref(), mint.key().as_ref()],
+        bump = supported_mint.bump,
+        has_one = betting_state @ ErrorCode::InvalidAccount,
+        has_one = mint @ ErrorCode::InvalidMint,
+        has_one = vault @ ErrorCode::InvalidAccount,
+        has_one = token_program @ ErrorCode::InvalidProgramId
+    )]
+    pub supported_mint: Account<'info, SupportedMint>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = bettor,
+        token::token_program = token_program
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"betting_state", betting_state.authority.as_ref()],
+        bump = betting_state.bump,
+        constraint = betting_state.is_initialized() @ ErrorCode::NotInitialized
+    )]
+    pub betting_state: Account<'info, BettingState>,
+
+    #[account(
+        seeds = [FIGHT_SEED, betting_state.key().as_ref(), &fight_id.to_le_bytes()],
+        bump = fight.bump,
+        has_one = betting_state
+    )]
+    pub fight: Box<Account<'info, Fight>>,
+
+    #[account(
+        seeds = [ROLES_SEED, betting_state.key().as_ref()],
+        bump = roles.bump,
+        constraint = roles.has_role(&authority.key(), Role::Settler) @ ErrorCode::MissingRole
+    )]
+    pub roles: Box<Account<'info, RoleRegistry>>,
+
+    /// CHECK: the bettor's limits PDA, which only holds data once they set limits
+    #[account(
+        mut,
+        seeds = [USER_LIMITS_SEED, bettor.key().as_ref()],
+        bump,
+    )]
+    pub user_limits: UncheckedAccount<'info>,
+
+    /// CHECK: the bettor's history PDA, which only holds data once they create it
+    #[account(
+        mut,
+        seeds = [BET_HISTORY_SEED, bettor.key().as_ref()],
+        bump,
+    )]
+    pub bet_history: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Settles an asset bet against the fight's posted result. A win is paid from
+/// the mint's vault in the same mint; a losing stake stays in the vault. The
+/// result goes into the bettor's history like a DUMBS or RAPR bet's.
+pub fn handler(ctx: Context<SettleAssetBet>, fight_id: u64) -> Result<()> {
+    let winner = ctx.accounts.fight.result()?;
+    let bettor = ctx.accounts.bettor.key();
+    let bet = &ctx.accounts.asset_bet;
+    let won = bet.fighter == winner;
+    let payout = if won { bet.potential_payout } else { 0 };
+    let potential_payout = bet.potential_payout;
+    let staked = bet.amount.saturating_add(bet.fee_amount);
+
+    if payout > 0 {
+        let authority = ctx.accounts.betting_state.authority;
+        let betting_state_seeds = &[
+            b"betting_state".as_ref(),
+            authority.as_ref(),
+            &[ctx.accounts.betting_state.bump],
+        ];
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.vault.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.user_token_account.to_account_info(),
+                    authority: ctx.accounts.betting_state.to_account_info(),
+                },
+                &[&betting_state_seeds[..]],
+            ),
+            payout,
+            ctx.accounts.mint.decimals,
+        )?;
+    }
+    ctx.accounts.supported_mint.record_settlement(potential_payout, payout)?;
+    let timestamp = Clock::get()?.unix_timestamp;
+    let mint = ctx.accounts.mint.key();
+    record_asset_bet_history(&ctx.accounts.bet_history, &ctx.accounts.asset_bet, mint, won, payout, timestamp)?;
+
+    // Count the result against the bettor's loss limits
+    let supported_mint = &ctx.accounts.supported_mint;
+    let staked = supported_mint.sol_value(staked, Rounding::Up)?;
+    let returned = supported_mint.sol_value(payout, Rounding::Down)?;
+    with_user_limits(&ctx.accounts.user_limits, |limits| {
+        limits.record_settlement(staked, returned, timestamp);
+        Ok(())
+    })?;
+
+    emit!(AssetBetSettled {
+        bettor,
+        mint,
+        fight_id,
+        won,
+        payout,
+        timestamp,
+    });
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::{Mint as MintInterface, TokenAccount as TokenAccountInterface};
+use crate::state::*;
+use crate::errors::error_code::ErrorCode;
+use crate::events::BalanceDeposited;
+
+#[derive(Accounts)]
+#[instruction(amount: u64, token_type: TokenType)]
+pub struct DepositToBalance<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [USER_BETTING_ACCOUNT_SEED, user.key().as_ref()],
+        bump = user_betting_account.bump,
+        constraint = user_betting_account.owner == user.key() @ ErrorCode::InvalidAccount
+    )]
+    pub user_betting_account: Account<'info, UserBettingAccount>,
+
+    #[account(
+        mut,
+        constraint = user_token_account.owner == user.key() @ ErrorCode::InvalidAccount
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccountInterface>,
+
+    #[account(
+        mut,
+        constraint = vault.key() == betting_state.vault(token_type) @ ErrorCode::InvalidAccount
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccountInterface>,
+
+    #[account(
+        mut,
+        address = betting_state.mint(token_type) @ ErrorCode::InvalidAccount
+    )]
+    pub mint: InterfaceAccount<'info, MintInterface>,
+
+    #[account(
+        seeds = [b"betting_state", betting_state.authority.as_ref()],
+        bump = betting_state.bump,
+        constraint = betting_state.is_initialized() @ ErrorCode::NotInitialized,
+        constraint = !betting_state.is_paused @ ErrorCode::ProgramPaused
+    )]
+    pub betting_state: Account<'info, BettingState>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, DepositToBalance<'info>>, amount: u64, token_type: TokenType) -> Result<()> {
+    require!(amount > 0, ErrorCode::InvalidAmount);
+
+    transfer_tokens(
+        TokenTransfer {
+            token_program: ctx.accounts.token_program.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            from: ctx.accounts.user_token_account.to_account_info(),
+            to: ctx.accounts.vault.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        },
+        &ctx.accounts.betting_state,
+        token_type,
+        amount,
+        ctx.remaining_accounts,
+    )?;
+
+    // A mint with a transfer fee delivers less than was sent
+    let withheld = withheld_fee(&ctx.accounts.mint.to_account_info(), amount)?.unwrap_or(0);
+    let credited = amount.checked_sub(withheld).ok_or(ErrorCode::CalculationOverflow)?;
+    let balance = ctx.accounts.user_betting_account.credit_balance(credited, token_type)?;
+
+    emit!(BalanceDeposited {
+        user: ctx.accounts.user.key(),
+        token_type,
+        amount: credited,
+        balance,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,13 @@
+pub mod deposit_to_balance;
+pub mod withdraw_from_balance;
+
+pub use deposit_to_balance::DepositToBalance;
+pub use withdraw_from_balance::WithdrawFromBalance;
+
+pub(crate) use deposit_to_balance::__client_accounts_deposit_to_balance;
+pub(crate) use withdraw_from_balance::__client_accounts_withdraw_from_balance;
+
+#[cfg(feature = "cpi")]
+pub(crate) use deposit_to_balance::__cpi_client_accounts_deposit_to_balance;
+#[cfg(feature = "cpi")]
+pub(crate) use withdraw_from_balance::__cpi_client_accounts_withdraw_from_balance;
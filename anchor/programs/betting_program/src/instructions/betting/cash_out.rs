@@ -4,9 +4,10 @@ use anchor_spl::token_2022::{self, Token2022};
 use anchor_spl::token_interface::{Mint as MintInterface, TokenAccount as TokenAccountInterface};
 use crate::state::*;
 use crate::errors::error_code::ErrorCode;
+use crate::events::{CashedOut, FeeCollected};
 
 #[derive(Accounts)]
-#[instruction(amount: u64, token_type: TokenType)]
+#[instruction(amount: u64, token_type: TokenType, quoted_sol: u64, max_slippage: u16)]
 pub struct CashOut<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
@@ -60,6 +61,10 @@ pub struct CashOut<'info> {
     )]
     pub dumbs_mint: InterfaceAccount<'info, MintInterface>,
 
+    /// CHECK: Token-2022 mint for RAPR tokens
+    #[account(address = betting_state.rapr_mint @ ErrorCode::InvalidAccount)]
+    pub rapr_mint: Box<InterfaceAccount<'info, MintInterface>>,
+
     #[account(
         mut,
         seeds = [b"treasury"],
@@ -69,65 +74,84 @@ pub struct CashOut<'info> {
 
     #[account(
         mut,
-        seeds = [b"betting_state"],
-        bump,
-        constraint = betting_state.is_initialized() @ ErrorCode::NotInitialized
+        seeds = [b"betting_state", betting_state.authority.as_ref()],
+        bump = betting_state.bump,
+        constraint = betting_state.is_initialized() @ ErrorCode::NotInitialized,
+        constraint = !betting_state.is_paused @ ErrorCode::ProgramPaused
     )]
     pub betting_state: Account<'info, BettingState>,
 
+    /// CHECK: the house's oracle config PDA, which only holds data once an admin configures it
+    #[account(
+        seeds = [ORACLE_CONFIG_SEED, betting_state.key().as_ref()],
+        bump,
+    )]
+    pub oracle_config: UncheckedAccount<'info>,
+
+    /// CHECK: SOL/USD price account, only read with oracle rates on and then checked against oracle_config
+    pub sol_usd_feed: UncheckedAccount<'info>,
+
+    /// CHECK: DUMBS/USD or RAPR/USD price account, only read with oracle rates on and then checked against oracle_config
+    pub token_usd_feed: UncheckedAccount<'info>,
+
     pub token_program: Program<'info, Token2022>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(mut ctx: Context<CashOut>, amount: u64, token_type: TokenType) -> Result<()> {
-    require!(amount > 0, ErrorCode::InvalidAmount);
+pub fn handler<'info>(
+    mut ctx: Context<'_, '_, '_, 'info, CashOut<'info>>,
+    amount: u64,
+    token_type: TokenType,
+    quoted_sol: u64,
+    max_slippage: u16,
+) -> Result<()> {
+    let withheld = withheld_fee(&ctx.accounts.rapr_mint.to_account_info(), amount)?;
+    let fee = ctx.accounts.betting_state.fee_for(amount, token_type, withheld)?;
+    let rate = exchange_rate(
+        &ctx.accounts.betting_state,
+        &ctx.accounts.oracle_config,
+        &ctx.accounts.sol_usd_feed,
+        &ctx.accounts.token_usd_feed,
+        token_type,
+        RateSide::Sell,
+    )?;
+    let quote = ctx.accounts.betting_state.price_cash_out_with_fee(amount, fee, rate)?;
+    check_slippage(quote.sol_returned, quoted_sol, max_slippage)?;
+    let fee = quote.fee;
 
-    let (fee, cashout_amount) = {
-        let fee = ctx.accounts.betting_state.calculate_fee(amount)?;
-        let cashout = amount.checked_sub(fee).ok_or(ErrorCode::CalculationOverflow)?;
-        (fee, cashout)
+    let sol_returned = match token_type {
+        TokenType::DUMBS => handle_dumbs_cashout(&mut ctx, amount, quote.sol_returned)?,
+        TokenType::RAPR => handle_rapr_cashout(&mut ctx, amount, quote.sol_returned)?,
     };
 
-    // Create longer-lived value for the seeds
-    let vault_bump = ctx.bumps.sol_vault;
-    let seeds = [b"sol_vault".as_ref(), &[vault_bump]];
-    let sol_vault_signer = &[&seeds[..]];
-
-    match token_type {
-        TokenType::DUMBS => handle_dumbs_cashout(
-            &mut ctx,
-            amount,
-            cashout_amount,
-            sol_vault_signer
-        )?,
-        TokenType::RAPR => handle_rapr_cashout(
-            &mut ctx,
-            amount,
-            cashout_amount,
-            sol_vault_signer
-        )?,
+    if !ctx.accounts.betting_state.uses_transfer_fee(token_type) {
+        ctx.accounts.treasury.collect_deposit_fee(fee)?;
     }
 
-    ctx.accounts.treasury.collect_deposit_fee(fee)?;
+    let timestamp = Clock::get()?.unix_timestamp;
+    emit!(CashedOut {
+        user: ctx.accounts.user.key(),
+        token_type,
+        amount,
+        fee,
+        sol_returned,
+        timestamp,
+    });
+    emit!(FeeCollected {
+        payer: ctx.accounts.user.key(),
+        asset: token_type.into(),
+        amount: fee,
+        timestamp,
+    });
     Ok(())
 }
 
 #[inline(always)]
 fn handle_dumbs_cashout<'info>(
-    ctx: &mut Context<CashOut<'info>>,
+    ctx: &mut Context<'_, '_, '_, 'info, CashOut<'info>>,
     amount: u64,
-    cashout_amount: u64,
-    sol_vault_signer: &[&[&[u8]]],
-) -> Result<()> {
-    let sol_return = cashout_amount
-        .checked_div(ctx.accounts.betting_state.sol_dumbs_rate)
-        .ok_or(ErrorCode::CalculationOverflow)?;
-
-    require!(
-        ctx.accounts.sol_vault.balance >= sol_return,
-        ErrorCode::InsufficientSolBalance
-    );
-
+    sol_return: u64,
+) -> Result<u64> {
     token_2022::burn(
         CpiContext::new(
             ctx.accounts.token_program.to_account_info(),
@@ -140,59 +164,42 @@ fn handle_dumbs_cashout<'info>(
         amount
     )?;
 
-    anchor_lang::system_program::transfer(
-        CpiContext::new_with_signer(
-            ctx.accounts.system_program.to_account_info(),
-            anchor_lang::system_program::Transfer {
-                from: ctx.accounts.sol_vault.to_account_info(),
-                to: ctx.accounts.user.to_account_info(),
-            },
-            sol_vault_signer,
-        ),
-        sol_return
-    )?;
+    pay_from_sol_vault(&mut ctx.accounts.sol_vault, &ctx.accounts.user.to_account_info(), sol_return)?;
 
-    ctx.accounts.sol_vault.balance = ctx.accounts.sol_vault.balance
-        .checked_sub(sol_return)
+    ctx.accounts.betting_state.total_dumbs_in_circulation = ctx.accounts.betting_state
+        .total_dumbs_in_circulation
+        .checked_sub(amount)
         .ok_or(ErrorCode::CalculationOverflow)?;
 
-    Ok(())
+    Ok(sol_return)
 }
 
 #[inline(always)]
 fn handle_rapr_cashout<'info>(
-    ctx: &mut Context<CashOut<'info>>,
+    ctx: &mut Context<'_, '_, '_, 'info, CashOut<'info>>,
     amount: u64,
-    cashout_amount: u64,
-    sol_vault_signer: &[&[&[u8]]],
-) -> Result<()> {
-    token_2022::transfer(
-        CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            token_2022::Transfer {
-                from: ctx.accounts.user_rapr_account.to_account_info(),
-                to: ctx.accounts.bet_vault_rapr.to_account_info(),
-                authority: ctx.accounts.user.to_account_info(),
-            },
-        ),
-        amount
+    sol_return: u64,
+) -> Result<u64> {
+    transfer_tokens(
+        TokenTransfer {
+            token_program: ctx.accounts.token_program.to_account_info(),
+            mint: ctx.accounts.rapr_mint.to_account_info(),
+            from: ctx.accounts.user_rapr_account.to_account_info(),
+            to: ctx.accounts.bet_vault_rapr.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        },
+        &ctx.accounts.betting_state,
+        TokenType::RAPR,
+        amount,
+        ctx.remaining_accounts,
     )?;
 
-    let sol_return = cashout_amount
-        .checked_mul(ctx.accounts.betting_state.sol_rapr_rate)
-        .ok_or(ErrorCode::CalculationOverflow)?;
+    pay_from_sol_vault(&mut ctx.accounts.sol_vault, &ctx.accounts.user.to_account_info(), sol_return)?;
 
-    anchor_lang::system_program::transfer(
-        CpiContext::new_with_signer(
-            ctx.accounts.system_program.to_account_info(),
-            anchor_lang::system_program::Transfer {
-                from: ctx.accounts.sol_vault.to_account_info(),
-                to: ctx.accounts.user.to_account_info(),
-            },
-            sol_vault_signer,
-        ),
-        sol_return
-    )?;
+    ctx.accounts.betting_state.total_rapr_in_circulation = ctx.accounts.betting_state
+        .total_rapr_in_circulation
+        .checked_sub(amount)
+        .ok_or(ErrorCode::CalculationOverflow)?;
 
-    Ok(())
+    Ok(sol_return)
 }
\ No newline at end of file
@@ -0,0 +1,108 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::{self, Token2022};
+use anchor_spl::token_interface::{Mint as MintInterface, TokenAccount as TokenAccountInterface};
+use crate::state::*;
+use crate::errors::error_code::ErrorCode;
+use crate::events::QueuedWinningsPaid;
+
+#[derive(Accounts)]
+pub struct ClaimQueuedWinnings<'info> {
+    pub bettor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [USER_BETTING_ACCOUNT_SEED, bettor.key().as_ref()],
+        bump,
+        constraint = user_betting_account.owner == bettor.key() @ ErrorCode::InvalidAccount
+    )]
+    pub user_betting_account: Account<'info, UserBettingAccount>,
+
+    #[account(
+        mut,
+        constraint = user_dumbs_account.owner == bettor.key() @ ErrorCode::InvalidAccount,
+        constraint = user_dumbs_account.mint == dumbs_mint.key() @ ErrorCode::InvalidAccount
+    )]
+    pub user_dumbs_account: InterfaceAccount<'info, TokenAccountInterface>,
+
+    #[account(
+        mut,
+        seeds = [b"bet_vault"],
+        bump,
+    )]
+    pub bet_vault_dumbs: InterfaceAccount<'info, TokenAccountInterface>,
+
+    #[account(
+        mut,
+        seeds = [b"dumbs_mint"],
+        bump,
+    )]
+    pub dumbs_mint: InterfaceAccount<'info, MintInterface>,
+
+    #[account(
+        mut,
+        seeds = [b"betting_state", betting_state.authority.as_ref()],
+        bump = betting_state.bump,
+        constraint = betting_state.is_initialized() @ ErrorCode::NotInitialized
+    )]
+    pub betting_state: Account<'info, BettingState>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+/// Pays the bettor's queued winnings to their wallet, as far as this epoch's
+/// emission budget and the house's DUMBS in the bet vault allow.
+pub fn handler(ctx: Context<ClaimQueuedWinnings>) -> Result<()> {
+    let queued = ctx.accounts.user_betting_account.queued_winnings;
+    require!(queued > 0, ErrorCode::NoQueuedWinnings);
+
+    let funding = ctx.accounts.betting_state.release_queued(queued, Clock::get()?.epoch)?;
+    require!(funding.paid() > 0, ErrorCode::EmissionBudgetExhausted);
+    let remaining = ctx.accounts.user_betting_account.unqueue_winnings(funding.paid())?;
+
+    let authority = ctx.accounts.betting_state.authority;
+    let betting_state_seeds = &[
+        b"betting_state".as_ref(),
+        authority.as_ref(),
+        &[ctx.accounts.betting_state.bump],
+    ];
+
+    if funding.minted > 0 {
+        token_2022::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token_2022::MintTo {
+                    mint: ctx.accounts.dumbs_mint.to_account_info(),
+                    to: ctx.accounts.user_dumbs_account.to_account_info(),
+                    authority: ctx.accounts.betting_state.to_account_info(),
+                },
+                &[&betting_state_seeds[..]],
+            ),
+            funding.minted,
+        )?;
+    }
+    if funding.from_vault > 0 {
+        transfer_tokens(
+            TokenTransfer {
+                token_program: ctx.accounts.token_program.to_account_info(),
+                mint: ctx.accounts.dumbs_mint.to_account_info(),
+                from: ctx.accounts.bet_vault_dumbs.to_account_info(),
+                to: ctx.accounts.user_dumbs_account.to_account_info(),
+                authority: ctx.accounts.betting_state.to_account_info(),
+            },
+            &ctx.accounts.betting_state,
+            TokenType::DUMBS,
+            funding.from_vault,
+            &[],
+        )?;
+    }
+
+    emit!(QueuedWinningsPaid {
+        bettor: ctx.accounts.bettor.key(),
+        amount: funding.paid(),
+        minted: funding.minted,
+        from_vault: funding.from_vault,
+        remaining,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
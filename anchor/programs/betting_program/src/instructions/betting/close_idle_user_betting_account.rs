@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::error_code::ErrorCode;
+use crate::events::UserBettingAccountClosed;
+
+/// Permissionless crank: anyone may close an account that has sat idle past
+/// `IDLE_ACCOUNT_GRACE_PERIOD`, but the rent always goes back to its owner.
+#[derive(Accounts)]
+pub struct CloseIdleUserBettingAccount<'info> {
+    pub cranker: Signer<'info>,
+
+    #[account(mut)]
+    pub owner: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [USER_BETTING_ACCOUNT_SEED, owner.key().as_ref()],
+        bump = user_betting_account.bump,
+        has_one = owner @ ErrorCode::InvalidAccount,
+    )]
+    pub user_betting_account: Account<'info, UserBettingAccount>,
+}
+
+pub fn handler(ctx: Context<CloseIdleUserBettingAccount>) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+    ctx.accounts.user_betting_account.require_idle(timestamp)?;
+
+    emit!(UserBettingAccountClosed {
+        owner: ctx.accounts.owner.key(),
+        closed_by: ctx.accounts.cranker.key(),
+        rent_returned: ctx.accounts.user_betting_account.to_account_info().lamports(),
+        timestamp,
+    });
+
+    Ok(())
+}
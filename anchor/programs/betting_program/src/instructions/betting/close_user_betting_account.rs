@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::error_code::ErrorCode;
+use crate::events::UserBettingAccountClosed;
+
+#[derive(Accounts)]
+pub struct CloseUserBettingAccount<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [USER_BETTING_ACCOUNT_SEED, owner.key().as_ref()],
+        bump = user_betting_account.bump,
+        has_one = owner @ ErrorCode::InvalidAccount,
+    )]
+    pub user_betting_account: Account<'info, UserBettingAccount>,
+}
+
+pub fn handler(ctx: Context<CloseUserBettingAccount>) -> Result<()> {
+    ctx.accounts.user_betting_account.require_closable()?;
+
+    let owner = ctx.accounts.owner.key();
+    emit!(UserBettingAccountClosed {
+        owner,
+        closed_by: owner,
+        rent_returned: ctx.accounts.user_betting_account.to_account_info().lamports(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
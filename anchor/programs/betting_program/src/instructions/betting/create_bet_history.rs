@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+
+/// Opts the payer into on-chain bet history. Settlements record into it from
+/// then on.
+#[derive(Accounts)]
+pub struct CreateBetHistory<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = BetHistory::LEN,
+        seeds = [BET_HISTORY_SEED, payer.key().as_ref()],
+        bump,
+    )]
+    pub bet_history: AccountLoader<'info, BetHistory>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CreateBetHistory>) -> Result<()> {
+    let mut history = ctx.accounts.bet_history.load_init()?;
+    history.initialize(ctx.accounts.payer.key(), ctx.bumps.bet_history);
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::error_code::ErrorCode;
+use crate::events::FightCreated;
+
+#[derive(Accounts)]
+#[instruction(fight_id: u64)]
+pub struct CreateFight<'info> {
+    #[account(mut)]
+    pub odds_maker: Signer<'info>,
+
+    #[account(
+        constraint = betting_state.is_initialized() @ ErrorCode::NotInitialized
+    )]
+    pub betting_state: Account<'info, BettingState>,
+
+    #[account(
+        seeds = [ROLES_SEED, betting_state.key().as_ref()],
+        bump = roles.bump,
+        constraint = roles.has_role(&odds_maker.key(), Role::OddsMaker) @ ErrorCode::MissingRole
+    )]
+    pub roles: Account<'info, RoleRegistry>,
+
+    #[account(
+        init,
+        payer = odds_maker,
+        space = Fight::LEN,
+        seeds = [FIGHT_SEED, betting_state.key().as_ref(), &fight_id.to_le_bytes()],
+        bump
+    )]
+    pub fight: Account<'info, Fight>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CreateFight>, fight_id: u64, fighter_one: Pubkey, fighter_two: Pubkey) -> Result<()> {
+    ctx.accounts.fight.initialize(
+        ctx.accounts.betting_state.key(),
+        fight_id,
+        fighter_one,
+        fighter_two,
+        ctx.bumps.fight,
+    )?;
+
+    emit!(FightCreated {
+        betting_state: ctx.accounts.betting_state.key(),
+        fight_id,
+        fighter_one,
+        fighter_two,
+        timestamp: ctx.accounts.fight.created_at,
+    });
+    Ok(())
+}
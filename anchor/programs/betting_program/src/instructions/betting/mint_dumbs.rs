@@ -3,9 +3,9 @@ use anchor_spl::token_2022::{self, Token2022};
 use anchor_spl::token_interface::{Mint as MintInterface, TokenAccount as TokenAccountInterface};
 
 use crate::state::*;
+use casino_math::Rounding;
 use crate::errors::error_code::ErrorCode;
-
-const DUMBS_MINT_SEED: &[u8] = b"dumbs_mint";
+use crate::events::{BetSettled, WinningsMinted, WinningsQueued};
 
 #[derive(Accounts)]
 #[instruction(secure_fight_id: u64)]
@@ -31,6 +31,13 @@ pub struct MintDumbsForWin<'info> {
     )]
     pub user_dumbs_account: InterfaceAccount<'info, TokenAccountInterface>,
 
+    #[account(
+        mut,
+        seeds = [b"bet_vault"],
+        bump,
+    )]
+    pub bet_vault_dumbs: InterfaceAccount<'info, TokenAccountInterface>,
+
     /// CHECK: Token-2022 vault for RAPR tokens
     #[account(
         mut,
@@ -49,12 +56,51 @@ pub struct MintDumbsForWin<'info> {
 
     #[account(
         mut,
-        seeds = [b"betting_state", authority.key().as_ref()],
-        bump,
+        seeds = [b"betting_state", betting_state.authority.as_ref()],
+        bump = betting_state.bump,
         constraint = betting_state.is_initialized() @ ErrorCode::NotInitialized
     )]
     pub betting_state: Account<'info, BettingState>,
 
+    #[account(
+        seeds = [ROLES_SEED, betting_state.key().as_ref()],
+        bump = roles.bump,
+        constraint = roles.has_role(&authority.key(), Role::Settler) @ ErrorCode::MissingRole
+    )]
+    pub roles: Box<Account<'info, RoleRegistry>>,
+
+    #[account(
+        seeds = [FIGHT_SEED, betting_state.key().as_ref(), &secure_fight_id.to_le_bytes()],
+        bump = fight.bump,
+        has_one = betting_state
+    )]
+    pub fight: Box<Account<'info, Fight>>,
+
+    /// CHECK: the bettor's limits PDA, which only holds data once they set limits
+    #[account(
+        mut,
+        seeds = [USER_LIMITS_SEED, bettor.key().as_ref()],
+        bump,
+    )]
+    pub user_limits: UncheckedAccount<'info>,
+
+    /// CHECK: the bettor's history PDA, which only holds data once they create it
+    #[account(
+        mut,
+        seeds = [BET_HISTORY_SEED, bettor.key().as_ref()],
+        bump,
+    )]
+    pub bet_history: UncheckedAccount<'info>,
+
+    /// CHECK: the bettor's win record for this fight, created when the house
+    /// can claw DUMBS back
+    #[account(
+        mut,
+        seeds = [WIN_RECORD_SEED, betting_state.key().as_ref(), &secure_fight_id.to_le_bytes(), bettor.key().as_ref()],
+        bump,
+    )]
+    pub win_record: UncheckedAccount<'info>,
+
     pub token2022_program: Program<'info, Token2022>,
     pub system_program: Program<'info, System>,
 }
@@ -71,50 +117,141 @@ pub fn handler(ctx: Context<MintDumbsForWin>, secure_fight_id: u64) -> Result<()
 
     // Ensure the bet matches the fight_id and bettor
     require!(
-        bet.fight_id == secure_fight_id as u32 && bet.bettor == bettor,
+        bet.fight_id as u64 == secure_fight_id && bet.bettor == bettor,
         ErrorCode::InvalidBettor
     );
 
     // Check if bet was already settled
     require!(!bet.settled, ErrorCode::BetAlreadySettled);
 
-    // Calculate payout amount for DUMBS and update stats
-    let dumbs_to_mint = ctx
+    // Only a bet on the posted winner is paid
+    let winner = ctx.accounts.fight.result()?;
+    require!(bet.fighter == winner, ErrorCode::BetNotWon);
+
+    // The stake stays in its vault; a DUMBS stake becomes the house's
+    ctx.accounts.betting_state.keep_stake(bet.amount, bet.token_type)?;
+
+    // Fund the payout within the emission budget and update stats
+    let funding = ctx
         .accounts
         .betting_state
-        .mint_dumbs_for_win(bet.potential_payout, bet.token_type)?;
-
-    // Mint DUMBS tokens to the user
-    let seeds = DUMBS_MINT_SEED;
-    let dumbs_mint_bump = ctx.bumps.dumbs_mint; // Access the bump value directly
-    let signer_seeds: &[&[&[u8]]] = &[&[seeds, &[dumbs_mint_bump]]];
-
-    let mint_ctx = CpiContext::new_with_signer(
-        ctx.accounts.token2022_program.to_account_info(),
-        token_2022::MintTo {
-            mint: ctx.accounts.dumbs_mint.to_account_info(),
-            to: ctx.accounts.user_dumbs_account.to_account_info(),
-            authority: ctx.accounts.authority.to_account_info(),
-        },
-        signer_seeds,
-    );
-    token_2022::mint_to(mint_ctx, dumbs_to_mint as u64)?;
+        .fund_win(bet.potential_payout, Clock::get()?.epoch)?;
+    let dumbs_payout = funding.paid() + funding.queued;
+
+    // Mint DUMBS tokens to the user, signed by the mint authority
+    let authority = ctx.accounts.betting_state.authority;
+    let betting_state_seeds = &[
+        b"betting_state".as_ref(),
+        authority.as_ref(),
+        &[ctx.accounts.betting_state.bump],
+    ];
+    let signer_seeds = &[&betting_state_seeds[..]];
+
+    if funding.minted > 0 {
+        let mint_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token2022_program.to_account_info(),
+            token_2022::MintTo {
+                mint: ctx.accounts.dumbs_mint.to_account_info(),
+                to: ctx.accounts.user_dumbs_account.to_account_info(),
+                authority: ctx.accounts.betting_state.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token_2022::mint_to(mint_ctx, funding.minted)?;
+    }
+
+    // Past the budget, house DUMBS in the bet vault pays the rest it can
+    if funding.from_vault > 0 {
+        transfer_tokens(
+            TokenTransfer {
+                token_program: ctx.accounts.token2022_program.to_account_info(),
+                mint: ctx.accounts.dumbs_mint.to_account_info(),
+                from: ctx.accounts.bet_vault_dumbs.to_account_info(),
+                to: ctx.accounts.user_dumbs_account.to_account_info(),
+                authority: ctx.accounts.betting_state.to_account_info(),
+            },
+            &ctx.accounts.betting_state,
+            TokenType::DUMBS,
+            funding.from_vault,
+            &[],
+        )?;
+    }
 
     // Settle the bet
     bet.settle(true)?;
     bet.won = true;
-    bet.actual_payout = dumbs_to_mint;
+    bet.actual_payout = dumbs_payout;
+    let token_type = bet.token_type;
+    let potential_payout = bet.potential_payout;
+    let staked = bet.amount.saturating_add(bet.fee_amount);
 
-    // Remove the active bet
+    ctx.accounts
+        .betting_state
+        .record_bet_settled(potential_payout, dumbs_payout)?;
+
+    // Remove the active bet, keeping it in the bettor's history
+    record_bet_history(&ctx.accounts.bet_history, bet, dumbs_payout)?;
     user_account.remove_active_bet()?;
 
-    // Update user winnings
-    user_account.update_winnings(dumbs_to_mint as u64)?;
+    // Update user winnings, queueing what could not be paid yet
+    user_account.update_winnings(dumbs_payout)?;
+    let queued = user_account.queue_winnings(funding.queued)?;
 
-    msg!(
-        "Minted {} DUMBS tokens to {}",
-        dumbs_to_mint,
-        ctx.accounts.bettor.key()
-    );
+    // Count the result against the bettor's loss limits
+    let staked = ctx.accounts.betting_state.sol_value(staked, token_type, Rounding::Up)?;
+    let returned = ctx.accounts.betting_state.sol_value(dumbs_payout, TokenType::DUMBS, Rounding::Down)?;
+
+    let timestamp = Clock::get()?.unix_timestamp;
+    with_user_limits(&ctx.accounts.user_limits, |limits| {
+        limits.record_settlement(staked, returned, timestamp);
+        Ok(())
+    })?;
+
+    // Keep what the win paid so it can be recovered if the result is reversed
+    if ctx.accounts.betting_state.dumbs_clawback {
+        let betting_state_key = ctx.accounts.betting_state.key();
+        let fight_id_bytes = secure_fight_id.to_le_bytes();
+        record_win(
+            &ctx.accounts.win_record,
+            &ctx.accounts.authority,
+            &ctx.accounts.system_program,
+            &[WIN_RECORD_SEED, betting_state_key.as_ref(), &fight_id_bytes, bettor.as_ref()],
+            WinRecord {
+                betting_state: betting_state_key,
+                bettor,
+                fight_id: secure_fight_id,
+                winner,
+                dumbs_payout,
+                settled_at: timestamp,
+                bump: ctx.bumps.win_record,
+                ..WinRecord::default()
+            },
+        )?;
+    }
+    emit!(BetSettled {
+        bettor,
+        fight_id: secure_fight_id,
+        token_type,
+        won: true,
+        payout: dumbs_payout,
+        timestamp,
+    });
+    if funding.paid() > 0 {
+        emit!(WinningsMinted {
+            bettor,
+            fight_id: secure_fight_id,
+            amount: funding.paid(),
+            timestamp,
+        });
+    }
+    if funding.queued > 0 {
+        emit!(WinningsQueued {
+            bettor,
+            fight_id: secure_fight_id,
+            amount: funding.queued,
+            queued,
+            timestamp,
+        });
+    }
     Ok(())
 }
\ No newline at end of file
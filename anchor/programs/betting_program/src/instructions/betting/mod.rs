@@ -1,11 +1,27 @@
 pub mod place_bet;
+pub mod place_bet_from_balance;
 pub mod settle_bet;
 pub mod cash_out;
 pub mod mint_dumbs;
 pub mod create_user_betting_account;
+pub mod create_bet_history;
+pub mod create_fight;
+pub mod post_fight_result;
+pub mod reverse_fight_result;
+pub mod close_user_betting_account;
+pub mod close_idle_user_betting_account;
+pub mod claim_queued_winnings;
 
 pub use place_bet::*;
+pub use place_bet_from_balance::*;
 pub use settle_bet::*;
 pub use cash_out::*;
 pub use mint_dumbs::*;
-pub use create_user_betting_account::*;
\ No newline at end of file
+pub use create_user_betting_account::*;
+pub use create_bet_history::*;
+pub use create_fight::*;
+pub use post_fight_result::*;
+pub use reverse_fight_result::*;
+pub use close_user_betting_account::*;
+pub use close_idle_user_betting_account::*;
+pub use claim_queued_winnings::*;
\ No newline at end of file
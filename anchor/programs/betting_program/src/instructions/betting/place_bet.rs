@@ -1,13 +1,15 @@
 // place_bet.rs
 use anchor_lang::prelude::*;
-use anchor_spl::token_2022::{self, Token2022};
+use anchor_spl::token_2022::Token2022;
 use anchor_spl::token_interface::{Mint as MintInterface, TokenAccount as TokenAccountInterface, TokenInterface};
 use crate::state::*;
+use casino_math::Rounding;
 use crate::errors::error_code::ErrorCode;
 use crate::state::bet::Bet;
+use crate::events::{BetPlaced, FeeCollected};
 
 #[derive(Accounts)]
-#[instruction(amount: u64, fight_id: u64, odds: u64, token_type: TokenType)]
+#[instruction(amount: u64, fight_id: u64, fighter: Pubkey, odds: u64, token_type: TokenType)]
 pub struct PlaceBet<'info> {
     #[account(mut)]
     pub bettor: Signer<'info>,
@@ -58,55 +60,92 @@ pub struct PlaceBet<'info> {
 
     #[account(
         mut,
-        seeds = [b"betting_state"],
-        bump,
-        constraint = betting_state.is_initialized() @ ErrorCode::NotInitialized
+        seeds = [b"betting_state", betting_state.authority.as_ref()],
+        bump = betting_state.bump,
+        constraint = betting_state.is_initialized() @ ErrorCode::NotInitialized,
+        constraint = !betting_state.is_paused @ ErrorCode::ProgramPaused
     )]
     pub betting_state: Account<'info, BettingState>,
 
+    #[account(
+        seeds = [FIGHT_SEED, betting_state.key().as_ref(), &fight_id.to_le_bytes()],
+        bump = fight.bump,
+        has_one = betting_state
+    )]
+    pub fight: Box<Account<'info, Fight>>,
+
     /// CHECK: Token-2022 mint for DUMBS tokens
+    #[account(
+        mut,
+        address = betting_state.dumbs_mint @ ErrorCode::InvalidAccount
+    )]
     pub dumbs_mint: InterfaceAccount<'info, MintInterface>,
 
+    /// CHECK: Token-2022 mint for RAPR tokens
+    #[account(address = betting_state.rapr_mint @ ErrorCode::InvalidAccount)]
+    pub rapr_mint: Box<InterfaceAccount<'info, MintInterface>>,
+
+    /// CHECK: the user's limits PDA, which only holds data once they set limits
+    #[account(
+        mut,
+        seeds = [USER_LIMITS_SEED, bettor.key().as_ref()],
+        bump,
+    )]
+    pub user_limits: UncheckedAccount<'info>,
+
+    /// CHECK: the bettor's RAPR lock PDA, which only holds data while they lock RAPR
+    #[account(
+        seeds = [RAPR_LOCK_SEED, bettor.key().as_ref()],
+        bump,
+    )]
+    pub rapr_lock: UncheckedAccount<'info>,
+
     pub token_program: Program<'info, Token2022>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<PlaceBet>, amount: u64, fight_id: u64, odds: u64, token_type: TokenType) -> Result<()> {
-    require!(amount > 0, ErrorCode::InvalidAmount);
-    require!(amount <= ctx.accounts.betting_state.max_bet, ErrorCode::BetTooLarge);
-
-    let (fee, bet_amount) = {
-        let fee = ctx.accounts.betting_state.calculate_fee(amount)?;
-        (fee, amount.checked_sub(fee).ok_or(ErrorCode::CalculationOverflow)?)
-    };
-
-    let (adjusted_odds, potential_payout) = {
-        let adj_odds = ctx.accounts.betting_state.calculate_odds(odds, token_type)?;
-        let payout = bet_amount
-            .checked_mul(adj_odds)
-            .and_then(|p| p.checked_div(100))
-            .ok_or(ErrorCode::CalculationOverflow)?;
-        (adj_odds, payout)
-    };
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, PlaceBet<'info>>, amount: u64, fight_id: u64, fighter: Pubkey, odds: u64, token_type: TokenType) -> Result<()> {
+    ctx.accounts.fight.check_backable(fighter)?;
+    // In transfer-fee mode the RAPR mint withholds the fee, so the stake is
+    // what reaches the vault
+    let withheld = withheld_fee(&ctx.accounts.rapr_mint.to_account_info(), amount)?;
+    let fee = ctx.accounts.betting_state.fee_for(amount, token_type, withheld)?;
+    let now = Clock::get()?.unix_timestamp;
+    let lock_boost = lock_boost(&ctx.accounts.rapr_lock, now, amount)?;
+    let terms = ctx.accounts.betting_state.price_bet_with_fee(amount, fee, odds, token_type, lock_boost)?;
+    let BetTerms { fee, stake: bet_amount, odds: adjusted_odds, potential_payout, .. } = terms;
+    let value = ctx.accounts.betting_state.sol_value(amount, token_type, Rounding::Up)?;
+    with_user_limits(&ctx.accounts.user_limits, |limits| limits.record_bet(value, now))?;
 
     // Perform token transfer based on token type
-    let transfer_accounts = match token_type {
-        TokenType::DUMBS => token_2022::Transfer {
-            from: ctx.accounts.user_dumbs_account.to_account_info(),
-            to: ctx.accounts.bet_vault_dumbs.to_account_info(),
-            authority: ctx.accounts.bettor.to_account_info(),
-        },
-        TokenType::RAPR => token_2022::Transfer {
-            from: ctx.accounts.user_rapr_account.to_account_info(),
-            to: ctx.accounts.bet_vault_rapr.to_account_info(),
-            authority: ctx.accounts.bettor.to_account_info(),
-        },
-    };
-    
-    token_2022::transfer(
-        CpiContext::new(ctx.accounts.token_program.to_account_info(), transfer_accounts),
-        amount
-    )?;
+    match token_type {
+        TokenType::DUMBS => transfer_tokens(
+            TokenTransfer {
+                token_program: ctx.accounts.token_program.to_account_info(),
+                mint: ctx.accounts.dumbs_mint.to_account_info(),
+                from: ctx.accounts.user_dumbs_account.to_account_info(),
+                to: ctx.accounts.bet_vault_dumbs.to_account_info(),
+                authority: ctx.accounts.bettor.to_account_info(),
+            },
+            &ctx.accounts.betting_state,
+            token_type,
+            amount,
+            ctx.remaining_accounts,
+        )?,
+        TokenType::RAPR => transfer_tokens(
+            TokenTransfer {
+                token_program: ctx.accounts.token_program.to_account_info(),
+                mint: ctx.accounts.rapr_mint.to_account_info(),
+                from: ctx.accounts.user_rapr_account.to_account_info(),
+                to: ctx.accounts.bet_vault_rapr.to_account_info(),
+                authority: ctx.accounts.bettor.to_account_info(),
+            },
+            &ctx.accounts.betting_state,
+            token_type,
+            amount,
+            ctx.remaining_accounts,
+        )?,
+    }
 
     // Access the bump directly from ctx.bumps
     let bump = ctx.bumps.user_betting_account;
@@ -116,22 +155,39 @@ pub fn handler(ctx: Context<PlaceBet>, amount: u64, fight_id: u64, odds: u64, to
     bet.initialize(
         ctx.accounts.bettor.key(),
         token_type,
-        bet_amount as u32,
-        fee as u32,
-        fight_id as u32,
-        adjusted_odds as u16,
-        if token_type == TokenType::RAPR { Some(ctx.accounts.betting_state.rapr_multiplier as u16) } else { None },
+        u32::try_from(fight_id).map_err(|_| ErrorCode::InvalidFightId)?,
+        fighter,
+        &terms,
         bump,
     )?;
 
     // Update accounts
     ctx.accounts.user_betting_account.add_active_bet(bet)?;
-    ctx.accounts.betting_state.total_potential_payout = ctx.accounts.betting_state
-        .total_potential_payout
-        .checked_add(potential_payout)
-        .ok_or(ErrorCode::CalculationOverflow)?;
-    ctx.accounts.treasury.collect_bet_fee(fee, token_type)?;
+    ctx.accounts.betting_state.record_bet_placed(bet_amount, token_type, potential_payout)?;
+    // Withheld fees reach the treasury ledger when they are harvested
+    if !ctx.accounts.betting_state.uses_transfer_fee(token_type) {
+        let fee_value = ctx.accounts.betting_state.sol_value(fee, token_type, Rounding::Down)?;
+        ctx.accounts.treasury.collect_bet_fee(fee, token_type, fee_value)?;
+    }
     ctx.accounts.user_betting_account.update_wagered_amount(bet_amount, token_type)?;
 
+    let timestamp = Clock::get()?.unix_timestamp;
+    emit!(BetPlaced {
+        bettor: ctx.accounts.bettor.key(),
+        fight_id,
+        token_type,
+        amount: bet_amount,
+        fee,
+        odds: adjusted_odds.bps() as u64,
+        potential_payout,
+        timestamp,
+    });
+    emit!(FeeCollected {
+        payer: ctx.accounts.bettor.key(),
+        asset: token_type.into(),
+        amount: fee,
+        timestamp,
+    });
+
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use casino_math::Rounding;
+use crate::errors::error_code::ErrorCode;
+use crate::state::bet::Bet;
+use crate::events::{BetPlaced, FeeCollected};
+
+/// Same bet as `place_bet`, staked from the bettor's internal balance. The
+/// tokens already sit in the vault, so no token accounts are needed.
+#[derive(Accounts)]
+#[instruction(amount: u64, fight_id: u64, fighter: Pubkey, odds: u64, token_type: TokenType)]
+pub struct PlaceBetFromBalance<'info> {
+    pub bettor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [USER_BETTING_ACCOUNT_SEED, bettor.key().as_ref()],
+        bump = user_betting_account.bump,
+        constraint = user_betting_account.owner == bettor.key() @ ErrorCode::InvalidAccount
+    )]
+    pub user_betting_account: Account<'info, UserBettingAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        mut,
+        seeds = [b"betting_state", betting_state.authority.as_ref()],
+        bump = betting_state.bump,
+        constraint = betting_state.is_initialized() @ ErrorCode::NotInitialized,
+        constraint = !betting_state.is_paused @ ErrorCode::ProgramPaused
+    )]
+    pub betting_state: Account<'info, BettingState>,
+
+    #[account(
+        seeds = [FIGHT_SEED, betting_state.key().as_ref(), &fight_id.to_le_bytes()],
+        bump = fight.bump,
+        has_one = betting_state
+    )]
+    pub fight: Box<Account<'info, Fight>>,
+
+    /// CHECK: the user's limits PDA, which only holds data once they set limits
+    #[account(
+        mut,
+        seeds = [USER_LIMITS_SEED, bettor.key().as_ref()],
+        bump,
+    )]
+    pub user_limits: UncheckedAccount<'info>,
+
+    /// CHECK: the bettor's RAPR lock PDA, which only holds data while they lock RAPR
+    #[account(
+        seeds = [RAPR_LOCK_SEED, bettor.key().as_ref()],
+        bump,
+    )]
+    pub rapr_lock: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<PlaceBetFromBalance>, amount: u64, fight_id: u64, fighter: Pubkey, odds: u64, token_type: TokenType) -> Result<()> {
+    ctx.accounts.fight.check_backable(fighter)?;
+    let now = Clock::get()?.unix_timestamp;
+    let lock_boost = lock_boost(&ctx.accounts.rapr_lock, now, amount)?;
+    // In transfer-fee mode the RAPR mint already withheld its fee when the
+    // balance was deposited, so nothing more is owed here
+    let fee = ctx.accounts.betting_state.fee_for(amount, token_type, None)?;
+    let terms = ctx.accounts.betting_state.price_bet_with_fee(amount, fee, odds, token_type, lock_boost)?;
+    let BetTerms { fee, stake: bet_amount, odds: adjusted_odds, potential_payout, .. } = terms;
+    let value = ctx.accounts.betting_state.sol_value(amount, token_type, Rounding::Up)?;
+    with_user_limits(&ctx.accounts.user_limits, |limits| limits.record_bet(value, now))?;
+
+    let user_account = &mut ctx.accounts.user_betting_account;
+    user_account.debit_balance(amount, token_type)?;
+
+    let mut bet = Bet::default();
+    bet.initialize(
+        ctx.accounts.bettor.key(),
+        token_type,
+        u32::try_from(fight_id).map_err(|_| ErrorCode::InvalidFightId)?,
+        fighter,
+        &terms,
+        user_account.bump,
+    )?;
+    bet.from_balance = true;
+
+    user_account.add_active_bet(bet)?;
+    user_account.update_wagered_amount(bet_amount, token_type)?;
+    ctx.accounts.betting_state.record_bet_placed(bet_amount, token_type, potential_payout)?;
+    if !ctx.accounts.betting_state.uses_transfer_fee(token_type) {
+        let fee_value = ctx.accounts.betting_state.sol_value(fee, token_type, Rounding::Down)?;
+        ctx.accounts.treasury.collect_bet_fee(fee, token_type, fee_value)?;
+    }
+
+    let timestamp = Clock::get()?.unix_timestamp;
+    emit!(BetPlaced {
+        bettor: ctx.accounts.bettor.key(),
+        fight_id,
+        token_type,
+        amount: bet_amount,
+        fee,
+        odds: adjusted_odds.bps() as u64,
+        potential_payout,
+        timestamp,
+    });
+    emit!(FeeCollected {
+        payer: ctx.accounts.bettor.key(),
+        asset: token_type.into(),
+        amount: fee,
+        timestamp,
+    });
+
+    Ok(())
+}
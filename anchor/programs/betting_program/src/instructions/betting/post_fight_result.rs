@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::error_code::ErrorCode;
+use crate::events::FightResultPosted;
+
+#[derive(Accounts)]
+pub struct PostFightResult<'info> {
+    pub settler: Signer<'info>,
+
+    #[account(
+        constraint = betting_state.is_initialized() @ ErrorCode::NotInitialized
+    )]
+    pub betting_state: Account<'info, BettingState>,
+
+    #[account(
+        seeds = [ROLES_SEED, betting_state.key().as_ref()],
+        bump = roles.bump,
+        constraint = roles.has_role(&settler.key(), Role::Settler) @ ErrorCode::MissingRole
+    )]
+    pub roles: Account<'info, RoleRegistry>,
+
+    #[account(
+        mut,
+        seeds = [FIGHT_SEED, betting_state.key().as_ref(), &fight.fight_id.to_le_bytes()],
+        bump = fight.bump,
+        has_one = betting_state
+    )]
+    pub fight: Account<'info, Fight>,
+}
+
+pub fn handler(ctx: Context<PostFightResult>, winner: Pubkey) -> Result<()> {
+    ctx.accounts.fight.resolve(winner)?;
+
+    emit!(FightResultPosted {
+        betting_state: ctx.accounts.betting_state.key(),
+        fight_id: ctx.accounts.fight.fight_id,
+        winner,
+        timestamp: ctx.accounts.fight.resolved_at,
+    });
+    Ok(())
+}
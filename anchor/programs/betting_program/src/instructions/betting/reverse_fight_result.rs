@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::error_code::ErrorCode;
+use crate::events::FightResultReversed;
+
+#[derive(Accounts)]
+pub struct ReverseFightResult<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        constraint = betting_state.is_initialized() @ ErrorCode::NotInitialized
+    )]
+    pub betting_state: Account<'info, BettingState>,
+
+    #[account(
+        seeds = [ROLES_SEED, betting_state.key().as_ref()],
+        bump = roles.bump,
+        constraint = roles.has_role(&admin.key(), Role::Admin) @ ErrorCode::MissingRole
+    )]
+    pub roles: Account<'info, RoleRegistry>,
+
+    #[account(
+        mut,
+        seeds = [FIGHT_SEED, betting_state.key().as_ref(), &fight.fight_id.to_le_bytes()],
+        bump = fight.bump,
+        has_one = betting_state
+    )]
+    pub fight: Account<'info, Fight>,
+}
+
+/// Settles a dispute by overturning a posted result. Winnings already paid
+/// against the overturned winner become clawable through `clawback`.
+pub fn handler(ctx: Context<ReverseFightResult>, winner: Pubkey) -> Result<()> {
+    ctx.accounts.fight.reverse(winner)?;
+
+    emit!(FightResultReversed {
+        betting_state: ctx.accounts.betting_state.key(),
+        fight_id: ctx.accounts.fight.fight_id,
+        overturned_winner: ctx.accounts.fight.overturned_winner,
+        winner,
+        reversed_by: ctx.accounts.admin.key(),
+        timestamp: ctx.accounts.fight.reversed_at,
+    });
+    Ok(())
+}
@@ -1,13 +1,15 @@
 // settle_bet.rs
+use crate::errors::error_code::ErrorCode;
+use crate::events::{BetSettled, WinningsMinted, WinningsQueued};
+use crate::state::bet::Bet;
+use crate::state::*;
 use anchor_lang::prelude::*;
 use anchor_spl::token_2022::{self, Token2022};
 use anchor_spl::token_interface::{Mint as MintInterface, TokenAccount as TokenAccountInterface};
-use crate::state::*;
-use crate::errors::error_code::ErrorCode;
-use crate::state::bet::Bet;
+use casino_math::Rounding;
 
 #[derive(Accounts)]
-#[instruction(fight_id: u64, winner: Pubkey)]
+#[instruction(fight_id: u64)]
 pub struct SettleBet<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
@@ -57,6 +59,10 @@ pub struct SettleBet<'info> {
     )]
     pub dumbs_mint: InterfaceAccount<'info, MintInterface>,
 
+    /// CHECK: Token-2022 mint for RAPR tokens
+    #[account(address = betting_state.rapr_mint @ ErrorCode::InvalidAccount)]
+    pub rapr_mint: Box<InterfaceAccount<'info, MintInterface>>,
+
     #[account(
         mut,
         seeds = [b"treasury"],
@@ -66,18 +72,61 @@ pub struct SettleBet<'info> {
 
     #[account(
         mut,
-        seeds = [b"betting_state"],
-        bump,
+        seeds = [b"betting_state", betting_state.authority.as_ref()],
+        bump = betting_state.bump,
         has_one = treasury,
         constraint = betting_state.is_initialized() @ ErrorCode::NotInitialized
     )]
     pub betting_state: Account<'info, BettingState>,
 
+    #[account(
+        seeds = [ROLES_SEED, betting_state.key().as_ref()],
+        bump = roles.bump,
+        constraint = roles.has_role(&authority.key(), Role::Settler) @ ErrorCode::MissingRole
+    )]
+    pub roles: Box<Account<'info, RoleRegistry>>,
+
+    #[account(
+        seeds = [FIGHT_SEED, betting_state.key().as_ref(), &fight_id.to_le_bytes()],
+        bump = fight.bump,
+        has_one = betting_state
+    )]
+    pub fight: Box<Account<'info, Fight>>,
+
+    /// CHECK: the bettor's limits PDA, which only holds data once they set limits
+    #[account(
+        mut,
+        seeds = [USER_LIMITS_SEED, bettor.key().as_ref()],
+        bump,
+    )]
+    pub user_limits: UncheckedAccount<'info>,
+
+    /// CHECK: the bettor's history PDA, which only holds data once they create it
+    #[account(
+        mut,
+        seeds = [BET_HISTORY_SEED, bettor.key().as_ref()],
+        bump,
+    )]
+    pub bet_history: UncheckedAccount<'info>,
+
+    /// CHECK: the bettor's win record for this fight, created on a win when
+    /// the house can claw winnings back
+    #[account(
+        mut,
+        seeds = [WIN_RECORD_SEED, betting_state.key().as_ref(), &fight_id.to_le_bytes(), bettor.key().as_ref()],
+        bump,
+    )]
+    pub win_record: UncheckedAccount<'info>,
+
     pub token_program: Program<'info, Token2022>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(mut ctx: Context<SettleBet>, fight_id: u64, winner: Pubkey) -> Result<()> {
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, SettleBet<'info>>,
+    fight_id: u64,
+) -> Result<()> {
+    let winner = ctx.accounts.fight.result()?;
     let bettor = ctx.accounts.bettor.key();
     let user_account = &mut ctx.accounts.user_betting_account;
 
@@ -89,99 +138,206 @@ pub fn handler(mut ctx: Context<SettleBet>, fight_id: u64, winner: Pubkey) -> Re
 
     // Ensure the bet matches the fight_id and bettor
     require!(
-        bet.fight_id == fight_id as u32 && bet.bettor == bettor,
+        bet.fight_id as u64 == fight_id && bet.bettor == bettor,
         ErrorCode::InvalidBettor
     );
 
-    // Settle the bet
-    bet.settle(bet.bettor == winner)?;
+    // Settle the bet against the posted result
+    bet.settle(bet.fighter == winner)?;
+    let settled_bet = *bet;
 
     let token_type = bet.token_type;
     let bet_amount = bet.amount;
+    let won = bet.won;
+    let potential_payout = bet.potential_payout;
+    let from_balance = bet.from_balance;
+    let fee_amount = bet.fee_amount;
 
-    let dumbs_payout = if bet.won {
-        let payout = ctx
+    // The stake stays in its vault; a DUMBS stake becomes the house's
+    ctx.accounts
+        .betting_state
+        .keep_stake(bet_amount, token_type)?;
+
+    let epoch = Clock::get()?.epoch;
+    let funding = if won {
+        let funding = ctx
             .accounts
             .betting_state
-            .mint_dumbs_for_win(bet.potential_payout, token_type)?;
+            .fund_win(potential_payout, epoch)?;
 
+        let authority = ctx.accounts.betting_state.authority;
         let betting_state_seeds = &[
             b"betting_state".as_ref(),
-            &[ctx.bumps.betting_state], // Access the bump value directly
+            authority.as_ref(),
+            &[ctx.accounts.betting_state.bump],
         ];
         let signer = &[&betting_state_seeds[..]];
 
-        // Mint DUMBS to vault
-        token_2022::mint_to(
-            CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                token_2022::MintTo {
-                    mint: ctx.accounts.dumbs_mint.to_account_info(),
-                    to: ctx.accounts.bet_vault_dumbs.to_account_info(),
-                    authority: ctx.accounts.betting_state.to_account_info(),
-                },
-                signer,
-            ),
-            payout as u64,
-        )?;
-
-        // Transfer DUMBS to user
-        token_2022::transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                token_2022::Transfer {
-                    from: ctx.accounts.bet_vault_dumbs.to_account_info(),
-                    to: ctx.accounts.user_dumbs_account.to_account_info(),
-                    authority: ctx.accounts.betting_state.to_account_info(),
-                },
-                signer,
-            ),
-            payout as u64,
-        )?;
-
-        // Handle RAPR return if applicable
-        if token_type == TokenType::RAPR {
-            token_2022::transfer(
+        // Mint what the emission budget allows to the vault, which already
+        // holds any part drawn from house DUMBS
+        if funding.minted > 0 {
+            token_2022::mint_to(
                 CpiContext::new_with_signer(
                     ctx.accounts.token_program.to_account_info(),
-                    token_2022::Transfer {
-                        from: ctx.accounts.bet_vault_rapr.to_account_info(),
-                        to: ctx.accounts.user_rapr_account.to_account_info(),
+                    token_2022::MintTo {
+                        mint: ctx.accounts.dumbs_mint.to_account_info(),
+                        to: ctx.accounts.bet_vault_dumbs.to_account_info(),
                         authority: ctx.accounts.betting_state.to_account_info(),
                     },
                     signer,
                 ),
-                bet_amount as u64,
+                funding.minted,
             )?;
+        }
 
-            ctx.accounts.betting_state.total_rapr_in_circulation = ctx
-                .accounts
-                .betting_state
-                .total_rapr_in_circulation
-                .checked_sub(bet_amount as u64)
-                .ok_or(ErrorCode::CalculationOverflow)?;
+        if from_balance {
+            // Balance bets keep their winnings in the vault, credited to the ledger
+            user_account.credit_balance(funding.paid(), TokenType::DUMBS)?;
+            if token_type == TokenType::RAPR {
+                user_account.credit_balance(bet_amount, TokenType::RAPR)?;
+            }
+        } else {
+            // Transfer DUMBS to user
+            if funding.paid() > 0 {
+                transfer_tokens(
+                    TokenTransfer {
+                        token_program: ctx.accounts.token_program.to_account_info(),
+                        mint: ctx.accounts.dumbs_mint.to_account_info(),
+                        from: ctx.accounts.bet_vault_dumbs.to_account_info(),
+                        to: ctx.accounts.user_dumbs_account.to_account_info(),
+                        authority: ctx.accounts.betting_state.to_account_info(),
+                    },
+                    &ctx.accounts.betting_state,
+                    TokenType::DUMBS,
+                    funding.paid(),
+                    ctx.remaining_accounts,
+                )?;
+            }
+
+            // Handle RAPR return if applicable
+            if token_type == TokenType::RAPR {
+                transfer_tokens(
+                    TokenTransfer {
+                        token_program: ctx.accounts.token_program.to_account_info(),
+                        mint: ctx.accounts.rapr_mint.to_account_info(),
+                        from: ctx.accounts.bet_vault_rapr.to_account_info(),
+                        to: ctx.accounts.user_rapr_account.to_account_info(),
+                        authority: ctx.accounts.betting_state.to_account_info(),
+                    },
+                    &ctx.accounts.betting_state,
+                    TokenType::RAPR,
+                    bet_amount,
+                    ctx.remaining_accounts,
+                )?;
+            }
         }
 
-        payout
+        // The rest waits for a later epoch's budget
+        user_account.queue_winnings(funding.queued)?;
+        funding
     } else {
-        0
+        WinFunding::default()
     };
+    let dumbs_payout = funding.paid() + funding.queued;
 
     // Update state
-    ctx.accounts.treasury.collect_house_edge(bet_amount as u32)?;
-    ctx.accounts.betting_state.total_potential_payout = ctx
-        .accounts
+    let stake_value =
+        ctx.accounts
+            .betting_state
+            .sol_value(bet_amount, token_type, Rounding::Down)?;
+    ctx.accounts.treasury.collect_house_edge(stake_value)?;
+    ctx.accounts
         .betting_state
-        .total_potential_payout
-        .checked_sub(bet.potential_payout as u64)
-        .ok_or(ErrorCode::CalculationOverflow)?;
+        .record_bet_settled(potential_payout, dumbs_payout)?;
 
-        if dumbs_payout > 0 {
-            user_account.update_winnings(dumbs_payout as u64)?;
-        }
+    if dumbs_payout > 0 {
+        user_account.update_winnings(dumbs_payout)?;
+    }
 
-    // Remove the active bet
+    // Remove the active bet, keeping it in the bettor's history
+    record_bet_history(&ctx.accounts.bet_history, &settled_bet, dumbs_payout)?;
     user_account.remove_active_bet()?;
 
+    // Count the result against the bettor's loss limits
+    let betting_state = &ctx.accounts.betting_state;
+    let staked = betting_state.sol_value(
+        bet_amount.saturating_add(fee_amount),
+        token_type,
+        Rounding::Up,
+    )?;
+    let mut returned = betting_state.sol_value(dumbs_payout, TokenType::DUMBS, Rounding::Down)?;
+    if won && token_type == TokenType::RAPR {
+        returned = returned.saturating_add(betting_state.sol_value(
+            bet_amount,
+            TokenType::RAPR,
+            Rounding::Down,
+        )?);
+    }
+
+    let timestamp = Clock::get()?.unix_timestamp;
+    with_user_limits(&ctx.accounts.user_limits, |limits| {
+        limits.record_settlement(staked, returned, timestamp);
+        Ok(())
+    })?;
+
+    // Keep what the win paid so it can be recovered if the result is reversed
+    let rapr_returned = if won && token_type == TokenType::RAPR {
+        bet_amount
+    } else {
+        0
+    };
+    if won && (betting_state.dumbs_clawback || (rapr_returned > 0 && betting_state.rapr_clawback)) {
+        let betting_state_key = betting_state.key();
+        let fight_id_bytes = fight_id.to_le_bytes();
+        record_win(
+            &ctx.accounts.win_record,
+            &ctx.accounts.authority,
+            &ctx.accounts.system_program,
+            &[
+                WIN_RECORD_SEED,
+                betting_state_key.as_ref(),
+                &fight_id_bytes,
+                bettor.as_ref(),
+            ],
+            WinRecord {
+                betting_state: betting_state_key,
+                bettor,
+                fight_id,
+                winner,
+                dumbs_payout,
+                rapr_returned,
+                settled_at: timestamp,
+                bump: ctx.bumps.win_record,
+                ..WinRecord::default()
+            },
+        )?;
+    }
+
+    emit!(BetSettled {
+        bettor,
+        fight_id,
+        token_type,
+        won,
+        payout: dumbs_payout,
+        timestamp,
+    });
+    if funding.paid() > 0 {
+        emit!(WinningsMinted {
+            bettor,
+            fight_id,
+            amount: funding.paid(),
+            timestamp,
+        });
+    }
+    if funding.queued > 0 {
+        emit!(WinningsQueued {
+            bettor,
+            fight_id,
+            amount: funding.queued,
+            queued: ctx.accounts.user_betting_account.queued_winnings,
+            timestamp,
+        });
+    }
+
     Ok(())
-}
\ No newline at end of file
+}
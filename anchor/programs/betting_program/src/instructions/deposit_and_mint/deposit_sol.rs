@@ -6,14 +6,14 @@ use anchor_spl::{
 };
 use crate::state::*;
 use crate::errors::ErrorCode;
+use crate::events::{DepositMade, FeeAsset, FeeCollected};
 pub const TOKEN_2022_PROGRAM_ID: Pubkey = solana_program::pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
-use spl_associated_token_account::instruction;
 
 const BETTING_STATE_SEED: &[u8] = b"betting_state";
 
 
 #[derive(Accounts)]
-#[instruction(amount: u64)]
+#[instruction(amount: u64, quoted_dumbs: u64, max_slippage: u16)]
 pub struct DepositSol<'info> {
     #[account(mut)]
     pub depositor: Signer<'info>,
@@ -31,12 +31,7 @@ pub struct DepositSol<'info> {
         seeds = [b"bet_vault"],
         bump,
         constraint = bet_vault_dumbs.key() == betting_state.bet_vault @ ErrorCode::InvalidAccount,
-        constraint = {
-            msg!("Bet Vault Dumbs Owner: {}", bet_vault_dumbs.owner);
-            msg!("Expected Token-2022 Program ID: {}", TOKEN_2022_PROGRAM_ID);
-            bet_vault_dumbs.owner == TOKEN_2022_PROGRAM_ID} @ ErrorCode::InvalidProgramId
-        
-
+        constraint = bet_vault_dumbs.to_account_info().owner == &TOKEN_2022_PROGRAM_ID @ ErrorCode::InvalidProgramId
     )]
     pub bet_vault_dumbs: Box<InterfaceAccount<'info, TokenAccount>>,  
 
@@ -67,25 +62,127 @@ pub struct DepositSol<'info> {
 
     #[account(
         mut,
-        seeds = [BETTING_STATE_SEED, depositor.key().as_ref()],
-        bump,
+        seeds = [BETTING_STATE_SEED, betting_state.authority.as_ref()],
+        bump = betting_state.bump,
         has_one = sol_vault,
         has_one = treasury,
     )]
     pub betting_state: Account<'info, BettingState>,
 
+    /// CHECK: the user's limits PDA, which only holds data once they set limits
+    #[account(
+        mut,
+        seeds = [USER_LIMITS_SEED, depositor.key().as_ref()],
+        bump,
+    )]
+    pub user_limits: UncheckedAccount<'info>,
+
+    /// CHECK: the house's oracle config PDA, which only holds data once an admin configures it
+    #[account(
+        seeds = [ORACLE_CONFIG_SEED, betting_state.key().as_ref()],
+        bump,
+    )]
+    pub oracle_config: UncheckedAccount<'info>,
+
+    /// CHECK: SOL/USD price account, only read with oracle rates on and then checked against oracle_config
+    pub sol_usd_feed: UncheckedAccount<'info>,
+
+    /// CHECK: DUMBS/USD price account, only read with oracle rates on and then checked against oracle_config
+    pub token_usd_feed: UncheckedAccount<'info>,
+
     pub token_program: Program<'info, Token2022>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
 
-pub fn handler(ctx: Context<DepositSol>, amount: u64) -> Result<()> {
-    msg!("Starting deposit with amount: {}", amount);
+pub fn handler(ctx: Context<DepositSol>, amount: u64, quoted_dumbs: u64, max_slippage: u16) -> Result<()> {
+    ctx.accounts.betting_state.validate_sol_deposit(amount)?;
+    ctx.accounts.sol_vault.process_deposit(amount)?;
+    let now = Clock::get()?.unix_timestamp;
+    with_user_limits(&ctx.accounts.user_limits, |limits| limits.record_deposit(amount, now))?;
+
+    let rate = exchange_rate(
+        &ctx.accounts.betting_state,
+        &ctx.accounts.oracle_config,
+        &ctx.accounts.sol_usd_feed,
+        &ctx.accounts.token_usd_feed,
+        TokenType::DUMBS,
+        RateSide::Buy,
+    )?;
+    let (fee, dumbs_to_mint) = ctx.accounts.betting_state.handle_sol_deposit(amount, rate)?;
+    check_slippage(dumbs_to_mint, quoted_dumbs, max_slippage)?;
+    let net_amount = amount.checked_sub(fee).ok_or(ErrorCode::CalculationOverflow)?;
+
+    // Fee goes to the treasury, the rest backs DUMBS in the SOL vault
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.depositor.to_account_info(),
+                to: ctx.accounts.sol_vault.to_account_info(),
+            },
+        ),
+        net_amount,
+    )?;
 
-    // Debugging: Print owner and program ID
-    msg!("bet_vault_dumbs owner: {:?}", ctx.accounts.bet_vault_dumbs.owner);
+    if fee > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.depositor.to_account_info(),
+                    to: ctx.accounts.treasury.to_account_info(),
+                },
+            ),
+            fee,
+        )?;
+    }
+
+    // The SOL vault only holds the backing portion of the deposit
+    ctx.accounts.sol_vault.balance = ctx.accounts.sol_vault.balance
+        .checked_sub(fee)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+    ctx.accounts.sol_vault.total_dumbs_minted = ctx.accounts.sol_vault.total_dumbs_minted
+        .checked_add(dumbs_to_mint)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+    ctx.accounts.treasury.collect_deposit_fee(fee)?;
+
+    let authority = ctx.accounts.betting_state.authority;
+    let betting_state_seeds = &[
+        BETTING_STATE_SEED,
+        authority.as_ref(),
+        &[ctx.accounts.betting_state.bump],
+    ];
+    let signer = &[&betting_state_seeds[..]];
+
+    token_2022::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token_2022::MintTo {
+                mint: ctx.accounts.dumbs_mint.to_account_info(),
+                to: ctx.accounts.user_dumbs_account.to_account_info(),
+                authority: ctx.accounts.betting_state.to_account_info(),
+            },
+            signer,
+        ),
+        dumbs_to_mint,
+    )?;
+
+    let timestamp = Clock::get()?.unix_timestamp;
+    emit!(DepositMade {
+        depositor: ctx.accounts.depositor.key(),
+        sol_amount: amount,
+        fee,
+        dumbs_minted: dumbs_to_mint,
+        timestamp,
+    });
+    emit!(FeeCollected {
+        payer: ctx.accounts.depositor.key(),
+        asset: FeeAsset::Sol,
+        amount: fee,
+        timestamp,
+    });
 
-    // SOL transfer and other logic...
     Ok(())
-}
\ No newline at end of file
+}
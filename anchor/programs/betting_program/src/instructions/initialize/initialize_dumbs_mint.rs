@@ -1,23 +1,27 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token_interface::{Mint as MintInterface, TokenAccount as TokenAccountInterface, TokenInterface};
+use anchor_lang::system_program::{self, CreateAccount};
+use anchor_spl::token_2022::{self, Token2022};
+use anchor_spl::token_interface::{
+    metadata_pointer_initialize, non_transferable_mint_initialize, permanent_delegate_initialize,
+    MetadataPointerInitialize, NonTransferableMintInitialize, PermanentDelegateInitialize,
+};
+use anchor_spl::token_2022::spl_token_2022::{extension::ExtensionType, state::Mint};
 use crate::state::*;
 
 #[derive(Accounts)]
-#[instruction()]
+#[instruction(non_transferable: bool, clawback: bool)]
 pub struct InitializeDumbsMint<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
 
-    /// CHECK: This account is initialized as a Token-2022 or SPL mint.
+    /// CHECK: Created here as a Token-2022 mint whose metadata pointer names
+    /// itself; `initialize_token_metadata` fills the metadata in.
     #[account(
-        init,
-        payer = authority,
+        mut,
         seeds = [b"dumbs_mint"],
         bump,
-        mint::decimals = 9,
-        mint::authority = betting_state,
     )]
-    pub dumbs_mint: InterfaceAccount<'info, MintInterface>,
+    pub dumbs_mint: UncheckedAccount<'info>,
     
     #[account(
         mut,
@@ -28,17 +32,96 @@ pub struct InitializeDumbsMint<'info> {
 
     
     pub rent: Sysvar<'info, Rent>,
-    pub token_program: Interface<'info, TokenInterface>,
+    pub token_program: Program<'info, Token2022>,
     pub system_program: Program<'info, System>,
 
 }
 
+/// Creates the DUMBS mint. With `non_transferable` it is play money: the
+/// NonTransferable extension blocks every transfer, and the program moves
+/// DUMBS by burning and minting instead (see `transfer_tokens`). With
+/// `clawback` the governance PDA is its permanent delegate, so `clawback` can
+/// recover winnings paid against a reversed result.
 pub fn handler(
     ctx: Context<InitializeDumbsMint>,
+    non_transferable: bool,
+    clawback: bool,
 ) -> Result<()> {
     msg!("Starting DUMBS mint initialization...");
     msg!("Dumbs Mint address: {}", ctx.accounts.dumbs_mint.key());
 
+    let mut extensions = vec![ExtensionType::MetadataPointer];
+    if non_transferable {
+        extensions.push(ExtensionType::NonTransferable);
+    }
+    if clawback {
+        extensions.push(ExtensionType::PermanentDelegate);
+    }
+    let space = ExtensionType::try_calculate_account_len::<Mint>(&extensions)?;
+
+    let mint_seeds: &[&[u8]] = &[b"dumbs_mint", &[ctx.bumps.dumbs_mint]];
+    system_program::create_account(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            CreateAccount {
+                from: ctx.accounts.authority.to_account_info(),
+                to: ctx.accounts.dumbs_mint.to_account_info(),
+            },
+            &[mint_seeds],
+        ),
+        ctx.accounts.rent.minimum_balance(space),
+        space as u64,
+        &ctx.accounts.token_program.key(),
+    )?;
+
+    // Extensions must be initialized before the mint itself
+    metadata_pointer_initialize(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            MetadataPointerInitialize {
+                token_program_id: ctx.accounts.token_program.to_account_info(),
+                mint: ctx.accounts.dumbs_mint.to_account_info(),
+            },
+        ),
+        Some(ctx.accounts.betting_state.key()),
+        Some(ctx.accounts.dumbs_mint.key()),
+    )?;
+    if non_transferable {
+        non_transferable_mint_initialize(CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            NonTransferableMintInitialize {
+                token_program_id: ctx.accounts.token_program.to_account_info(),
+                mint: ctx.accounts.dumbs_mint.to_account_info(),
+            },
+        ))?;
+    }
+    if clawback {
+        permanent_delegate_initialize(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                PermanentDelegateInitialize {
+                    token_program_id: ctx.accounts.token_program.to_account_info(),
+                    mint: ctx.accounts.dumbs_mint.to_account_info(),
+                },
+            ),
+            &governance_address(&ctx.accounts.betting_state.key()).0,
+        )?;
+    }
+    token_2022::initialize_mint2(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token_2022::InitializeMint2 {
+                mint: ctx.accounts.dumbs_mint.to_account_info(),
+            },
+        ),
+        9,
+        &ctx.accounts.betting_state.key(),
+        None,
+    )?;
+
+    ctx.accounts.betting_state.dumbs_non_transferable = non_transferable;
+    ctx.accounts.betting_state.dumbs_clawback = clawback;
+
     msg!("DUMBS mint initialized successfully");
     Ok(())
-}
\ No newline at end of file
+}
@@ -56,10 +56,12 @@ pub fn handler(ctx: Context<InitializeStateAccounts>,) -> Result<()> {
     betting_state.dumbs_mint = ctx.accounts.dumbs_mint.key();
     betting_state.rapr_mint = ctx.accounts.rapr_mint.key();
     betting_state.house_fee = 250;
-    betting_state.rapr_multiplier = 1000;
+    betting_state.rapr_multiplier = 100_000; // 10x RAPR odds, in basis points
     betting_state.sol_dumbs_rate = 1000;
     betting_state.sol_rapr_rate = 10_000_000;
     betting_state.max_bet = 100_000_000_000;
+    betting_state.dumbs_max_supply = u64::MAX; // Uncapped until governance sets limits
+    betting_state.dumbs_epoch_budget = u64::MAX;
     betting_state.is_paused = false;
     betting_state.bump = ctx.bumps.betting_state;
 
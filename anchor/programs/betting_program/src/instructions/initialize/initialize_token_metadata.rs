@@ -0,0 +1,108 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    spl_pod::optional_keys::OptionalNonZeroPubkey, token_metadata_initialize, token_metadata_update_field,
+    Mint, Token2022, TokenMetadataInitialize, TokenMetadataUpdateField,
+};
+use spl_token_metadata_interface::state::{Field, TokenMetadata};
+use crate::state::*;
+use crate::errors::error_code::ErrorCode;
+
+/// Writes the Token-2022 metadata of the DUMBS or RAPR mint, with the house
+/// and its SOL rate as custom fields. The mint's metadata pointer must name
+/// the mint itself; `initialize_dumbs_mint` sets this up for DUMBS, and RAPR
+/// must be created with it.
+#[derive(Accounts)]
+#[instruction(token_type: TokenType)]
+pub struct InitializeTokenMetadata<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"betting_state", authority.key().as_ref()],
+        bump = betting_state.bump,
+        constraint = betting_state.is_initialized() @ ErrorCode::NotInitialized
+    )]
+    pub betting_state: Account<'info, BettingState>,
+
+    #[account(
+        mut,
+        address = betting_state.mint(token_type) @ ErrorCode::InvalidAccount,
+        mint::token_program = token_program,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<InitializeTokenMetadata>,
+    token_type: TokenType,
+    name: String,
+    symbol: String,
+    uri: String,
+) -> Result<()> {
+    let betting_state = &ctx.accounts.betting_state;
+    let fields = [
+        (HOUSE_METADATA_KEY.to_string(), betting_state.key().to_string()),
+        (RATE_METADATA_KEY.to_string(), betting_state.sol_rate(token_type).to_string()),
+    ];
+    let metadata = TokenMetadata {
+        update_authority: OptionalNonZeroPubkey::try_from(Some(betting_state.key()))?,
+        mint: ctx.accounts.mint.key(),
+        name: name.clone(),
+        symbol: symbol.clone(),
+        uri: uri.clone(),
+        additional_metadata: fields.to_vec(),
+    };
+
+    // Fund the final size up front; each CPI below grows the mint
+    fund_token_metadata(
+        &ctx.accounts.authority.to_account_info(),
+        &ctx.accounts.mint.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &metadata,
+    )?;
+
+    let authority = ctx.accounts.authority.key();
+    let betting_state_seeds = &[
+        b"betting_state".as_ref(),
+        authority.as_ref(),
+        &[betting_state.bump],
+    ];
+    let signer = &[&betting_state_seeds[..]];
+
+    token_metadata_initialize(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TokenMetadataInitialize {
+                token_program_id: ctx.accounts.token_program.to_account_info(),
+                metadata: ctx.accounts.mint.to_account_info(),
+                update_authority: betting_state.to_account_info(),
+                mint_authority: betting_state.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+            },
+            signer,
+        ),
+        name,
+        symbol,
+        uri,
+    )?;
+
+    for (key, value) in fields {
+        token_metadata_update_field(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TokenMetadataUpdateField {
+                    token_program_id: ctx.accounts.token_program.to_account_info(),
+                    metadata: ctx.accounts.mint.to_account_info(),
+                    update_authority: betting_state.to_account_info(),
+                },
+                signer,
+            ),
+            Field::Key(key),
+            value,
+        )?;
+    }
+    Ok(())
+}
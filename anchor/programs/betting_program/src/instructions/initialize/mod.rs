@@ -2,8 +2,10 @@ pub mod initialize_betting_state;
 pub mod initialize_dumbs_mint;
 pub mod initialize_state_accounts;
 pub mod initialize_betting_state_base;
+pub mod initialize_token_metadata;
 
 pub use initialize_betting_state::*;
 pub use initialize_dumbs_mint::*;
 pub use initialize_state_accounts::*;
-pub use initialize_betting_state_base::*;
\ No newline at end of file
+pub use initialize_betting_state_base::*;
+pub use initialize_token_metadata::*;
\ No newline at end of file
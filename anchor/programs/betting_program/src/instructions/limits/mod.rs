@@ -0,0 +1,13 @@
+pub mod set_limits;
+pub mod self_exclude;
+
+pub use set_limits::SetLimits;
+pub use self_exclude::SelfExclude;
+
+pub(crate) use set_limits::__client_accounts_set_limits;
+pub(crate) use self_exclude::__client_accounts_self_exclude;
+
+#[cfg(feature = "cpi")]
+pub(crate) use set_limits::__cpi_client_accounts_set_limits;
+#[cfg(feature = "cpi")]
+pub(crate) use self_exclude::__cpi_client_accounts_self_exclude;
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::error_code::ErrorCode;
+use crate::events::SelfExcluded;
+
+#[derive(Accounts)]
+pub struct SelfExclude<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserLimits::LEN,
+        seeds = [USER_LIMITS_SEED, user.key().as_ref()],
+        bump,
+    )]
+    pub user_limits: Account<'info, UserLimits>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Blocks deposits, swaps and bets for `duration` seconds, or for good when
+/// `duration` is `None`. Cash outs and withdrawals stay open.
+pub fn handler(ctx: Context<SelfExclude>, duration: Option<i64>) -> Result<()> {
+    let user = ctx.accounts.user.key();
+    let user_limits = &mut ctx.accounts.user_limits;
+    if user_limits.owner == Pubkey::default() {
+        user_limits.initialize(user, ctx.bumps.user_limits);
+    }
+    require!(user_limits.owner == user, ErrorCode::InvalidAccount);
+
+    let timestamp = Clock::get()?.unix_timestamp;
+    let until = match duration {
+        Some(duration) => timestamp.checked_add(duration).ok_or(ErrorCode::CalculationOverflow)?,
+        None => PERMANENT_EXCLUSION,
+    };
+    user_limits.self_exclude(until, timestamp)?;
+
+    emit!(SelfExcluded {
+        user,
+        until: user_limits.excluded_until,
+        timestamp,
+    });
+
+    Ok(())
+}
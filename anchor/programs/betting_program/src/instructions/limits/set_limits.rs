@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::error_code::ErrorCode;
+use crate::events::LimitsChanged;
+
+#[derive(Accounts)]
+pub struct SetLimits<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserLimits::LEN,
+        seeds = [USER_LIMITS_SEED, user.key().as_ref()],
+        bump,
+    )]
+    pub user_limits: Account<'info, UserLimits>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<SetLimits>, limits: LimitSettings) -> Result<()> {
+    let user = ctx.accounts.user.key();
+    let user_limits = &mut ctx.accounts.user_limits;
+    if user_limits.owner == Pubkey::default() {
+        user_limits.initialize(user, ctx.bumps.user_limits);
+    }
+    require!(user_limits.owner == user, ErrorCode::InvalidAccount);
+
+    let timestamp = Clock::get()?.unix_timestamp;
+    let effective_at = user_limits.set_limits(limits, timestamp)?;
+
+    emit!(LimitsChanged {
+        user,
+        limits: user_limits.limits,
+        pending: user_limits.pending,
+        effective_at,
+        timestamp,
+    });
+
+    Ok(())
+}
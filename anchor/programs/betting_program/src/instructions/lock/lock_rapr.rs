@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::{Mint as MintInterface, TokenAccount as TokenAccountInterface};
+use crate::state::*;
+use crate::errors::error_code::ErrorCode;
+use crate::events::RaprLocked;
+
+#[derive(Accounts)]
+pub struct LockRapr<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = RaprLock::LEN,
+        seeds = [RAPR_LOCK_SEED, owner.key().as_ref()],
+        bump
+    )]
+    pub rapr_lock: Account<'info, RaprLock>,
+
+    #[account(
+        mut,
+        constraint = user_rapr_account.owner == owner.key() @ ErrorCode::InvalidAccount
+    )]
+    pub user_rapr_account: InterfaceAccount<'info, TokenAccountInterface>,
+
+    #[account(
+        mut,
+        seeds = [b"rapr_vault"],
+        bump,
+        constraint = rapr_vault.key() == betting_state.rapr_vault @ ErrorCode::InvalidAccount
+    )]
+    pub rapr_vault: InterfaceAccount<'info, TokenAccountInterface>,
+
+    #[account(address = betting_state.rapr_mint @ ErrorCode::InvalidAccount)]
+    pub rapr_mint: InterfaceAccount<'info, MintInterface>,
+
+    #[account(
+        seeds = [b"betting_state", betting_state.authority.as_ref()],
+        bump = betting_state.bump,
+        constraint = betting_state.is_initialized() @ ErrorCode::NotInitialized,
+        constraint = !betting_state.is_paused @ ErrorCode::ProgramPaused
+    )]
+    pub betting_state: Account<'info, BettingState>,
+
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Locks `amount` of the owner's RAPR in the RAPR vault for `period`,
+/// boosting the odds of their RAPR bets until it ends.
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, LockRapr<'info>>, amount: u64, period: LockPeriod) -> Result<()> {
+    transfer_tokens(
+        TokenTransfer {
+            token_program: ctx.accounts.token_program.to_account_info(),
+            mint: ctx.accounts.rapr_mint.to_account_info(),
+            from: ctx.accounts.user_rapr_account.to_account_info(),
+            to: ctx.accounts.rapr_vault.to_account_info(),
+            authority: ctx.accounts.owner.to_account_info(),
+        },
+        &ctx.accounts.betting_state,
+        TokenType::RAPR,
+        amount,
+        ctx.remaining_accounts,
+    )?;
+
+    // A mint with a transfer fee delivers less than was sent
+    let withheld = withheld_fee(&ctx.accounts.rapr_mint.to_account_info(), amount)?.unwrap_or(0);
+    let locked = amount.checked_sub(withheld).ok_or(ErrorCode::CalculationOverflow)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let lock = &mut ctx.accounts.rapr_lock;
+    lock.initialize(ctx.accounts.owner.key(), locked, period, now, ctx.bumps.rapr_lock)?;
+
+    emit!(RaprLocked {
+        owner: lock.owner,
+        amount: locked,
+        period,
+        unlocks_at: lock.unlocks_at,
+        boost: lock.boost_at(now, locked).get(),
+        timestamp: now,
+    });
+    Ok(())
+}
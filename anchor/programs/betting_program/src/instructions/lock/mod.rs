@@ -0,0 +1,13 @@
+pub mod lock_rapr;
+pub mod unlock_rapr;
+
+pub use lock_rapr::LockRapr;
+pub use unlock_rapr::UnlockRapr;
+
+pub(crate) use lock_rapr::__client_accounts_lock_rapr;
+pub(crate) use unlock_rapr::__client_accounts_unlock_rapr;
+
+#[cfg(feature = "cpi")]
+pub(crate) use lock_rapr::__cpi_client_accounts_lock_rapr;
+#[cfg(feature = "cpi")]
+pub(crate) use unlock_rapr::__cpi_client_accounts_unlock_rapr;
@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::{Mint as MintInterface, TokenAccount as TokenAccountInterface};
+use casino_math::Rounding;
+use crate::state::*;
+use crate::errors::error_code::ErrorCode;
+use crate::events::RaprUnlocked;
+
+#[derive(Accounts)]
+pub struct UnlockRapr<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [RAPR_LOCK_SEED, owner.key().as_ref()],
+        bump = rapr_lock.bump,
+        has_one = owner @ ErrorCode::InvalidAccount
+    )]
+    pub rapr_lock: Account<'info, RaprLock>,
+
+    /// CHECK: the owner's betting account PDA, read only to refuse the unlock
+    /// while a RAPR bet the lock may have boosted is open
+    #[account(
+        seeds = [USER_BETTING_ACCOUNT_SEED, owner.key().as_ref()],
+        bump,
+    )]
+    pub user_betting_account: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = user_rapr_account.owner == owner.key() @ ErrorCode::InvalidAccount
+    )]
+    pub user_rapr_account: InterfaceAccount<'info, TokenAccountInterface>,
+
+    #[account(
+        mut,
+        seeds = [b"rapr_vault"],
+        bump,
+        constraint = rapr_vault.key() == betting_state.rapr_vault @ ErrorCode::InvalidAccount
+    )]
+    pub rapr_vault: InterfaceAccount<'info, TokenAccountInterface>,
+
+    #[account(address = betting_state.rapr_mint @ ErrorCode::InvalidAccount)]
+    pub rapr_mint: InterfaceAccount<'info, MintInterface>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        mut,
+        seeds = [b"betting_state", betting_state.authority.as_ref()],
+        bump = betting_state.bump,
+        has_one = treasury,
+        constraint = betting_state.is_initialized() @ ErrorCode::NotInitialized
+    )]
+    pub betting_state: Account<'info, BettingState>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+/// Returns the owner's locked RAPR and closes the lock. Before the lock ends
+/// `EARLY_UNLOCK_PENALTY` of it stays in the RAPR vault for the treasury. The
+/// lock stays put while the owner has an open RAPR bet, so a boost cannot be
+/// bought for the price of the penalty.
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, UnlockRapr<'info>>) -> Result<()> {
+    require_no_open_rapr_bet(&ctx.accounts.user_betting_account)?;
+    let now = Clock::get()?.unix_timestamp;
+    let amount = ctx.accounts.rapr_lock.amount;
+    let penalty = ctx.accounts.rapr_lock.penalty_at(now)?;
+    let penalty = ctx.accounts.betting_state.keep_dust(penalty)?;
+    let returned = amount.checked_sub(penalty).ok_or(ErrorCode::CalculationOverflow)?;
+
+    transfer_tokens(
+        TokenTransfer {
+            token_program: ctx.accounts.token_program.to_account_info(),
+            mint: ctx.accounts.rapr_mint.to_account_info(),
+            from: ctx.accounts.rapr_vault.to_account_info(),
+            to: ctx.accounts.user_rapr_account.to_account_info(),
+            authority: ctx.accounts.betting_state.to_account_info(),
+        },
+        &ctx.accounts.betting_state,
+        TokenType::RAPR,
+        returned,
+        ctx.remaining_accounts,
+    )?;
+    if penalty > 0 {
+        let penalty_value = ctx.accounts.betting_state.sol_value(penalty, TokenType::RAPR, Rounding::Down)?;
+        ctx.accounts.treasury.collect_lock_penalty(penalty, penalty_value)?;
+    }
+
+    emit!(RaprUnlocked {
+        owner: ctx.accounts.owner.key(),
+        amount: returned,
+        penalty,
+        timestamp: now,
+    });
+    Ok(())
+}
@@ -3,9 +3,25 @@ pub mod initialize;
 pub mod swap;
 pub mod vault;
 pub mod deposit_and_mint;
+pub mod admin;
+pub mod quote;
+pub mod balance;
+pub mod limits;
+pub mod lock;
+pub mod asset;
 
+// Newer modules re-export only their Accounts structs and the client account
+// modules Anchor generates for them, which #[program] looks up at the crate
+// root (the CPI ones only exist with the `cpi` feature); lib.rs calls every
+// handler by its path.
 pub use betting::*;
 pub use initialize::*;
 pub use swap::*;
 pub use vault::*; 
 pub use deposit_and_mint::*;
+pub use admin::*;
+pub use quote::*;
+pub use balance::*;
+pub use limits::*;
+pub use lock::*;
+pub use asset::*;
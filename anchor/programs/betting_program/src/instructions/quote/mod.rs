@@ -0,0 +1,28 @@
+pub mod quote_bet;
+pub mod quote_deposit;
+pub mod quote_cash_out;
+pub mod quote_swap;
+pub mod quote_house_pnl;
+
+pub use quote_bet::QuoteBet;
+pub use quote_deposit::QuoteDeposit;
+pub use quote_cash_out::QuoteCashOut;
+pub use quote_swap::QuoteSwap;
+pub use quote_house_pnl::QuoteHousePnl;
+
+pub(crate) use quote_bet::__client_accounts_quote_bet;
+pub(crate) use quote_deposit::__client_accounts_quote_deposit;
+pub(crate) use quote_cash_out::__client_accounts_quote_cash_out;
+pub(crate) use quote_swap::__client_accounts_quote_swap;
+pub(crate) use quote_house_pnl::__client_accounts_quote_house_pnl;
+
+#[cfg(feature = "cpi")]
+pub(crate) use quote_bet::__cpi_client_accounts_quote_bet;
+#[cfg(feature = "cpi")]
+pub(crate) use quote_deposit::__cpi_client_accounts_quote_deposit;
+#[cfg(feature = "cpi")]
+pub(crate) use quote_cash_out::__cpi_client_accounts_quote_cash_out;
+#[cfg(feature = "cpi")]
+pub(crate) use quote_swap::__cpi_client_accounts_quote_swap;
+#[cfg(feature = "cpi")]
+pub(crate) use quote_house_pnl::__cpi_client_accounts_quote_house_pnl;
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::error_code::ErrorCode;
+
+#[derive(Accounts)]
+#[instruction(amount: u64, odds: u64, token_type: TokenType)]
+pub struct QuoteBet<'info> {
+    #[account(
+        seeds = [b"betting_state", betting_state.authority.as_ref()],
+        bump = betting_state.bump,
+        constraint = betting_state.is_initialized() @ ErrorCode::NotInitialized,
+        constraint = !betting_state.is_paused @ ErrorCode::ProgramPaused
+    )]
+    pub betting_state: Account<'info, BettingState>,
+
+    /// CHECK: RAPR mint, read for its transfer fee
+    #[account(address = betting_state.rapr_mint @ ErrorCode::InvalidAccount)]
+    pub rapr_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Bettor the quote is for, whose RAPR lock boosts RAPR odds
+    pub bettor: UncheckedAccount<'info>,
+
+    /// CHECK: the bettor's RAPR lock PDA, which only holds data while they lock RAPR
+    #[account(
+        seeds = [RAPR_LOCK_SEED, bettor.key().as_ref()],
+        bump,
+    )]
+    pub rapr_lock: UncheckedAccount<'info>,
+}
+
+/// Prices a bet exactly as `place_bet` would, without placing it.
+pub fn handler(ctx: Context<QuoteBet>, amount: u64, odds: u64, token_type: TokenType) -> Result<BetQuote> {
+    let withheld = withheld_fee(&ctx.accounts.rapr_mint, amount)?;
+    let fee = ctx.accounts.betting_state.fee_for(amount, token_type, withheld)?;
+    let lock_boost = lock_boost(&ctx.accounts.rapr_lock, Clock::get()?.unix_timestamp, amount)?;
+    Ok(ctx.accounts.betting_state.quote_bet_with_fee(amount, fee, odds, token_type, lock_boost)?.into())
+}
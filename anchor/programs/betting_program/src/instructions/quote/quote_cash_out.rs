@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::error_code::ErrorCode;
+
+#[derive(Accounts)]
+#[instruction(amount: u64, token_type: TokenType)]
+pub struct QuoteCashOut<'info> {
+    #[account(
+        seeds = [b"sol_vault"],
+        bump
+    )]
+    pub sol_vault: Account<'info, SolVault>,
+
+    #[account(
+        seeds = [b"betting_state", betting_state.authority.as_ref()],
+        bump = betting_state.bump,
+        constraint = betting_state.is_initialized() @ ErrorCode::NotInitialized,
+        constraint = !betting_state.is_paused @ ErrorCode::ProgramPaused
+    )]
+    pub betting_state: Account<'info, BettingState>,
+
+    /// CHECK: RAPR mint, read for its transfer fee
+    #[account(address = betting_state.rapr_mint @ ErrorCode::InvalidAccount)]
+    pub rapr_mint: UncheckedAccount<'info>,
+
+    /// CHECK: the house's oracle config PDA, which only holds data once an admin configures it
+    #[account(
+        seeds = [ORACLE_CONFIG_SEED, betting_state.key().as_ref()],
+        bump,
+    )]
+    pub oracle_config: UncheckedAccount<'info>,
+
+    /// CHECK: SOL/USD price account, only read with oracle rates on and then checked against oracle_config
+    pub sol_usd_feed: UncheckedAccount<'info>,
+
+    /// CHECK: DUMBS/USD or RAPR/USD price account, only read with oracle rates on and then checked against oracle_config
+    pub token_usd_feed: UncheckedAccount<'info>,
+}
+
+/// Prices a cash-out exactly as `cash_out` would, without making it. Fails
+/// if the SOL vault could not pay it right now. Oracle rates are quoted as
+/// they are, with no slippage limit.
+pub fn handler(ctx: Context<QuoteCashOut>, amount: u64, token_type: TokenType) -> Result<CashOutQuote> {
+    let withheld = withheld_fee(&ctx.accounts.rapr_mint, amount)?;
+    let fee = ctx.accounts.betting_state.fee_for(amount, token_type, withheld)?;
+    let rate = exchange_rate(
+        &ctx.accounts.betting_state,
+        &ctx.accounts.oracle_config,
+        &ctx.accounts.sol_usd_feed,
+        &ctx.accounts.token_usd_feed,
+        token_type,
+        RateSide::Sell,
+    )?;
+    let quote = ctx.accounts.betting_state.quote_cash_out_with_fee(amount, fee, rate)?;
+    require!(
+        ctx.accounts.sol_vault.balance >= quote.sol_returned,
+        ErrorCode::InsufficientSolBalance
+    );
+    Ok(quote)
+}
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+#[instruction(amount: u64)]
+pub struct QuoteDeposit<'info> {
+    #[account(
+        seeds = [b"sol_vault"],
+        bump
+    )]
+    pub sol_vault: Account<'info, SolVault>,
+
+    #[account(
+        seeds = [b"betting_state", betting_state.authority.as_ref()],
+        bump = betting_state.bump,
+        has_one = sol_vault,
+    )]
+    pub betting_state: Account<'info, BettingState>,
+
+    /// CHECK: the house's oracle config PDA, which only holds data once an admin configures it
+    #[account(
+        seeds = [ORACLE_CONFIG_SEED, betting_state.key().as_ref()],
+        bump,
+    )]
+    pub oracle_config: UncheckedAccount<'info>,
+
+    /// CHECK: SOL/USD price account, only read with oracle rates on and then checked against oracle_config
+    pub sol_usd_feed: UncheckedAccount<'info>,
+
+    /// CHECK: DUMBS/USD price account, only read with oracle rates on and then checked against oracle_config
+    pub token_usd_feed: UncheckedAccount<'info>,
+}
+
+/// Prices a SOL deposit exactly as `deposit_sol` would, without making it.
+/// Oracle rates are quoted as they are, with no slippage limit.
+pub fn handler(ctx: Context<QuoteDeposit>, amount: u64) -> Result<DepositQuote> {
+    ctx.accounts.betting_state.validate_sol_deposit(amount)?;
+    ctx.accounts.sol_vault.check_deposit_limits(amount)?;
+    let rate = exchange_rate(
+        &ctx.accounts.betting_state,
+        &ctx.accounts.oracle_config,
+        &ctx.accounts.sol_usd_feed,
+        &ctx.accounts.token_usd_feed,
+        TokenType::DUMBS,
+        RateSide::Buy,
+    )?;
+    ctx.accounts.betting_state.quote_sol_deposit(amount, rate)
+}
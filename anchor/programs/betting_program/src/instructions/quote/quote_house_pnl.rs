@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use casino_math::Rounding;
+use crate::state::*;
+use crate::errors::error_code::ErrorCode;
+
+#[derive(Accounts)]
+pub struct QuoteHousePnl<'info> {
+    #[account(
+        seeds = [b"betting_state", betting_state.authority.as_ref()],
+        bump = betting_state.bump,
+        constraint = betting_state.is_initialized() @ ErrorCode::NotInitialized
+    )]
+    pub betting_state: Account<'info, BettingState>,
+
+    #[account(
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        seeds = [b"sol_vault"],
+        bump
+    )]
+    pub sol_vault: Account<'info, SolVault>,
+
+    #[account(
+        seeds = [ORACLE_CONFIG_SEED, betting_state.key().as_ref()],
+        bump = oracle_config.bump,
+        has_one = betting_state @ ErrorCode::InvalidAccount
+    )]
+    pub oracle_config: Account<'info, OracleConfig>,
+
+    /// CHECK: SOL/USD price account, checked against oracle_config and parsed
+    pub sol_usd_feed: UncheckedAccount<'info>,
+}
+
+/// Values the house's fees, stakes kept and net SOL position in SOL and
+/// USD. Fails if the SOL/USD price is stale or too uncertain.
+pub fn handler(ctx: Context<QuoteHousePnl>) -> Result<HousePnl> {
+    let now = Clock::get()?.unix_timestamp;
+    let price = ctx.accounts.oracle_config.sol_usd_price(&ctx.accounts.sol_usd_feed, now)?;
+    let betting_state = &ctx.accounts.betting_state;
+    let treasury = &ctx.accounts.treasury;
+
+    let backing = ctx.accounts.sol_vault.balance;
+    let liabilities = betting_state
+        .sol_value(betting_state.total_dumbs_in_circulation, TokenType::DUMBS, Rounding::Up)?
+        .checked_add(betting_state.sol_value(betting_state.total_rapr_in_circulation, TokenType::RAPR, Rounding::Up)?)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+    let net = backing as i128 - liabilities as i128;
+    let net = i64::try_from(net).map_err(|_| ErrorCode::CalculationOverflow)?;
+
+    Ok(HousePnl {
+        sol_usd: price,
+        fees: treasury.total_fees_collected,
+        fees_usd: price.usd_value(treasury.total_fees_collected)?,
+        house_edge: treasury.total_house_edge,
+        house_edge_usd: price.usd_value(treasury.total_house_edge)?,
+        backing,
+        backing_usd: price.usd_value(backing)?,
+        liabilities,
+        liabilities_usd: price.usd_value(liabilities)?,
+        net,
+        net_usd: price.signed_usd_value(net)?,
+    })
+}
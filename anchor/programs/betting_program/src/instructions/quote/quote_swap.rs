@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::error_code::ErrorCode;
+
+#[derive(Accounts)]
+#[instruction(sol_amount: u64)]
+pub struct QuoteSwap<'info> {
+    #[account(
+        seeds = [b"sol_vault"],
+        bump,
+        constraint = sol_vault.authority == betting_state.authority @ ErrorCode::Unauthorized
+    )]
+    pub sol_vault: Account<'info, SolVault>,
+
+    #[account(
+        seeds = [b"betting_state", betting_state.authority.as_ref()],
+        bump = betting_state.bump,
+        constraint = !betting_state.is_paused @ ErrorCode::ProgramPaused
+    )]
+    pub betting_state: Account<'info, BettingState>,
+
+    /// CHECK: the house's oracle config PDA, which only holds data once an admin configures it
+    #[account(
+        seeds = [ORACLE_CONFIG_SEED, betting_state.key().as_ref()],
+        bump,
+    )]
+    pub oracle_config: UncheckedAccount<'info>,
+
+    /// CHECK: SOL/USD price account, only read with oracle rates on and then checked against oracle_config
+    pub sol_usd_feed: UncheckedAccount<'info>,
+
+    /// CHECK: RAPR/USD price account, only read with oracle rates on and then checked against oracle_config
+    pub token_usd_feed: UncheckedAccount<'info>,
+}
+
+/// Prices a swap exactly as `swap_sol_for_rapr` would, without making it.
+/// Oracle rates are quoted as they are, with no slippage limit.
+pub fn handler(ctx: Context<QuoteSwap>, sol_amount: u64) -> Result<SwapQuote> {
+    ctx.accounts.sol_vault.check_deposit_limits(sol_amount)?;
+    let rate = exchange_rate(
+        &ctx.accounts.betting_state,
+        &ctx.accounts.oracle_config,
+        &ctx.accounts.sol_usd_feed,
+        &ctx.accounts.token_usd_feed,
+        TokenType::RAPR,
+        RateSide::Buy,
+    )?;
+    ctx.accounts.betting_state.quote_swap(sol_amount, rate)
+}
@@ -3,9 +3,10 @@ use anchor_spl::token_2022::{self, Token2022};
 use anchor_spl::token_interface::{Mint as MintInterface, TokenAccount as TokenAccountInterface, TokenInterface};
 use crate::state::*;
 use crate::errors::error_code::ErrorCode;
+use crate::events::Swapped;
 
 #[derive(Accounts)]
-#[instruction(sol_amount: u64)]
+#[instruction(sol_amount: u64, quoted_rapr: u64, max_slippage: u16)]
 pub struct SwapSolForRapr<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
@@ -27,37 +28,61 @@ pub struct SwapSolForRapr<'info> {
 
     /// CHECK: Token-2022 mint for RAPR tokens
     #[account(
+        mut,
         constraint = rapr_mint.key() == betting_state.rapr_mint @ ErrorCode::InvalidMint
     )]
     pub rapr_mint: AccountInfo<'info>,
 
     #[account(
         mut,
-        seeds = [b"betting_state"],
-        bump,
+        seeds = [b"betting_state", betting_state.authority.as_ref()],
+        bump = betting_state.bump,
         constraint = !betting_state.is_paused @ ErrorCode::ProgramPaused
     )]
     pub betting_state: Account<'info, BettingState>,
 
+    /// CHECK: the user's limits PDA, which only holds data once they set limits
+    #[account(
+        mut,
+        seeds = [USER_LIMITS_SEED, user.key().as_ref()],
+        bump,
+    )]
+    pub user_limits: UncheckedAccount<'info>,
+
+    /// CHECK: the house's oracle config PDA, which only holds data once an admin configures it
+    #[account(
+        seeds = [ORACLE_CONFIG_SEED, betting_state.key().as_ref()],
+        bump,
+    )]
+    pub oracle_config: UncheckedAccount<'info>,
+
+    /// CHECK: SOL/USD price account, only read with oracle rates on and then checked against oracle_config
+    pub sol_usd_feed: UncheckedAccount<'info>,
+
+    /// CHECK: RAPR/USD price account, only read with oracle rates on and then checked against oracle_config
+    pub token_usd_feed: UncheckedAccount<'info>,
+
     pub token_program: Program<'info, Token2022>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<SwapSolForRapr>, sol_amount: u64) -> Result<()> {
+pub fn handler(ctx: Context<SwapSolForRapr>, sol_amount: u64, quoted_rapr: u64, max_slippage: u16) -> Result<()> {
     // Verify swap amount is within limits
-    require!(
-        sol_amount >= ctx.accounts.sol_vault.min_deposit_amount,
-        ErrorCode::AmountTooSmall
-    );
-    require!(
-        sol_amount <= ctx.accounts.sol_vault.max_deposit_amount,
-        ErrorCode::AmountTooLarge
-    );
+    ctx.accounts.sol_vault.check_deposit_limits(sol_amount)?;
+    let now = Clock::get()?.unix_timestamp;
+    with_user_limits(&ctx.accounts.user_limits, |limits| limits.record_deposit(sol_amount, now))?;
 
     // Calculate RAPR tokens to mint based on rate
-    let rapr_amount = sol_amount
-        .checked_mul(ctx.accounts.betting_state.sol_rapr_rate)
-        .ok_or(ErrorCode::CalculationOverflow)?;
+    let rate = exchange_rate(
+        &ctx.accounts.betting_state,
+        &ctx.accounts.oracle_config,
+        &ctx.accounts.sol_usd_feed,
+        &ctx.accounts.token_usd_feed,
+        TokenType::RAPR,
+        RateSide::Buy,
+    )?;
+    let rapr_amount = ctx.accounts.betting_state.quote_swap(sol_amount, rate)?.rapr_minted;
+    check_slippage(rapr_amount, quoted_rapr, max_slippage)?;
 
     // Transfer SOL from user to vault
     let cpi_context = CpiContext::new(
@@ -73,9 +98,11 @@ pub fn handler(ctx: Context<SwapSolForRapr>, sol_amount: u64) -> Result<()> {
     ctx.accounts.sol_vault.process_deposit(sol_amount)?;
 
     // Mint RAPR to user using Token-2022
+    let authority = ctx.accounts.betting_state.authority;
     let betting_state_seeds = &[
         b"betting_state".as_ref(),
-        &[ctx.bumps.betting_state],
+        authority.as_ref(),
+        &[ctx.accounts.betting_state.bump],
     ];
     let betting_state_signer = &[&betting_state_seeds[..]];
 
@@ -91,11 +118,17 @@ pub fn handler(ctx: Context<SwapSolForRapr>, sol_amount: u64) -> Result<()> {
     );
     token_2022::mint_to(cpi_ctx, rapr_amount)?;
 
-    msg!(
-        "Swapped {} SOL for {} RAPR tokens",
+    ctx.accounts.betting_state.total_rapr_in_circulation = ctx.accounts.betting_state
+        .total_rapr_in_circulation
+        .checked_add(rapr_amount)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+
+    emit!(Swapped {
+        user: ctx.accounts.user.key(),
         sol_amount,
-        rapr_amount
-    );
+        rapr_amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
 
     Ok(())
 }
\ No newline at end of file
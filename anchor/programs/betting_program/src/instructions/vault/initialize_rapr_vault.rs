@@ -1,27 +1,33 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token_2022::{self, Token2022};
+use anchor_spl::token_2022::spl_token_2022::{
+    extension::{transfer_fee::TransferFeeConfig, BaseStateWithExtensions, StateWithExtensions},
+    state::Mint,
+};
 use anchor_spl::token_interface::{Mint as MintInterface, TokenAccount as TokenAccountInterface, TokenInterface};
 use crate::state::*;
+use crate::errors::error_code::ErrorCode;
 
 #[derive(Accounts)]
 pub struct InitializeRaprVault<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
     
-    /// CHECK: Token-2022 account being initialized
+    /// CHECK: The Token-2022 mint we want to hold
+    pub rapr_mint: AccountInfo<'info>,
+
+    /// CHECK: Token-2022 account being initialized, sized for the extensions
+    /// the mint requires of its accounts, such as TransferHookAccount
     #[account(
         init,
         payer = authority,
         seeds = [b"rapr_vault"],
-        space = 165,
+        space = token_account_len(&rapr_mint)?,
         bump,
         owner = token_2022::ID
     )]
     pub rapr_vault: AccountInfo<'info>,
 
-    /// CHECK: The Token-2022 mint we want to hold
-    pub rapr_mint: AccountInfo<'info>,
-
     #[account(mut)]
     pub betting_state: Account<'info, BettingState>,
 
@@ -32,6 +38,24 @@ pub struct InitializeRaprVault<'info> {
 pub fn handler(ctx: Context<InitializeRaprVault>) -> Result<()> {
     ctx.accounts.betting_state.rapr_vault_bump = ctx.bumps.rapr_vault;
 
+    // A RAPR mint with a transfer fee replaces the house fee on RAPR, so the
+    // house must be able to withdraw what the mint withholds
+    {
+        let data = ctx.accounts.rapr_mint.try_borrow_data()?;
+        let mint = StateWithExtensions::<Mint>::unpack(&data)?;
+        if let Ok(config) = mint.get_extension::<TransferFeeConfig>() {
+            require!(
+                Option::<Pubkey>::from(config.withdraw_withheld_authority) == Some(ctx.accounts.betting_state.key()),
+                ErrorCode::InvalidTransferFeeAuthority
+            );
+            ctx.accounts.betting_state.rapr_transfer_fee = true;
+        }
+    }
+
+    // RAPR can be clawed back when its permanent delegate is the governance PDA
+    let governance = governance_address(&ctx.accounts.betting_state.key()).0;
+    ctx.accounts.betting_state.rapr_clawback = permanent_delegate(&ctx.accounts.rapr_mint)? == Some(governance);
+
     token_2022::initialize_account3(
         CpiContext::new(
             ctx.accounts.token_program.to_account_info(),
@@ -5,6 +5,7 @@ use anchor_spl::associated_token::AssociatedToken;
 use anchor_lang::solana_program::program_pack::Pack;
 
 pub mod errors;
+pub mod events;
 pub mod instructions;
 pub mod state;
 
@@ -27,14 +28,24 @@ pub mod betting_program {
         instructions::initialize::initialize_state_accounts::handler(ctx)
     }
 
-    pub fn initialize_dumbs_mint(ctx: Context<InitializeDumbsMint>) -> Result<()> {
-        instructions::initialize::initialize_dumbs_mint::handler(ctx)
+    pub fn initialize_dumbs_mint(ctx: Context<InitializeDumbsMint>, non_transferable: bool, clawback: bool) -> Result<()> {
+        instructions::initialize::initialize_dumbs_mint::handler(ctx, non_transferable, clawback)
     }
 
     pub fn initialize_betting_state(ctx: Context<InitializeBettingState>) -> Result<()> {
         instructions::initialize::initialize_betting_state::handler(ctx)
     }
 
+    pub fn initialize_token_metadata(
+        ctx: Context<InitializeTokenMetadata>,
+        token_type: TokenType,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        instructions::initialize::initialize_token_metadata::handler(ctx, token_type, name, symbol, uri)
+    }
+
     pub fn initialize_bet_vault(ctx: Context<InitializeBetVault>) -> Result<()> {
         instructions::vault::initialize_bet_vault::handler(ctx)
     }
@@ -56,50 +67,248 @@ pub mod betting_program {
     }
 
     // Deposit, Mint Instructions and Create User Account
-    pub fn deposit_sol(ctx: Context<DepositSol>, amount: u64) -> Result<()> {
-        instructions::deposit_and_mint::deposit_sol::handler(ctx, amount)
+    pub fn deposit_sol(ctx: Context<DepositSol>, amount: u64, quoted_dumbs: u64, max_slippage: u16) -> Result<()> {
+        instructions::deposit_and_mint::deposit_sol::handler(ctx, amount, quoted_dumbs, max_slippage)
     }
 
     // Betting Instructions
-    pub fn place_bet(
-        ctx: Context<PlaceBet>,
+    pub fn place_bet<'info>(
+        ctx: Context<'_, '_, '_, 'info, PlaceBet<'info>>,
         amount: u64,
         fight_id: u64,
+        fighter: Pubkey,
         odds: u64,
         token_type: TokenType
     ) -> Result<()> {
-        instructions::betting::place_bet::handler(ctx, amount, fight_id, odds, token_type)
+        instructions::betting::place_bet::handler(ctx, amount, fight_id, fighter, odds, token_type)
     }
 
-    pub fn settle_bet(
-        ctx: Context<SettleBet>,
+    pub fn place_bet_from_balance(
+        ctx: Context<PlaceBetFromBalance>,
+        amount: u64,
         fight_id: u64,
-        winner: Pubkey
+        fighter: Pubkey,
+        odds: u64,
+        token_type: TokenType
     ) -> Result<()> {
-        instructions::betting::settle_bet::handler(ctx, fight_id, winner)
+        instructions::betting::place_bet_from_balance::handler(ctx, amount, fight_id, fighter, odds, token_type)
     }
 
-    pub fn cash_out(
-        ctx: Context<CashOut>,
+    pub fn settle_bet<'info>(
+        ctx: Context<'_, '_, '_, 'info, SettleBet<'info>>,
+        fight_id: u64
+    ) -> Result<()> {
+        instructions::betting::settle_bet::handler(ctx, fight_id)
+    }
+
+    pub fn cash_out<'info>(
+        ctx: Context<'_, '_, '_, 'info, CashOut<'info>>,
         amount: u64,
-        token_type: TokenType
+        token_type: TokenType,
+        quoted_sol: u64,
+        max_slippage: u16
     ) -> Result<()> {
-        instructions::betting::cash_out::handler(ctx, amount, token_type)
+        instructions::betting::cash_out::handler(ctx, amount, token_type, quoted_sol, max_slippage)
     }
 
     pub fn mint_dumbs_for_win(ctx: Context<MintDumbsForWin>, secure_fight_id: u64) -> Result<()> { // Modified function signature
         instructions::betting::mint_dumbs::handler(ctx, secure_fight_id)
     }
 
+    pub fn claim_queued_winnings(ctx: Context<ClaimQueuedWinnings>) -> Result<()> {
+        instructions::betting::claim_queued_winnings::handler(ctx)
+    }
+
     pub fn create_user_betting_account(ctx: Context<CreateUserBettingAccount>) -> Result<()> {
         instructions::betting::create_user_betting_account::handler(ctx)
     }
 
+    pub fn create_bet_history(ctx: Context<CreateBetHistory>) -> Result<()> {
+        instructions::betting::create_bet_history::handler(ctx)
+    }
+
+    pub fn close_user_betting_account(ctx: Context<CloseUserBettingAccount>) -> Result<()> {
+        instructions::betting::close_user_betting_account::handler(ctx)
+    }
+
+    pub fn close_idle_user_betting_account(ctx: Context<CloseIdleUserBettingAccount>) -> Result<()> {
+        instructions::betting::close_idle_user_betting_account::handler(ctx)
+    }
+
+    pub fn create_fight(
+        ctx: Context<CreateFight>,
+        fight_id: u64,
+        fighter_one: Pubkey,
+        fighter_two: Pubkey
+    ) -> Result<()> {
+        instructions::betting::create_fight::handler(ctx, fight_id, fighter_one, fighter_two)
+    }
+
+    pub fn post_fight_result(ctx: Context<PostFightResult>, winner: Pubkey) -> Result<()> {
+        instructions::betting::post_fight_result::handler(ctx, winner)
+    }
+
+    pub fn reverse_fight_result(ctx: Context<ReverseFightResult>, winner: Pubkey) -> Result<()> {
+        instructions::betting::reverse_fight_result::handler(ctx, winner)
+    }
+
+    // Internal Balance Instructions
+    pub fn deposit_to_balance<'info>(ctx: Context<'_, '_, '_, 'info, DepositToBalance<'info>>, amount: u64, token_type: TokenType) -> Result<()> {
+        instructions::balance::deposit_to_balance::handler(ctx, amount, token_type)
+    }
+
+    pub fn withdraw_from_balance<'info>(ctx: Context<'_, '_, '_, 'info, WithdrawFromBalance<'info>>, amount: u64, token_type: TokenType) -> Result<()> {
+        instructions::balance::withdraw_from_balance::handler(ctx, amount, token_type)
+    }
+
+    // Responsible Gambling Instructions
+    pub fn set_limits(ctx: Context<SetLimits>, limits: LimitSettings) -> Result<()> {
+        instructions::limits::set_limits::handler(ctx, limits)
+    }
+
+    pub fn self_exclude(ctx: Context<SelfExclude>, duration: Option<i64>) -> Result<()> {
+        instructions::limits::self_exclude::handler(ctx, duration)
+    }
+
+    // RAPR Lock Instructions
+    pub fn lock_rapr<'info>(ctx: Context<'_, '_, '_, 'info, LockRapr<'info>>, amount: u64, period: LockPeriod) -> Result<()> {
+        instructions::lock::lock_rapr::handler(ctx, amount, period)
+    }
+
+    pub fn unlock_rapr<'info>(ctx: Context<'_, '_, '_, 'info, UnlockRapr<'info>>) -> Result<()> {
+        instructions::lock::unlock_rapr::handler(ctx)
+    }
+
+    // Registered Mint Instructions
+    pub fn place_asset_bet(
+        ctx: Context<PlaceAssetBet>,
+        amount: u64,
+        fight_id: u64,
+        fighter: Pubkey,
+        odds: u64
+    ) -> Result<()> {
+        instructions::asset::place_asset_bet::handler(ctx, amount, fight_id, fighter, odds)
+    }
+
+    pub fn settle_asset_bet(ctx: Context<SettleAssetBet>, fight_id: u64) -> Result<()> {
+        instructions::asset::settle_asset_bet::handler(ctx, fight_id)
+    }
+
+    pub fn cash_out_asset(ctx: Context<CashOutAsset>, amount: u64, min_out: u64) -> Result<()> {
+        instructions::asset::cash_out_asset::handler(ctx, amount, min_out)
+    }
+
     // Swap Instructions
     pub fn swap_sol_for_rapr(
         ctx: Context<SwapSolForRapr>,
-        sol_amount: u64
+        sol_amount: u64,
+        quoted_rapr: u64,
+        max_slippage: u16
     ) -> Result<()> {
-        instructions::swap::sol_for_rapr::handler(ctx, sol_amount)
+        instructions::swap::sol_for_rapr::handler(ctx, sol_amount, quoted_rapr, max_slippage)
+    }
+
+    // Quote Instructions, read-only and returning their result as return data
+    pub fn quote_bet(
+        ctx: Context<QuoteBet>,
+        amount: u64,
+        odds: u64,
+        token_type: TokenType
+    ) -> Result<BetQuote> {
+        instructions::quote::quote_bet::handler(ctx, amount, odds, token_type)
+    }
+
+    pub fn quote_deposit(ctx: Context<QuoteDeposit>, amount: u64) -> Result<DepositQuote> {
+        instructions::quote::quote_deposit::handler(ctx, amount)
+    }
+
+    pub fn quote_cash_out(
+        ctx: Context<QuoteCashOut>,
+        amount: u64,
+        token_type: TokenType
+    ) -> Result<CashOutQuote> {
+        instructions::quote::quote_cash_out::handler(ctx, amount, token_type)
+    }
+
+    pub fn quote_swap(ctx: Context<QuoteSwap>, sol_amount: u64) -> Result<SwapQuote> {
+        instructions::quote::quote_swap::handler(ctx, sol_amount)
+    }
+
+    pub fn quote_house_pnl(ctx: Context<QuoteHousePnl>) -> Result<HousePnl> {
+        instructions::quote::quote_house_pnl::handler(ctx)
+    }
+
+    // Admin Instructions
+    pub fn initialize_roles(ctx: Context<InitializeRoles>) -> Result<()> {
+        instructions::admin::initialize_roles::handler(ctx)
+    }
+
+    pub fn grant_role(ctx: Context<GrantRole>, member: Pubkey, role: Role) -> Result<()> {
+        instructions::admin::grant_role::handler(ctx, member, role)
+    }
+
+    pub fn revoke_role(ctx: Context<RevokeRole>, member: Pubkey, role: Role) -> Result<()> {
+        instructions::admin::revoke_role::handler(ctx, member, role)
+    }
+
+    pub fn set_paused(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+        instructions::admin::set_paused::handler(ctx, paused)
+    }
+
+    pub fn withdraw_treasury(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
+        instructions::admin::withdraw_treasury::handler(ctx, amount)
+    }
+
+    pub fn update_token_metadata(
+        ctx: Context<UpdateTokenMetadata>,
+        token_type: TokenType,
+        field: MetadataField,
+        value: String,
+    ) -> Result<()> {
+        instructions::admin::update_token_metadata::handler(ctx, token_type, field, value)
+    }
+
+    pub fn harvest_and_withdraw_fees<'info>(
+        ctx: Context<'_, '_, '_, 'info, HarvestAndWithdrawFees<'info>>,
+    ) -> Result<()> {
+        instructions::admin::harvest_and_withdraw_fees::handler(ctx)
+    }
+
+    pub fn clawback<'info>(
+        ctx: Context<'_, '_, '_, 'info, Clawback<'info>>,
+        fight_id: u64,
+        token_type: TokenType,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::admin::clawback::handler(ctx, fight_id, token_type, amount)
+    }
+
+    pub fn register_mint(ctx: Context<RegisterMint>, params: SupportedMintParams) -> Result<()> {
+        instructions::admin::register_mint::handler(ctx, params)
+    }
+
+    pub fn update_supported_mint(ctx: Context<UpdateSupportedMint>, params: SupportedMintParams) -> Result<()> {
+        instructions::admin::update_supported_mint::handler(ctx, params)
+    }
+
+    pub fn fund_mint_liquidity(ctx: Context<FundMintLiquidity>, amount: u64) -> Result<()> {
+        instructions::admin::fund_mint_liquidity::handler(ctx, amount)
+    }
+
+    pub fn create_oracle_config(ctx: Context<CreateOracleConfig>) -> Result<()> {
+        instructions::admin::create_oracle_config::handler(ctx)
+    }
+
+    // Timelocked Config Instructions
+    pub fn queue_change(ctx: Context<QueueChange>, change: ConfigChange) -> Result<()> {
+        instructions::admin::queue_change::handler(ctx, change)
+    }
+
+    pub fn execute_change(ctx: Context<ExecuteChange>) -> Result<()> {
+        instructions::admin::execute_change::handler(ctx)
+    }
+
+    pub fn cancel_change(ctx: Context<CancelChange>) -> Result<()> {
+        instructions::admin::cancel_change::handler(ctx)
     }
 }
\ No newline at end of file
@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
-use super::betting_state::TokenType;
+use super::betting_state::{BetTerms, TokenType};
 use crate::errors::error_code::ErrorCode;
+use casino_math::BasisPoints;
 
 pub const BET_SEED: &[u8] = b"bet";
 
@@ -9,20 +10,23 @@ pub struct Bet {
     pub bettor: Pubkey,
     pub token_type: TokenType,
     // Bet details
-    pub amount: u32,           // Original bet amount (after fees)
+    pub amount: u64,           // Original bet amount (after fees)
     pub fight_id: u32,
-    pub odds: u16,            // In basis points (e.g., 150 = 1.5x)
-    pub potential_payout: u32, // Maximum possible payout
+    pub fighter: Pubkey,       // Fighter the bet backs
+    pub odds: u32,            // Decimal odds in basis points (e.g., 15_000 = 1.5x)
+    pub potential_payout: u64, // Maximum possible payout
     // Fee tracking
-    pub fee_amount: u32,      // Fee paid at bet placement
+    pub fee_amount: u64,      // Fee paid at bet placement
     // Status
     pub timestamp: i64,
     pub settled: bool,
     pub won: bool,
     pub settlement_timestamp: i64,
-    pub actual_payout: u32,
-    // If RAPR bet, track multiplier used
-    pub rapr_multiplier: u16,
+    pub actual_payout: u64,
+    // If RAPR bet, track multiplier used (basis points)
+    pub rapr_multiplier: u32,
+    // Staked from and settled into the owner's internal balance
+    pub from_balance: bool,
     pub bump: u8,
 }
 
@@ -30,65 +34,53 @@ impl Bet {
     pub const LEN: usize = 8 + // discriminator
         32 + // bettor
         1 + // token_type
-        4 + // amount
+        8 + // amount
         4 + // fight_id
-        2 + // odds
-        4 + // potential_payout
-        4 + // fee_amount
+        32 + // fighter
+        4 + // odds
+        8 + // potential_payout
+        8 + // fee_amount
         8 + // timestamp
         1 + // settled
         1 + // won
         8 + // settlement_timestamp
-        4 + // actual_payout
-        2 + // rapr_multiplier
+        8 + // actual_payout
+        4 + // rapr_multiplier
+        1 + // from_balance
         1; // bump
 
+    /// Records a bet backing `fighter` on the `terms` `BettingState::price_bet`
+    /// produced, whose odds are final, including any RAPR boost.
     pub fn initialize(
         &mut self,
         bettor: Pubkey,
         token_type: TokenType,
-        amount: u32,
-        fee_amount: u32,
         fight_id: u32,
-        odds: u16,
-        rapr_multiplier: Option<u16>,
+        fighter: Pubkey,
+        terms: &BetTerms,
         bump: u8,
     ) -> Result<()> {
-        require!(amount > 0, ErrorCode::InvalidAmount);
-        require!(odds > 0, ErrorCode::InvalidOdds);
+        require!(terms.stake > 0, ErrorCode::InvalidAmount);
         if token_type == TokenType::RAPR {
-            require!(rapr_multiplier.is_some(), ErrorCode::InvalidRaprMultiplier);
+            require!(terms.rapr_multiplier.is_some(), ErrorCode::InvalidRaprMultiplier);
         }
 
         self.bettor = bettor;
         self.token_type = token_type;
-        self.amount = amount;
-        self.fee_amount = fee_amount;
+        self.amount = terms.stake;
+        self.fee_amount = terms.fee;
         self.fight_id = fight_id;
-        self.odds = odds;
-        
-        // Calculate potential payout based on token type
-        let base_payout = (amount as u64)
-            .checked_mul(odds as u64)
-            .ok_or(ErrorCode::CalculationOverflow)?
-            .checked_div(100)
-            .ok_or(ErrorCode::CalculationOverflow)? as u32;
-
-        self.potential_payout = match token_type {
-            TokenType::RAPR => {
-                base_payout
-                    .checked_mul(rapr_multiplier.unwrap() as u32)
-                    .ok_or(ErrorCode::CalculationOverflow)?
-            },
-            TokenType::DUMBS => base_payout,
-        };
+        self.fighter = fighter;
+        self.odds = terms.odds.bps();
+        self.potential_payout = terms.potential_payout;
 
         self.timestamp = Clock::get()?.unix_timestamp;
         self.settled = false;
         self.won = false;
         self.settlement_timestamp = 0;
         self.actual_payout = 0;
-        self.rapr_multiplier = rapr_multiplier.unwrap_or(0);
+        self.rapr_multiplier = terms.rapr_multiplier.map_or(0, BasisPoints::get);
+        self.from_balance = false;
         self.bump = bump;
         Ok(())
     }
@@ -115,6 +107,7 @@ impl Default for Bet {
             token_type: TokenType::DUMBS,
             amount: 0,
             fight_id: 0,
+            fighter: Pubkey::default(),
             odds: 0,
             potential_payout: 0,
             fee_amount: 0,
@@ -124,6 +117,7 @@ impl Default for Bet {
             settlement_timestamp: 0,
             actual_payout: 0,
             rapr_multiplier: 0,
+            from_balance: false,
             bump: 0
         }
     }
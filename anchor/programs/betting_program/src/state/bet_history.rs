@@ -0,0 +1,232 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use super::bet::Bet;
+use super::betting_state::TokenType;
+use super::supported_mint::AssetBet;
+use crate::errors::error_code::ErrorCode;
+
+pub const BET_HISTORY_SEED: &[u8] = b"bet-history";
+pub const BET_HISTORY_LEN: usize = 64;       // Settled bets kept per user
+
+pub const ASSET_ENTRY: u8 = 2;                // `HistoryEntry::token_type` of a bet in a registered mint
+
+/// One settled bet, as kept in `BetHistory`.
+#[zero_copy]
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct HistoryEntry {
+    pub fight_id: u64,
+    pub stake: u64,             // After fees
+    pub fee: u64,
+    pub payout: u64,            // DUMBS, or the registered mint, paid out; 0 on a loss
+    pub placed_at: i64,
+    pub settled_at: i64,
+    pub mint: Pubkey,           // Registered mint of an asset bet, default for DUMBS and RAPR
+    pub odds: u32,              // Final odds in basis points
+    pub token_type: u8,         // 0 = DUMBS, 1 = RAPR, ASSET_ENTRY = registered mint
+    pub won: u8,
+    pub _padding: [u8; 2],
+}
+
+impl HistoryEntry {
+    /// DUMBS or RAPR, or `None` for a bet in the registered `mint`.
+    pub fn token_type(&self) -> Option<TokenType> {
+        match self.token_type {
+            0 => Some(TokenType::DUMBS),
+            ASSET_ENTRY => None,
+            _ => Some(TokenType::RAPR),
+        }
+    }
+
+    pub fn won(&self) -> bool {
+        self.won != 0
+    }
+}
+
+/// The last `BET_HISTORY_LEN` settled bets of one user in a ring buffer,
+/// with running win, loss and streak counters.
+#[account(zero_copy)]
+pub struct BetHistory {
+    pub owner: Pubkey,
+    pub total_settled: u64,     // Also the next slot to write, modulo BET_HISTORY_LEN
+    pub wins: u64,
+    pub losses: u64,
+    pub current_streak: i64,    // Positive for consecutive wins, negative for losses
+    pub longest_win_streak: u64,
+    pub longest_loss_streak: u64,
+    pub bump: u8,
+    pub _padding: [u8; 7],
+    pub entries: [HistoryEntry; BET_HISTORY_LEN],
+}
+
+impl BetHistory {
+    pub const LEN: usize = 8 + std::mem::size_of::<BetHistory>();
+
+    pub fn initialize(&mut self, owner: Pubkey, bump: u8) {
+        self.owner = owner;
+        self.bump = bump;
+    }
+
+    /// Appends a settled bet, overwriting the oldest once the buffer is full
+    pub fn record(&mut self, bet: &Bet, payout: u64) {
+        self.push(HistoryEntry {
+            fight_id: bet.fight_id as u64,
+            stake: bet.amount,
+            fee: bet.fee_amount,
+            payout,
+            placed_at: bet.timestamp,
+            settled_at: bet.settlement_timestamp,
+            mint: Pubkey::default(),
+            odds: bet.odds,
+            token_type: match bet.token_type {
+                TokenType::DUMBS => 0,
+                TokenType::RAPR => 1,
+            },
+            won: bet.won as u8,
+            _padding: [0; 2],
+        });
+    }
+
+    /// Appends a settled bet in the registered `mint`, like `record`
+    pub fn record_asset(&mut self, bet: &AssetBet, mint: Pubkey, won: bool, payout: u64, settled_at: i64) {
+        self.push(HistoryEntry {
+            fight_id: bet.fight_id,
+            stake: bet.amount,
+            fee: bet.fee_amount,
+            payout,
+            placed_at: bet.placed_at,
+            settled_at,
+            mint,
+            odds: bet.odds,
+            token_type: ASSET_ENTRY,
+            won: won as u8,
+            _padding: [0; 2],
+        });
+    }
+
+    fn push(&mut self, entry: HistoryEntry) {
+        let won = entry.won();
+        let slot = (self.total_settled % BET_HISTORY_LEN as u64) as usize;
+        self.entries[slot] = entry;
+        self.total_settled = self.total_settled.saturating_add(1);
+
+        if won {
+            self.wins = self.wins.saturating_add(1);
+            self.current_streak = self.current_streak.max(0).saturating_add(1);
+            self.longest_win_streak = self.longest_win_streak.max(self.current_streak.unsigned_abs());
+        } else {
+            self.losses = self.losses.saturating_add(1);
+            self.current_streak = self.current_streak.min(0).saturating_sub(1);
+            self.longest_loss_streak = self.longest_loss_streak.max(self.current_streak.unsigned_abs());
+        }
+    }
+
+    /// Kept entries, newest first
+    pub fn recent(&self) -> impl Iterator<Item = &HistoryEntry> {
+        let kept = self.total_settled.min(BET_HISTORY_LEN as u64) as usize;
+        let next = (self.total_settled % BET_HISTORY_LEN as u64) as usize;
+        (1..=kept).map(move |back| &self.entries[(next + BET_HISTORY_LEN - back) % BET_HISTORY_LEN])
+    }
+}
+
+/// Records a settled bet in the user's history if they have created one.
+/// `account` must already be checked to be the user's history PDA.
+pub fn record_bet_history(account: &AccountInfo, bet: &Bet, payout: u64) -> Result<()> {
+    with_bet_history(account, |history| history.record(bet, payout))
+}
+
+/// `record_bet_history` for a settled bet in the registered `mint`.
+pub fn record_asset_bet_history(
+    account: &AccountInfo,
+    bet: &AssetBet,
+    mint: Pubkey,
+    won: bool,
+    payout: u64,
+    settled_at: i64,
+) -> Result<()> {
+    with_bet_history(account, |history| history.record_asset(bet, mint, won, payout, settled_at))
+}
+
+fn with_bet_history(account: &AccountInfo, record: impl FnOnce(&mut BetHistory)) -> Result<()> {
+    if account.owner != &crate::ID {
+        return Ok(());
+    }
+    let mut data = account.try_borrow_mut_data()?;
+    require!(
+        data.len() >= BetHistory::LEN && data[..8] == BetHistory::DISCRIMINATOR,
+        ErrorCode::InvalidAccount
+    );
+    record(bytemuck::from_bytes_mut(&mut data[8..BetHistory::LEN]));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settled(fight_id: u32, won: bool, token_type: TokenType) -> Bet {
+        Bet { fight_id, won, token_type, settled: true, ..Bet::default() }
+    }
+
+    #[test]
+    fn the_oldest_entries_are_overwritten_once_full() {
+        let mut history: Box<BetHistory> = Box::new(bytemuck::Zeroable::zeroed());
+        let total = BET_HISTORY_LEN as u32 + 3;
+        for fight_id in 1..=total {
+            history.record(&settled(fight_id, true, TokenType::DUMBS), 1);
+        }
+
+        assert_eq!(history.total_settled, total as u64);
+        let kept: Vec<_> = history.recent().map(|entry| entry.fight_id).collect();
+        assert_eq!(kept.len(), BET_HISTORY_LEN);
+        assert_eq!(kept.first(), Some(&(total as u64)));
+        assert_eq!(kept.last(), Some(&4));
+        assert_eq!(history.entries[0].fight_id, BET_HISTORY_LEN as u64 + 1);
+    }
+
+    #[test]
+    fn recent_keeps_only_what_was_recorded_before_wrapping() {
+        let mut history: Box<BetHistory> = Box::new(bytemuck::Zeroable::zeroed());
+        assert_eq!(history.recent().count(), 0);
+        history.record(&settled(1, true, TokenType::RAPR), 5);
+        history.record(&settled(2, false, TokenType::DUMBS), 0);
+
+        let recent: Vec<_> = history.recent().map(|entry| (entry.fight_id, entry.token_type())).collect();
+        assert_eq!(recent, vec![(2, Some(TokenType::DUMBS)), (1, Some(TokenType::RAPR))]);
+    }
+
+    #[test]
+    fn streaks_flip_and_keep_their_longest_run() {
+        let mut history: Box<BetHistory> = Box::new(bytemuck::Zeroable::zeroed());
+        for (fight_id, won) in [(1, true), (2, true), (3, false), (4, false), (5, false), (6, true)] {
+            history.record(&settled(fight_id, won, TokenType::DUMBS), 0);
+        }
+        assert_eq!((history.wins, history.losses), (3, 3));
+        assert_eq!(history.current_streak, 1);
+        assert_eq!((history.longest_win_streak, history.longest_loss_streak), (2, 3));
+    }
+
+    #[test]
+    fn asset_bets_are_kept_with_their_mint() {
+        let mut history: Box<BetHistory> = Box::new(bytemuck::Zeroable::zeroed());
+        let mint = Pubkey::new_unique();
+        let bet = AssetBet {
+            bettor: Pubkey::default(),
+            supported_mint: Pubkey::default(),
+            fight_id: 7,
+            fighter: Pubkey::default(),
+            amount: 100,
+            fee_amount: 3,
+            odds: 24_000,
+            potential_payout: 240,
+            placed_at: 10,
+            bump: 0,
+        };
+        history.record_asset(&bet, mint, true, 240, 20);
+
+        let entry = history.recent().next().unwrap();
+        assert_eq!(entry.token_type(), None);
+        assert_eq!((entry.mint, entry.stake, entry.fee, entry.payout), (mint, 100, 3, 240));
+        assert_eq!((entry.placed_at, entry.settled_at), (10, 20));
+        assert_eq!(history.current_streak, 1);
+    }
+}
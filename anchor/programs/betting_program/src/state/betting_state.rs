@@ -1,5 +1,8 @@
 use anchor_lang::prelude::*;
 use crate::errors::error_code::ErrorCode;
+use casino_math::{BasisPoints, Odds, Rounded, Rounding};
+use super::oracle::ExchangeRate;
+use super::quote::{CashOutQuote, DepositQuote, SwapQuote};
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
 pub enum TokenType {
@@ -30,8 +33,8 @@ pub struct BettingState {
     pub rapr_vault: Pubkey,     // Separate RAPR vault
     pub treasury: Pubkey,
     pub sol_vault: Pubkey,
-    pub house_fee: u32,
-    pub rapr_multiplier: u64,
+    pub house_fee: u32,         // Basis points
+    pub rapr_multiplier: u64,   // Basis points applied to RAPR odds (10_000 = 1x)
     pub sol_dumbs_rate: u64,
     pub sol_rapr_rate: u64,
     pub total_bets_placed: u64,
@@ -44,11 +47,22 @@ pub struct BettingState {
     pub total_potential_payout: u64,
     pub total_dumbs_in_circulation: u64,
     pub total_rapr_in_circulation: u64,
+    pub rounding_dust: u64,     // Ten-thousandths of a base unit kept by house-favourable rounding
     pub max_bet: u64,
     pub is_paused: bool,
     pub bump: u8,
     pub bet_vault_bump: u8,
     pub rapr_vault_bump: u8,
+    pub dumbs_non_transferable: bool,   // Play-money DUMBS, moved by burning and minting
+    pub rapr_transfer_fee: bool,        // RAPR's mint withholds the house fee on every transfer
+    pub dumbs_clawback: bool,           // DUMBS's permanent delegate is the governance PDA
+    pub rapr_clawback: bool,            // RAPR's permanent delegate is the governance PDA
+    pub dumbs_max_supply: u64,          // Cap on DUMBS in circulation (u64::MAX = uncapped)
+    pub dumbs_epoch_budget: u64,        // DUMBS wins may mint per epoch (u64::MAX = unlimited)
+    pub emission_epoch: u64,            // Epoch dumbs_emitted counts against
+    pub dumbs_emitted: u64,             // DUMBS minted for wins in emission_epoch
+    pub house_dumbs: u64,               // Settled DUMBS stakes in the bet vault, paying wins past the budget
+    pub total_dumbs_queued: u64,        // Winnings owed to bettors until the budget frees up
 }
 
 impl Default for BettingState {
@@ -75,31 +89,51 @@ impl Default for BettingState {
            total_potential_payout: 0,
            total_dumbs_in_circulation: 0,
            total_rapr_in_circulation: 0,
+           rounding_dust: 0,
            max_bet: 0,
            is_paused: false,
            bump: 0,
            bet_vault_bump: 0,
-           rapr_vault_bump:0
+           rapr_vault_bump:0,
+           dumbs_non_transferable: false,
+           rapr_transfer_fee: false,
+           dumbs_clawback: false,
+           rapr_clawback: false,
+           dumbs_max_supply: u64::MAX,
+           dumbs_epoch_budget: u64::MAX,
+           emission_epoch: 0,
+           dumbs_emitted: 0,
+           house_dumbs: 0,
+           total_dumbs_queued: 0,
        }
    }
 }
 
-#[account]
-pub struct Bet {
-    pub bettor: Pubkey,
-    pub token_type: TokenType,
-    pub amount: u32,           // Original bet amount (after fees)
-    pub fight_id: u32,
-    pub odds: u16,            // In basis points (e.g., 150 = 1.5x)
-    pub potential_payout: u32, // Maximum possible payout
-    pub fee_amount: u32,      // Fee paid at bet placement
-    pub timestamp: i64,
-    pub settled: bool,
-    pub won: bool,
-    pub settlement_timestamp: i64,
-    pub actual_payout: u32,
-    pub rapr_multiplier: u16,
-    pub bump: u8,
+/// How a DUMBS win is paid: minted within the emission budget, drawn from
+/// the house's DUMBS in the bet vault, and whatever is left queued.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct WinFunding {
+    pub minted: u64,
+    pub from_vault: u64,
+    pub queued: u64,
+}
+
+impl WinFunding {
+    /// Part of the win paid out now.
+    pub fn paid(&self) -> u64 {
+        self.minted + self.from_vault
+    }
+}
+
+/// What a bet costs and pays, as `place_bet` records it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BetTerms {
+    pub fee: u64,
+    pub stake: u64,            // Amount after the fee
+    pub odds: Odds,            // Final odds, boosted for RAPR
+    pub potential_payout: u64,
+    pub rapr_multiplier: Option<BasisPoints>,
+    pub dust: u64,             // Rounding dust the house keeps on this bet
 }
 
 #[account]
@@ -137,12 +171,22 @@ impl BettingState {
         8 + // total_potential_payout
         8 + // total_dumbs_in_circulation
         8 + // total_rapr_in_circulation
+        8 + // rounding_dust
         8 + // max_bet
         1 + // is_paused
         1 + // bump
         1 + // bet_vault_bump
-        1; // rapr_vault_bump
-        
+        1 + // rapr_vault_bump
+        1 + // dumbs_non_transferable
+        1 + // rapr_transfer_fee
+        1 + // dumbs_clawback
+        1 + // rapr_clawback
+        8 + // dumbs_max_supply
+        8 + // dumbs_epoch_budget
+        8 + // emission_epoch
+        8 + // dumbs_emitted
+        8 + // house_dumbs
+        8; // total_dumbs_queued
 
     pub fn initialize(
         &mut self,
@@ -181,9 +225,16 @@ impl BettingState {
         self.total_potential_payout = 0;
         self.total_dumbs_in_circulation = 0;
         self.total_rapr_in_circulation = 0;
+        self.rounding_dust = 0;
         self.max_bet = max_bet;
         self.is_paused = false;
         self.bump = bump;
+        self.dumbs_max_supply = u64::MAX;
+        self.dumbs_epoch_budget = u64::MAX;
+        self.emission_epoch = 0;
+        self.dumbs_emitted = 0;
+        self.house_dumbs = 0;
+        self.total_dumbs_queued = 0;
     }
 
    pub fn is_initialized(&self) -> bool {
@@ -193,86 +244,396 @@ impl BettingState {
             && self.bet_vault != Pubkey::default()
     }
 
-    
-    pub fn calculate_fee(&self, amount: u64) -> Result<u64> {
-        Ok(amount
-            .checked_mul(self.house_fee as u64)
-            .ok_or(ErrorCode::CalculationOverflow)?
-            .checked_div(10000)
-            .ok_or(ErrorCode::CalculationOverflow)?)
+
+    /// Whether `token_type` is minted non-transferable, so the house must
+    /// burn and mint it rather than transfer it.
+    pub fn is_non_transferable(&self, token_type: TokenType) -> bool {
+        token_type == TokenType::DUMBS && self.dumbs_non_transferable
     }
 
-    pub fn calculate_odds(&self, base_odds: u64, token_type: TokenType) -> Result<u64> {
+    /// Whether `token_type` pays its fee through the mint's TransferFeeConfig
+    /// rather than the house fee.
+    pub fn uses_transfer_fee(&self, token_type: TokenType) -> bool {
+        token_type == TokenType::RAPR && self.rapr_transfer_fee
+    }
+
+    /// Whether governance can claw `token_type` back through the mint's
+    /// permanent delegate.
+    pub fn clawback_enabled(&self, token_type: TokenType) -> bool {
         match token_type {
-            TokenType::DUMBS => Ok(base_odds),
-            TokenType::RAPR => {
-                Ok(base_odds
-                    .checked_mul(self.rapr_multiplier as u64)
-                    .ok_or(ErrorCode::CalculationOverflow)?
-                    .checked_div(100)
-                    .ok_or(ErrorCode::CalculationOverflow)?)
-            }
+            TokenType::DUMBS => self.dumbs_clawback,
+            TokenType::RAPR => self.rapr_clawback,
         }
     }
 
-   pub fn handle_sol_deposit(
+    /// Mint of `token_type`.
+    pub fn mint(&self, token_type: TokenType) -> Pubkey {
+        match token_type {
+            TokenType::DUMBS => self.dumbs_mint,
+            TokenType::RAPR => self.rapr_mint,
+        }
+    }
+
+    /// Tokens per SOL for `token_type`.
+    pub fn sol_rate(&self, token_type: TokenType) -> u64 {
+        match token_type {
+            TokenType::DUMBS => self.sol_dumbs_rate,
+            TokenType::RAPR => self.sol_rapr_rate,
+        }
+    }
+
+    /// The fixed SOL rate for `token_type`, used unless oracle rates are on.
+    pub fn fixed_rate(&self, token_type: TokenType) -> ExchangeRate {
+        ExchangeRate::fixed(self.sol_rate(token_type))
+    }
+
+    /// Vault holding stakes and internal balances of `token_type`.
+    pub fn vault(&self, token_type: TokenType) -> Pubkey {
+        match token_type {
+            TokenType::DUMBS => self.bet_vault,
+            TokenType::RAPR => self.rapr_vault,
+        }
+    }
+
+    /// SOL value of `amount` of `token_type` in lamports, through the SOL rates.
+    pub fn sol_value(&self, amount: u64, token_type: TokenType, rounding: Rounding) -> Result<u64> {
+        Ok(casino_math::mul_div(amount, 1, self.sol_rate(token_type), rounding).map_err(ErrorCode::from)?.value)
+    }
+
+    pub fn fee_rate(&self) -> Result<BasisPoints> {
+        Ok(BasisPoints::fraction(self.house_fee).map_err(ErrorCode::from)?)
+    }
+
+    pub fn rapr_boost(&self) -> Result<BasisPoints> {
+        Ok(BasisPoints::from_u64(self.rapr_multiplier).map_err(ErrorCode::from)?)
+    }
+
+    /// RAPR multiplier raised by `lock_boost` from the bettor's RAPR lock.
+    pub fn rapr_boost_with_lock(&self, lock_boost: BasisPoints) -> Result<BasisPoints> {
+        let raise = BasisPoints::new(BasisPoints::SCALE.saturating_add(lock_boost.get()));
+        let boosted = raise.apply(self.rapr_multiplier, Rounding::Down).map_err(ErrorCode::from)?;
+        Ok(BasisPoints::from_u64(boosted.value).map_err(ErrorCode::from)?)
+    }
+
+    /// House fee on `amount`, rounded up. Pass the result to `keep_dust`.
+    pub fn calculate_fee(&self, amount: u64) -> Result<Rounded> {
+        Ok(self.fee_rate()?.fee_on(amount).map_err(ErrorCode::from)?)
+    }
+
+    /// Fee on moving `amount` of `token_type` into the house. In transfer-fee
+    /// mode RAPR pays only what its mint withheld on the transfer, `withheld`;
+    /// everything else pays the house fee.
+    pub fn fee_for(&self, amount: u64, token_type: TokenType, withheld: Option<u64>) -> Result<Rounded> {
+        if self.uses_transfer_fee(token_type) {
+            return Ok(Rounded { value: withheld.unwrap_or(0), dust: 0 });
+        }
+        self.calculate_fee(amount)
+    }
+
+    /// Odds offered for `token_type`; RAPR bets get the RAPR multiplier,
+    /// raised by `lock_boost` from the bettor's RAPR lock, and this is the
+    /// only place it applies.
+    pub fn calculate_odds(&self, base_odds: u64, token_type: TokenType, lock_boost: BasisPoints) -> Result<Odds> {
+        let odds = Odds::from_u64(base_odds).map_err(ErrorCode::from)?;
+        match token_type {
+            TokenType::DUMBS => Ok(odds),
+            TokenType::RAPR => Ok(odds.boost(self.rapr_boost_with_lock(lock_boost)?).map_err(ErrorCode::from)?),
+        }
+    }
+
+    /// What a winning `stake` at `odds` returns, rounded down.
+    pub fn calculate_payout(&self, stake: u64, odds: Odds) -> Result<Rounded> {
+        Ok(odds.payout(stake).map_err(ErrorCode::from)?)
+    }
+
+    /// Records the dust from a rounded amount and returns the amount.
+    pub fn keep_dust(&mut self, rounded: Rounded) -> Result<u64> {
+        self.rounding_dust = self.rounding_dust
+            .checked_add(rounded.dust)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        Ok(rounded.value)
+    }
+
+    /// Prices a bet of `amount` at `base_odds` without changing anything.
+    /// `place_bet`, `quote_bet` and the off-chain simulator all go through here.
+    pub fn quote_bet(&self, amount: u64, base_odds: u64, token_type: TokenType) -> Result<BetTerms> {
+        self.quote_bet_with_fee(amount, self.calculate_fee(amount)?, base_odds, token_type, BasisPoints::ZERO)
+    }
+
+    /// Prices a bet as `quote_bet` does, charging `fee` from `fee_for` and
+    /// raising RAPR odds by `lock_boost` from the bettor's RAPR lock.
+    pub fn quote_bet_with_fee(
+        &self,
+        amount: u64,
+        fee: Rounded,
+        base_odds: u64,
+        token_type: TokenType,
+        lock_boost: BasisPoints,
+    ) -> Result<BetTerms> {
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        require!(amount <= self.max_bet, ErrorCode::BetTooLarge);
+
+        let stake = amount.checked_sub(fee.value).ok_or(ErrorCode::CalculationOverflow)?;
+
+        let odds = self.calculate_odds(base_odds, token_type, lock_boost)?;
+        let potential_payout = self.calculate_payout(stake, odds)?;
+        let rapr_multiplier = match token_type {
+            TokenType::RAPR => Some(self.rapr_boost_with_lock(lock_boost)?),
+            TokenType::DUMBS => None,
+        };
+
+        Ok(BetTerms {
+            fee: fee.value,
+            stake,
+            odds,
+            potential_payout: potential_payout.value,
+            rapr_multiplier,
+            dust: fee.dust + potential_payout.dust,
+        })
+    }
+
+    /// Prices a bet as `quote_bet` does and keeps its rounding dust.
+    pub fn price_bet(&mut self, amount: u64, base_odds: u64, token_type: TokenType) -> Result<BetTerms> {
+        let fee = self.calculate_fee(amount)?;
+        self.price_bet_with_fee(amount, fee, base_odds, token_type, BasisPoints::ZERO)
+    }
+
+    /// Prices a bet as `quote_bet_with_fee` does and keeps its rounding dust.
+    pub fn price_bet_with_fee(
         &mut self,
-        amount: u64, // Amount in lamports of SOL
-    ) -> Result<(u64, u64)> {  // Returns (fee, dumbs_to_mint in lamports of DUMB)
-        // Calculate fee (2.5% = 250/10000)
+        amount: u64,
+        fee: Rounded,
+        base_odds: u64,
+        token_type: TokenType,
+        lock_boost: BasisPoints,
+    ) -> Result<BetTerms> {
+        let terms = self.quote_bet_with_fee(amount, fee, base_odds, token_type, lock_boost)?;
+        self.keep_dust(Rounded { value: 0, dust: terms.dust })?;
+        Ok(terms)
+    }
+
+    /// What depositing `amount` lamports mints at `rate`, rounding down,
+    /// without changing anything.
+    pub fn quote_sol_deposit(&self, amount: u64, rate: ExchangeRate) -> Result<DepositQuote> {
         let fee = self.calculate_fee(amount)?;
-    
-        // Subtract the fee from the deposit amount
-        let deposit_amount = amount
-            .checked_sub(fee)
+        let net_amount = amount
+            .checked_sub(fee.value)
             .ok_or(ErrorCode::CalculationOverflow)?;
-    
-        // Calculate DUMBs to mint in lamports of DUMB
-        // Since 1 SOL = 1000 DUMBs, and both SOL and DUMB use 9 decimal places,
-        // the conversion factor is 1000.
-        let dumbs_to_mint = deposit_amount
-            .checked_mul(self.sol_dumbs_rate)
+
+        let dumbs_minted = rate.to_tokens(net_amount, Rounding::Down)?;
+
+        Ok(DepositQuote {
+            fee: fee.value,
+            net_amount,
+            dumbs_minted: dumbs_minted.value,
+            dust: fee.dust + dumbs_minted.dust,
+        })
+    }
+
+    /// What cashing out `amount` of `token_type` at its fixed SOL rate
+    /// returns, without changing anything. Swaps mint `rate` tokens per
+    /// lamport, so cashing out divides by it, rounding down.
+    pub fn quote_cash_out(&self, amount: u64, token_type: TokenType) -> Result<CashOutQuote> {
+        self.quote_cash_out_with_fee(amount, self.calculate_fee(amount)?, self.fixed_rate(token_type))
+    }
+
+    /// Prices a cash-out as `quote_cash_out` does, charging `fee` from
+    /// `fee_for` and converting at `rate`.
+    pub fn quote_cash_out_with_fee(&self, amount: u64, fee: Rounded, rate: ExchangeRate) -> Result<CashOutQuote> {
+        require!(amount > 0, ErrorCode::InvalidAmount);
+
+        let net_amount = amount
+            .checked_sub(fee.value)
             .ok_or(ErrorCode::CalculationOverflow)?;
-    
+
+        let sol_returned = rate.to_lamports(net_amount, Rounding::Down)?;
+
+        Ok(CashOutQuote {
+            fee: fee.value,
+            net_amount,
+            sol_returned: sol_returned.value,
+            dust: fee.dust + sol_returned.dust,
+        })
+    }
+
+    /// RAPR minted for `sol_amount` lamports at `rate`, rounding down.
+    /// Swaps charge no fee.
+    pub fn quote_swap(&self, sol_amount: u64, rate: ExchangeRate) -> Result<SwapQuote> {
+        let rapr_minted = rate.to_tokens(sol_amount, Rounding::Down)?.value;
+        Ok(SwapQuote { fee: 0, net_amount: sol_amount, rapr_minted })
+    }
+
+   pub fn handle_sol_deposit(
+        &mut self,
+        amount: u64, // Amount in lamports of SOL
+        rate: ExchangeRate,
+    ) -> Result<(u64, u64)> {  // Returns (fee, dumbs_to_mint in lamports of DUMB)
+        let quote = self.quote_sol_deposit(amount, rate)?;
+        self.keep_dust(Rounded { value: 0, dust: quote.dust })?;
+
         // Update state
         self.total_fees_collected = self.total_fees_collected
-            .checked_add(fee)
+            .checked_add(quote.fee)
             .ok_or(ErrorCode::CalculationOverflow)?;
     
         self.total_dumbs_in_circulation = self.total_dumbs_in_circulation
-            .checked_add(dumbs_to_mint)
+            .checked_add(quote.dumbs_minted)
             .ok_or(ErrorCode::CalculationOverflow)?;
+        require!(
+            self.total_dumbs_in_circulation <= self.dumbs_max_supply,
+            ErrorCode::DumbsMaxSupplyExceeded
+        );
     
-        Ok((fee, dumbs_to_mint))
+        Ok((quote.fee, quote.dumbs_minted))
     }
 
-    pub fn mint_dumbs_for_win(
+    /// Prices a cash-out as `quote_cash_out` does and keeps its rounding dust.
+    pub fn price_cash_out(&mut self, amount: u64, token_type: TokenType) -> Result<CashOutQuote> {
+        let fee = self.calculate_fee(amount)?;
+        self.price_cash_out_with_fee(amount, fee, self.fixed_rate(token_type))
+    }
+
+    /// Prices a cash-out as `quote_cash_out_with_fee` does and keeps its rounding dust.
+    pub fn price_cash_out_with_fee(&mut self, amount: u64, fee: Rounded, rate: ExchangeRate) -> Result<CashOutQuote> {
+        let quote = self.quote_cash_out_with_fee(amount, fee, rate)?;
+        self.keep_dust(Rounded { value: 0, dust: quote.dust })?;
+        Ok(quote)
+    }
+
+    /// Winnings are the bet's stored potential payout, already boosted for
+    /// RAPR bets when the odds were set.
+    pub fn mint_dumbs_for_win(&mut self, payout: u64) -> Result<u64> {  // Returns amount to mint
+        self.total_dumbs_in_circulation = self.total_dumbs_in_circulation
+            .checked_add(payout)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+
+        Ok(payout)
+    }
+
+    /// DUMBS win-minting may still add in `epoch`, within both the epoch
+    /// budget and the max supply.
+    pub fn mintable_for_wins(&self, epoch: u64) -> u64 {
+        let emitted = if epoch == self.emission_epoch { self.dumbs_emitted } else { 0 };
+        let budget = self.dumbs_epoch_budget.saturating_sub(emitted);
+        let supply = self.dumbs_max_supply.saturating_sub(self.total_dumbs_in_circulation);
+        budget.min(supply)
+    }
+
+    /// Splits a win's DUMBS payout into what is minted, what the house's
+    /// DUMBS in the bet vault covers and what is queued for the bettor, and
+    /// records each part.
+    pub fn fund_win(&mut self, payout: u64, epoch: u64) -> Result<WinFunding> {
+        let funding = self.fund(payout, epoch)?;
+        self.total_dumbs_queued = self.total_dumbs_queued
+            .checked_add(funding.queued)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        Ok(funding)
+    }
+
+    /// Funds as much of `queued` winnings as the budget and house DUMBS now
+    /// allow, taking what is paid off the queue.
+    pub fn release_queued(&mut self, queued: u64, epoch: u64) -> Result<WinFunding> {
+        let funding = self.fund(queued, epoch)?;
+        self.total_dumbs_queued = self.total_dumbs_queued
+            .checked_sub(funding.paid())
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        Ok(funding)
+    }
+
+    fn fund(&mut self, amount: u64, epoch: u64) -> Result<WinFunding> {
+        let minted = amount.min(self.mintable_for_wins(epoch));
+        let from_vault = (amount - minted).min(self.house_dumbs);
+        let queued = amount - minted - from_vault;
+
+        if epoch != self.emission_epoch {
+            self.emission_epoch = epoch;
+            self.dumbs_emitted = 0;
+        }
+        self.dumbs_emitted = self.dumbs_emitted
+            .checked_add(minted)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        self.mint_dumbs_for_win(minted)?;
+        self.house_dumbs -= from_vault;
+
+        Ok(WinFunding { minted, from_vault, queued })
+    }
+
+    /// A settled DUMBS stake stays in the bet vault as the house's.
+    pub fn keep_stake(&mut self, amount: u64, token_type: TokenType) -> Result<()> {
+        if token_type == TokenType::DUMBS {
+            self.house_dumbs = self.house_dumbs
+                .checked_add(amount)
+                .ok_or(ErrorCode::CalculationOverflow)?;
+        }
+        Ok(())
+    }
+
+    /// Takes clawed-back winnings that were never paid off the queue.
+    pub fn cancel_queued(&mut self, amount: u64) -> Result<()> {
+        self.total_dumbs_queued = self.total_dumbs_queued
+            .checked_sub(amount)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        Ok(())
+    }
+
+    pub fn record_bet_placed(
         &mut self,
-        amount: u32,
+        amount: u64,
         token_type: TokenType,
-    ) -> Result<u32> {  // Returns amount to mint
-        let dumbs_to_mint = match token_type {
-            TokenType::DUMBS => amount,
+        potential_payout: u64,
+    ) -> Result<()> {
+        self.total_bets_placed = self.total_bets_placed
+            .checked_add(1)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+
+        match token_type {
+            TokenType::DUMBS => {
+                self.total_dumbs_wagered = self.total_dumbs_wagered
+                    .checked_add(amount)
+                    .ok_or(ErrorCode::CalculationOverflow)?;
+            }
             TokenType::RAPR => {
-                amount
-                    .checked_mul(self.rapr_multiplier as u32)
-                    .ok_or(ErrorCode::CalculationOverflow)?
+                self.total_rapr_wagered = self.total_rapr_wagered
+                    .checked_add(amount)
+                    .ok_or(ErrorCode::CalculationOverflow)?;
             }
-        };
+        }
 
-        // Update circulation
-        self.total_dumbs_in_circulation = self.total_dumbs_in_circulation
-            .checked_add(dumbs_to_mint as u64)
+        self.total_potential_payout = self.total_potential_payout
+            .checked_add(potential_payout)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+
+        Ok(())
+    }
+
+    pub fn record_bet_settled(
+        &mut self,
+        potential_payout: u64,
+        dumbs_payout: u64,
+    ) -> Result<()> {
+        self.total_bets_settled = self.total_bets_settled
+            .checked_add(1)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+
+        // Winnings are always paid in DUMBS, whichever token was staked
+        self.total_dumbs_won = self.total_dumbs_won
+            .checked_add(dumbs_payout)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+
+        self.total_potential_payout = self.total_potential_payout
+            .checked_sub(potential_payout)
             .ok_or(ErrorCode::CalculationOverflow)?;
 
-        Ok(dumbs_to_mint)
+        Ok(())
     }
 
+    /// Checks a deposit of `amount` lamports can be taken at all. What it
+    /// mints depends on the rate in effect, fixed or oracle, and
+    /// `quote_sol_deposit` checks that for overflow when it applies the rate.
     pub fn validate_sol_deposit(
         &self,
         amount: u64,
     ) -> Result<()> {
-        // Add any validation logic here
         if amount == 0 {
             return Err(ErrorCode::InvalidAmount.into());
         }
@@ -281,16 +642,6 @@ impl BettingState {
             return Err(ErrorCode::ProgramPaused.into());
         }
 
-        // Calculate resulting DUMBS and ensure it won't overflow
-        let fee = self.calculate_fee(amount)?;
-        let deposit_amount = amount
-            .checked_sub(fee)
-            .ok_or(ErrorCode::CalculationOverflow)?;
-        
-        let _dumbs_to_mint = deposit_amount
-            .checked_mul(self.sol_dumbs_rate)
-            .ok_or(ErrorCode::CalculationOverflow)?;
-
         Ok(())
     }
 
@@ -301,86 +652,6 @@ impl BettingState {
     }
 }
 
-impl Bet {
-    pub const LEN: usize = 8 + // discriminator
-        32 + // bettor
-        1 + // token_type
-        4 + // amount
-        4 + // fight_id
-        2 + // odds
-        4 + // potential_payout
-        4 + // fee_amount
-        8 + // timestamp
-        1 + // settled
-        1 + // won
-        8 + // settlement_timestamp
-        4 + // actual_payout
-        2 + // rapr_multiplier
-        1; // bump
-        
-
-    pub fn initialize(
-        &mut self,
-        bettor: Pubkey,
-        token_type: TokenType,
-        amount: u32,
-        fee_amount: u32,
-        fight_id: u32,
-        odds: u16,
-        rapr_multiplier: Option<u16>,
-        bump: u8,
-    ) -> Result<()> {
-        require!(amount > 0, ErrorCode::InvalidAmount);
-        require!(odds > 0, ErrorCode::InvalidOdds);
-        if token_type == TokenType::RAPR {
-            require!(rapr_multiplier.is_some(), ErrorCode::InvalidRaprMultiplier);
-        }
-
-        self.bettor = bettor;
-        self.token_type = token_type;
-        self.amount = amount;
-        self.fee_amount = fee_amount;
-        self.fight_id = fight_id;
-        self.odds = odds;
-        
-        // Calculate potential payout based on token type
-       let base_payout = (amount as u64)
-            .checked_mul(odds as u64)
-            .ok_or(ErrorCode::CalculationOverflow)?
-            .checked_div(100)
-            .ok_or(ErrorCode::CalculationOverflow)? as u32;
-
-
-        self.potential_payout = match token_type {
-            TokenType::RAPR => {
-               base_payout
-                    .checked_mul(rapr_multiplier.unwrap_or(0) as u32)
-                    .ok_or(ErrorCode::CalculationOverflow)?
-            },
-            TokenType::DUMBS => base_payout,
-        };
-
-        self.timestamp = Clock::get()?.unix_timestamp;
-        self.settled = false;
-        self.won = false;
-        self.settlement_timestamp = 0;
-        self.actual_payout = 0;
-        self.rapr_multiplier = rapr_multiplier.unwrap_or(0);
-        self.bump = bump;
-        Ok(())
-    }
-
-    pub fn settle(&mut self, won: bool) -> Result<()> {
-        require!(!self.settled, ErrorCode::BetAlreadySettled);
-        
-        self.settled = true;
-        self.won = won;
-        self.settlement_timestamp = Clock::get()?.unix_timestamp;
-        
-        Ok(())
-    }
-}
-
 impl FightEpoch {
     pub const LEN: usize = 8 +    // discriminator
         4 +                       // epoch_id
@@ -398,4 +669,69 @@ pub struct InitializeBumps {
     pub betting_state: u8,
     pub bet_vault: u8,
     pub treasury: u8,
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(budget: u64, max_supply: u64) -> BettingState {
+        BettingState {
+            dumbs_epoch_budget: budget,
+            dumbs_max_supply: max_supply,
+            ..BettingState::default()
+        }
+    }
+
+    #[test]
+    fn wins_mint_then_draw_on_house_dumbs_then_queue() {
+        let mut state = state(100, u64::MAX);
+        state.keep_stake(30, TokenType::DUMBS).unwrap();
+        state.keep_stake(1_000, TokenType::RAPR).unwrap();
+        assert_eq!(state.house_dumbs, 30);
+
+        let funding = state.fund_win(150, 1).unwrap();
+        assert_eq!(funding, WinFunding { minted: 100, from_vault: 30, queued: 20 });
+        assert_eq!(funding.paid(), 130);
+        assert_eq!((state.dumbs_emitted, state.house_dumbs), (100, 0));
+        assert_eq!(state.total_dumbs_queued, 20);
+        assert_eq!(state.total_dumbs_in_circulation, 100);
+    }
+
+    #[test]
+    fn queued_winnings_wait_for_the_next_epoch() {
+        let mut state = state(100, u64::MAX);
+        state.fund_win(150, 1).unwrap();
+
+        // Nothing is left this epoch
+        assert_eq!(state.release_queued(50, 1).unwrap(), WinFunding { queued: 50, ..WinFunding::default() });
+        assert_eq!(state.total_dumbs_queued, 50);
+
+        let funding = state.release_queued(50, 2).unwrap();
+        assert_eq!(funding, WinFunding { minted: 50, ..WinFunding::default() });
+        assert_eq!((state.emission_epoch, state.dumbs_emitted), (2, 50));
+        assert_eq!(state.total_dumbs_queued, 0);
+    }
+
+    #[test]
+    fn releases_only_take_what_they_pay_off_the_queue() {
+        let mut state = state(100, u64::MAX);
+        state.fund_win(250, 1).unwrap();
+        assert_eq!(state.total_dumbs_queued, 150);
+
+        let funding = state.release_queued(150, 2).unwrap();
+        assert_eq!((funding.paid(), funding.queued), (100, 50));
+        assert_eq!(state.total_dumbs_queued, 50);
+    }
+
+    #[test]
+    fn the_max_supply_caps_minting_across_epochs() {
+        let mut state = state(u64::MAX, 120);
+        assert_eq!(state.fund_win(100, 1).unwrap().minted, 100);
+        assert_eq!(state.mintable_for_wins(2), 20);
+
+        let funding = state.fund_win(50, 2).unwrap();
+        assert_eq!(funding, WinFunding { minted: 20, from_vault: 0, queued: 30 });
+        assert_eq!(state.total_dumbs_in_circulation, 120);
+        assert_eq!(state.mintable_for_wins(3), 0);
+    }
+}
@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+use crate::errors::error_code::ErrorCode;
+
+pub const FIGHT_SEED: &[u8] = b"fight";
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FightStatus {
+    Open,
+    Resolved,
+    Reversed,   // Result overturned through the dispute flow
+}
+
+#[account]
+pub struct Fight {
+    pub betting_state: Pubkey,
+    pub fight_id: u64,
+    pub fighter_one: Pubkey,
+    pub fighter_two: Pubkey,
+    pub status: FightStatus,
+    pub winner: Pubkey,         // Default until the result is posted
+    pub created_at: i64,
+    pub resolved_at: i64,
+    pub overturned_winner: Pubkey, // Winner first posted, once the result is reversed
+    pub reversed_at: i64,
+    pub bump: u8,
+}
+
+impl Fight {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // betting_state
+        8 + // fight_id
+        32 + // fighter_one
+        32 + // fighter_two
+        1 + // status
+        32 + // winner
+        8 + // created_at
+        8 + // resolved_at
+        32 + // overturned_winner
+        8 + // reversed_at
+        1; // bump
+
+    pub fn initialize(
+        &mut self,
+        betting_state: Pubkey,
+        fight_id: u64,
+        fighter_one: Pubkey,
+        fighter_two: Pubkey,
+        bump: u8,
+    ) -> Result<()> {
+        require!(fighter_one != fighter_two, ErrorCode::InvalidFightId);
+
+        self.betting_state = betting_state;
+        self.fight_id = fight_id;
+        self.fighter_one = fighter_one;
+        self.fighter_two = fighter_two;
+        self.status = FightStatus::Open;
+        self.winner = Pubkey::default();
+        self.created_at = Clock::get()?.unix_timestamp;
+        self.resolved_at = 0;
+        self.overturned_winner = Pubkey::default();
+        self.reversed_at = 0;
+        self.bump = bump;
+        Ok(())
+    }
+
+    pub fn resolve(&mut self, winner: Pubkey) -> Result<()> {
+        require!(self.status == FightStatus::Open, ErrorCode::FightAlreadyResolved);
+        require!(
+            winner == self.fighter_one || winner == self.fighter_two,
+            ErrorCode::InvalidWinner
+        );
+
+        self.status = FightStatus::Resolved;
+        self.winner = winner;
+        self.resolved_at = Clock::get()?.unix_timestamp;
+        Ok(())
+    }
+
+    /// Checks a new bet backs one of the fighters before the result is posted.
+    pub fn check_backable(&self, fighter: Pubkey) -> Result<()> {
+        require!(self.status == FightStatus::Open, ErrorCode::FightAlreadyResolved);
+        require!(
+            fighter == self.fighter_one || fighter == self.fighter_two,
+            ErrorCode::InvalidFighter
+        );
+        Ok(())
+    }
+
+    /// The posted winner bets settle against; a reversal replaces it for
+    /// bets not yet settled.
+    pub fn result(&self) -> Result<Pubkey> {
+        require!(self.status != FightStatus::Open, ErrorCode::FightNotResolved);
+        Ok(self.winner)
+    }
+
+    /// Replaces a posted result with `winner`, keeping the overturned one so
+    /// its winnings can be clawed back. A result is reversed at most once.
+    pub fn reverse(&mut self, winner: Pubkey) -> Result<()> {
+        require!(self.status == FightStatus::Resolved, ErrorCode::FightNotResolved);
+        require!(
+            winner != self.winner && (winner == self.fighter_one || winner == self.fighter_two),
+            ErrorCode::InvalidWinner
+        );
+
+        self.status = FightStatus::Reversed;
+        self.overturned_winner = self.winner;
+        self.winner = winner;
+        self.reversed_at = Clock::get()?.unix_timestamp;
+        Ok(())
+    }
+}
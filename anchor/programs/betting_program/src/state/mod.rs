@@ -4,8 +4,19 @@ pub mod sol_vault;
 pub mod bet_vault;
 pub mod bet;
 pub mod rapr_vault;
-pub mod token_types;
 pub mod user_betting_account;
+pub mod roles;
+pub mod pending_change;
+pub mod fight;
+pub mod quote;
+pub mod user_limits;
+pub mod bet_history;
+pub mod token_metadata;
+pub mod token_transfer;
+pub mod win_record;
+pub mod rapr_lock;
+pub mod supported_mint;
+pub mod oracle;
 
 pub use betting_state::*;
 pub use treasury::*;
@@ -13,5 +24,16 @@ pub use sol_vault::*;
 pub use bet_vault::*;
 pub use bet::*;
 pub use rapr_vault::*;
-pub use token_types::*;
 pub use user_betting_account::*;
+pub use roles::*;
+pub use pending_change::*;
+pub use fight::*;
+pub use quote::*;
+pub use user_limits::*;
+pub use bet_history::*;
+pub use token_metadata::*;
+pub use token_transfer::*;
+pub use win_record::*;
+pub use rapr_lock::*;
+pub use supported_mint::*;
+pub use oracle::*;
@@ -0,0 +1,318 @@
+use anchor_lang::prelude::*;
+use crate::errors::error_code::ErrorCode;
+use casino_math::{BasisPoints, Rounded, Rounding};
+use super::betting_state::{BettingState, TokenType};
+
+pub const ORACLE_CONFIG_SEED: &[u8] = b"oracle_config";
+
+/// USD values are reported in millionths of a dollar.
+pub const USD_DECIMALS: u32 = 6;
+
+const SOL_DECIMALS: u32 = 9;
+
+// Pyth v2 price account layout. Only the header and the aggregate price are
+// read; publisher components after them are ignored.
+pub const PYTH_MAGIC: u32 = 0xa1b2_c3d4;
+pub const PYTH_VERSION: u32 = 2;
+pub const PYTH_PRICE_ACCOUNT: u32 = 3;
+pub const PYTH_STATUS_TRADING: u32 = 1;
+pub const PYTH_PRICE_ACCOUNT_LEN: usize = 240;
+
+const MAGIC_OFFSET: usize = 0;
+const VERSION_OFFSET: usize = 4;
+const ACCOUNT_TYPE_OFFSET: usize = 8;
+const EXPO_OFFSET: usize = 20;
+const TIMESTAMP_OFFSET: usize = 96;
+const AGG_PRICE_OFFSET: usize = 208;
+const AGG_CONF_OFFSET: usize = 216;
+const AGG_STATUS_OFFSET: usize = 224;
+
+/// Aggregate price read from a Pyth-style price account: `price · 10^expo`
+/// quote units per base unit, ± `conf` at the same scale.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OraclePrice {
+    pub price: i64,
+    pub conf: u64,
+    pub expo: i32,
+    pub publish_time: i64,
+}
+
+impl OraclePrice {
+    /// Parses a price account's raw data. Only the layout is checked here.
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        require!(data.len() >= PYTH_PRICE_ACCOUNT_LEN, ErrorCode::InvalidOracle);
+        require!(read_u32(data, MAGIC_OFFSET) == PYTH_MAGIC, ErrorCode::InvalidOracle);
+        require!(read_u32(data, VERSION_OFFSET) == PYTH_VERSION, ErrorCode::InvalidOracle);
+        require!(read_u32(data, ACCOUNT_TYPE_OFFSET) == PYTH_PRICE_ACCOUNT, ErrorCode::InvalidOracle);
+        require!(read_u32(data, AGG_STATUS_OFFSET) == PYTH_STATUS_TRADING, ErrorCode::InvalidOracle);
+
+        Ok(Self {
+            price: i64::from_le_bytes(data[AGG_PRICE_OFFSET..AGG_PRICE_OFFSET + 8].try_into().unwrap()),
+            conf: u64::from_le_bytes(data[AGG_CONF_OFFSET..AGG_CONF_OFFSET + 8].try_into().unwrap()),
+            expo: i32::from_le_bytes(data[EXPO_OFFSET..EXPO_OFFSET + 4].try_into().unwrap()),
+            publish_time: i64::from_le_bytes(data[TIMESTAMP_OFFSET..TIMESTAMP_OFFSET + 8].try_into().unwrap()),
+        })
+    }
+
+    /// Rejects a price that is not positive, older than `max_age` seconds at
+    /// `now`, or whose confidence interval is wider than `max_confidence`
+    /// basis points of the price.
+    pub fn validate(&self, now: i64, max_age: i64, max_confidence: u16) -> Result<()> {
+        require!(self.price > 0, ErrorCode::InvalidOracle);
+        require!(now.saturating_sub(self.publish_time) <= max_age, ErrorCode::StaleOraclePrice);
+        require!(
+            self.conf as u128 * 10_000 <= self.price as u128 * max_confidence as u128,
+            ErrorCode::OracleConfidenceTooWide
+        );
+        Ok(())
+    }
+
+    /// USD value of `lamports`, in millionths of a dollar, rounded down.
+    /// The price must be SOL/USD.
+    pub fn usd_value(&self, lamports: u64) -> Result<u64> {
+        let value = lamports as u128 * self.price as u128;
+        // value · 10^expo USD per SOL, scaled from lamports to micro-USD
+        let shift = self.expo as i64 + USD_DECIMALS as i64 - SOL_DECIMALS as i64;
+        let scale = 10u128
+            .checked_pow(shift.unsigned_abs() as u32)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        let usd = if shift >= 0 {
+            value.checked_mul(scale).ok_or(ErrorCode::CalculationOverflow)?
+        } else {
+            value / scale
+        };
+        Ok(u64::try_from(usd).map_err(|_| ErrorCode::CalculationOverflow)?)
+    }
+
+    /// Signed counterpart of `usd_value`, rounding toward zero.
+    pub fn signed_usd_value(&self, lamports: i64) -> Result<i64> {
+        let usd = self.usd_value(lamports.unsigned_abs())?;
+        let usd = i64::try_from(usd).map_err(|_| ErrorCode::CalculationOverflow)?;
+        Ok(if lamports < 0 { -usd } else { usd })
+    }
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+}
+
+/// Which way a user trades a house token against SOL.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RateSide {
+    Buy,  // SOL in, tokens out: deposits and swaps
+    Sell, // Tokens in, SOL out: cash-outs
+}
+
+/// `tokens` base units per `lamports` lamports. SOL and the house tokens
+/// all use 9 decimals, so the rate converts base units directly.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ExchangeRate {
+    pub tokens: u64,
+    pub lamports: u64,
+}
+
+impl ExchangeRate {
+    /// One of the house's fixed SOL rates, `rate` tokens per lamport.
+    pub fn fixed(rate: u64) -> Self {
+        Self { tokens: rate, lamports: 1 }
+    }
+
+    /// SOL/USD over token/USD, each taken at the edge of its confidence
+    /// interval that favours the house on `side`.
+    pub fn from_prices(sol_usd: &OraclePrice, token_usd: &OraclePrice, side: RateSide) -> Result<Self> {
+        let (sol, token) = match side {
+            RateSide::Buy => (
+                (sol_usd.price as u64).checked_sub(sol_usd.conf),
+                (token_usd.price as u64).checked_add(token_usd.conf),
+            ),
+            RateSide::Sell => (
+                (sol_usd.price as u64).checked_add(sol_usd.conf),
+                (token_usd.price as u64).checked_sub(token_usd.conf),
+            ),
+        };
+        let (Some(sol), Some(token)) = (sol, token) else {
+            return err!(ErrorCode::InvalidOracle);
+        };
+        require!(sol > 0 && token > 0, ErrorCode::InvalidOracle);
+
+        // Bring both prices to the smaller exponent
+        let shift = sol_usd.expo as i64 - token_usd.expo as i64;
+        let scale = 10u64
+            .checked_pow(shift.unsigned_abs() as u32)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        let (tokens, lamports) = if shift >= 0 {
+            (sol.checked_mul(scale).ok_or(ErrorCode::CalculationOverflow)?, token)
+        } else {
+            (sol, token.checked_mul(scale).ok_or(ErrorCode::CalculationOverflow)?)
+        };
+        Ok(Self { tokens, lamports })
+    }
+
+    /// Tokens `lamports` buys.
+    pub fn to_tokens(&self, lamports: u64, rounding: Rounding) -> Result<Rounded> {
+        Ok(casino_math::mul_div(lamports, self.tokens, self.lamports, rounding).map_err(ErrorCode::from)?)
+    }
+
+    /// Lamports `tokens` are worth.
+    pub fn to_lamports(&self, tokens: u64, rounding: Rounding) -> Result<Rounded> {
+        Ok(casino_math::mul_div(tokens, self.lamports, self.tokens, rounding).map_err(ErrorCode::from)?)
+    }
+}
+
+/// Settings an admin chooses for the house's price oracle.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OracleParams {
+    pub oracle_program: Pubkey,     // Owner every price account must have, e.g. Pyth's
+    pub sol_usd_feed: Pubkey,
+    pub dumbs_usd_feed: Pubkey,
+    pub rapr_usd_feed: Pubkey,
+    pub max_age: i64,               // Seconds a price stays usable after publishing
+    pub max_confidence: u16,        // Widest confidence interval, in basis points of the price
+    pub oracle_rates: bool,         // Price deposits, swaps and cash-outs through the feeds
+}
+
+impl OracleParams {
+    pub fn validate(&self) -> Result<()> {
+        require!(self.max_age > 0, ErrorCode::InvalidConfigValue);
+        require!(self.max_confidence > 0 && self.max_confidence <= 10_000, ErrorCode::InvalidConfigValue);
+        require!(
+            !self.oracle_rates || (self.dumbs_usd_feed != Pubkey::default() && self.rapr_usd_feed != Pubkey::default()),
+            ErrorCode::InvalidConfigValue
+        );
+        Ok(())
+    }
+}
+
+/// The price feeds a house values its SOL in, one per house. With oracle
+/// rates on, DUMBS and RAPR trade against SOL at the ratio of their USD
+/// prices instead of the fixed SOL rates. It is created empty, and its
+/// settings only change through a timelocked `ConfigChange::Oracle`.
+#[account]
+pub struct OracleConfig {
+    pub betting_state: Pubkey,
+    pub oracle_program: Pubkey,
+    pub sol_usd_feed: Pubkey,
+    pub dumbs_usd_feed: Pubkey,
+    pub rapr_usd_feed: Pubkey,
+    pub max_age: i64,
+    pub max_confidence: u16,
+    pub oracle_rates: bool,
+    pub bump: u8,
+}
+
+impl OracleConfig {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // betting_state
+        32 + // oracle_program
+        32 + // sol_usd_feed
+        32 + // dumbs_usd_feed
+        32 + // rapr_usd_feed
+        8 + // max_age
+        2 + // max_confidence
+        1 + // oracle_rates
+        1; // bump
+
+    /// Applies settings already checked by `OracleParams::validate` when
+    /// the change was queued.
+    pub fn set_params(&mut self, params: OracleParams) {
+        self.oracle_program = params.oracle_program;
+        self.sol_usd_feed = params.sol_usd_feed;
+        self.dumbs_usd_feed = params.dumbs_usd_feed;
+        self.rapr_usd_feed = params.rapr_usd_feed;
+        self.max_age = params.max_age;
+        self.max_confidence = params.max_confidence;
+        self.oracle_rates = params.oracle_rates;
+    }
+
+    pub fn params(&self) -> OracleParams {
+        OracleParams {
+            oracle_program: self.oracle_program,
+            sol_usd_feed: self.sol_usd_feed,
+            dumbs_usd_feed: self.dumbs_usd_feed,
+            rapr_usd_feed: self.rapr_usd_feed,
+            max_age: self.max_age,
+            max_confidence: self.max_confidence,
+            oracle_rates: self.oracle_rates,
+        }
+    }
+
+    /// USD price feed for `token_type`.
+    pub fn token_usd_feed(&self, token_type: TokenType) -> Pubkey {
+        match token_type {
+            TokenType::DUMBS => self.dumbs_usd_feed,
+            TokenType::RAPR => self.rapr_usd_feed,
+        }
+    }
+
+    /// Reads and validates the SOL/USD price from `feed`.
+    pub fn sol_usd_price(&self, feed: &AccountInfo, now: i64) -> Result<OraclePrice> {
+        require_keys_eq!(feed.key(), self.sol_usd_feed, ErrorCode::InvalidOracle);
+        load_price(feed, &self.oracle_program, now, self.max_age, self.max_confidence)
+    }
+
+    /// Oracle rate for trading `token_type` on `side`.
+    pub fn exchange_rate(
+        &self,
+        sol_usd_feed: &AccountInfo,
+        token_usd_feed: &AccountInfo,
+        token_type: TokenType,
+        side: RateSide,
+        now: i64,
+    ) -> Result<ExchangeRate> {
+        let sol_usd = self.sol_usd_price(sol_usd_feed, now)?;
+        require_keys_eq!(token_usd_feed.key(), self.token_usd_feed(token_type), ErrorCode::InvalidOracle);
+        let token_usd = load_price(token_usd_feed, &self.oracle_program, now, self.max_age, self.max_confidence)?;
+        ExchangeRate::from_prices(&sol_usd, &token_usd, side)
+    }
+}
+
+/// Rate `token_type` trades against SOL at on `side`. `oracle_config` must
+/// already be checked to be the house's config PDA; unless it exists with
+/// oracle rates on, this is the fixed SOL rate. Trades at this rate bound it
+/// against the caller's quote with `check_slippage`.
+pub fn exchange_rate(
+    betting_state: &BettingState,
+    oracle_config: &AccountInfo,
+    sol_usd_feed: &AccountInfo,
+    token_usd_feed: &AccountInfo,
+    token_type: TokenType,
+    side: RateSide,
+) -> Result<ExchangeRate> {
+    if oracle_config.owner != &crate::ID {
+        return Ok(betting_state.fixed_rate(token_type));
+    }
+    let config = OracleConfig::try_deserialize(&mut &oracle_config.try_borrow_data()?[..])?;
+    if !config.oracle_rates {
+        return Ok(betting_state.fixed_rate(token_type));
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    config.exchange_rate(sol_usd_feed, token_usd_feed, token_type, side, now)
+}
+
+/// Reads a price account owned by `oracle_program` and rejects stale or
+/// low-confidence prices.
+pub fn load_price(
+    feed: &AccountInfo,
+    oracle_program: &Pubkey,
+    now: i64,
+    max_age: i64,
+    max_confidence: u16,
+) -> Result<OraclePrice> {
+    require_keys_eq!(*feed.owner, *oracle_program, ErrorCode::InvalidOracle);
+    let price = OraclePrice::parse(&feed.try_borrow_data()?)?;
+    price.validate(now, max_age, max_confidence)?;
+    Ok(price)
+}
+
+/// Fails with `SlippageExceeded` if a trade returning `out` falls more than
+/// `max_slippage` basis points short of `quoted`, what the caller's quote of
+/// the same trade returned. With the amount in fixed, this bounds how far the
+/// rate may have moved against the caller since the quote.
+pub fn check_slippage(out: u64, quoted: u64, max_slippage: u16) -> Result<()> {
+    let tolerance = BasisPoints::fraction(max_slippage as u32)
+        .and_then(|bps| bps.apply(quoted, Rounding::Down))
+        .map_err(ErrorCode::from)?;
+    require!(out >= quoted - tolerance.value, ErrorCode::SlippageExceeded);
+    Ok(())
+}
@@ -0,0 +1,136 @@
+use anchor_lang::prelude::*;
+use super::betting_state::BettingState;
+use super::oracle::OracleParams;
+use crate::errors::error_code::ErrorCode;
+use casino_math::BasisPoints;
+
+pub const PENDING_CHANGE_SEED: &[u8] = b"pending_change";
+pub const CONFIG_CHANGE_DELAY: i64 = 48 * 60 * 60; // 48 hours notice before a change applies
+pub const MAX_HOUSE_FEE: u32 = 1_000;              // 10% in basis points
+pub const ORACLE_CHANGE_KIND: u8 = 8;              // ConfigChange::Oracle, one per house
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConfigChange {
+    HouseFee(u32),
+    RaprMultiplier(u64),
+    SolDumbsRate(u64),
+    SolRaprRate(u64),
+    MaxBet(u64),
+    DumbsMaxSupply(u64),
+    DumbsEpochBudget(u64),
+    MintPrice { mint: Pubkey, lamports_per_token: u64, usd_feed: Pubkey },
+    Oracle(OracleParams),
+}
+
+impl ConfigChange {
+    /// Seed byte so that each parameter has at most one pending change
+    pub fn kind(&self) -> u8 {
+        match self {
+            ConfigChange::HouseFee(_) => 0,
+            ConfigChange::RaprMultiplier(_) => 1,
+            ConfigChange::SolDumbsRate(_) => 2,
+            ConfigChange::SolRaprRate(_) => 3,
+            ConfigChange::MaxBet(_) => 4,
+            ConfigChange::DumbsMaxSupply(_) => 5,
+            ConfigChange::DumbsEpochBudget(_) => 6,
+            ConfigChange::MintPrice { .. } => 7,
+            ConfigChange::Oracle(_) => ORACLE_CHANGE_KIND,
+        }
+    }
+
+    /// Seed after the kind, so that a change to a registered mint's price is
+    /// pending per mint. Empty for house-wide changes.
+    pub fn target_seed(&self) -> &[u8] {
+        match self {
+            ConfigChange::MintPrice { mint, .. } => mint.as_ref(),
+            _ => &[],
+        }
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        match *self {
+            ConfigChange::HouseFee(fee) => {
+                require!(fee <= MAX_HOUSE_FEE, ErrorCode::InvalidConfigValue);
+            }
+            ConfigChange::RaprMultiplier(value) => {
+                // Basis points, and never below 1x so boosted odds stay valid
+                require!(
+                    value >= BasisPoints::ONE.get() as u64 && BasisPoints::from_u64(value).is_ok(),
+                    ErrorCode::InvalidConfigValue
+                );
+            }
+            ConfigChange::SolDumbsRate(value)
+            | ConfigChange::SolRaprRate(value)
+            | ConfigChange::MaxBet(value)
+            | ConfigChange::DumbsMaxSupply(value)
+            | ConfigChange::DumbsEpochBudget(value) => {
+                require!(value > 0, ErrorCode::InvalidConfigValue);
+            }
+            ConfigChange::MintPrice { lamports_per_token, .. } => {
+                require!(lamports_per_token > 0, ErrorCode::InvalidConfigValue);
+            }
+            ConfigChange::Oracle(params) => params.validate()?,
+        }
+        Ok(())
+    }
+
+    pub fn apply(&self, betting_state: &mut BettingState) {
+        match *self {
+            ConfigChange::HouseFee(fee) => betting_state.house_fee = fee,
+            ConfigChange::RaprMultiplier(value) => betting_state.rapr_multiplier = value,
+            ConfigChange::SolDumbsRate(value) => betting_state.sol_dumbs_rate = value,
+            ConfigChange::SolRaprRate(value) => betting_state.sol_rapr_rate = value,
+            ConfigChange::MaxBet(value) => betting_state.max_bet = value,
+            ConfigChange::DumbsMaxSupply(value) => betting_state.dumbs_max_supply = value,
+            ConfigChange::DumbsEpochBudget(value) => betting_state.dumbs_epoch_budget = value,
+            // Applied to the mint's registry entry or the house's oracle
+            // config by execute_change
+            ConfigChange::MintPrice { .. } | ConfigChange::Oracle(_) => {}
+        }
+    }
+}
+
+#[account]
+pub struct PendingConfigChange {
+    pub betting_state: Pubkey,
+    pub proposer: Pubkey,           // Receives the rent back on execute or cancel
+    pub change: ConfigChange,
+    pub queued_at: i64,
+    pub eta: i64,                   // Earliest time the change can be executed
+    pub bump: u8,
+}
+
+impl PendingConfigChange {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // betting_state
+        32 + // proposer
+        1 + 139 + // change, sized for Oracle
+        8 + // queued_at
+        8 + // eta
+        1; // bump
+
+    pub fn initialize(
+        &mut self,
+        betting_state: Pubkey,
+        proposer: Pubkey,
+        change: ConfigChange,
+        bump: u8,
+    ) -> Result<()> {
+        change.validate()?;
+
+        let now = Clock::get()?.unix_timestamp;
+        self.betting_state = betting_state;
+        self.proposer = proposer;
+        self.change = change;
+        self.queued_at = now;
+        self.eta = now
+            .checked_add(CONFIG_CHANGE_DELAY)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        self.bump = bump;
+        Ok(())
+    }
+
+    pub fn is_ready(&self, now: i64) -> bool {
+        now >= self.eta
+    }
+}
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use super::betting_state::BetTerms;
+use super::oracle::OraclePrice;
+
+// Returned by the quote_* instructions through return data. `dust` is the
+// rounding kept by the house, in ten-thousandths of a base unit.
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BetQuote {
+    pub fee: u64,
+    pub net_amount: u64,       // Stake after the fee
+    pub odds: u64,             // Adjusted odds in basis points, boosted for RAPR
+    pub potential_payout: u64, // DUMBS minted if the bet wins
+    pub dust: u64,
+}
+
+impl From<BetTerms> for BetQuote {
+    fn from(terms: BetTerms) -> Self {
+        Self {
+            fee: terms.fee,
+            net_amount: terms.stake,
+            odds: terms.odds.bps() as u64,
+            potential_payout: terms.potential_payout,
+            dust: terms.dust,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DepositQuote {
+    pub fee: u64,              // Lamports sent to the treasury
+    pub net_amount: u64,       // Lamports backing the minted DUMBS
+    pub dumbs_minted: u64,
+    pub dust: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CashOutQuote {
+    pub fee: u64,              // In the token cashed out
+    pub net_amount: u64,       // Tokens converted back to SOL
+    pub sol_returned: u64,
+    pub dust: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SwapQuote {
+    pub fee: u64,
+    pub net_amount: u64,       // Lamports swapped
+    pub rapr_minted: u64,
+}
+
+/// House P&L in SOL and USD, valued at the oracle's SOL/USD price. USD
+/// amounts are in millionths of a dollar.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HousePnl {
+    pub sol_usd: OraclePrice,
+    pub fees: u64,                 // Treasury fees, in lamports of SOL value
+    pub fees_usd: u64,
+    pub house_edge: u64,           // Stakes kept by the house, in lamports of SOL value
+    pub house_edge_usd: u64,
+    pub backing: u64,              // SOL vault balance
+    pub backing_usd: u64,
+    pub liabilities: u64,          // SOL value of DUMBS and RAPR in circulation
+    pub liabilities_usd: u64,
+    pub net: i64,                  // Backing less liabilities
+    pub net_usd: i64,
+}
@@ -0,0 +1,172 @@
+use anchor_lang::prelude::*;
+use casino_math::{BasisPoints, Rounded};
+use crate::errors::error_code::ErrorCode;
+use super::betting_state::TokenType;
+use super::user_betting_account::UserBettingAccount;
+
+pub const RAPR_LOCK_SEED: &[u8] = b"rapr_lock";
+pub const MIN_RAPR_LOCK: u64 = 1_000_000_000_000;   // 1,000 RAPR
+pub const MAX_LOCK_BOOST: u32 = 5_000;              // +50% on the RAPR multiplier at 90 days left
+pub const EARLY_UNLOCK_PENALTY: u32 = 1_000;        // 10% of the locked RAPR, in basis points
+
+const DAY: i64 = 24 * 60 * 60;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LockPeriod {
+    Week,
+    Month,
+    Quarter,
+}
+
+impl LockPeriod {
+    pub const LONGEST: i64 = 90 * DAY;
+
+    pub fn duration(self) -> i64 {
+        match self {
+            LockPeriod::Week => 7 * DAY,
+            LockPeriod::Month => 30 * DAY,
+            LockPeriod::Quarter => 90 * DAY,
+        }
+    }
+}
+
+/// RAPR a user has locked in the RAPR vault for a chosen period. While it
+/// runs, their RAPR bets get a boost on the RAPR multiplier weighted by the
+/// time left, so longer locks start higher and every lock decays to nothing
+/// at `unlocks_at`. Only the part of a stake the locked amount covers is
+/// boosted.
+#[account]
+pub struct RaprLock {
+    pub owner: Pubkey,
+    pub amount: u64,                // RAPR held for the owner in the RAPR vault
+    pub period: LockPeriod,
+    pub locked_at: i64,
+    pub unlocks_at: i64,
+    pub bump: u8,
+}
+
+impl RaprLock {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // owner
+        8 + // amount
+        1 + // period
+        8 + // locked_at
+        8 + // unlocks_at
+        1; // bump
+
+    pub fn initialize(&mut self, owner: Pubkey, amount: u64, period: LockPeriod, now: i64, bump: u8) -> Result<()> {
+        require!(amount >= MIN_RAPR_LOCK, ErrorCode::LockTooSmall);
+        self.owner = owner;
+        self.amount = amount;
+        self.period = period;
+        self.locked_at = now;
+        self.unlocks_at = now.checked_add(period.duration()).ok_or(ErrorCode::CalculationOverflow)?;
+        self.bump = bump;
+        Ok(())
+    }
+
+    /// Boost on the RAPR multiplier for a bet of `stake` at `now`:
+    /// `MAX_LOCK_BOOST` scaled by the time left over the longest period and
+    /// by the share of the stake the locked amount covers, rounded down.
+    pub fn boost_at(&self, now: i64, stake: u64) -> BasisPoints {
+        if stake == 0 {
+            return BasisPoints::ZERO;
+        }
+        let remaining = self.unlocks_at.saturating_sub(now).clamp(0, LockPeriod::LONGEST) as u128;
+        let covered = self.amount.min(stake) as u128;
+        let boost = MAX_LOCK_BOOST as u128 * remaining * covered / (LockPeriod::LONGEST as u128 * stake as u128);
+        BasisPoints::new(boost as u32)
+    }
+
+    /// Penalty for unlocking at `now`, rounded up; nothing once the lock ends.
+    pub fn penalty_at(&self, now: i64) -> Result<Rounded> {
+        if now >= self.unlocks_at {
+            return Ok(Rounded { value: 0, dust: 0 });
+        }
+        Ok(BasisPoints::new(EARLY_UNLOCK_PENALTY).fee_on(self.amount).map_err(ErrorCode::from)?)
+    }
+}
+
+/// Boost `account` gives its owner's RAPR bet of `stake` at `now`. `account`
+/// must already be checked to be the owner's lock PDA; if it was never
+/// created there is no boost.
+pub fn lock_boost(account: &AccountInfo, now: i64, stake: u64) -> Result<BasisPoints> {
+    if account.owner != &crate::ID {
+        return Ok(BasisPoints::ZERO);
+    }
+    let lock = RaprLock::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+    Ok(lock.boost_at(now, stake))
+}
+
+/// Refuses to release a lock while the owner has an open RAPR bet, whose odds
+/// the lock may have boosted. `account` must already be checked to be the
+/// owner's betting account PDA; if it was never created no bet is open.
+pub fn require_no_open_rapr_bet(account: &AccountInfo) -> Result<()> {
+    if account.owner != &crate::ID {
+        return Ok(());
+    }
+    let user_account = UserBettingAccount::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+    let open = user_account
+        .active_bet
+        .is_some_and(|bet| bet.token_type == TokenType::RAPR && !bet.settled);
+    require!(!open, ErrorCode::LockBacksOpenBet);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_700_000_000;
+
+    fn lock(amount: u64, period: LockPeriod) -> RaprLock {
+        let mut lock = RaprLock {
+            owner: Pubkey::default(),
+            amount: 0,
+            period,
+            locked_at: 0,
+            unlocks_at: 0,
+            bump: 0,
+        };
+        lock.initialize(Pubkey::default(), amount, period, NOW, 0).unwrap();
+        lock
+    }
+
+    #[test]
+    fn locks_below_the_minimum_are_refused() {
+        let mut lock = lock(MIN_RAPR_LOCK, LockPeriod::Week);
+        let result = lock.initialize(Pubkey::default(), MIN_RAPR_LOCK - 1, LockPeriod::Week, NOW, 0);
+        assert_eq!(result.unwrap_err(), ErrorCode::LockTooSmall.into());
+    }
+
+    #[test]
+    fn the_boost_decays_with_the_time_left() {
+        let quarter = lock(MIN_RAPR_LOCK, LockPeriod::Quarter);
+        assert_eq!(quarter.boost_at(NOW, MIN_RAPR_LOCK).get(), MAX_LOCK_BOOST);
+        assert_eq!(quarter.boost_at(NOW + 45 * DAY, MIN_RAPR_LOCK).get(), MAX_LOCK_BOOST / 2);
+        assert_eq!(quarter.boost_at(quarter.unlocks_at, MIN_RAPR_LOCK), BasisPoints::ZERO);
+        assert_eq!(quarter.boost_at(quarter.unlocks_at + DAY, MIN_RAPR_LOCK), BasisPoints::ZERO);
+
+        // Shorter locks start lower, rounded down
+        let week = lock(MIN_RAPR_LOCK, LockPeriod::Week);
+        assert_eq!(week.boost_at(NOW, MIN_RAPR_LOCK).get(), MAX_LOCK_BOOST * 7 / 90);
+    }
+
+    #[test]
+    fn the_boost_covers_only_the_locked_share_of_the_stake() {
+        let quarter = lock(MIN_RAPR_LOCK, LockPeriod::Quarter);
+        assert_eq!(quarter.boost_at(NOW, MIN_RAPR_LOCK / 2).get(), MAX_LOCK_BOOST);
+        assert_eq!(quarter.boost_at(NOW, 2 * MIN_RAPR_LOCK).get(), MAX_LOCK_BOOST / 2);
+        assert_eq!(quarter.boost_at(NOW, 3 * MIN_RAPR_LOCK).get(), MAX_LOCK_BOOST / 3);
+        assert_eq!(quarter.boost_at(NOW, 0), BasisPoints::ZERO);
+    }
+
+    #[test]
+    fn early_unlocks_pay_the_penalty_rounded_up() {
+        let month = lock(MIN_RAPR_LOCK + 1, LockPeriod::Month);
+        let penalty = month.penalty_at(NOW).unwrap();
+        assert_eq!(penalty.value, MIN_RAPR_LOCK / 10 + 1);
+        assert_eq!(month.penalty_at(month.unlocks_at - 1).unwrap(), penalty);
+        assert_eq!(month.penalty_at(month.unlocks_at).unwrap().value, 0);
+    }
+}
@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use crate::errors::error_code::ErrorCode;
+
+pub const ROLES_SEED: &[u8] = b"roles";
+pub const MAX_ROLE_MEMBERS: usize = 16;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Role {
+    Admin,      // Grants and revokes roles
+    Settler,    // Posts fight results and settles bets (oracle)
+    Treasurer,  // Withdraws collected fees
+    Pauser,     // Pauses and unpauses the house
+    OddsMaker,  // Sets odds and fight parameters
+}
+
+impl Role {
+    pub fn mask(self) -> u8 {
+        1 << (self as u8)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default)]
+pub struct RoleMember {
+    pub member: Pubkey,
+    pub roles: u8,              // Bitmask of Role::mask() values
+}
+
+#[account]
+pub struct RoleRegistry {
+    pub betting_state: Pubkey,
+    pub members: Vec<RoleMember>,
+    pub bump: u8,
+}
+
+impl RoleRegistry {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // betting_state
+        4 + MAX_ROLE_MEMBERS * (32 + 1) + // members
+        1; // bump
+
+    pub fn initialize(
+        &mut self,
+        betting_state: Pubkey,
+        admin: Pubkey,
+        bump: u8,
+    ) {
+        self.betting_state = betting_state;
+        self.members = vec![RoleMember {
+            member: admin,
+            roles: Role::Admin.mask(),
+        }];
+        self.bump = bump;
+    }
+
+    pub fn has_role(&self, member: &Pubkey, role: Role) -> bool {
+        self.members
+            .iter()
+            .any(|m| m.member == *member && m.roles & role.mask() != 0)
+    }
+
+    pub fn grant(&mut self, member: Pubkey, role: Role) -> Result<()> {
+        if let Some(entry) = self.members.iter_mut().find(|m| m.member == member) {
+            entry.roles |= role.mask();
+            return Ok(());
+        }
+
+        require!(self.members.len() < MAX_ROLE_MEMBERS, ErrorCode::RoleRegistryFull);
+        self.members.push(RoleMember {
+            member,
+            roles: role.mask(),
+        });
+        Ok(())
+    }
+
+    pub fn revoke(&mut self, member: Pubkey, role: Role) -> Result<()> {
+        require!(self.has_role(&member, role), ErrorCode::MissingRole);
+
+        // The house must always keep at least one admin
+        if role == Role::Admin {
+            let admins = self.members.iter().filter(|m| m.roles & Role::Admin.mask() != 0).count();
+            require!(admins > 1, ErrorCode::CannotRevokeLastAdmin);
+        }
+
+        if let Some(entry) = self.members.iter_mut().find(|m| m.member == member) {
+            entry.roles &= !role.mask();
+        }
+        self.members.retain(|m| m.roles != 0);
+        Ok(())
+    }
+}
@@ -26,10 +26,8 @@ impl SolVault {
         8 + // max_deposit_amount
         1; // bump
 
-    pub fn process_deposit(
-        &mut self,
-        sol_amount: u64,
-    ) -> Result<()> {
+    /// Deposits and swaps must fall within the vault's limits.
+    pub fn check_deposit_limits(&self, sol_amount: u64) -> Result<()> {
         require!(
             sol_amount >= self.min_deposit_amount,
             ErrorCode::AmountTooSmall
@@ -38,6 +36,14 @@ impl SolVault {
             sol_amount <= self.max_deposit_amount,
             ErrorCode::AmountTooLarge
         );
+        Ok(())
+    }
+
+    pub fn process_deposit(
+        &mut self,
+        sol_amount: u64,
+    ) -> Result<()> {
+        self.check_deposit_limits(sol_amount)?;
 
         // Update balance and stats
         self.balance = self.balance
@@ -71,4 +77,32 @@ impl SolVault {
         self.max_deposit_amount = max_amount;
         self.bump = bump;
     }
-}
\ No newline at end of file
+}
+
+/// Pays `amount` lamports out of the SOL vault to `to`. The vault is
+/// program-owned, so lamports are moved directly rather than through the
+/// system program, and never below its rent-exempt minimum.
+pub fn pay_from_sol_vault<'info>(vault: &mut Account<'info, SolVault>, to: &AccountInfo<'info>, amount: u64) -> Result<()> {
+    require!(vault.balance >= amount, ErrorCode::InsufficientSolBalance);
+    move_sol_vault_lamports(&vault.to_account_info(), to, amount)?;
+
+    vault.balance = vault.balance
+        .checked_sub(amount)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+    Ok(())
+}
+
+/// Pays `amount` lamports held in the SOL vault outside its DUMBS `balance`,
+/// such as a registered mint's `sol_liquidity`. The caller accounts for them.
+pub fn move_sol_vault_lamports<'info>(vault_info: &AccountInfo<'info>, to: &AccountInfo<'info>, amount: u64) -> Result<()> {
+    let rent_exempt = Rent::get()?.minimum_balance(vault_info.data_len());
+    let available = vault_info
+        .lamports()
+        .checked_sub(rent_exempt)
+        .ok_or(ErrorCode::InsufficientSolBalance)?;
+    require!(amount <= available, ErrorCode::InsufficientSolBalance);
+
+    **vault_info.try_borrow_mut_lamports()? -= amount;
+    **to.try_borrow_mut_lamports()? += amount;
+    Ok(())
+}
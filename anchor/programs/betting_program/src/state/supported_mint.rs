@@ -0,0 +1,308 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions},
+    state::Mint,
+};
+use casino_math::{BasisPoints, Odds, Rounding};
+use super::betting_state::{BetTerms, BettingState};
+use super::oracle::{load_price, ExchangeRate, OracleConfig, RateSide};
+use super::quote::CashOutQuote;
+use crate::errors::error_code::ErrorCode;
+
+pub const SUPPORTED_MINT_SEED: &[u8] = b"supported_mint";
+pub const MINT_VAULT_SEED: &[u8] = b"mint_vault";
+pub const ASSET_BET_SEED: &[u8] = b"asset_bet";
+
+/// Extensions that would let a registered mint skim or seize vault tokens,
+/// or move them only with accounts the generic instructions do not pass.
+const UNSUPPORTED_EXTENSIONS: [ExtensionType; 4] = [
+    ExtensionType::TransferFeeConfig,
+    ExtensionType::TransferHook,
+    ExtensionType::PermanentDelegate,
+    ExtensionType::NonTransferable,
+];
+
+/// Rejects Token-2022 mints with extensions the asset instructions cannot
+/// handle. Legacy SPL mints have no extensions.
+pub fn check_supported_mint(mint: &AccountInfo) -> Result<()> {
+    if mint.owner != &spl_token_2022::ID {
+        return Ok(());
+    }
+    let data = mint.try_borrow_data()?;
+    let extensions = StateWithExtensions::<Mint>::unpack(&data)?.get_extension_types()?;
+    require!(
+        !extensions.iter().any(|extension| UNSUPPORTED_EXTENSIONS.contains(extension)),
+        ErrorCode::UnsupportedMint
+    );
+    Ok(())
+}
+
+/// Settings an admin chooses for a registered mint. Its price only changes
+/// through the timelock, as `ConfigChange::MintPrice`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SupportedMintParams {
+    pub multiplier: u32,            // Odds multiplier in basis points, 10_000 = 1x
+    pub min_bet: u64,
+    pub max_bet: u64,
+    pub enabled: bool,
+}
+
+/// A mint the house takes bets in besides DUMBS and RAPR, one per house and
+/// mint. Stakes, fees and cash-outs go into the mint's vault, which pays
+/// winnings in the same mint, so the house must keep it funded. Cash-outs
+/// are paid in SOL only from the mint's own `sol_liquidity`, never from what
+/// backs DUMBS. A mint takes no bets or cash-outs until its first price
+/// change executes.
+#[account]
+pub struct SupportedMint {
+    pub betting_state: Pubkey,
+    pub mint: Pubkey,
+    pub token_program: Pubkey,      // Token-2022 or the legacy SPL Token program
+    pub vault: Pubkey,
+    pub decimals: u8,
+    pub multiplier: u32,
+    pub min_bet: u64,
+    pub max_bet: u64,
+    pub lamports_per_token: u64,    // SOL value of one whole token, zero until priced
+    pub usd_feed: Pubkey,           // Token/USD price account cash-outs use, default for the fixed value
+    pub enabled: bool,
+    pub open_liability: u64,        // Potential payouts of open bets the vault must cover
+    pub total_staked: u64,
+    pub total_paid: u64,
+    pub fees_collected: u64,        // Bet and cash-out fees kept in the vault
+    pub sol_liquidity: u64,         // Lamports set aside in the SOL vault for this mint's cash-outs
+    pub bump: u8,
+    pub vault_bump: u8,
+}
+
+impl SupportedMint {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // betting_state
+        32 + // mint
+        32 + // token_program
+        32 + // vault
+        1 + // decimals
+        4 + // multiplier
+        8 + // min_bet
+        8 + // max_bet
+        8 + // lamports_per_token
+        32 + // usd_feed
+        1 + // enabled
+        8 + // open_liability
+        8 + // total_staked
+        8 + // total_paid
+        8 + // fees_collected
+        8 + // sol_liquidity
+        1 + // bump
+        1; // vault_bump
+
+    pub fn set_params(&mut self, params: SupportedMintParams) -> Result<()> {
+        require!(params.multiplier > 0, ErrorCode::InvalidConfigValue);
+        require!(params.min_bet > 0 && params.min_bet <= params.max_bet, ErrorCode::InvalidConfigValue);
+        self.multiplier = params.multiplier;
+        self.min_bet = params.min_bet;
+        self.max_bet = params.max_bet;
+        self.enabled = params.enabled;
+        Ok(())
+    }
+
+    /// Applies an executed `ConfigChange::MintPrice`.
+    pub fn set_price(&mut self, lamports_per_token: u64, usd_feed: Pubkey) {
+        self.lamports_per_token = lamports_per_token;
+        self.usd_feed = usd_feed;
+    }
+
+    pub fn params(&self) -> SupportedMintParams {
+        SupportedMintParams {
+            multiplier: self.multiplier,
+            min_bet: self.min_bet,
+            max_bet: self.max_bet,
+            enabled: self.enabled,
+        }
+    }
+
+    /// SOL value of `amount` base units at the fixed value, in lamports.
+    /// Limits count asset bets at this value.
+    pub fn sol_value(&self, amount: u64, rounding: Rounding) -> Result<u64> {
+        let one_token = 10u64.checked_pow(self.decimals as u32).ok_or(ErrorCode::CalculationOverflow)?;
+        Ok(casino_math::mul_div(amount, self.lamports_per_token, one_token, rounding).map_err(ErrorCode::from)?.value)
+    }
+
+    /// Prices a bet of `amount` at `base_odds` with the house fee, raising
+    /// the odds by the mint's multiplier.
+    pub fn quote_bet(&self, betting_state: &BettingState, amount: u64, base_odds: u64) -> Result<BetTerms> {
+        require!(self.enabled, ErrorCode::MintDisabled);
+        require!(self.lamports_per_token > 0, ErrorCode::MintNotPriced);
+        require!(amount >= self.min_bet, ErrorCode::AmountTooSmall);
+        require!(amount <= self.max_bet, ErrorCode::BetTooLarge);
+
+        let fee = betting_state.calculate_fee(amount)?;
+        let stake = amount.checked_sub(fee.value).ok_or(ErrorCode::CalculationOverflow)?;
+        let multiplier = BasisPoints::from_u64(self.multiplier as u64).map_err(ErrorCode::from)?;
+        let odds = Odds::from_u64(base_odds)
+            .and_then(|odds| odds.boost(multiplier))
+            .map_err(ErrorCode::from)?;
+        let potential_payout = betting_state.calculate_payout(stake, odds)?;
+
+        Ok(BetTerms {
+            fee: fee.value,
+            stake,
+            odds,
+            potential_payout: potential_payout.value,
+            rapr_multiplier: None,
+            dust: fee.dust + potential_payout.dust,
+        })
+    }
+
+    /// Lamports one whole token sells back to the house for. With a USD
+    /// feed this is the ratio of the token/USD and SOL/USD prices at the edge
+    /// of their confidence intervals that favours the house; `oracle_config`
+    /// must already be checked to be the house's config PDA. Without one it
+    /// is the fixed value.
+    pub fn cash_out_price(
+        &self,
+        oracle_config: &AccountInfo,
+        sol_usd_feed: &AccountInfo,
+        token_usd_feed: &AccountInfo,
+        now: i64,
+    ) -> Result<u64> {
+        require!(self.lamports_per_token > 0, ErrorCode::MintNotPriced);
+        if self.usd_feed == Pubkey::default() {
+            return Ok(self.lamports_per_token);
+        }
+        require!(oracle_config.owner == &crate::ID, ErrorCode::InvalidOracle);
+        let config = OracleConfig::try_deserialize(&mut &oracle_config.try_borrow_data()?[..])?;
+        let sol_usd = config.sol_usd_price(sol_usd_feed, now)?;
+        require_keys_eq!(token_usd_feed.key(), self.usd_feed, ErrorCode::InvalidOracle);
+        let token_usd = load_price(token_usd_feed, &config.oracle_program, now, config.max_age, config.max_confidence)?;
+
+        // Prices are per whole unit, so one whole token is worth the lamports
+        // in one SOL's worth of the rate
+        let rate = ExchangeRate::from_prices(&sol_usd, &token_usd, RateSide::Sell)?;
+        let price = rate.to_lamports(LAMPORTS_PER_SOL, Rounding::Down)?.value;
+        require!(price > 0, ErrorCode::InvalidOracle);
+        Ok(price)
+    }
+
+    /// What cashing out `amount` base units at `lamports_per_token` returns
+    /// in lamports, after the house fee.
+    pub fn quote_cash_out(&self, betting_state: &BettingState, amount: u64, lamports_per_token: u64) -> Result<CashOutQuote> {
+        require!(self.enabled, ErrorCode::MintDisabled);
+        require!(amount > 0, ErrorCode::InvalidAmount);
+
+        let fee = betting_state.calculate_fee(amount)?;
+        let net_amount = amount.checked_sub(fee.value).ok_or(ErrorCode::CalculationOverflow)?;
+        let one_token = 10u64.checked_pow(self.decimals as u32).ok_or(ErrorCode::CalculationOverflow)?;
+        let sol_returned = casino_math::mul_div(net_amount, lamports_per_token, one_token, Rounding::Down)
+            .map_err(ErrorCode::from)?;
+
+        Ok(CashOutQuote {
+            fee: fee.value,
+            net_amount,
+            sol_returned: sol_returned.value,
+            dust: fee.dust + sol_returned.dust,
+        })
+    }
+
+    /// Records a placed bet. The vault, already holding the stake, must
+    /// cover every open bet's payout including this one.
+    pub fn record_bet(&mut self, terms: &BetTerms, vault_balance: u64) -> Result<()> {
+        self.open_liability = self.open_liability
+            .checked_add(terms.potential_payout)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        require!(vault_balance >= self.open_liability, ErrorCode::InsufficientVaultLiquidity);
+        self.total_staked = self.total_staked
+            .checked_add(terms.stake)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        self.collect_fee(terms.fee)
+    }
+
+    /// Releases a settled bet's liability and records what it paid.
+    pub fn record_settlement(&mut self, potential_payout: u64, paid: u64) -> Result<()> {
+        self.open_liability = self.open_liability
+            .checked_sub(potential_payout)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        self.total_paid = self.total_paid
+            .checked_add(paid)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        Ok(())
+    }
+
+    /// Adds lamports a treasurer moved into the SOL vault for cash-outs.
+    pub fn add_sol_liquidity(&mut self, amount: u64) -> Result<u64> {
+        self.sol_liquidity = self.sol_liquidity
+            .checked_add(amount)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        Ok(self.sol_liquidity)
+    }
+
+    /// Takes a cash-out's lamports out of the mint's liquidity, refusing
+    /// more than it has.
+    pub fn spend_sol_liquidity(&mut self, amount: u64) -> Result<()> {
+        self.sol_liquidity = self.sol_liquidity
+            .checked_sub(amount)
+            .ok_or(ErrorCode::InsufficientMintLiquidity)?;
+        Ok(())
+    }
+
+    pub fn collect_fee(&mut self, fee: u64) -> Result<()> {
+        self.fees_collected = self.fees_collected
+            .checked_add(fee)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        Ok(())
+    }
+}
+
+/// An open bet in a registered mint, one per bettor and mint. It is closed
+/// back to the bettor when settled.
+#[account]
+pub struct AssetBet {
+    pub bettor: Pubkey,
+    pub supported_mint: Pubkey,
+    pub fight_id: u64,
+    pub fighter: Pubkey,            // Fighter the bet backs
+    pub amount: u64,                // Stake after fees
+    pub fee_amount: u64,
+    pub odds: u32,                  // Decimal odds in basis points, after the mint's multiplier
+    pub potential_payout: u64,
+    pub placed_at: i64,
+    pub bump: u8,
+}
+
+impl AssetBet {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // bettor
+        32 + // supported_mint
+        8 + // fight_id
+        32 + // fighter
+        8 + // amount
+        8 + // fee_amount
+        4 + // odds
+        8 + // potential_payout
+        8 + // placed_at
+        1; // bump
+
+    pub fn initialize(
+        &mut self,
+        bettor: Pubkey,
+        supported_mint: Pubkey,
+        fight_id: u64,
+        fighter: Pubkey,
+        terms: &BetTerms,
+        now: i64,
+        bump: u8,
+    ) {
+        self.bettor = bettor;
+        self.supported_mint = supported_mint;
+        self.fight_id = fight_id;
+        self.fighter = fighter;
+        self.amount = terms.stake;
+        self.fee_amount = terms.fee;
+        self.odds = terms.odds.bps();
+        self.potential_payout = terms.potential_payout;
+        self.placed_at = now;
+        self.bump = bump;
+    }
+}
@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use anchor_spl::token_2022::spl_token_2022::{
+    extension::{BaseStateWithExtensions, StateWithExtensions},
+    state::Mint,
+};
+use spl_token_metadata_interface::state::{Field, TokenMetadata};
+
+/// Custom metadata field naming the house a mint belongs to
+pub const HOUSE_METADATA_KEY: &str = "house";
+/// Custom metadata field holding the mint's SOL rate, in tokens per SOL
+pub const RATE_METADATA_KEY: &str = "rate";
+
+/// A Token-2022 metadata field, as taken by `update_token_metadata`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub enum MetadataField {
+    Name,
+    Symbol,
+    Uri,
+    Key(String),    // Custom field, such as `house` or `rate`
+}
+
+impl From<MetadataField> for Field {
+    fn from(field: MetadataField) -> Self {
+        match field {
+            MetadataField::Name => Field::Name,
+            MetadataField::Symbol => Field::Symbol,
+            MetadataField::Uri => Field::Uri,
+            MetadataField::Key(key) => Field::Key(key),
+        }
+    }
+}
+
+/// The metadata stored in `mint`'s TokenMetadata extension, if initialized
+pub fn read_token_metadata(mint: &AccountInfo) -> Result<Option<TokenMetadata>> {
+    let data = mint.try_borrow_data()?;
+    let state = StateWithExtensions::<Mint>::unpack(&data)?;
+    Ok(state.get_variable_len_extension::<TokenMetadata>().ok())
+}
+
+/// Tops `mint` up from `payer` so it stays rent exempt once Token-2022
+/// reallocates it to hold `metadata`.
+pub fn fund_token_metadata<'info>(
+    payer: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    metadata: &TokenMetadata,
+) -> Result<()> {
+    let current_len = match read_token_metadata(mint)? {
+        Some(current) => current.tlv_size_of()?,
+        None => 0,
+    };
+    let new_len = mint
+        .data_len()
+        .saturating_sub(current_len)
+        .saturating_add(metadata.tlv_size_of()?);
+    let shortfall = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(mint.lamports());
+    if shortfall > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                Transfer { from: payer.clone(), to: mint.clone() },
+            ),
+            shortfall,
+        )?;
+    }
+    Ok(())
+}
@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022;
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{transfer_fee::TransferFeeConfig, BaseStateWithExtensions, ExtensionType, StateWithExtensions},
+    onchain::invoke_transfer_checked,
+    state::{Account as TokenAccount, Mint},
+};
+use super::betting_state::{BettingState, TokenType};
+use crate::errors::error_code::ErrorCode;
+
+/// Space a token account of `mint` needs, including the extensions the mint
+/// requires of its accounts. Non-transferable mints also need ImmutableOwner.
+pub fn token_account_len(mint: &AccountInfo) -> Result<usize> {
+    let data = mint.try_borrow_data()?;
+    let mint_extensions = StateWithExtensions::<Mint>::unpack(&data)?.get_extension_types()?;
+    let mut required = ExtensionType::get_required_init_account_extensions(&mint_extensions);
+    if mint_extensions.contains(&ExtensionType::NonTransferable) {
+        required.push(ExtensionType::ImmutableOwner);
+    }
+    Ok(ExtensionType::try_calculate_account_len::<TokenAccount>(&required)?)
+}
+
+/// What `mint` withholds from a transfer of `amount` this epoch, or `None`
+/// when the mint has no TransferFeeConfig.
+pub fn withheld_fee(mint: &AccountInfo, amount: u64) -> Result<Option<u64>> {
+    let data = mint.try_borrow_data()?;
+    let mint = StateWithExtensions::<Mint>::unpack(&data)?;
+    let Ok(config) = mint.get_extension::<TransferFeeConfig>() else {
+        return Ok(None);
+    };
+    let epoch = Clock::get()?.epoch;
+    Ok(Some(config.calculate_epoch_fee(epoch, amount).ok_or(ErrorCode::CalculationOverflow)?))
+}
+
+/// Accounts for moving house tokens between two token accounts.
+pub struct TokenTransfer<'info> {
+    pub token_program: AccountInfo<'info>,
+    pub mint: AccountInfo<'info>,
+    pub from: AccountInfo<'info>,
+    pub to: AccountInfo<'info>,
+    pub authority: AccountInfo<'info>,      // Owner of `from`: a user, or the BettingState PDA for vaults
+}
+
+/// Moves `amount` of `token_type` from `accounts.from` to `accounts.to`.
+/// Transfers are checked, so a mint with a transfer hook gets its extra
+/// accounts from `hook_accounts`, usually the instruction's remaining
+/// accounts. Non-transferable DUMBS cannot be transferred, so the house burns
+/// it from the source and mints it to the destination instead. Balances and
+/// supply end up as a transfer would leave them.
+pub fn transfer_tokens<'info>(
+    accounts: TokenTransfer<'info>,
+    betting_state: &Account<'info, BettingState>,
+    token_type: TokenType,
+    amount: u64,
+    hook_accounts: &[AccountInfo<'info>],
+) -> Result<()> {
+    let authority = betting_state.authority;
+    let betting_state_seeds = &[
+        b"betting_state".as_ref(),
+        authority.as_ref(),
+        &[betting_state.bump],
+    ];
+    let signer = &[&betting_state_seeds[..]];
+
+    if !betting_state.is_non_transferable(token_type) {
+        let decimals = StateWithExtensions::<Mint>::unpack(&accounts.mint.try_borrow_data()?)?.base.decimals;
+        invoke_transfer_checked(
+            &spl_token_2022::ID,
+            accounts.from,
+            accounts.mint,
+            accounts.to,
+            accounts.authority,
+            hook_accounts,
+            amount,
+            decimals,
+            signer,
+        )?;
+        return Ok(());
+    }
+
+    token_2022::burn(
+        CpiContext::new_with_signer(
+            accounts.token_program.clone(),
+            token_2022::Burn {
+                mint: accounts.mint.clone(),
+                from: accounts.from,
+                authority: accounts.authority,
+            },
+            signer,
+        ),
+        amount,
+    )?;
+    token_2022::mint_to(
+        CpiContext::new_with_signer(
+            accounts.token_program,
+            token_2022::MintTo {
+                mint: accounts.mint,
+                to: accounts.to,
+                authority: betting_state.to_account_info(),
+            },
+            signer,
+        ),
+        amount,
+    )
+}
@@ -10,8 +10,10 @@ pub struct Treasury {
     pub sol_fees_collected: u64,      // SOL fees from deposits
     pub dumbs_fees_collected: u64,    // DUMBS fees from bets
     pub rapr_fees_collected: u64,     // RAPR fees from bets
-    pub total_fees_collected: u64,    // Total fees in SOL value
-    pub total_house_edge: u32,        // From betting
+    pub rapr_fees_harvested: u64,     // Part of rapr_fees_collected withheld by the mint and harvested
+    pub rapr_lock_penalties: u64,     // Part of rapr_fees_collected kept from early RAPR unlocks
+    pub total_fees_collected: u64,    // Total fees in SOL value, lamports
+    pub total_house_edge: u64,        // Settled stakes in SOL value, lamports
     pub total_withdrawals: u64,
     pub last_withdrawal_timestamp: i64,
     pub bump: u8,
@@ -23,8 +25,10 @@ impl Treasury {
         8 + // sol_fees_collected
         8 + // dumbs_fees_collected
         8 + // rapr_fees_collected
+        8 + // rapr_fees_harvested
+        8 + // rapr_lock_penalties
         8 + // total_fees_collected
-        4 + // total_house_edge
+        8 + // total_house_edge
         8 + // total_withdrawals
         8 + // last_withdrawal_timestamp
         1; // bump
@@ -38,6 +42,8 @@ impl Treasury {
         self.sol_fees_collected = 0;
         self.dumbs_fees_collected = 0;
         self.rapr_fees_collected = 0;
+        self.rapr_fees_harvested = 0;
+        self.rapr_lock_penalties = 0;
         self.total_fees_collected = 0;
         self.total_house_edge = 0;
         self.total_withdrawals = 0;
@@ -60,44 +66,61 @@ impl Treasury {
         Ok(())
     }
 
+    /// Records a fee of `amount` DUMBS or RAPR worth `sol_value` lamports at
+    /// the house's SOL rate.
     pub fn collect_bet_fee(
         &mut self,
         amount: u64,
-        token_type: TokenType
+        token_type: TokenType,
+        sol_value: u64,
     ) -> Result<()> {
         match token_type {
             TokenType::DUMBS => {
                 self.dumbs_fees_collected = self.dumbs_fees_collected
                     .checked_add(amount)
                     .ok_or(ErrorCode::CalculationOverflow)?;
-
-                // Convert DUMBS amount to SOL value for total tracking
-                self.total_fees_collected = self.total_fees_collected
-                    .checked_add(amount)
-                    .ok_or(ErrorCode::CalculationOverflow)?;
             },
             TokenType::RAPR => {
                 self.rapr_fees_collected = self.rapr_fees_collected
                     .checked_add(amount)
                     .ok_or(ErrorCode::CalculationOverflow)?;
-                
-                // Convert RAPR amount to SOL value for total tracking
-                self.total_fees_collected = self.total_fees_collected
-                    .checked_add(amount)
-                    .ok_or(ErrorCode::CalculationOverflow)?;
             }
         }
 
+        self.total_fees_collected = self.total_fees_collected
+            .checked_add(sol_value)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+
         Ok(())
     }
 
-    pub fn collect_house_edge(&mut self, amount: u32) -> Result<()> {
-        self.total_house_edge = self.total_house_edge
+    /// Records RAPR transfer fees harvested into the RAPR vault. They count as
+    /// RAPR fees, which transfer-fee mode no longer records per bet.
+    pub fn collect_harvested_fees(&mut self, amount: u64, sol_value: u64) -> Result<()> {
+        self.collect_bet_fee(amount, TokenType::RAPR, sol_value)?;
+        self.rapr_fees_harvested = self.rapr_fees_harvested
             .checked_add(amount)
             .ok_or(ErrorCode::CalculationOverflow)?;
         Ok(())
     }
 
+    /// Records the penalty kept in the RAPR vault when a lock is ended early.
+    pub fn collect_lock_penalty(&mut self, amount: u64, sol_value: u64) -> Result<()> {
+        self.collect_bet_fee(amount, TokenType::RAPR, sol_value)?;
+        self.rapr_lock_penalties = self.rapr_lock_penalties
+            .checked_add(amount)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        Ok(())
+    }
+
+    /// Records a settled stake worth `sol_value` lamports.
+    pub fn collect_house_edge(&mut self, sol_value: u64) -> Result<()> {
+        self.total_house_edge = self.total_house_edge
+            .checked_add(sol_value)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        Ok(())
+    }
+
     pub fn record_withdrawal(&mut self, amount: u64) -> Result<()> {
         self.total_withdrawals = self.total_withdrawals
             .checked_add(amount)
@@ -105,4 +128,72 @@ impl Treasury {
         self.last_withdrawal_timestamp = Clock::get()?.unix_timestamp;
         Ok(())
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn treasury() -> Treasury {
+        let mut treasury = Treasury {
+            authority: Pubkey::default(),
+            sol_fees_collected: 0,
+            dumbs_fees_collected: 0,
+            rapr_fees_collected: 0,
+            rapr_fees_harvested: 0,
+            rapr_lock_penalties: 0,
+            total_fees_collected: 0,
+            total_house_edge: 0,
+            total_withdrawals: 0,
+            last_withdrawal_timestamp: 0,
+            bump: 0,
+        };
+        treasury.initialize(Pubkey::default(), 0);
+        treasury
+    }
+
+    #[test]
+    fn fees_are_kept_per_token_and_totalled_in_sol() {
+        let mut treasury = treasury();
+        treasury.collect_deposit_fee(25).unwrap();
+        treasury.collect_bet_fee(1_000, TokenType::DUMBS, 3).unwrap();
+        treasury.collect_bet_fee(50_000, TokenType::RAPR, 7).unwrap();
+
+        assert_eq!(treasury.sol_fees_collected, 25);
+        assert_eq!(treasury.dumbs_fees_collected, 1_000);
+        assert_eq!(treasury.rapr_fees_collected, 50_000);
+        assert_eq!(treasury.total_fees_collected, 25 + 3 + 7);
+    }
+
+    #[test]
+    fn harvested_fees_and_lock_penalties_count_as_rapr_fees() {
+        let mut treasury = treasury();
+        treasury.collect_bet_fee(10, TokenType::RAPR, 1).unwrap();
+        treasury.collect_harvested_fees(200, 2).unwrap();
+        treasury.collect_lock_penalty(3_000, 30).unwrap();
+
+        assert_eq!(treasury.rapr_fees_collected, 10 + 200 + 3_000);
+        assert_eq!(treasury.rapr_fees_harvested, 200);
+        assert_eq!(treasury.rapr_lock_penalties, 3_000);
+        assert_eq!(treasury.total_fees_collected, 1 + 2 + 30);
+        assert_eq!(treasury.dumbs_fees_collected, 0);
+    }
+
+    #[test]
+    fn house_edge_is_separate_from_fees() {
+        let mut treasury = treasury();
+        treasury.collect_house_edge(400).unwrap();
+        treasury.collect_house_edge(100).unwrap();
+        assert_eq!(treasury.total_house_edge, 500);
+        assert_eq!(treasury.total_fees_collected, 0);
+    }
+
+    #[test]
+    fn overflowing_totals_are_refused() {
+        let mut treasury = treasury();
+        treasury.collect_bet_fee(1, TokenType::DUMBS, u64::MAX).unwrap();
+        let result = treasury.collect_deposit_fee(1);
+        assert_eq!(result.unwrap_err(), ErrorCode::CalculationOverflow.into());
+        let result = treasury.collect_bet_fee(u64::MAX, TokenType::DUMBS, 0);
+        assert_eq!(result.unwrap_err(), ErrorCode::CalculationOverflow.into());
+    }
+}
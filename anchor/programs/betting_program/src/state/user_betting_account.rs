@@ -1,7 +1,9 @@
 use anchor_lang::prelude::*;
 use crate::state::bet::Bet;
+use crate::errors::error_code::ErrorCode;
 
 pub const USER_BETTING_ACCOUNT_SEED: &[u8] = b"user-bet-account";
+pub const IDLE_ACCOUNT_GRACE_PERIOD: i64 = 30 * 24 * 60 * 60; // 30 days idle before anyone may close the account
 
 #[account]
 pub struct UserBettingAccount {
@@ -12,6 +14,10 @@ pub struct UserBettingAccount {
     pub total_rapr_wagered: u64,    // Total RAPR tokens wagered
     pub total_winnings: u64,        // Total winnings across all bets
     pub last_bet_timestamp: i64,    // Timestamp of the last bet placed
+    pub last_activity_timestamp: i64, // Timestamp of the last creation, bet or settlement
+    pub dumbs_balance: u64,         // DUMBS held for the owner in the bet vault
+    pub rapr_balance: u64,          // RAPR held for the owner in the RAPR vault
+    pub queued_winnings: u64,       // DUMBS won past the emission budget, not yet paid
     pub bump: u8,                   // Bump seed for the account
 }
 
@@ -24,6 +30,10 @@ impl UserBettingAccount {
         8 +                         // total_rapr_wagered
         8 +                         // total_winnings
         8 +                         // last_bet_timestamp
+        8 +                         // last_activity_timestamp
+        8 +                         // dumbs_balance
+        8 +                         // rapr_balance
+        8 +                         // queued_winnings
         1;                          // bump
 
     /// Initializes the UserBettingAccount
@@ -35,13 +45,76 @@ impl UserBettingAccount {
         self.total_rapr_wagered = 0;
         self.total_winnings = 0;
         self.last_bet_timestamp = 0;
+        self.last_activity_timestamp = Clock::get()?.unix_timestamp;
+        self.dumbs_balance = 0;
+        self.rapr_balance = 0;
+        self.queued_winnings = 0;
         self.bump = bump;
         Ok(())
     }
 
-    /// Removes the active bet (if it exists)
+    /// Removes the active bet (if it exists) once it has been settled and paid
     pub fn remove_active_bet(&mut self) -> Result<()> {
         self.active_bet = None;
+        self.last_activity_timestamp = Clock::get()?.unix_timestamp;
+        Ok(())
+    }
+
+    /// Checks the account holds nothing that still needs it: no open bet,
+    /// nothing left in the internal balance and no winnings waiting to be paid.
+    pub fn require_closable(&self) -> Result<()> {
+        require!(self.active_bet.is_none(), ErrorCode::OpenBetExists);
+        require!(self.dumbs_balance == 0 && self.rapr_balance == 0, ErrorCode::BalanceNotWithdrawn);
+        require!(self.queued_winnings == 0, ErrorCode::WinningsQueued);
+        Ok(())
+    }
+
+    /// Adds winnings the emission budget could not pay, returning the new total
+    pub fn queue_winnings(&mut self, amount: u64) -> Result<u64> {
+        self.queued_winnings = self.queued_winnings.checked_add(amount).ok_or(ErrorCode::CalculationOverflow)?;
+        Ok(self.queued_winnings)
+    }
+
+    /// Takes paid or cancelled winnings off the queue, returning what is left
+    pub fn unqueue_winnings(&mut self, amount: u64) -> Result<u64> {
+        self.queued_winnings = self.queued_winnings.checked_sub(amount).ok_or(ErrorCode::CalculationOverflow)?;
+        Ok(self.queued_winnings)
+    }
+
+    /// Internal balance held for the given token type
+    pub fn balance(&self, token_type: crate::state::betting_state::TokenType) -> u64 {
+        match token_type {
+            crate::state::betting_state::TokenType::DUMBS => self.dumbs_balance,
+            crate::state::betting_state::TokenType::RAPR => self.rapr_balance,
+        }
+    }
+
+    fn balance_mut(&mut self, token_type: crate::state::betting_state::TokenType) -> &mut u64 {
+        match token_type {
+            crate::state::betting_state::TokenType::DUMBS => &mut self.dumbs_balance,
+            crate::state::betting_state::TokenType::RAPR => &mut self.rapr_balance,
+        }
+    }
+
+    /// Adds to the internal balance, returning the new balance
+    pub fn credit_balance(&mut self, amount: u64, token_type: crate::state::betting_state::TokenType) -> Result<u64> {
+        let balance = self.balance_mut(token_type);
+        *balance = balance.checked_add(amount).ok_or(ErrorCode::CalculationOverflow)?;
+        Ok(*balance)
+    }
+
+    /// Takes from the internal balance, returning the new balance
+    pub fn debit_balance(&mut self, amount: u64, token_type: crate::state::betting_state::TokenType) -> Result<u64> {
+        let balance = self.balance_mut(token_type);
+        *balance = balance.checked_sub(amount).ok_or(ErrorCode::InsufficientBalance)?;
+        Ok(*balance)
+    }
+
+    /// Checks the account is closable and has been idle for the grace period
+    pub fn require_idle(&self, now: i64) -> Result<()> {
+        self.require_closable()?;
+        let idle_for = now.saturating_sub(self.last_activity_timestamp);
+        require!(idle_for >= IDLE_ACCOUNT_GRACE_PERIOD, ErrorCode::AccountNotIdle);
         Ok(())
     }
 
@@ -64,11 +137,13 @@ impl UserBettingAccount {
         Ok(())
     }
 
-    /// Adds a new active bet (replaces the existing one if any)
+    /// Adds a new active bet. Fails with `BetAlreadyPlaced` while one is still open.
     pub fn add_active_bet(&mut self, bet: Bet) -> Result<()> {
+        require!(self.active_bet.is_none(), ErrorCode::BetAlreadyPlaced);
         self.active_bet = Some(bet);
         self.total_bets_placed = self.total_bets_placed.checked_add(1).unwrap();
         self.last_bet_timestamp = Clock::get()?.unix_timestamp;
+        self.last_activity_timestamp = self.last_bet_timestamp;
         Ok(())
     }
 }
@@ -83,6 +158,10 @@ impl Default for UserBettingAccount {
             total_rapr_wagered: 0,
             total_winnings: 0,
             last_bet_timestamp: 0,
+            last_activity_timestamp: 0,
+            dumbs_balance: 0,
+            rapr_balance: 0,
+            queued_winnings: 0,
             bump: 0,
         }
     }
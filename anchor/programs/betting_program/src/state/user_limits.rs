@@ -0,0 +1,323 @@
+use anchor_lang::prelude::*;
+use crate::errors::error_code::ErrorCode;
+
+pub const USER_LIMITS_SEED: &[u8] = b"user-limits";
+pub const LIMIT_LOOSEN_DELAY: i64 = 24 * 60 * 60;  // 24 hours before a looser limit applies
+pub const PERMANENT_EXCLUSION: i64 = i64::MAX;
+
+const DAY: i64 = 24 * 60 * 60;
+const WEEK: i64 = 7 * DAY;
+
+/// Limits a user places on themselves. Amounts are in lamports, with bets
+/// valued through the house's SOL rates, and `None` means no limit.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LimitSettings {
+    pub daily_deposit: Option<u64>,
+    pub weekly_deposit: Option<u64>,
+    pub daily_wager: Option<u64>,
+    pub weekly_wager: Option<u64>,
+    pub daily_loss: Option<u64>,     // Net of winnings
+    pub weekly_loss: Option<u64>,
+    pub bet_cooldown: i64,           // Minimum seconds between bets
+}
+
+fn stricter(a: Option<u64>, b: Option<u64>) -> Option<u64> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (limit, None) | (None, limit) => limit,
+    }
+}
+
+fn within(limit: Option<u64>, used: u64, amount: u64) -> bool {
+    // `Option::is_none_or` is newer than the Solana 1.18 platform tools
+    match limit {
+        Some(limit) => used.saturating_add(amount) <= limit,
+        None => true,
+    }
+}
+
+impl LimitSettings {
+    pub const LEN: usize = 6 * (1 + 8) + // amount limits
+        8; // bet_cooldown
+
+    pub fn validate(&self) -> Result<()> {
+        require!(self.bet_cooldown >= 0, ErrorCode::InvalidLimit);
+        Ok(())
+    }
+
+    /// The stricter of each limit in `self` and `other`
+    pub fn stricter(&self, other: &Self) -> Self {
+        Self {
+            daily_deposit: stricter(self.daily_deposit, other.daily_deposit),
+            weekly_deposit: stricter(self.weekly_deposit, other.weekly_deposit),
+            daily_wager: stricter(self.daily_wager, other.daily_wager),
+            weekly_wager: stricter(self.weekly_wager, other.weekly_wager),
+            daily_loss: stricter(self.daily_loss, other.daily_loss),
+            weekly_loss: stricter(self.weekly_loss, other.weekly_loss),
+            bet_cooldown: self.bet_cooldown.max(other.bet_cooldown),
+        }
+    }
+}
+
+/// Activity within one UTC day or week, counted from the Unix epoch.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LimitWindow {
+    pub index: i64,
+    pub deposited: u64,
+    pub wagered: u64,
+    pub lost: u64,
+    pub won: u64,
+}
+
+impl LimitWindow {
+    pub const LEN: usize = 8 * 5;
+
+    /// Starts a fresh window once `now` has moved past this one
+    fn roll(&mut self, now: i64, length: i64) {
+        let index = now.div_euclid(length);
+        if index != self.index {
+            *self = Self { index, ..Self::default() };
+        }
+    }
+
+    pub fn net_loss(&self) -> u64 {
+        self.lost.saturating_sub(self.won)
+    }
+}
+
+#[account]
+pub struct UserLimits {
+    pub owner: Pubkey,
+    pub limits: LimitSettings,              // Limits in force
+    pub pending: Option<LimitSettings>,     // Looser limits waiting for `pending_at`
+    pub pending_at: i64,
+    pub excluded_until: i64,                // PERMANENT_EXCLUSION if self-excluded for good
+    pub last_bet_timestamp: i64,
+    pub day: LimitWindow,
+    pub week: LimitWindow,
+    pub bump: u8,
+}
+
+impl UserLimits {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // owner
+        LimitSettings::LEN + // limits
+        1 + LimitSettings::LEN + // pending
+        8 + // pending_at
+        8 + // excluded_until
+        8 + // last_bet_timestamp
+        LimitWindow::LEN + // day
+        LimitWindow::LEN + // week
+        1; // bump
+
+    pub fn initialize(&mut self, owner: Pubkey, bump: u8) {
+        self.owner = owner;
+        self.limits = LimitSettings::default();
+        self.pending = None;
+        self.pending_at = 0;
+        self.excluded_until = 0;
+        self.last_bet_timestamp = 0;
+        self.day = LimitWindow::default();
+        self.week = LimitWindow::default();
+        self.bump = bump;
+    }
+
+    /// Applies any queued limits that have come due and rolls the windows over
+    pub fn refresh(&mut self, now: i64) {
+        if let Some(pending) = self.pending {
+            if now >= self.pending_at {
+                self.limits = pending;
+                self.pending = None;
+            }
+        }
+        self.day.roll(now, DAY);
+        self.week.roll(now, WEEK);
+    }
+
+    /// Tightens limits at once and queues any loosening behind
+    /// `LIMIT_LOOSEN_DELAY`. Returns when `limits` will be fully in force.
+    pub fn set_limits(&mut self, limits: LimitSettings, now: i64) -> Result<i64> {
+        limits.validate()?;
+        self.refresh(now);
+
+        self.limits = self.limits.stricter(&limits);
+        if self.limits == limits {
+            self.pending = None;
+            return Ok(now);
+        }
+        self.pending = Some(limits);
+        self.pending_at = now
+            .checked_add(LIMIT_LOOSEN_DELAY)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        Ok(self.pending_at)
+    }
+
+    /// Excludes the user until `until`. Exclusions can be extended but never
+    /// shortened.
+    pub fn self_exclude(&mut self, until: i64, now: i64) -> Result<()> {
+        require!(until > now, ErrorCode::InvalidLimit);
+        self.excluded_until = self.excluded_until.max(until);
+        Ok(())
+    }
+
+    pub fn is_excluded(&self, now: i64) -> bool {
+        now < self.excluded_until
+    }
+
+    /// Checks and records a SOL deposit of `amount` lamports
+    pub fn record_deposit(&mut self, amount: u64, now: i64) -> Result<()> {
+        self.refresh(now);
+        require!(!self.is_excluded(now), ErrorCode::SelfExcluded);
+        require!(
+            within(self.limits.daily_deposit, self.day.deposited, amount)
+                && within(self.limits.weekly_deposit, self.week.deposited, amount),
+            ErrorCode::DepositLimitExceeded
+        );
+        self.day.deposited = self.day.deposited.saturating_add(amount);
+        self.week.deposited = self.week.deposited.saturating_add(amount);
+        Ok(())
+    }
+
+    /// Checks and records a bet worth `value` lamports. Loss limits assume the
+    /// bet loses, so no bet can take the user past them.
+    pub fn record_bet(&mut self, value: u64, now: i64) -> Result<()> {
+        self.refresh(now);
+        require!(!self.is_excluded(now), ErrorCode::SelfExcluded);
+        require!(
+            self.last_bet_timestamp == 0
+                || now >= self.last_bet_timestamp.saturating_add(self.limits.bet_cooldown),
+            ErrorCode::BetCooldownActive
+        );
+        require!(
+            within(self.limits.daily_wager, self.day.wagered, value)
+                && within(self.limits.weekly_wager, self.week.wagered, value),
+            ErrorCode::WagerLimitExceeded
+        );
+        require!(
+            within(self.limits.daily_loss, self.day.net_loss(), value)
+                && within(self.limits.weekly_loss, self.week.net_loss(), value),
+            ErrorCode::LossLimitExceeded
+        );
+        self.day.wagered = self.day.wagered.saturating_add(value);
+        self.week.wagered = self.week.wagered.saturating_add(value);
+        self.last_bet_timestamp = now;
+        Ok(())
+    }
+
+    /// Records a settled bet that cost `staked` and paid back `returned`,
+    /// both in lamports. Never fails, so limits cannot block settlement.
+    pub fn record_settlement(&mut self, staked: u64, returned: u64, now: i64) {
+        self.refresh(now);
+        for window in [&mut self.day, &mut self.week] {
+            window.lost = window.lost.saturating_add(staked);
+            window.won = window.won.saturating_add(returned);
+        }
+    }
+}
+
+/// Runs `f` on the limits a user has set, if any. `account` must already be
+/// checked to be the user's limits PDA; if it was never created the user has
+/// no limits.
+pub fn with_user_limits(account: &AccountInfo, f: impl FnOnce(&mut UserLimits) -> Result<()>) -> Result<()> {
+    if account.owner != &crate::ID {
+        return Ok(());
+    }
+    let mut limits = UserLimits::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+    f(&mut limits)?;
+    limits.try_serialize(&mut &mut account.try_borrow_mut_data()?[..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_700_000_000;
+
+    fn user_limits() -> UserLimits {
+        UserLimits {
+            owner: Pubkey::default(),
+            limits: LimitSettings::default(),
+            pending: None,
+            pending_at: 0,
+            excluded_until: 0,
+            last_bet_timestamp: 0,
+            day: LimitWindow::default(),
+            week: LimitWindow::default(),
+            bump: 0,
+        }
+    }
+
+    fn daily_wager(limit: Option<u64>) -> LimitSettings {
+        LimitSettings { daily_wager: limit, ..LimitSettings::default() }
+    }
+
+    #[test]
+    fn tighter_limits_apply_at_once() {
+        let mut limits = user_limits();
+        assert_eq!(limits.set_limits(daily_wager(Some(500)), NOW).unwrap(), NOW);
+        assert_eq!(limits.limits, daily_wager(Some(500)));
+        assert_eq!(limits.set_limits(daily_wager(Some(100)), NOW + 1).unwrap(), NOW + 1);
+        assert_eq!(limits.limits, daily_wager(Some(100)));
+        assert_eq!(limits.pending, None);
+    }
+
+    #[test]
+    fn looser_limits_wait_out_the_delay() {
+        let mut limits = user_limits();
+        limits.set_limits(daily_wager(Some(100)), NOW).unwrap();
+
+        let due = limits.set_limits(daily_wager(None), NOW).unwrap();
+        assert_eq!(due, NOW + LIMIT_LOOSEN_DELAY);
+        assert_eq!(limits.limits, daily_wager(Some(100)));
+        assert_eq!(limits.pending, Some(daily_wager(None)));
+
+        limits.refresh(due - 1);
+        assert_eq!(limits.limits, daily_wager(Some(100)));
+        limits.refresh(due);
+        assert_eq!(limits.limits, daily_wager(None));
+        assert_eq!(limits.pending, None);
+    }
+
+    #[test]
+    fn a_mixed_change_tightens_now_and_loosens_later() {
+        let mut limits = user_limits();
+        limits.set_limits(LimitSettings { daily_deposit: Some(100), ..daily_wager(Some(100)) }, NOW).unwrap();
+
+        let wanted = LimitSettings { daily_deposit: Some(50), ..daily_wager(Some(200)) };
+        let due = limits.set_limits(wanted, NOW).unwrap();
+        assert_eq!(limits.limits, LimitSettings { daily_deposit: Some(50), ..daily_wager(Some(100)) });
+        limits.refresh(due);
+        assert_eq!(limits.limits, wanted);
+    }
+
+    #[test]
+    fn tightening_drops_a_pending_loosening() {
+        let mut limits = user_limits();
+        limits.set_limits(daily_wager(Some(100)), NOW).unwrap();
+        limits.set_limits(daily_wager(Some(500)), NOW).unwrap();
+        limits.set_limits(daily_wager(Some(100)), NOW + 1).unwrap();
+        assert_eq!(limits.pending, None);
+        limits.refresh(NOW + LIMIT_LOOSEN_DELAY);
+        assert_eq!(limits.limits, daily_wager(Some(100)));
+    }
+
+    #[test]
+    fn limits_bind_within_the_window_and_reset_after_it() {
+        let mut limits = user_limits();
+        limits.set_limits(daily_wager(Some(100)), NOW).unwrap();
+        limits.record_bet(60, NOW).unwrap();
+        assert_eq!(limits.record_bet(41, NOW).unwrap_err(), ErrorCode::WagerLimitExceeded.into());
+        limits.record_bet(40, NOW).unwrap();
+        limits.record_bet(100, NOW + DAY).unwrap();
+    }
+
+    #[test]
+    fn exclusions_only_grow() {
+        let mut limits = user_limits();
+        limits.self_exclude(NOW + WEEK, NOW).unwrap();
+        limits.self_exclude(NOW + DAY, NOW).unwrap();
+        assert_eq!(limits.excluded_until, NOW + WEEK);
+        assert_eq!(limits.record_deposit(1, NOW + DAY).unwrap_err(), ErrorCode::SelfExcluded.into());
+        limits.record_deposit(1, NOW + WEEK).unwrap();
+    }
+}
@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, CreateAccount};
+use anchor_spl::token_2022::spl_token_2022::{
+    extension::{permanent_delegate::PermanentDelegate, BaseStateWithExtensions, StateWithExtensions},
+    state::Mint,
+};
+use super::betting_state::TokenType;
+use crate::errors::error_code::ErrorCode;
+
+pub const GOVERNANCE_SEED: &[u8] = b"governance";
+pub const WIN_RECORD_SEED: &[u8] = b"win_record";
+
+/// Governance PDA of `betting_state`. It holds no data; mints created for
+/// clawbacks name it as their permanent delegate, and `clawback` signs as it.
+pub fn governance_address(betting_state: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[GOVERNANCE_SEED, betting_state.as_ref()], &crate::ID)
+}
+
+/// Permanent delegate of `mint`, if it has one.
+pub fn permanent_delegate(mint: &AccountInfo) -> Result<Option<Pubkey>> {
+    let data = mint.try_borrow_data()?;
+    let mint = StateWithExtensions::<Mint>::unpack(&data)?;
+    Ok(mint
+        .get_extension::<PermanentDelegate>()
+        .ok()
+        .and_then(|extension| Option::<Pubkey>::from(extension.delegate)))
+}
+
+/// What one bettor was paid for winning one fight, kept in clawback mode so
+/// winnings from a reversed result can be recovered, and no more than them.
+#[account]
+#[derive(Default)]
+pub struct WinRecord {
+    pub betting_state: Pubkey,
+    pub bettor: Pubkey,
+    pub fight_id: u64,
+    pub winner: Pubkey,             // Winner the bets were settled against
+    pub dumbs_payout: u64,
+    pub rapr_returned: u64,         // RAPR stakes handed back with the win
+    pub dumbs_clawed_back: u64,
+    pub rapr_clawed_back: u64,
+    pub settled_at: i64,
+    pub bump: u8,
+}
+
+impl WinRecord {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // betting_state
+        32 + // bettor
+        8 + // fight_id
+        32 + // winner
+        8 + // dumbs_payout
+        8 + // rapr_returned
+        8 + // dumbs_clawed_back
+        8 + // rapr_clawed_back
+        8 + // settled_at
+        1; // bump
+
+    /// Part of the payout in `token_type` not yet clawed back.
+    pub fn clawable(&self, token_type: TokenType) -> u64 {
+        match token_type {
+            TokenType::DUMBS => self.dumbs_payout.saturating_sub(self.dumbs_clawed_back),
+            TokenType::RAPR => self.rapr_returned.saturating_sub(self.rapr_clawed_back),
+        }
+    }
+
+    /// Records a clawback, which may not take more than `clawable` allows.
+    pub fn record_clawback(&mut self, amount: u64, token_type: TokenType) -> Result<()> {
+        require!(amount <= self.clawable(token_type), ErrorCode::ClawbackExceedsPayout);
+        let clawed_back = match token_type {
+            TokenType::DUMBS => &mut self.dumbs_clawed_back,
+            TokenType::RAPR => &mut self.rapr_clawed_back,
+        };
+        *clawed_back = clawed_back.checked_add(amount).ok_or(ErrorCode::CalculationOverflow)?;
+        Ok(())
+    }
+}
+
+/// Adds a win to the bettor's record for the fight, creating it with rent
+/// from `payer` the first time. `seeds` are the record's PDA seeds without
+/// the bump.
+pub fn record_win<'info>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    seeds: &[&[u8]],
+    win: WinRecord,
+) -> Result<()> {
+    if account.owner == &crate::ID {
+        let mut record = WinRecord::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+        record.dumbs_payout = record.dumbs_payout
+            .checked_add(win.dumbs_payout)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        record.rapr_returned = record.rapr_returned
+            .checked_add(win.rapr_returned)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        record.settled_at = win.settled_at;
+        return record.try_serialize(&mut &mut account.try_borrow_mut_data()?[..]);
+    }
+
+    let bump = [win.bump];
+    let signer_seeds = [seeds, &[&bump[..]]].concat();
+    system_program::create_account(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            CreateAccount { from: payer.clone(), to: account.clone() },
+            &[&signer_seeds[..]],
+        ),
+        Rent::get()?.minimum_balance(WinRecord::LEN),
+        WinRecord::LEN as u64,
+        &crate::ID,
+    )?;
+    win.try_serialize(&mut &mut account.try_borrow_mut_data()?[..])
+}
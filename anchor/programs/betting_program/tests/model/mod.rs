@@ -0,0 +1,365 @@
+//! Off-chain model of the house used by the property tests and the fuzz target.
+//!
+//! Each `Op` replays the arithmetic of its instruction handler through the real
+//! `BettingState`, `SolVault` and `Bet` methods, with token and lamport
+//! movements kept as plain balances. A failing op is rolled back, as the
+//! runtime would roll back the transaction.
+
+#![allow(dead_code)]
+
+use std::sync::Once;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::SUCCESS;
+use anchor_lang::solana_program::program_stubs::{set_syscall_stubs, SyscallStubs};
+use betting_program::errors::error_code::ErrorCode;
+use betting_program::state::bet::Bet;
+use betting_program::state::betting_state::{BetTerms, TokenType};
+use betting_program::state::{BettingState, SolVault};
+
+pub const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+struct ClockStub;
+
+impl SyscallStubs for ClockStub {
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        // SAFETY: the sysvar getter passes a pointer to a `Clock`
+        unsafe { *(var_addr as *mut Clock) = Clock::default() };
+        SUCCESS
+    }
+}
+
+/// `Bet::initialize` and `Bet::settle` read the clock sysvar, which has no
+/// off-chain implementation unless stubbed.
+pub fn install_clock() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        set_syscall_stubs(Box::new(ClockStub));
+    });
+}
+
+/// House configuration as set by `initialize_state_accounts` and
+/// `initialize_sol_vault`.
+pub fn default_state() -> BettingState {
+    BettingState {
+        house_fee: 250,
+        rapr_multiplier: 100_000,
+        sol_dumbs_rate: 1000,
+        sol_rapr_rate: 10_000_000,
+        max_bet: 100 * LAMPORTS_PER_SOL,
+        ..BettingState::default()
+    }
+}
+
+pub fn default_sol_vault() -> SolVault {
+    SolVault {
+        authority: Pubkey::default(),
+        balance: 0,
+        total_deposits: 0,
+        total_sol_received: 0,
+        total_dumbs_minted: 0,
+        min_deposit_amount: 10_000_000,
+        max_deposit_amount: 100 * LAMPORTS_PER_SOL,
+        bump: 0,
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum Op {
+    Deposit { amount: u64 },
+    Swap { sol_amount: u64 },
+    PlaceBet { amount: u64, fight_id: u64, odds: u64, token_type: TokenType },
+    Settle { won: bool },
+    CashOut { amount: u64, token_type: TokenType },
+}
+
+#[derive(Clone)]
+pub struct Model {
+    pub state: BettingState,
+    pub sol_vault: SolVault,
+    pub active_bet: Option<Bet>,
+    /// Gross stake transferred into a bet vault for the active bet.
+    pub active_stake: u64,
+
+    // Balances as the token program and the runtime would hold them
+    pub vault_lamports: u64,
+    pub treasury_lamports: u64,
+    pub user_dumbs: u64,
+    pub user_rapr: u64,
+    pub bet_vault_dumbs: u64,
+    pub bet_vault_rapr: u64,
+    pub dumbs_supply: u64,
+    pub rapr_supply: u64,
+
+    // Where every minted or burned token came from
+    pub sol_backing_dumbs: u64,
+    pub sol_backing_rapr: u64,
+    pub dumbs_minted_for_deposits: u64,
+    pub dumbs_minted_for_wins: u64,
+    pub dumbs_paid_by_house: u64,
+    pub queued_winnings: u64,
+    pub dumbs_burned: u64,
+    pub rapr_minted: u64,
+    pub rapr_cashed_out: u64,
+}
+
+impl Default for Model {
+    fn default() -> Self {
+        Self::new(default_state(), default_sol_vault())
+    }
+}
+
+fn credit(balance: &mut u64, amount: u64) -> Result<()> {
+    *balance = balance.checked_add(amount).ok_or(ErrorCode::CalculationOverflow)?;
+    Ok(())
+}
+
+fn debit(balance: &mut u64, amount: u64) -> Result<()> {
+    *balance = balance.checked_sub(amount).ok_or(ProgramError::InsufficientFunds)?;
+    Ok(())
+}
+
+impl Model {
+    pub fn new(state: BettingState, sol_vault: SolVault) -> Self {
+        install_clock();
+        Self {
+            state,
+            sol_vault,
+            active_bet: None,
+            active_stake: 0,
+            vault_lamports: 0,
+            treasury_lamports: 0,
+            user_dumbs: 0,
+            user_rapr: 0,
+            bet_vault_dumbs: 0,
+            bet_vault_rapr: 0,
+            dumbs_supply: 0,
+            rapr_supply: 0,
+            sol_backing_dumbs: 0,
+            sol_backing_rapr: 0,
+            dumbs_minted_for_deposits: 0,
+            dumbs_minted_for_wins: 0,
+            dumbs_paid_by_house: 0,
+            queued_winnings: 0,
+            dumbs_burned: 0,
+            rapr_minted: 0,
+            rapr_cashed_out: 0,
+        }
+    }
+
+    /// Applies `op`, leaving the model untouched if it fails.
+    pub fn apply(&mut self, op: Op) -> Result<()> {
+        let mut next = self.clone();
+        let result = match op {
+            Op::Deposit { amount } => next.deposit(amount),
+            Op::Swap { sol_amount } => next.swap(sol_amount),
+            Op::PlaceBet { amount, fight_id, odds, token_type } => next.place_bet(amount, fight_id, odds, token_type),
+            Op::Settle { won } => next.settle(won),
+            Op::CashOut { amount, token_type } => next.cash_out(amount, token_type),
+        };
+        if result.is_ok() {
+            *self = next;
+        }
+        result
+    }
+
+    fn deposit(&mut self, amount: u64) -> Result<()> {
+        self.state.validate_sol_deposit(amount)?;
+        self.sol_vault.process_deposit(amount)?;
+
+        let dust = self.state.rounding_dust;
+        let rate = self.state.fixed_rate(TokenType::DUMBS);
+        let (fee, dumbs_to_mint) = self.state.handle_sol_deposit(amount, rate)?;
+        assert!(self.state.rounding_dust - dust < casino_math::DUST_SCALE, "deposit kept a whole unit of dust");
+        assert!(fee <= amount, "deposit fee {} exceeds amount {}", fee, amount);
+        let net_amount = amount - fee;
+
+        credit(&mut self.vault_lamports, net_amount)?;
+        credit(&mut self.treasury_lamports, fee)?;
+        self.sol_vault.balance = self.sol_vault.balance
+            .checked_sub(fee)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        self.sol_vault.total_dumbs_minted = self.sol_vault.total_dumbs_minted
+            .checked_add(dumbs_to_mint)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+
+        credit(&mut self.dumbs_supply, dumbs_to_mint)?;
+        credit(&mut self.user_dumbs, dumbs_to_mint)?;
+        credit(&mut self.sol_backing_dumbs, net_amount)?;
+        credit(&mut self.dumbs_minted_for_deposits, dumbs_to_mint)
+    }
+
+    fn swap(&mut self, sol_amount: u64) -> Result<()> {
+        self.sol_vault.check_deposit_limits(sol_amount)?;
+
+        let rate = self.state.fixed_rate(TokenType::RAPR);
+        let rapr_amount = self.state.quote_swap(sol_amount, rate)?.rapr_minted;
+
+        credit(&mut self.vault_lamports, sol_amount)?;
+        self.sol_vault.process_deposit(sol_amount)?;
+
+        credit(&mut self.rapr_supply, rapr_amount)?;
+        credit(&mut self.user_rapr, rapr_amount)?;
+        self.state.total_rapr_in_circulation = self.state.total_rapr_in_circulation
+            .checked_add(rapr_amount)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+
+        credit(&mut self.sol_backing_rapr, sol_amount)?;
+        credit(&mut self.rapr_minted, rapr_amount)
+    }
+
+    fn place_bet(&mut self, amount: u64, fight_id: u64, odds: u64, token_type: TokenType) -> Result<()> {
+        let terms = self.state.price_bet(amount, odds, token_type)?;
+        let BetTerms { fee, stake: bet_amount, .. } = terms;
+        assert!(fee <= amount, "bet fee {} exceeds amount {}", fee, amount);
+
+        match token_type {
+            TokenType::DUMBS => {
+                debit(&mut self.user_dumbs, amount)?;
+                credit(&mut self.bet_vault_dumbs, amount)?;
+            }
+            TokenType::RAPR => {
+                debit(&mut self.user_rapr, amount)?;
+                credit(&mut self.bet_vault_rapr, amount)?;
+            }
+        }
+
+        let mut bet = Bet::default();
+        bet.initialize(
+            Pubkey::default(),
+            token_type,
+            u32::try_from(fight_id).map_err(|_| ErrorCode::InvalidFightId)?,
+            Pubkey::default(),
+            &terms,
+            0,
+        )?;
+
+        require!(self.active_bet.is_none(), ErrorCode::BetAlreadyPlaced);
+        self.active_bet = Some(bet);
+        self.active_stake = amount;
+        self.state.record_bet_placed(bet_amount, token_type, bet.potential_payout)
+    }
+
+    fn settle(&mut self, won: bool) -> Result<()> {
+        let mut bet = self.active_bet.take().ok_or(ErrorCode::BetNotFound)?;
+        bet.settle(won)?;
+
+        // The stake stays in its vault; a DUMBS stake becomes the house's
+        self.state.keep_stake(bet.amount, bet.token_type)?;
+
+        let dumbs_payout = if bet.won {
+            let funding = self.state.fund_win(bet.potential_payout, 0)?;
+
+            // Minted into the bet vault, then paid out to the bettor along
+            // with whatever the house's DUMBS covers
+            credit(&mut self.dumbs_supply, funding.minted)?;
+            credit(&mut self.bet_vault_dumbs, funding.minted)?;
+            debit(&mut self.bet_vault_dumbs, funding.paid())?;
+            credit(&mut self.user_dumbs, funding.paid())?;
+            credit(&mut self.dumbs_minted_for_wins, funding.minted)?;
+            credit(&mut self.dumbs_paid_by_house, funding.from_vault)?;
+            credit(&mut self.queued_winnings, funding.queued)?;
+
+            if bet.token_type == TokenType::RAPR {
+                debit(&mut self.bet_vault_rapr, bet.amount)?;
+                credit(&mut self.user_rapr, bet.amount)?;
+            }
+            funding.paid() + funding.queued
+        } else {
+            0
+        };
+
+        self.state.record_bet_settled(bet.potential_payout, dumbs_payout)?;
+        self.active_stake = 0;
+        Ok(())
+    }
+
+    fn cash_out(&mut self, amount: u64, token_type: TokenType) -> Result<()> {
+        let quote = self.state.price_cash_out(amount, token_type)?;
+        assert!(quote.fee <= amount, "cash-out fee {} exceeds amount {}", quote.fee, amount);
+        let sol_return = quote.sol_returned;
+
+        match token_type {
+            TokenType::DUMBS => {
+                debit(&mut self.user_dumbs, amount)?;
+                debit(&mut self.dumbs_supply, amount)?;
+                self.pay_from_sol_vault(sol_return)?;
+                self.state.total_dumbs_in_circulation = self.state.total_dumbs_in_circulation
+                    .checked_sub(amount)
+                    .ok_or(ErrorCode::CalculationOverflow)?;
+                credit(&mut self.dumbs_burned, amount)
+            }
+            TokenType::RAPR => {
+                debit(&mut self.user_rapr, amount)?;
+                credit(&mut self.bet_vault_rapr, amount)?;
+                self.pay_from_sol_vault(sol_return)?;
+                self.state.total_rapr_in_circulation = self.state.total_rapr_in_circulation
+                    .checked_sub(amount)
+                    .ok_or(ErrorCode::CalculationOverflow)?;
+                credit(&mut self.rapr_cashed_out, amount)
+            }
+        }
+    }
+
+    fn pay_from_sol_vault(&mut self, sol_return: u64) -> Result<()> {
+        require!(self.sol_vault.balance >= sol_return, ErrorCode::InsufficientSolBalance);
+        require!(sol_return <= self.vault_lamports, ErrorCode::InsufficientSolBalance);
+
+        self.vault_lamports -= sol_return;
+        self.sol_vault.balance = self.sol_vault.balance
+            .checked_sub(sol_return)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        Ok(())
+    }
+
+    /// Panics if the house's books disagree with the balances it holds.
+    pub fn check_invariants(&self) {
+        // The vault's recorded balance is exactly the lamports it holds
+        assert_eq!(self.sol_vault.balance, self.vault_lamports, "SOL vault balance drifted from its lamports");
+
+        // Tokens only exist in wallets and vaults the model tracks
+        assert_eq!(self.user_dumbs + self.bet_vault_dumbs, self.dumbs_supply, "DUMBS supply is not fully held");
+        assert_eq!(self.user_rapr + self.bet_vault_rapr, self.rapr_supply, "RAPR supply is not fully held");
+
+        // Every DUMBS in circulation was minted against SOL or a settled winning bet
+        assert_eq!(self.state.total_dumbs_in_circulation, self.dumbs_supply, "DUMBS circulation counter drifted");
+        assert_eq!(
+            self.dumbs_minted_for_deposits,
+            self.sol_backing_dumbs * self.state.sol_dumbs_rate,
+            "DUMBS minted without SOL backing"
+        );
+        assert_eq!(
+            self.dumbs_minted_for_wins + self.dumbs_paid_by_house + self.queued_winnings,
+            self.state.total_dumbs_won,
+            "winnings paid without a winning bet"
+        );
+        assert_eq!(self.queued_winnings, self.state.total_dumbs_queued, "queued winnings drifted");
+        assert_eq!(
+            self.dumbs_supply,
+            self.dumbs_minted_for_deposits + self.dumbs_minted_for_wins - self.dumbs_burned,
+            "DUMBS supply does not match mints and burns"
+        );
+
+        // RAPR is only minted by swaps and leaves circulation when cashed out
+        assert_eq!(self.rapr_minted, self.sol_backing_rapr * self.state.sol_rapr_rate, "RAPR minted without SOL backing");
+        assert_eq!(self.rapr_supply, self.rapr_minted, "RAPR supply does not match swaps");
+        assert_eq!(
+            self.state.total_rapr_in_circulation,
+            self.rapr_minted - self.rapr_cashed_out,
+            "RAPR circulation counter drifted"
+        );
+
+        // Outstanding liability is exactly the open bet, whose stake the vault can return
+        match self.active_bet {
+            Some(bet) => {
+                assert_eq!(self.state.total_potential_payout, bet.potential_payout, "liability drifted from open bet");
+                assert_eq!(bet.amount + bet.fee_amount, self.active_stake, "bet stake was truncated");
+                assert!(bet.potential_payout >= bet.amount, "odds below 1x were accepted");
+                if bet.token_type == TokenType::RAPR {
+                    assert!(self.bet_vault_rapr >= bet.amount, "RAPR vault cannot return the open stake");
+                }
+            }
+            None => assert_eq!(self.state.total_potential_payout, 0, "liability left after settlement"),
+        }
+    }
+}
@@ -0,0 +1,283 @@
+//! Property tests for the fee, odds, deposit, oracle rate and payout arithmetic, plus random
+//! instruction sequences replayed against the house model.
+
+mod model;
+
+use anchor_lang::prelude::Pubkey;
+use betting_program::state::bet::Bet;
+use betting_program::state::betting_state::{BetTerms, TokenType};
+use betting_program::state::{
+    BetHistory, BettingState, ExchangeRate, OraclePrice, RateSide, BET_HISTORY_LEN, MAX_HOUSE_FEE,
+};
+use casino_math::{BasisPoints, Odds, Rounding, DUST_SCALE};
+use model::{default_state, Model, Op, LAMPORTS_PER_SOL};
+use proptest::prelude::*;
+
+fn state_with(house_fee: u32, rapr_multiplier: u64, sol_dumbs_rate: u64) -> BettingState {
+    BettingState {
+        house_fee,
+        rapr_multiplier,
+        sol_dumbs_rate,
+        ..default_state()
+    }
+}
+
+fn token_type() -> impl Strategy<Value = TokenType> {
+    prop_oneof![Just(TokenType::DUMBS), Just(TokenType::RAPR)]
+}
+
+/// Odds from 1x up to the largest the program accepts.
+fn odds_bps() -> impl Strategy<Value = u32> {
+    prop_oneof![10_000..30_000u32, 10_000..=u32::MAX]
+}
+
+/// Payout the program would store for a bet, or `None` if it rejects it.
+fn potential_payout(amount: u64, odds: u64, token_type: TokenType, rapr_multiplier: u64) -> Option<u64> {
+    model::install_clock();
+    let state = state_with(250, rapr_multiplier, 1000);
+    let odds = state.calculate_odds(odds, token_type, BasisPoints::ZERO).ok()?;
+    let payout = state.calculate_payout(amount, odds).ok()?.value;
+    let multiplier = (token_type == TokenType::RAPR).then(|| state.rapr_boost().unwrap());
+
+    let mut bet = Bet::default();
+    let terms = BetTerms { fee: 0, stake: amount, odds, potential_payout: payout, rapr_multiplier: multiplier, dust: 0 };
+    bet.initialize(Pubkey::default(), token_type, 1, Pubkey::default(), &terms, 0).ok()?;
+    Some(bet.potential_payout)
+}
+
+/// Amounts around the edges the handlers care about: dust, the SOL vault
+/// limits, the old u32 limit on `Bet`, and values near u64::MAX.
+fn amount() -> impl Strategy<Value = u64> {
+    prop_oneof![
+        0..1_000u64,
+        10_000_000..=100 * LAMPORTS_PER_SOL,
+        (u32::MAX as u64 - 1_000)..=(u32::MAX as u64 + 1_000),
+        any::<u64>(),
+    ]
+}
+
+/// A positive price at exponent -8 with a confidence of up to half of it.
+fn oracle_price() -> impl Strategy<Value = OraclePrice> {
+    (1..1_000_000_000_000i64, 0..5_000u64).prop_map(|(price, conf_bps)| OraclePrice {
+        price,
+        conf: (price as u128 * conf_bps as u128 / 10_000) as u64,
+        expo: -8,
+        publish_time: 0,
+    })
+}
+
+fn op() -> impl Strategy<Value = Op> {
+    prop_oneof![
+        amount().prop_map(|amount| Op::Deposit { amount }),
+        amount().prop_map(|sol_amount| Op::Swap { sol_amount }),
+        (amount(), 0..3u64, 0..40_000u64, token_type())
+            .prop_map(|(amount, fight_id, odds, token_type)| Op::PlaceBet { amount, fight_id, odds, token_type }),
+        any::<bool>().prop_map(|won| Op::Settle { won }),
+        (amount(), token_type()).prop_map(|(amount, token_type)| Op::CashOut { amount, token_type }),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn fee_never_exceeds_amount(house_fee in 0..=MAX_HOUSE_FEE, amount in any::<u64>()) {
+        let state = state_with(house_fee, 100_000, 1000);
+        let fee = state.calculate_fee(amount).unwrap();
+        prop_assert!(fee.value <= amount);
+        // Rounded up: never less than the exact fee, never a whole unit more
+        prop_assert!(fee.value as u128 * 10_000 >= amount as u128 * house_fee as u128);
+        prop_assert!(fee.dust < DUST_SCALE);
+    }
+
+    #[test]
+    fn fee_is_monotonic_in_amount(house_fee in 0..=MAX_HOUSE_FEE, a in amount(), b in amount()) {
+        let state = state_with(house_fee, 100_000, 1000);
+        let (low, high) = (a.min(b), a.max(b));
+        let low_fee = state.calculate_fee(low).unwrap().value;
+        let high_fee = state.calculate_fee(high).unwrap().value;
+        prop_assert!(low_fee <= high_fee);
+        // The bettor's net stake never shrinks when they stake more
+        prop_assert!(low - low_fee <= high - high_fee);
+    }
+
+    #[test]
+    fn odds_are_monotonic(rapr_multiplier in 10_000..1_000_000u64, token_type in token_type(), a in odds_bps(), b in odds_bps()) {
+        let state = state_with(250, rapr_multiplier, 1000);
+        let (low, high) = (a.min(b) as u64, a.max(b) as u64);
+        if let (Ok(low_odds), Ok(high_odds)) = (state.calculate_odds(low, token_type, BasisPoints::ZERO), state.calculate_odds(high, token_type, BasisPoints::ZERO)) {
+            prop_assert!(low_odds <= high_odds);
+            prop_assert!(low_odds >= Odds::MIN);
+            if token_type == TokenType::DUMBS {
+                prop_assert_eq!(low_odds.bps() as u64, low);
+            }
+        }
+    }
+
+    #[test]
+    fn odds_below_one_are_rejected(odds in 0..10_000u64, token_type in token_type()) {
+        let state = state_with(250, 100_000, 1000);
+        prop_assert!(state.calculate_odds(odds, token_type, BasisPoints::ZERO).is_err());
+    }
+
+    #[test]
+    fn sol_deposit_accounts_for_every_lamport(
+        house_fee in 0..=MAX_HOUSE_FEE,
+        sol_dumbs_rate in 1..10_000u64,
+        amount in amount(),
+    ) {
+        let mut state = state_with(house_fee, 100_000, sol_dumbs_rate);
+        let before = state.clone();
+        let rate = state.fixed_rate(TokenType::DUMBS);
+        match state.handle_sol_deposit(amount, rate) {
+            Ok((fee, dumbs_to_mint)) => {
+                prop_assert!(fee <= amount);
+                prop_assert_eq!(dumbs_to_mint, (amount - fee) * sol_dumbs_rate);
+                prop_assert_eq!(state.total_fees_collected, before.total_fees_collected + fee);
+                prop_assert_eq!(
+                    state.total_dumbs_in_circulation,
+                    before.total_dumbs_in_circulation + dumbs_to_mint
+                );
+            }
+            Err(_) => {
+                // Overflow is only reported when the minted amount does not fit
+                let fee = (amount as u128 * house_fee as u128).div_ceil(10_000);
+                prop_assert!((amount as u128 - fee) * sol_dumbs_rate as u128 > u64::MAX as u128);
+            }
+        }
+    }
+
+    #[test]
+    fn oracle_rates_never_beat_the_mid_price(sol in oracle_price(), token in oracle_price(), amount in amount()) {
+        let exact = |price: OraclePrice| OraclePrice { conf: 0, ..price };
+        let mid = ExchangeRate::from_prices(&exact(sol), &exact(token), RateSide::Buy).unwrap();
+        // Buying tokens and selling them for SOL both get no more than at the mid price
+        if let (Ok(buy), Ok(tokens)) = (
+            ExchangeRate::from_prices(&sol, &token, RateSide::Buy),
+            mid.to_tokens(amount, Rounding::Down),
+        ) {
+            prop_assert!(buy.to_tokens(amount, Rounding::Down).unwrap().value <= tokens.value);
+        }
+        if let (Ok(sell), Ok(lamports)) = (
+            ExchangeRate::from_prices(&sol, &token, RateSide::Sell),
+            mid.to_lamports(amount, Rounding::Down),
+        ) {
+            prop_assert!(sell.to_lamports(amount, Rounding::Down).unwrap().value <= lamports.value);
+        }
+    }
+
+    #[test]
+    fn winnings_minted_match_circulation(payout in any::<u64>(), circulating in any::<u64>()) {
+        let mut state = BettingState { total_dumbs_in_circulation: circulating, ..default_state() };
+        match state.mint_dumbs_for_win(payout) {
+            Ok(minted) => {
+                prop_assert_eq!(minted, payout);
+                prop_assert_eq!(state.total_dumbs_in_circulation, circulating + payout);
+            }
+            Err(_) => prop_assert_eq!(state.total_dumbs_in_circulation, circulating),
+        }
+    }
+
+    #[test]
+    fn win_funding_respects_supply_and_budget(
+        payout in 0..u64::MAX / 4,
+        circulating in 0..u64::MAX / 4,
+        max_supply in any::<u64>(),
+        budget in any::<u64>(),
+        emitted in any::<u64>(),
+        house_dumbs in 0..u64::MAX / 4,
+        new_epoch in any::<bool>(),
+    ) {
+        let mut state = BettingState {
+            total_dumbs_in_circulation: circulating,
+            dumbs_max_supply: max_supply,
+            dumbs_epoch_budget: budget,
+            emission_epoch: 7,
+            dumbs_emitted: emitted,
+            house_dumbs,
+            ..default_state()
+        };
+        let epoch = if new_epoch { 8 } else { 7 };
+        let funding = state.fund_win(payout, epoch).unwrap();
+        prop_assert_eq!(funding.minted + funding.from_vault + funding.queued, payout);
+        prop_assert_eq!(state.total_dumbs_in_circulation, circulating + funding.minted);
+        prop_assert!(funding.minted == 0 || state.total_dumbs_in_circulation <= max_supply);
+        let emitted_before = if new_epoch { 0 } else { emitted };
+        prop_assert!(state.dumbs_emitted <= budget.max(emitted_before));
+        prop_assert_eq!(state.house_dumbs, house_dumbs - funding.from_vault);
+        prop_assert_eq!(state.total_dumbs_queued, funding.queued);
+
+        // Releasing the queue never pays more than is queued
+        let released = state.release_queued(funding.queued, epoch + 1).unwrap();
+        prop_assert!(released.paid() <= funding.queued);
+        prop_assert_eq!(state.total_dumbs_queued, funding.queued - released.paid());
+    }
+
+    #[test]
+    fn bet_payout_is_exact_or_rejected(amount in 1..=u64::MAX, odds in odds_bps(), rapr_multiplier in 10_000..1_000_000u64, token_type in token_type()) {
+        let odds = odds as u128;
+        let boosted = match token_type {
+            TokenType::DUMBS => odds,
+            TokenType::RAPR => odds * rapr_multiplier as u128 / 10_000,
+        };
+        let expected = amount as u128 * boosted / 10_000;
+        match potential_payout(amount, odds as u64, token_type, rapr_multiplier) {
+            Some(payout) => prop_assert_eq!(payout as u128, expected),
+            None => prop_assert!(boosted > u32::MAX as u128 || expected > u64::MAX as u128),
+        }
+    }
+
+    #[test]
+    fn bet_payout_covers_the_stake(amount in 1..=u64::MAX, odds in odds_bps(), token_type in token_type()) {
+        if let Some(payout) = potential_payout(amount, odds as u64, token_type, 100_000) {
+            prop_assert!(payout >= amount);
+        }
+    }
+
+    #[test]
+    fn bet_payout_is_monotonic_in_stake(a in 1..=u64::MAX, b in 1..=u64::MAX, odds in odds_bps(), token_type in token_type()) {
+        let (low, high) = (a.min(b), a.max(b));
+        if let (Some(low_payout), Some(high_payout)) =
+            (potential_payout(low, odds as u64, token_type, 100_000), potential_payout(high, odds as u64, token_type, 100_000))
+        {
+            prop_assert!(low_payout <= high_payout);
+        }
+    }
+
+    #[test]
+    fn bet_payout_is_monotonic_in_odds(amount in 1..=u64::MAX, a in odds_bps(), b in odds_bps(), token_type in token_type()) {
+        let (low, high) = (a.min(b) as u64, a.max(b) as u64);
+        if let (Some(low_payout), Some(high_payout)) =
+            (potential_payout(amount, low, token_type, 100_000), potential_payout(amount, high, token_type, 100_000))
+        {
+            prop_assert!(low_payout <= high_payout);
+        }
+    }
+
+    #[test]
+    fn bet_history_keeps_the_newest_results_and_streaks(results in prop::collection::vec(any::<bool>(), 0..200)) {
+        let mut history: BetHistory = bytemuck::Zeroable::zeroed();
+        for (fight_id, &won) in results.iter().enumerate() {
+            let bet = Bet { fight_id: fight_id as u32, won, ..Bet::default() };
+            history.record(&bet, won as u64);
+        }
+
+        let kept: Vec<_> = history.recent().map(|entry| (entry.fight_id as usize, entry.won())).collect();
+        let expected: Vec<_> = results.iter().copied().enumerate().rev().take(BET_HISTORY_LEN).collect();
+        prop_assert_eq!(kept, expected);
+        prop_assert_eq!(history.wins + history.losses, results.len() as u64);
+
+        let streak = results.iter().rev().take_while(|&&won| Some(&won) == results.last()).count() as i64;
+        prop_assert_eq!(history.current_streak, if results.last() == Some(&true) { streak } else { -streak });
+        let longest = |side: bool| results.split(|&won| won != side).map(<[bool]>::len).max().unwrap_or(0) as u64;
+        prop_assert_eq!(history.longest_win_streak, longest(true));
+        prop_assert_eq!(history.longest_loss_streak, longest(false));
+    }
+
+    #[test]
+    fn instruction_sequences_keep_the_books_balanced(ops in prop::collection::vec(op(), 1..64)) {
+        let mut model = Model::default();
+        for op in ops {
+            let _ = model.apply(op);
+            model.check_invariants();
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct CollectionCreated {
+    pub collection: Pubkey,
+    pub collection_id: Pubkey,
+    pub authority: Pubkey,
+    pub strategy: String,
+    pub fee: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AgentMinted {
+    pub ai_agent: Pubkey,
+    pub agent_id: Pubkey,
+    pub mint: Pubkey,
+    pub collection: Pubkey,
+    pub owner: Pubkey,
+    pub model_hash: [u8; 32],
+    pub fee: u64,
+    pub timestamp: i64,
+}
@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
 use crate::state::{Collection, treasury::{Treasury, TREASURY_SEED, COLLECTION_FEE}};
 use crate::errors::ErrorCode;
+use crate::events::CollectionCreated;
 
 #[derive(Accounts)]
 #[instruction(name: String, symbol: String, strategy: String, collection_id: Pubkey)]
@@ -69,6 +70,15 @@ pub fn create_collection_handler(
     collection.authority = ctx.accounts.authority.key();
     collection.collection_id = collection_id;
     msg!("Setting collection_id to: {}", collection_id);
+
+    emit!(CollectionCreated {
+        collection: collection.key(),
+        collection_id,
+        authority: collection.authority,
+        strategy: strategy_upper,
+        fee: COLLECTION_FEE,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
     Ok(())
 }
 
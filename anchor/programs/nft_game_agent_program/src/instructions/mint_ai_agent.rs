@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
 use crate::state::{Collection, AIAgent, treasury::{Treasury, TREASURY_SEED, COLLECTION_FEE, AGENT_FEE}};
 use crate::errors::errors::ErrorCode;
+use crate::events::AgentMinted;
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::metadata::{
     create_master_edition_v3, create_metadata_accounts_v3, CreateMasterEditionV3,
@@ -215,6 +216,17 @@ pub fn mint_ai_agent_handler(
     let treasury = &mut ctx.accounts.treasury;
     treasury.total_collected = treasury.total_collected.checked_add(AGENT_FEE).unwrap();
 
+    emit!(AgentMinted {
+        ai_agent: ctx.accounts.ai_agent.key(),
+        agent_id: id,
+        mint: ctx.accounts.mint.key(),
+        collection: ctx.accounts.collection.key(),
+        owner: ctx.accounts.authority.key(),
+        model_hash,
+        fee: AGENT_FEE,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
     msg!("AI Agent NFT minted successfully");
     Ok(())
 }
\ No newline at end of file
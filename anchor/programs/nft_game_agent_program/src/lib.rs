@@ -3,6 +3,7 @@ use anchor_lang::prelude::*;
 declare_id!("2HuYN4A2wYEiTpP7tdx4rKKcLPauwDwwchjxgMkM3gJ3");
 
 pub mod errors;
+pub mod events;
 pub mod instructions;
 pub mod state;
 
@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("The hook may only run during a token transfer")]
+    NotTransferring,
+
+    #[msg("Mint is not the house's RAPR mint")]
+    InvalidMint,
+
+    #[msg("The sender is self-excluded")]
+    SenderSelfExcluded,
+}
@@ -0,0 +1,10 @@
+use anchor_lang::prelude::*;
+use crate::state::LoyaltyTier;
+
+#[event]
+pub struct LoyaltyTierReached {
+    pub owner: Pubkey,
+    pub tier: LoyaltyTier,
+    pub total_sent: u64,
+    pub timestamp: i64,
+}
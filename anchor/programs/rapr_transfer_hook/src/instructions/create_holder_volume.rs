@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+
+/// Opts `owner` into loyalty volume tracking. Anyone may pay for it.
+#[derive(Accounts)]
+#[instruction(owner: Pubkey)]
+pub struct CreateHolderVolume<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = HolderVolume::LEN,
+        seeds = [HOLDER_VOLUME_SEED, owner.as_ref()],
+        bump,
+    )]
+    pub holder_volume: Account<'info, HolderVolume>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CreateHolderVolume>, owner: Pubkey) -> Result<()> {
+    ctx.accounts.holder_volume.initialize(owner, ctx.bumps.holder_volume);
+    Ok(())
+}
@@ -0,0 +1,7 @@
+pub mod initialize_extra_account_meta_list;
+pub mod create_holder_volume;
+pub mod transfer_hook;
+
+pub use initialize_extra_account_meta_list::*;
+pub use create_holder_volume::*;
+pub use transfer_hook::*;
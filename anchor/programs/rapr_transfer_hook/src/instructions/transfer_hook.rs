@@ -0,0 +1,116 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::{
+    extension::{transfer_hook::TransferHookAccount, BaseStateWithExtensions, StateWithExtensions},
+    state::Account as SplTokenAccount,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use anchor_lang::solana_program::program_option::COption;
+use betting_program::state::{UserLimits, USER_LIMITS_SEED};
+use crate::errors::error_code::ErrorCode;
+use crate::events::LoyaltyTierReached;
+use crate::state::*;
+
+/// Token-2022's Execute call, made on every RAPR transfer with the accounts
+/// listed by `extra_account_metas`.
+#[derive(Accounts)]
+pub struct TransferHook<'info> {
+    #[account(token::mint = mint)]
+    pub source: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(token::mint = mint)]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: owner or delegate of the source, verified by Token-2022
+    pub authority: UncheckedAccount<'info>,
+
+    /// CHECK: the meta list this call was resolved from
+    #[account(
+        seeds = [EXTRA_ACCOUNT_METAS_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+
+    /// CHECK: only used to resolve the betting program PDAs below
+    #[account(address = betting_program::ID)]
+    pub betting_program: UncheckedAccount<'info>,
+
+    /// CHECK: the sender's limits PDA, which only holds data once they set limits
+    #[account(
+        seeds = [USER_LIMITS_SEED, source.owner.as_ref()],
+        bump,
+        seeds::program = betting_program::ID,
+    )]
+    pub user_limits: UncheckedAccount<'info>,
+
+    /// CHECK: the sender's volume PDA, which only holds data once they opt in
+    #[account(
+        mut,
+        seeds = [HOLDER_VOLUME_SEED, source.owner.as_ref()],
+        bump,
+    )]
+    pub holder_volume: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<TransferHook>, amount: u64) -> Result<()> {
+    require_transferring(&ctx.accounts.source.to_account_info())?;
+    let sender = ctx.accounts.source.owner;
+    let now = Clock::get()?.unix_timestamp;
+
+    record_volume(&ctx.accounts.holder_volume, amount, now)?;
+
+    // The house moves its own vaults, and transfers into the house are bets,
+    // deposits and cash-outs the betting program already checks
+    let house = ctx.accounts.mint.mint_authority;
+    if house == COption::Some(sender) || house == COption::Some(ctx.accounts.destination.owner) {
+        return Ok(());
+    }
+
+    // RAPR staked in bets or lock-ups is escrowed in the RAPR vault, which
+    // only the betting program moves, so a wallet holds no locked RAPR
+    if let Some(limits) = load::<UserLimits>(&ctx.accounts.user_limits) {
+        require!(!limits.is_excluded(now), ErrorCode::SenderSelfExcluded);
+    }
+
+    Ok(())
+}
+
+/// Rejects calls made outside a transfer, which could otherwise record
+/// volume that never moved.
+fn require_transferring(source: &AccountInfo) -> Result<()> {
+    let data = source.try_borrow_data()?;
+    let account = StateWithExtensions::<SplTokenAccount>::unpack(&data)?;
+    let extension = account.get_extension::<TransferHookAccount>()?;
+    require!(bool::from(extension.transferring), ErrorCode::NotTransferring);
+    Ok(())
+}
+
+/// Reads a betting program account, or `None` if it does not hold one yet.
+/// Accounts created earlier in the same instruction are still zeroed.
+fn load<T: AccountDeserialize>(account: &AccountInfo) -> Option<T> {
+    if account.owner != &betting_program::ID {
+        return None;
+    }
+    let data = account.try_borrow_data().ok()?;
+    T::try_deserialize(&mut &data[..]).ok()
+}
+
+fn record_volume(account: &AccountInfo, amount: u64, now: i64) -> Result<()> {
+    if account.owner != &crate::ID {
+        return Ok(());
+    }
+
+    let mut data = account.try_borrow_mut_data()?;
+    let mut volume = HolderVolume::try_deserialize(&mut &data[..])?;
+    if let Some(tier) = volume.record_transfer(amount, now) {
+        emit!(LoyaltyTierReached {
+            owner: volume.owner,
+            tier,
+            total_sent: volume.total_sent,
+            timestamp: now,
+        });
+    }
+    volume.try_serialize(&mut &mut data[..])?;
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use spl_transfer_hook_interface::instruction::TransferHookInstruction;
+
+pub mod errors;
+pub mod events;
+pub mod instructions;
+pub mod state;
+
+use instructions::*;
+
+declare_id!("C73ZMt3zjKbgRDzuWuUS7N5Q7dNrqLD6Z8mSmmshdWq4");
+
+#[program]
+pub mod rapr_transfer_hook {
+    use super::*;
+
+    pub fn initialize_extra_account_meta_list(ctx: Context<InitializeExtraAccountMetaList>) -> Result<()> {
+        instructions::initialize_extra_account_meta_list::handler(ctx)
+    }
+
+    pub fn create_holder_volume(ctx: Context<CreateHolderVolume>, owner: Pubkey) -> Result<()> {
+        instructions::create_holder_volume::handler(ctx, owner)
+    }
+
+    pub fn transfer_hook(ctx: Context<TransferHook>, amount: u64) -> Result<()> {
+        instructions::transfer_hook::handler(ctx, amount)
+    }
+
+    // Token-2022 calls Execute with the transfer-hook interface discriminator,
+    // not Anchor's, so route it to `transfer_hook` by hand
+    pub fn fallback<'info>(
+        program_id: &Pubkey,
+        accounts: &'info [AccountInfo<'info>],
+        data: &[u8],
+    ) -> Result<()> {
+        match TransferHookInstruction::unpack(data)? {
+            TransferHookInstruction::Execute { amount } => {
+                __private::__global::transfer_hook(program_id, accounts, &amount.to_le_bytes())
+            }
+            _ => Err(ProgramError::InvalidInstructionData.into()),
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use betting_program::state::USER_LIMITS_SEED;
+use spl_tlv_account_resolution::{account::ExtraAccountMeta, seeds::Seed};
+use super::holder_volume::HOLDER_VOLUME_SEED;
+
+pub const EXTRA_ACCOUNT_METAS_SEED: &[u8] = b"extra-account-metas";
+
+// Index of the betting program in the Execute accounts, after source, mint,
+// destination, authority and the meta list
+const BETTING_PROGRAM_INDEX: u8 = 5;
+
+/// The source token account's owner, read from its data
+fn source_owner() -> Seed {
+    Seed::AccountData { account_index: 0, data_index: 32, length: 32 }
+}
+
+/// Accounts Token-2022 appends to every RAPR transfer: the betting program,
+/// then the sender's limits and volume PDAs.
+pub fn extra_account_metas() -> Result<Vec<ExtraAccountMeta>> {
+    Ok(vec![
+        ExtraAccountMeta::new_with_pubkey(&betting_program::ID, false, false)?,
+        ExtraAccountMeta::new_external_pda_with_seeds(
+            BETTING_PROGRAM_INDEX,
+            &[Seed::Literal { bytes: USER_LIMITS_SEED.to_vec() }, source_owner()],
+            false,
+            false,
+        )?,
+        ExtraAccountMeta::new_with_seeds(
+            &[Seed::Literal { bytes: HOLDER_VOLUME_SEED.to_vec() }, source_owner()],
+            false,
+            true,
+        )?,
+    ])
+}
@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+
+pub const HOLDER_VOLUME_SEED: &[u8] = b"holder-volume";
+
+// RAPR sent before each tier is reached, in base units
+pub const SILVER_TIER_VOLUME: u64 = 10_000 * 1_000_000_000;
+pub const GOLD_TIER_VOLUME: u64 = 100_000 * 1_000_000_000;
+pub const PLATINUM_TIER_VOLUME: u64 = 1_000_000 * 1_000_000_000;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LoyaltyTier {
+    #[default]
+    Bronze,
+    Silver,
+    Gold,
+    Platinum,
+}
+
+impl LoyaltyTier {
+    pub fn for_volume(volume: u64) -> Self {
+        match volume {
+            v if v >= PLATINUM_TIER_VOLUME => LoyaltyTier::Platinum,
+            v if v >= GOLD_TIER_VOLUME => LoyaltyTier::Gold,
+            v if v >= SILVER_TIER_VOLUME => LoyaltyTier::Silver,
+            _ => LoyaltyTier::Bronze,
+        }
+    }
+}
+
+/// RAPR a holder has sent, recorded by the hook on every transfer out of
+/// their accounts once the holder has opted in.
+#[account]
+#[derive(Default)]
+pub struct HolderVolume {
+    pub owner: Pubkey,
+    pub total_sent: u64,
+    pub transfers: u64,
+    pub tier: LoyaltyTier,
+    pub last_transfer_timestamp: i64,
+    pub bump: u8,
+}
+
+impl HolderVolume {
+    pub const LEN: usize = 8 +  // Discriminator
+        32 +                    // owner
+        8 +                     // total_sent
+        8 +                     // transfers
+        1 +                     // tier
+        8 +                     // last_transfer_timestamp
+        1;                      // bump
+
+    pub fn initialize(&mut self, owner: Pubkey, bump: u8) {
+        self.owner = owner;
+        self.bump = bump;
+    }
+
+    /// Adds a transfer of `amount` and returns the tier it reached if it moved up.
+    pub fn record_transfer(&mut self, amount: u64, now: i64) -> Option<LoyaltyTier> {
+        self.total_sent = self.total_sent.saturating_add(amount);
+        self.transfers = self.transfers.saturating_add(1);
+        self.last_transfer_timestamp = now;
+
+        let tier = LoyaltyTier::for_volume(self.total_sent);
+        if tier > self.tier {
+            self.tier = tier;
+            Some(tier)
+        } else {
+            None
+        }
+    }
+}
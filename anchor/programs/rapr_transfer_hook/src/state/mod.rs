@@ -0,0 +1,5 @@
+pub mod extra_account_metas;
+pub mod holder_volume;
+
+pub use extra_account_metas::*;
+pub use holder_volume::*;
@@ -165,7 +165,7 @@ describe("betting_program", () => {
   it("Places a bet", async () => {
     const betAmount = new anchor.BN(0.5 * LAMPORTS_PER_SOL);
     const fightId = new anchor.BN(1);
-    const odds = new anchor.BN(200); // 2.00 odds
+    const odds = new anchor.BN(20_000); // 2.00x in basis points
 
     const [betAccount] = await PublicKey.findProgramAddressSync(
       [Buffer.from("bet"), user1.publicKey.toBuffer(), fightId.toArrayLike(Buffer, 'le', 8)],
@@ -280,7 +280,7 @@ describe("betting_program", () => {
   it("Fails to place bet above maximum amount", async () => {
     const betAmount = new anchor.BN(1.5 * LAMPORTS_PER_SOL); // More than maxBet
     const fightId = new anchor.BN(2);
-    const odds = new anchor.BN(150);
+    const odds = new anchor.BN(15_000);
 
     try {
       await program.methods
@@ -79,7 +79,7 @@ describe("betting_program_edge_cases", () => {
   it("Handles bet of exactly maximum amount", async () => {
     const maxBet = (await program.account.bettingState.fetch(bettingState)).maxBet;
     const fightId = new anchor.BN(1);
-    const odds = new anchor.BN(200); // 2.00 odds
+    const odds = new anchor.BN(20_000); // 2.00x in basis points
 
     const [betAccount] = await PublicKey.findProgramAddress(
       [Buffer.from("bet"), user.publicKey.toBuffer(), fightId.toArrayLike(Buffer, 'le', 8)],
@@ -113,7 +113,7 @@ describe("betting_program_edge_cases", () => {
   it("Processes bet with odds of 1 (no profit)", async () => {
     const betAmount = new anchor.BN(0.1 * LAMPORTS_PER_SOL);
     const fightId = new anchor.BN(1);
-    const odds = new anchor.BN(100); // 1.00 odds
+    const odds = new anchor.BN(10_000); // 1.00x in basis points
 
     const [betAccount] = await PublicKey.findProgramAddress(
       [Buffer.from("bet"), user.publicKey.toBuffer(), fightId.toArrayLike(Buffer, 'le', 8)],
@@ -51,7 +51,7 @@ describe("betting_program_error_conditions", () => {
   it("Fails to place bet with insufficient funds", async () => {
     const betAmount = new anchor.BN(100 * LAMPORTS_PER_SOL); // More than user balance
     const fightId = new anchor.BN(1);
-    const odds = new anchor.BN(200);
+    const odds = new anchor.BN(20_000);
 
     try {
       await program.methods.placeBet(betAmount, fightId, odds)
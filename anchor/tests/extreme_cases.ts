@@ -78,7 +78,7 @@ describe("betting_program_extreme_cases", () => {
 
   it("Places multiple bets in rapid succession", async () => {
     const betAmount = new anchor.BN(0.1 * LAMPORTS_PER_SOL);
-    const odds = new anchor.BN(200);
+    const odds = new anchor.BN(20_000);
     const numBets = 100;
 
     const placeBetPromises = [];